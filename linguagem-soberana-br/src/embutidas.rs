@@ -0,0 +1,7 @@
+// src/embutidas.rs
+// Funções embutidas da Linguagem Soberana, compartilhadas pelo validador,
+// pelo interpretador e pelos transpiladores.
+
+/// Funções embutidas disponíveis a todo programa. O interpretador as
+/// implementa em `chamar_embutida`; os transpiladores, na geração de código.
+pub const FUNCOES_EMBUTIDAS: &[&str] = &["arredondar", "tamanho"];
@@ -1,2 +1,5 @@
 #[path = "parser/lib.rs"]
 pub mod parser;
+pub mod embutidas;
+pub mod runtime;
+pub mod transpiler;
@@ -4,10 +4,12 @@
 
 use logos::Logos;
 
+pub mod validator;
+
 /// Tokens léxicos da Linguagem Soberana
 #[derive(Logos, Debug, PartialEq, Clone)]
 #[logos(skip r"[ \t\n\f]+")] // Ignorar espaços
-#[logos(skip r"//[^\n]*")]   // Ignorar comentários
+#[logos(skip(r"//[^\n]*", allow_greedy = true))] // Ignorar comentários
 pub enum Token {
     // Palavras-chave
     #[token("funcao")]
@@ -32,6 +34,10 @@ pub enum Token {
     Enquanto,
     #[token("etapa")]
     Etapa,
+    #[token("verdadeiro")]
+    Verdadeiro,
+    #[token("falso")]
+    Falso,
 
     // Anotações constitucionais
     #[token("verificavel_mathematicamente")]
@@ -70,6 +76,8 @@ pub enum Token {
     Vezes,
     #[token("/")]
     Dividido,
+    #[token("=")]
+    Atribui,
     #[token("==")]
     Igual,
     #[token("!=")]
@@ -149,13 +157,31 @@ pub enum Operador {
     Igual, Diferente, Menor, Maior, MenorIgual, MaiorIgual,
 }
 
+/// Posição no código-fonte (offsets em bytes, linha/coluna a partir de 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub inicio: usize,
+    pub fim: usize,
+    pub linha: usize,
+    pub coluna: usize,
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.linha, self.coluna)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Funcao {
     pub nome: String,
     pub parametros: Vec<(String, Tipo)>,
     pub retorno: Tipo,
     pub anotacoes: Vec<Anotacao>,
+    /// Posição de cada anotação, na mesma ordem de `anotacoes`
+    pub spans_anotacoes: Vec<Span>,
     pub corpo: Vec<Comando>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -164,13 +190,17 @@ pub struct Processo {
     pub parametros: Vec<(String, Tipo)>,
     pub retorno: Tipo,
     pub anotacoes: Vec<Anotacao>,
+    /// Posição de cada anotação, na mesma ordem de `anotacoes`
+    pub spans_anotacoes: Vec<Span>,
     pub etapas: Vec<Etapa>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
 pub struct Etapa {
     pub nome: String,
     pub corpo: Vec<Comando>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -199,7 +229,7 @@ pub enum Comando {
     Atribuicao { var: String, expr: Expr },
     Retorno(Expr),
     Se { cond: Expr, entao: Vec<Comando>, senao: Option<Vec<Comando>> },
-    ParaCada { var: String, colecao: String, corpo: Vec<Comando> },
+    ParaCada { var: String, colecao: String, corpo: Vec<Comando>, span: Span },
     Expressao(Expr),
 }
 
@@ -212,12 +242,30 @@ pub enum Declaracao {
 /// Parser recursivo descendente
 pub struct Parser {
     tokens: Vec<Token>,
+    spans: Vec<Span>,
     posicao: usize,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, posicao: 0 }
+        Self { tokens, spans: Vec::new(), posicao: 0 }
+    }
+
+    /// Parser com a posição de cada token, para diagnósticos com localização.
+    pub fn com_spans(tokens: Vec<Token>, spans: Vec<Span>) -> Self {
+        Self { tokens, spans, posicao: 0 }
+    }
+
+    fn span_atual(&self) -> Span {
+        self.spans.get(self.posicao).copied().unwrap_or_default()
+    }
+
+    /// De `inicio` até o fim do último token consumido.
+    fn span_desde(&self, inicio: Span) -> Span {
+        let fim = self.posicao.checked_sub(1)
+            .and_then(|i| self.spans.get(i))
+            .map_or(inicio.fim, |s| s.fim);
+        Span { fim, ..inicio }
     }
 
     fn atual(&self) -> Option<&Token> {
@@ -256,6 +304,7 @@ impl Parser {
     pub fn parse_funcao(&mut self) -> Result<Funcao, String> {
         self.espera(Token::Funcao)?;
 
+        let span = self.span_atual();
         let nome = match self.avancar() {
             Some(Token::Identificador(n)) => n.clone(),
             _ => return Err("Esperado identificador após 'funcao'".to_string()),
@@ -268,7 +317,7 @@ impl Parser {
         self.espera(Token::Seta)?;
         let retorno = self.parse_tipo()?;
 
-        let anotacoes = self.parse_anotacoes()?;
+        let (anotacoes, spans_anotacoes) = self.parse_anotacoes()?;
         let corpo = self.parse_bloco()?;
 
         Ok(Funcao {
@@ -276,12 +325,15 @@ impl Parser {
             parametros,
             retorno,
             anotacoes,
+            spans_anotacoes,
             corpo,
+            span,
         })
     }
 
     pub fn parse_processo(&mut self) -> Result<Processo, String> {
         self.espera(Token::Processo)?;
+        let span = self.span_atual();
         let nome = match self.avancar() {
             Some(Token::Identificador(n)) => n.clone(),
             _ => return Err("Esperado identificador após 'processo'".to_string()),
@@ -294,7 +346,7 @@ impl Parser {
         self.espera(Token::Seta)?;
         let retorno = self.parse_tipo()?;
 
-        let anotacoes = self.parse_anotacoes()?;
+        let (anotacoes, spans_anotacoes) = self.parse_anotacoes()?;
 
         self.espera(Token::ChaveEsq)?;
         let mut etapas = Vec::new();
@@ -311,18 +363,21 @@ impl Parser {
             parametros,
             retorno,
             anotacoes,
+            spans_anotacoes,
             etapas,
+            span,
         })
     }
 
     fn parse_etapa(&mut self) -> Result<Etapa, String> {
         self.espera(Token::Etapa)?;
+        let span = self.span_atual();
         let nome = match self.avancar() {
             Some(Token::Identificador(n)) => n.clone(),
             _ => return Err("Esperado identificador de etapa".to_string()),
         };
         let corpo = self.parse_bloco()?;
-        Ok(Etapa { nome, corpo })
+        Ok(Etapa { nome, corpo, span })
     }
 
     fn parse_parametros(&mut self) -> Result<Vec<(String, Tipo)>, String> {
@@ -387,10 +442,12 @@ impl Parser {
         }
     }
 
-    fn parse_anotacoes(&mut self) -> Result<Vec<Anotacao>, String> {
+    fn parse_anotacoes(&mut self) -> Result<(Vec<Anotacao>, Vec<Span>), String> {
         let mut anotacoes = Vec::new();
+        let mut spans = Vec::new();
 
         while let Some(tok) = self.atual() {
+            let inicio = self.span_atual();
             match tok {
                 Token::VerificavelMathematicamente => {
                     self.avancar();
@@ -411,6 +468,43 @@ impl Parser {
                         return Err("Esperado valor inteiro".to_string());
                     }
                 }
+                Token::ComComplexidade => {
+                    self.avancar();
+                    self.espera(Token::ParenEsq)?;
+                    anotacoes.push(Anotacao::Complexidade(self.parse_notacao_o()?));
+                    self.espera(Token::ParenDir)?;
+                }
+                Token::ComRastreamentoCompleto => {
+                    self.avancar();
+                    self.espera(Token::ParenEsq)?;
+                    if let Some(Token::TextoLit(s)) = self.avancar() {
+                        anotacoes.push(Anotacao::RastreamentoCompleto(s.clone()));
+                        self.espera(Token::ParenDir)?;
+                    } else {
+                        return Err("Esperado texto da invariante em com_rastreamento_completo".to_string());
+                    }
+                }
+                Token::SujeitoAVoto => {
+                    self.avancar();
+                    self.espera(Token::ParenEsq)?;
+                    let autoridade = match self.avancar() {
+                        Some(Token::TextoLit(s)) => s.clone(),
+                        _ => return Err("Esperado autoridade em sujeito_a_voto".to_string()),
+                    };
+                    self.espera(Token::Virgula)?;
+                    match self.avancar() {
+                        Some(Token::Identificador(s)) if s == "quorum" => {},
+                        _ => return Err("Esperado 'quorum'".to_string()),
+                    }
+                    self.espera(Token::DoisPontos)?;
+                    let quorum = match self.avancar() {
+                        Some(Token::DecimalLit(q)) => *q,
+                        Some(Token::InteiroLit(q)) => *q as f64,
+                        _ => return Err("Esperado valor do quorum".to_string()),
+                    };
+                    anotacoes.push(Anotacao::SujeitoAVoto(autoridade, quorum));
+                    self.espera(Token::ParenDir)?;
+                }
                 Token::AuditavelPor => {
                     self.avancar();
                     self.espera(Token::ParenEsq)?;
@@ -423,9 +517,31 @@ impl Parser {
                 }
                 _ => break,
             }
+            spans.push(self.span_desde(inicio));
         }
 
-        Ok(anotacoes)
+        Ok((anotacoes, spans))
+    }
+
+    /// Lê `O(...)` em `com_complexidade`, normalizando para texto (ex.: "O(n log n)").
+    fn parse_notacao_o(&mut self) -> Result<String, String> {
+        match self.avancar() {
+            Some(Token::Identificador(o)) if o == "O" => {},
+            _ => return Err("Esperado notação 'O(...)' em com_complexidade".to_string()),
+        }
+        self.espera(Token::ParenEsq)?;
+        let mut termos = Vec::new();
+        loop {
+            match self.avancar_clonado() {
+                Some(Token::ParenDir) => break,
+                Some(Token::Identificador(s)) => termos.push(s),
+                Some(Token::InteiroLit(n)) => termos.push(n.to_string()),
+                Some(Token::Vezes) => termos.push("*".to_string()),
+                Some(tok) => return Err(format!("Termo inválido na notação O: {:?}", tok)),
+                None => return Err("Fim inesperado do arquivo".to_string()),
+            }
+        }
+        Ok(format!("O({})", termos.join(" ")))
     }
 
     fn parse_bloco(&mut self) -> Result<Vec<Comando>, String> {
@@ -455,6 +571,18 @@ impl Parser {
             }
             Some(Token::Se) => self.parse_se(),
             Some(Token::Para) => self.parse_para_cada(),
+            Some(Token::Identificador(var))
+                if matches!(self.tokens.get(self.posicao + 1), Some(Token::Atribui)) =>
+            {
+                let var = var.clone();
+                self.avancar();
+                self.avancar();
+                let expr = self.parse_expressao()?;
+                if let Some(Token::PontoVirgula) = self.atual() {
+                    self.avancar();
+                }
+                Ok(Comando::Atribuicao { var, expr })
+            }
             _ => {
                 let expr = self.parse_expressao()?;
                 if let Some(Token::PontoVirgula) = self.atual() {
@@ -482,6 +610,7 @@ impl Parser {
     }
 
     fn parse_para_cada(&mut self) -> Result<Comando, String> {
+        let span = self.span_atual();
         self.espera(Token::Para)?;
         self.espera(Token::Cada)?;
 
@@ -499,7 +628,7 @@ impl Parser {
 
         let corpo = self.parse_bloco()?;
 
-        Ok(Comando::ParaCada { var, colecao, corpo })
+        Ok(Comando::ParaCada { var, colecao, corpo, span })
     }
 
     fn parse_expressao(&mut self) -> Result<Expr, String> {
@@ -650,8 +779,15 @@ impl Parser {
             Token::InteiroLit(n) => Ok(Expr::Literal(Literal::Inteiro(n))),
             Token::DecimalLit(n) => Ok(Expr::Literal(Literal::Decimal(n, 2))),
             Token::TextoLit(s) => Ok(Expr::Literal(Literal::Texto(s))),
+            Token::Verdadeiro => Ok(Expr::Literal(Literal::Booleano(true))),
+            Token::Falso => Ok(Expr::Literal(Literal::Booleano(false))),
+            Token::ParenEsq => {
+                let expr = self.parse_expressao()?;
+                self.espera(Token::ParenDir)?;
+                Ok(expr)
+            }
             Token::Identificador(nome) => {
-                let is_chamada = if let Some(Token::ParenEsq) = self.atual() { true } else { false };
+                let is_chamada = matches!(self.atual(), Some(Token::ParenEsq));
                 if is_chamada {
                     self.avancar();
                     let mut args = Vec::new();
@@ -676,15 +812,20 @@ impl Parser {
 }
 
 pub fn parse(input: &str) -> Result<Vec<Declaracao>, String> {
-    let mut lexer = Token::lexer(input);
+    let inicios_linha: Vec<usize> = std::iter::once(0)
+        .chain(input.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
     let mut tokens = Vec::new();
-    while let Some(tok) = lexer.next() {
+    let mut spans = Vec::new();
+    for (tok, intervalo) in Token::lexer(input).spanned() {
+        let span = span_de(input, &inicios_linha, intervalo);
         match tok {
             Ok(t) => tokens.push(t),
-            Err(_) => return Err("Erro léxico".to_string()),
+            Err(_) => return Err(format!("Erro léxico em {}", span)),
         }
+        spans.push(span);
     }
-    let mut parser = Parser::new(tokens);
+    let mut parser = Parser::com_spans(tokens, spans);
     let mut declaracoes = Vec::new();
     while parser.atual().is_some() {
         declaracoes.push(parser.parse_declaracao()?);
@@ -692,6 +833,19 @@ pub fn parse(input: &str) -> Result<Vec<Declaracao>, String> {
     Ok(declaracoes)
 }
 
+/// `inicios_linha` é o offset de início de cada linha, em ordem crescente.
+fn span_de(fonte: &str, inicios_linha: &[usize], intervalo: std::ops::Range<usize>) -> Span {
+    // Última linha que começa antes do token
+    let linha = inicios_linha.partition_point(|&inicio| inicio <= intervalo.start);
+    let inicio_linha = inicios_linha[linha - 1];
+    Span {
+        inicio: intervalo.start,
+        fim: intervalo.end,
+        linha,
+        coluna: fonte[inicio_linha..intervalo.start].chars().count() + 1,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,519 @@
+// src/parser/validator.rs
+// Validador constitucional da Linguagem Soberana - Fase A
+// Confere as anotações de cada declaração contra o corpo que elas descrevem.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use logos::Logos;
+
+use super::{Anotacao, Comando, Declaracao, Expr, Span, Token};
+use crate::embutidas::FUNCOES_EMBUTIDAS;
+
+/// Regra constitucional violada
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Regra {
+    /// Aninhamento de `para cada` acima do permitido por `com_complexidade`
+    ComplexidadeExcedida,
+    /// Notação de `com_complexidade` não reconhecida
+    ComplexidadeInvalida,
+    /// Invariante de `com_rastreamento_completo` cita nome inexistente
+    InvarianteInvalida,
+    /// Quorum de `sujeito_a_voto` fora de (0, 1]
+    QuorumInvalido,
+    /// Autoridade/órgão vazio em `sujeito_a_voto` ou `auditavel_por`
+    AutoridadeVazia,
+    /// `com_precisao` além de 28 casas (limite do Decimal de ponto fixo)
+    PrecisaoInvalida,
+    /// Função verificável chamando código não verificável
+    VerificabilidadeViolada,
+    /// Recursão, proibida na Fase A
+    Recursao,
+}
+
+/// Diagnóstico com localização no código-fonte
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostico {
+    pub regra: Regra,
+    pub mensagem: String,
+    pub span: Span,
+}
+
+impl fmt::Display for Diagnostico {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.span, self.mensagem)
+    }
+}
+
+/// Valida as anotações constitucionais de um programa já parseado.
+pub fn validar(declaracoes: &[Declaracao]) -> Vec<Diagnostico> {
+    Validador::new(declaracoes).validar()
+}
+
+struct Validador<'a> {
+    declaracoes: &'a [Declaracao],
+    /// Corpo de cada função, para seguir chamadas
+    funcoes: HashMap<&'a str, &'a [Comando]>,
+    verificaveis: HashSet<&'a str>,
+    diagnosticos: Vec<Diagnostico>,
+}
+
+impl<'a> Validador<'a> {
+    fn new(declaracoes: &'a [Declaracao]) -> Self {
+        let mut funcoes = HashMap::new();
+        let mut verificaveis = HashSet::new();
+        for decl in declaracoes {
+            if let Declaracao::Funcao(f) = decl {
+                funcoes.insert(f.nome.as_str(), f.corpo.as_slice());
+                if f.anotacoes.iter().any(|a| matches!(a, Anotacao::VerificavelMathematicamente)) {
+                    verificaveis.insert(f.nome.as_str());
+                }
+            }
+        }
+        Self { declaracoes, funcoes, verificaveis, diagnosticos: Vec::new() }
+    }
+
+    fn validar(mut self) -> Vec<Diagnostico> {
+        for decl in self.declaracoes {
+            let (nome, parametros, anotacoes, spans_anotacoes, corpos, span) = match decl {
+                Declaracao::Funcao(f) => {
+                    (&f.nome, &f.parametros, &f.anotacoes, &f.spans_anotacoes, vec![&f.corpo], f.span)
+                }
+                Declaracao::Processo(p) => (
+                    &p.nome,
+                    &p.parametros,
+                    &p.anotacoes,
+                    &p.spans_anotacoes,
+                    p.etapas.iter().map(|e| &e.corpo).collect(),
+                    p.span,
+                ),
+            };
+
+            if self.recursiva(nome) {
+                self.reportar(Regra::Recursao, format!("'{}' é recursiva; recursão é proibida na Fase A", nome), span);
+            }
+
+            // Diagnósticos de anotação apontam para a própria anotação
+            for (i, anotacao) in anotacoes.iter().enumerate() {
+                let span = spans_anotacoes.get(i).copied().unwrap_or(span);
+                match anotacao {
+                    Anotacao::VerificavelMathematicamente => {
+                        for corpo in &corpos {
+                            self.validar_verificavel(nome, corpo, span);
+                        }
+                    }
+                    Anotacao::Precisao(n) if *n > 28 => self.reportar(
+                        Regra::PrecisaoInvalida,
+                        format!("com_precisao(decimais: {}) excede o limite de 28 casas", n),
+                        span,
+                    ),
+                    Anotacao::Precisao(_) => {}
+                    Anotacao::Complexidade(notacao) => match profundidade_permitida(notacao) {
+                        Some(limite) => {
+                            for corpo in &corpos {
+                                self.validar_complexidade(nome, notacao, limite, corpo);
+                            }
+                        }
+                        None => self.reportar(
+                            Regra::ComplexidadeInvalida,
+                            format!("notação de complexidade não reconhecida: {}", notacao),
+                            span,
+                        ),
+                    },
+                    Anotacao::RastreamentoCompleto(invariante) => {
+                        let mut conhecidos: HashSet<&str> = parametros.iter().map(|(p, _)| p.as_str()).collect();
+                        for corpo in &corpos {
+                            variaveis_locais(corpo, &mut conhecidos);
+                        }
+                        self.validar_invariante(invariante, &conhecidos, span);
+                    }
+                    Anotacao::AuditavelPor(orgao) if orgao.trim().is_empty() => {
+                        self.reportar(Regra::AutoridadeVazia, "auditavel_por exige um órgão".to_string(), span)
+                    }
+                    Anotacao::AuditavelPor(_) => {}
+                    Anotacao::SujeitoAVoto(autoridade, quorum) => {
+                        if autoridade.trim().is_empty() {
+                            self.reportar(Regra::AutoridadeVazia, "sujeito_a_voto exige uma autoridade".to_string(), span);
+                        }
+                        if !(*quorum > 0.0 && *quorum <= 1.0) {
+                            self.reportar(
+                                Regra::QuorumInvalido,
+                                format!("quorum {} fora do intervalo (0, 1]", quorum),
+                                span,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        self.diagnosticos
+    }
+
+    fn reportar(&mut self, regra: Regra, mensagem: String, span: Span) {
+        self.diagnosticos.push(Diagnostico { regra, mensagem, span });
+    }
+
+    /// `para cada` dentro de `para cada` (inclusive via chamadas) conta como
+    /// mais um fator de n.
+    fn validar_complexidade(&mut self, nome: &str, notacao: &str, limite: usize, corpo: &[Comando]) {
+        let mut excedidos = Vec::new();
+        self.lacos_excedidos(corpo, 0, limite, Span::default(), &mut Vec::new(), &mut excedidos);
+        for (profundidade, span) in excedidos {
+            self.reportar(
+                Regra::ComplexidadeExcedida,
+                format!(
+                    "'{}' declara {}, mas tem {} laços 'para cada' aninhados",
+                    nome, notacao, profundidade
+                ),
+                span,
+            );
+        }
+    }
+
+    fn lacos_excedidos(
+        &self,
+        corpo: &[Comando],
+        profundidade: usize,
+        limite: usize,
+        laco: Span,
+        pilha: &mut Vec<&'a str>,
+        excedidos: &mut Vec<(usize, Span)>,
+    ) {
+        for comando in corpo {
+            match comando {
+                Comando::ParaCada { corpo, span, .. } => {
+                    let profundidade = profundidade + 1;
+                    if profundidade > limite {
+                        excedidos.push((profundidade, *span));
+                        // Reportar só o laço mais externo que excede
+                        continue;
+                    }
+                    self.lacos_excedidos(corpo, profundidade, limite, *span, pilha, excedidos);
+                }
+                Comando::Se { entao, senao, .. } => {
+                    self.lacos_excedidos(entao, profundidade, limite, laco, pilha, excedidos);
+                    if let Some(senao) = senao {
+                        self.lacos_excedidos(senao, profundidade, limite, laco, pilha, excedidos);
+                    }
+                }
+                _ => {}
+            }
+            if profundidade > 0 {
+                // Chamadas dentro de um laço herdam a profundidade atual
+                for chamada in chamadas_diretas(comando) {
+                    if let Some((nome, corpo)) = self.funcoes.get_key_value(chamada.as_str()) {
+                        if pilha.contains(nome) {
+                            continue;
+                        }
+                        let antes = excedidos.len();
+                        pilha.push(nome);
+                        self.lacos_excedidos(corpo, profundidade, limite, laco, pilha, excedidos);
+                        pilha.pop();
+                        // O laço excedente está em outra função; aponta para o laço que chama
+                        if excedidos.len() > antes {
+                            excedidos.truncate(antes);
+                            excedidos.push((limite + 1, laco));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn validar_verificavel(&mut self, nome: &str, corpo: &[Comando], span: Span) {
+        let mut chamadas = Vec::new();
+        for comando in corpo {
+            chamadas.extend(chamadas_do_comando(comando));
+        }
+        for chamada in chamadas {
+            let permitida = FUNCOES_EMBUTIDAS.contains(&chamada.as_str())
+                || self.verificaveis.contains(chamada.as_str());
+            if !permitida {
+                self.reportar(
+                    Regra::VerificabilidadeViolada,
+                    format!(
+                        "'{}' é verificavel_mathematicamente, mas chama '{}', que não é",
+                        nome, chamada
+                    ),
+                    span,
+                );
+            }
+        }
+    }
+
+    /// Toda variável citada na invariante deve ser parâmetro ou variável local;
+    /// toda chamada deve ser a uma função conhecida.
+    fn validar_invariante(&mut self, invariante: &str, conhecidos: &HashSet<&str>, span: Span) {
+        let mut tokens = Vec::new();
+        for tok in Token::lexer(invariante) {
+            match tok {
+                Ok(t) => tokens.push(t),
+                Err(_) => {
+                    self.reportar(
+                        Regra::InvarianteInvalida,
+                        format!("invariante '{}' não é uma expressão válida", invariante),
+                        span,
+                    );
+                    return;
+                }
+            }
+        }
+
+        for (i, tok) in tokens.iter().enumerate() {
+            let Token::Identificador(nome) = tok else { continue };
+            let chamada = matches!(tokens.get(i + 1), Some(Token::ParenEsq));
+            let existe = if chamada {
+                self.funcoes.contains_key(nome.as_str()) || FUNCOES_EMBUTIDAS.contains(&nome.as_str())
+            } else {
+                conhecidos.contains(nome.as_str())
+            };
+            if !existe {
+                let tipo = if chamada { "função" } else { "variável" };
+                self.reportar(
+                    Regra::InvarianteInvalida,
+                    format!("invariante '{}' cita {} inexistente '{}'", invariante, tipo, nome),
+                    span,
+                );
+            }
+        }
+    }
+
+    fn recursiva(&self, nome: &str) -> bool {
+        let mut visitados = HashSet::new();
+        let mut pendentes: Vec<String> = match self.funcoes.get(nome) {
+            Some(corpo) => corpo.iter().flat_map(chamadas_do_comando).collect(),
+            None => return false,
+        };
+        while let Some(atual) = pendentes.pop() {
+            if atual == nome {
+                return true;
+            }
+            if let Some(corpo) = self.funcoes.get(atual.as_str()) {
+                if visitados.insert(atual) {
+                    pendentes.extend(corpo.iter().flat_map(chamadas_do_comando));
+                }
+            }
+        }
+        false
+    }
+}
+
+/// Profundidade máxima de `para cada` aninhados permitida por uma notação O:
+/// O(1) → 0, O(n) e O(n log n) → 1, O(n * n) → 2.
+fn profundidade_permitida(notacao: &str) -> Option<usize> {
+    let termos: Vec<&str> = notacao.strip_prefix("O(")?.strip_suffix(')')?.split_whitespace().collect();
+    if termos == ["1"] {
+        return Some(0);
+    }
+
+    let mut fatores = 0;
+    let mut anterior = "";
+    for termo in &termos {
+        match *termo {
+            "n" if anterior != "log" => fatores += 1,
+            "n" | "log" | "*" => {}
+            _ => return None,
+        }
+        anterior = termo;
+    }
+    (fatores > 0).then_some(fatores)
+}
+
+/// Chamadas nas expressões do próprio comando, sem entrar nos blocos de
+/// `para cada`/`se` (que o chamador percorre com a profundidade certa).
+fn chamadas_diretas(comando: &Comando) -> Vec<String> {
+    let mut chamadas = Vec::new();
+    match comando {
+        Comando::Atribuicao { expr, .. } | Comando::Retorno(expr) | Comando::Expressao(expr) => {
+            chamadas_da_expr(expr, &mut chamadas)
+        }
+        Comando::Se { cond, .. } => chamadas_da_expr(cond, &mut chamadas),
+        Comando::ParaCada { .. } => {}
+    }
+    chamadas
+}
+
+/// Todas as chamadas de um comando, incluindo as de blocos aninhados.
+fn chamadas_do_comando(comando: &Comando) -> Vec<String> {
+    let mut chamadas = Vec::new();
+    match comando {
+        Comando::Atribuicao { expr, .. } | Comando::Retorno(expr) | Comando::Expressao(expr) => {
+            chamadas_da_expr(expr, &mut chamadas)
+        }
+        Comando::Se { cond, entao, senao } => {
+            chamadas_da_expr(cond, &mut chamadas);
+            for c in entao.iter().chain(senao.iter().flatten()) {
+                chamadas.extend(chamadas_do_comando(c));
+            }
+        }
+        Comando::ParaCada { corpo, .. } => {
+            for c in corpo {
+                chamadas.extend(chamadas_do_comando(c));
+            }
+        }
+    }
+    chamadas
+}
+
+fn chamadas_da_expr(expr: &Expr, chamadas: &mut Vec<String>) {
+    match expr {
+        Expr::Chamada { funcao, args } => {
+            chamadas.push(funcao.clone());
+            for arg in args {
+                chamadas_da_expr(arg, chamadas);
+            }
+        }
+        Expr::Binaria { esq, dir, .. } => {
+            chamadas_da_expr(esq, chamadas);
+            chamadas_da_expr(dir, chamadas);
+        }
+        Expr::Literal(_) | Expr::Identificador(_) => {}
+    }
+}
+
+fn variaveis_locais<'b>(corpo: &'b [Comando], nomes: &mut HashSet<&'b str>) {
+    for comando in corpo {
+        match comando {
+            Comando::Atribuicao { var, .. } => {
+                nomes.insert(var);
+            }
+            Comando::ParaCada { var, corpo, .. } => {
+                nomes.insert(var);
+                variaveis_locais(corpo, nomes);
+            }
+            Comando::Se { entao, senao, .. } => {
+                variaveis_locais(entao, nomes);
+                if let Some(senao) = senao {
+                    variaveis_locais(senao, nomes);
+                }
+            }
+            Comando::Retorno(_) | Comando::Expressao(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn diagnosticos(codigo: &str) -> Vec<Diagnostico> {
+        validar(&parse(codigo).expect("código deve ser sintaticamente válido"))
+    }
+
+    #[test]
+    fn test_programa_valido_sem_diagnosticos() {
+        let codigo = r#"
+        funcao calcular_icms(valor: Decimal, aliquota: Decimal) -> Decimal
+            verificavel_mathematicamente
+            com_precisao(decimais: 2)
+            com_complexidade(O(1))
+        {
+            retorne arredondar(valor * aliquota, 2);
+        }
+
+        processo reparticao(arrecadacao: Decimal, indices: Lista<Decimal>) -> Decimal
+            com_complexidade(O(n))
+            com_rastreamento_completo("total <= arrecadacao")
+            sujeito_a_voto("Assembleia Legislativa", quorum: 0.5)
+            auditavel_por("Tribunal de Contas")
+        {
+            etapa distribuir {
+                total = 0;
+                para cada indice em indices {
+                    total = total + calcular_icms(arrecadacao, indice);
+                }
+                retorne total;
+            }
+        }
+        "#;
+        assert_eq!(diagnosticos(codigo), vec![]);
+    }
+
+    #[test]
+    fn test_laco_aninhado_excede_o_n() {
+        let codigo = "funcao soma(linhas: Lista<Decimal>, colunas: Lista<Decimal>) -> Decimal
+    com_complexidade(O(n))
+{
+    total = 0;
+    para cada a em linhas {
+        para cada b em colunas {
+            total = total + a * b;
+        }
+    }
+    retorne total;
+}";
+        let diags = diagnosticos(codigo);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].regra, Regra::ComplexidadeExcedida);
+        assert_eq!((diags[0].span.linha, diags[0].span.coluna), (6, 9));
+
+        // O mesmo laço é permitido sob O(n * n)
+        let quadratico = codigo.replace("O(n)", "O(n * n)");
+        assert_eq!(diagnosticos(&quadratico), vec![]);
+    }
+
+    #[test]
+    fn test_laco_aninhado_via_chamada() {
+        let codigo = r#"
+        funcao somar(valores: Lista<Decimal>) -> Decimal {
+            total = 0;
+            para cada v em valores { total = total + v; }
+            retorne total;
+        }
+
+        funcao somar_todos(valores: Lista<Decimal>) -> Decimal
+            com_complexidade(O(n))
+        {
+            total = 0;
+            para cada v em valores { total = total + somar(valores); }
+            retorne total;
+        }
+        "#;
+        let diags = diagnosticos(codigo);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].regra, Regra::ComplexidadeExcedida);
+        assert_eq!(diags[0].span.linha, 12);
+    }
+
+    #[test]
+    fn test_invariante_e_quorum() {
+        let codigo = r#"
+        processo reparticao(arrecadacao: Decimal) -> Decimal
+            com_rastreamento_completo("cota <= arrecadacao + reserva")
+            sujeito_a_voto("Conselho", quorum: 1.5)
+        {
+            etapa calculo {
+                cota = arrecadacao * 0.25;
+                retorne cota;
+            }
+        }
+        "#;
+        let diags = diagnosticos(codigo);
+        let regras: Vec<_> = diags.iter().map(|d| d.regra.clone()).collect();
+        assert_eq!(regras, vec![Regra::InvarianteInvalida, Regra::QuorumInvalido]);
+        assert!(diags[0].mensagem.contains("'reserva'"));
+        // Cada diagnóstico aponta para a anotação, não para a declaração
+        assert_eq!((diags[0].span.linha, diags[0].span.coluna), (3, 13));
+        assert_eq!((diags[1].span.linha, diags[1].span.coluna), (4, 13));
+
+        let zero = codigo.replace("1.5", "0").replace(" + reserva", "");
+        let regras: Vec<_> = diagnosticos(&zero).into_iter().map(|d| d.regra).collect();
+        assert_eq!(regras, vec![Regra::QuorumInvalido]);
+    }
+
+    #[test]
+    fn test_verificavel_e_recursao() {
+        let codigo = r#"
+        funcao auxiliar(x: Decimal) -> Decimal { retorne auxiliar(x); }
+
+        funcao principal(x: Decimal) -> Decimal
+            verificavel_mathematicamente
+        {
+            retorne auxiliar(x);
+        }
+        "#;
+        let regras: Vec<_> = diagnosticos(codigo).into_iter().map(|d| d.regra).collect();
+        assert_eq!(regras, vec![Regra::Recursao, Regra::VerificabilidadeViolada]);
+    }
+}
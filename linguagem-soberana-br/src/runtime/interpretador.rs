@@ -0,0 +1,597 @@
+// src/runtime/interpretador.rs
+// Interpretador da Linguagem Soberana - Fase A (ICMS)
+// Avalia `funcao` e `processo` diretamente sobre a AST, com aritmética decimal
+// de ponto fixo (sem ponto flutuante).
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::prelude::*;
+use rust_decimal::RoundingStrategy;
+
+use crate::parser::{
+    Anotacao, Comando, Declaracao, Expr, Funcao, Literal, Operador, Processo, Tipo,
+};
+
+/// Profundidade máxima de chamadas. A especificação proíbe recursão na Fase A;
+/// o limite evita que um programa inválido estoure a pilha do host.
+const PROFUNDIDADE_MAXIMA: usize = 64;
+
+/// Arredondamento ABNT NBR 5891 (meio para o par), usado ao aplicar precisão.
+const ARREDONDAMENTO: RoundingStrategy = RoundingStrategy::MidpointNearestEven;
+
+/// Valores em tempo de execução
+#[derive(Debug, Clone, PartialEq)]
+pub enum Valor {
+    Decimal(Decimal),
+    Inteiro(i64),
+    Booleano(bool),
+    Texto(String),
+    Lista(Vec<Valor>),
+}
+
+impl Valor {
+    /// Constrói um decimal a partir de texto (ex.: `"1234.56"`).
+    pub fn decimal(texto: &str) -> Result<Valor, ErroExecucao> {
+        Decimal::from_str(texto)
+            .map(Valor::Decimal)
+            .map_err(|_| ErroExecucao::DecimalInvalido(texto.to_string()))
+    }
+
+    pub fn como_decimal(&self) -> Option<Decimal> {
+        match self {
+            Valor::Decimal(d) => Some(*d),
+            Valor::Inteiro(i) => Some(Decimal::from(*i)),
+            _ => None,
+        }
+    }
+
+    fn nome_tipo(&self) -> &'static str {
+        match self {
+            Valor::Decimal(_) => "Decimal",
+            Valor::Inteiro(_) => "Inteiro",
+            Valor::Booleano(_) => "Booleano",
+            Valor::Texto(_) => "Texto",
+            Valor::Lista(_) => "Lista",
+        }
+    }
+}
+
+impl fmt::Display for Valor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Valor::Decimal(d) => write!(f, "{}", d),
+            Valor::Inteiro(i) => write!(f, "{}", i),
+            Valor::Booleano(b) => write!(f, "{}", if *b { "verdadeiro" } else { "falso" }),
+            Valor::Texto(t) => write!(f, "{}", t),
+            Valor::Lista(itens) => {
+                write!(f, "[")?;
+                for (i, item) in itens.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Erros de execução
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroExecucao {
+    Sintaxe(String),
+    FuncaoDesconhecida(String),
+    VariavelDesconhecida(String),
+    Aridade { funcao: String, esperado: usize, recebido: usize },
+    TipoIncompativel { esperado: String, encontrado: String },
+    DivisaoPorZero,
+    Estouro,
+    /// Texto que não representa um decimal
+    DecimalInvalido(String),
+    SemRetorno(String),
+    ProfundidadeExcedida(String),
+    /// Execução WebAssembly interrompida pelo limite de combustível
+    CombustivelEsgotado(u64),
+    /// Falha ao carregar ou executar o módulo WebAssembly
+    Wasm(String),
+}
+
+impl fmt::Display for ErroExecucao {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErroExecucao::Sintaxe(msg) => write!(f, "erro de sintaxe: {}", msg),
+            ErroExecucao::FuncaoDesconhecida(nome) => write!(f, "função desconhecida: {}", nome),
+            ErroExecucao::VariavelDesconhecida(nome) => write!(f, "variável desconhecida: {}", nome),
+            ErroExecucao::Aridade { funcao, esperado, recebido } => write!(
+                f,
+                "{} espera {} argumento(s), recebeu {}",
+                funcao, esperado, recebido
+            ),
+            ErroExecucao::TipoIncompativel { esperado, encontrado } => {
+                write!(f, "tipo incompatível: esperado {}, encontrado {}", esperado, encontrado)
+            }
+            ErroExecucao::DivisaoPorZero => write!(f, "divisão por zero"),
+            ErroExecucao::Estouro => write!(f, "estouro aritmético"),
+            ErroExecucao::DecimalInvalido(texto) => write!(f, "decimal inválido: {:?}", texto),
+            ErroExecucao::SemRetorno(nome) => write!(f, "{} terminou sem 'retorne'", nome),
+            ErroExecucao::ProfundidadeExcedida(nome) => {
+                write!(f, "profundidade máxima de chamadas excedida em {}", nome)
+            }
+            ErroExecucao::CombustivelEsgotado(limite) => {
+                write!(f, "combustível esgotado (limite de {})", limite)
+            }
+            ErroExecucao::Wasm(msg) => write!(f, "erro WebAssembly: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ErroExecucao {}
+
+/// Resultado de executar um bloco: segue para o próximo comando ou retorna.
+enum Fluxo {
+    Continua,
+    Retorna(Valor),
+}
+
+type Ambiente = HashMap<String, Valor>;
+
+/// Interpretador tree-walking sobre as declarações parseadas
+pub struct Interpretador {
+    funcoes: HashMap<String, Funcao>,
+    processos: HashMap<String, Processo>,
+}
+
+impl Interpretador {
+    pub fn new(declaracoes: Vec<Declaracao>) -> Self {
+        let mut funcoes = HashMap::new();
+        let mut processos = HashMap::new();
+        for decl in declaracoes {
+            match decl {
+                Declaracao::Funcao(f) => {
+                    funcoes.insert(f.nome.clone(), f);
+                }
+                Declaracao::Processo(p) => {
+                    processos.insert(p.nome.clone(), p);
+                }
+            }
+        }
+        Self { funcoes, processos }
+    }
+
+    /// Faz o parse do código-fonte e prepara o interpretador.
+    pub fn carregar(codigo: &str) -> Result<Self, ErroExecucao> {
+        let declaracoes = crate::parser::parse(codigo).map_err(ErroExecucao::Sintaxe)?;
+        Ok(Self::new(declaracoes))
+    }
+
+    /// Executa uma `funcao` ou um `processo` pelo nome.
+    pub fn executar(&self, nome: &str, args: Vec<Valor>) -> Result<Valor, ErroExecucao> {
+        if self.processos.contains_key(nome) {
+            self.executar_processo(nome, args)
+        } else {
+            self.chamar_funcao(nome, args, 0)
+        }
+    }
+
+    /// Executa as etapas de um processo em ordem, compartilhando o ambiente.
+    /// O primeiro `retorne` encerra o processo.
+    pub fn executar_processo(&self, nome: &str, args: Vec<Valor>) -> Result<Valor, ErroExecucao> {
+        let processo = self
+            .processos
+            .get(nome)
+            .ok_or_else(|| ErroExecucao::FuncaoDesconhecida(nome.to_string()))?;
+
+        let mut ambiente = vincular_parametros(nome, &processo.parametros, args)?;
+        for etapa in &processo.etapas {
+            if let Fluxo::Retorna(valor) = self.executar_bloco(&etapa.corpo, &mut ambiente, 0)? {
+                return ajustar_retorno(valor, &processo.retorno, &processo.anotacoes);
+            }
+        }
+
+        Err(ErroExecucao::SemRetorno(nome.to_string()))
+    }
+
+    fn chamar_funcao(&self, nome: &str, args: Vec<Valor>, profundidade: usize) -> Result<Valor, ErroExecucao> {
+        if profundidade >= PROFUNDIDADE_MAXIMA {
+            return Err(ErroExecucao::ProfundidadeExcedida(nome.to_string()));
+        }
+
+        let funcao = match self.funcoes.get(nome) {
+            Some(f) => f,
+            None => return chamar_embutida(nome, args),
+        };
+
+        let mut ambiente = vincular_parametros(nome, &funcao.parametros, args)?;
+        match self.executar_bloco(&funcao.corpo, &mut ambiente, profundidade + 1)? {
+            Fluxo::Retorna(valor) => ajustar_retorno(valor, &funcao.retorno, &funcao.anotacoes),
+            Fluxo::Continua => Err(ErroExecucao::SemRetorno(nome.to_string())),
+        }
+    }
+
+    fn executar_bloco(
+        &self,
+        comandos: &[Comando],
+        ambiente: &mut Ambiente,
+        profundidade: usize,
+    ) -> Result<Fluxo, ErroExecucao> {
+        for comando in comandos {
+            if let Fluxo::Retorna(valor) = self.executar_comando(comando, ambiente, profundidade)? {
+                return Ok(Fluxo::Retorna(valor));
+            }
+        }
+        Ok(Fluxo::Continua)
+    }
+
+    fn executar_comando(
+        &self,
+        comando: &Comando,
+        ambiente: &mut Ambiente,
+        profundidade: usize,
+    ) -> Result<Fluxo, ErroExecucao> {
+        match comando {
+            Comando::Atribuicao { var, expr } => {
+                let valor = self.avaliar(expr, ambiente, profundidade)?;
+                ambiente.insert(var.clone(), valor);
+                Ok(Fluxo::Continua)
+            }
+            Comando::Retorno(expr) => Ok(Fluxo::Retorna(self.avaliar(expr, ambiente, profundidade)?)),
+            Comando::Se { cond, entao, senao } => match self.avaliar(cond, ambiente, profundidade)? {
+                Valor::Booleano(true) => self.executar_bloco(entao, ambiente, profundidade),
+                Valor::Booleano(false) => match senao {
+                    Some(bloco) => self.executar_bloco(bloco, ambiente, profundidade),
+                    None => Ok(Fluxo::Continua),
+                },
+                outro => Err(incompativel("Booleano", &outro)),
+            },
+            Comando::ParaCada { var, colecao, corpo, .. } => {
+                let itens = match ambiente.get(colecao) {
+                    Some(Valor::Lista(itens)) => itens.clone(),
+                    Some(outro) => return Err(incompativel("Lista", outro)),
+                    None => return Err(ErroExecucao::VariavelDesconhecida(colecao.clone())),
+                };
+                // A variável do laço só existe dentro do laço
+                let anterior = ambiente.remove(var);
+                let mut fluxo = Fluxo::Continua;
+                for item in itens {
+                    ambiente.insert(var.clone(), item);
+                    fluxo = self.executar_bloco(corpo, ambiente, profundidade)?;
+                    if let Fluxo::Retorna(_) = fluxo {
+                        break;
+                    }
+                }
+                ambiente.remove(var);
+                if let Some(valor) = anterior {
+                    ambiente.insert(var.clone(), valor);
+                }
+                Ok(fluxo)
+            }
+            Comando::Expressao(expr) => {
+                self.avaliar(expr, ambiente, profundidade)?;
+                Ok(Fluxo::Continua)
+            }
+        }
+    }
+
+    fn avaliar(&self, expr: &Expr, ambiente: &Ambiente, profundidade: usize) -> Result<Valor, ErroExecucao> {
+        match expr {
+            Expr::Literal(literal) => Ok(avaliar_literal(literal)),
+            Expr::Identificador(nome) => ambiente
+                .get(nome)
+                .cloned()
+                .ok_or_else(|| ErroExecucao::VariavelDesconhecida(nome.clone())),
+            Expr::Binaria { esq, op, dir } => {
+                let esq = self.avaliar(esq, ambiente, profundidade)?;
+                let dir = self.avaliar(dir, ambiente, profundidade)?;
+                aplicar_operador(op, esq, dir)
+            }
+            Expr::Chamada { funcao, args } => {
+                let args = args
+                    .iter()
+                    .map(|a| self.avaliar(a, ambiente, profundidade))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.chamar_funcao(funcao, args, profundidade)
+            }
+        }
+    }
+}
+
+fn avaliar_literal(literal: &Literal) -> Valor {
+    match literal {
+        // O lexer entrega f64; a representação decimal mais curta do f64
+        // reconstrói exatamente o literal escrito (ex.: 0.25, 0.175).
+        Literal::Decimal(valor, _) => Valor::Decimal(
+            Decimal::from_str(&valor.to_string()).unwrap_or_else(|_| Decimal::from_f64(*valor).unwrap_or_default()),
+        ),
+        Literal::Inteiro(i) => Valor::Inteiro(*i),
+        Literal::Booleano(b) => Valor::Booleano(*b),
+        Literal::Texto(t) => Valor::Texto(t.clone()),
+    }
+}
+
+fn aplicar_operador(op: &Operador, esq: Valor, dir: Valor) -> Result<Valor, ErroExecucao> {
+    use Operador::*;
+
+    match op {
+        Igual | Diferente => {
+            let iguais = match (esq.como_decimal(), dir.como_decimal()) {
+                (Some(a), Some(b)) => a == b,
+                _ => esq == dir,
+            };
+            return Ok(Valor::Booleano(if let Igual = op { iguais } else { !iguais }));
+        }
+        Soma => {
+            if let (Valor::Texto(a), Valor::Texto(b)) = (&esq, &dir) {
+                return Ok(Valor::Texto(format!("{}{}", a, b)));
+            }
+        }
+        _ => {}
+    }
+
+    // Inteiro (+, -, *) Inteiro permanece Inteiro; o resto é Decimal.
+    if let (Valor::Inteiro(a), Valor::Inteiro(b)) = (&esq, &dir) {
+        let resultado = match op {
+            Soma => Some(a.checked_add(*b)),
+            Subtracao => Some(a.checked_sub(*b)),
+            Multiplicacao => Some(a.checked_mul(*b)),
+            _ => None,
+        };
+        if let Some(resultado) = resultado {
+            return resultado.map(Valor::Inteiro).ok_or(ErroExecucao::Estouro);
+        }
+    }
+
+    let a = esq.como_decimal().ok_or_else(|| incompativel("Decimal", &esq))?;
+    let b = dir.como_decimal().ok_or_else(|| incompativel("Decimal", &dir))?;
+
+    let decimal = |r: Option<Decimal>| r.map(Valor::Decimal).ok_or(ErroExecucao::Estouro);
+    match op {
+        Soma => decimal(a.checked_add(b)),
+        Subtracao => decimal(a.checked_sub(b)),
+        Multiplicacao => decimal(a.checked_mul(b)),
+        Divisao if b.is_zero() => Err(ErroExecucao::DivisaoPorZero),
+        Divisao => decimal(a.checked_div(b)),
+        Menor => Ok(Valor::Booleano(a < b)),
+        Maior => Ok(Valor::Booleano(a > b)),
+        MenorIgual => Ok(Valor::Booleano(a <= b)),
+        MaiorIgual => Ok(Valor::Booleano(a >= b)),
+        Igual | Diferente => unreachable!("tratado acima"),
+    }
+}
+
+/// Implementação de `crate::embutidas::FUNCOES_EMBUTIDAS`.
+fn chamar_embutida(nome: &str, args: Vec<Valor>) -> Result<Valor, ErroExecucao> {
+    let aridade = |esperado: usize| {
+        if args.len() == esperado {
+            Ok(())
+        } else {
+            Err(ErroExecucao::Aridade { funcao: nome.to_string(), esperado, recebido: args.len() })
+        }
+    };
+
+    match nome {
+        "arredondar" => {
+            aridade(2)?;
+            let valor = args[0].como_decimal().ok_or_else(|| incompativel("Decimal", &args[0]))?;
+            let casas = match &args[1] {
+                Valor::Inteiro(n) if (0..=28).contains(n) => *n as u32,
+                outro => return Err(incompativel("Inteiro entre 0 e 28", outro)),
+            };
+            Ok(Valor::Decimal(valor.round_dp_with_strategy(casas, ARREDONDAMENTO)))
+        }
+        "tamanho" => {
+            aridade(1)?;
+            match &args[0] {
+                Valor::Lista(itens) => Ok(Valor::Inteiro(itens.len() as i64)),
+                Valor::Texto(t) => Ok(Valor::Inteiro(t.chars().count() as i64)),
+                outro => Err(incompativel("Lista", outro)),
+            }
+        }
+        _ => Err(ErroExecucao::FuncaoDesconhecida(nome.to_string())),
+    }
+}
+
+fn vincular_parametros(
+    nome: &str,
+    parametros: &[(String, Tipo)],
+    args: Vec<Valor>,
+) -> Result<Ambiente, ErroExecucao> {
+    if parametros.len() != args.len() {
+        return Err(ErroExecucao::Aridade {
+            funcao: nome.to_string(),
+            esperado: parametros.len(),
+            recebido: args.len(),
+        });
+    }
+
+    parametros
+        .iter()
+        .zip(args)
+        .map(|((param, tipo), valor)| Ok((param.clone(), converter(valor, tipo)?)))
+        .collect()
+}
+
+/// Converte um valor para o tipo declarado. `Inteiro` é promovido a `Decimal`;
+/// entradas decimais são mantidas exatas (a precisão é aplicada no retorno).
+fn converter(valor: Valor, tipo: &Tipo) -> Result<Valor, ErroExecucao> {
+    match (tipo, valor) {
+        (Tipo::Decimal(_), Valor::Inteiro(i)) => Ok(Valor::Decimal(Decimal::from(i))),
+        (Tipo::Decimal(_), v @ Valor::Decimal(_))
+        | (Tipo::Inteiro, v @ Valor::Inteiro(_))
+        | (Tipo::Booleano, v @ Valor::Booleano(_))
+        | (Tipo::Texto, v @ Valor::Texto(_)) => Ok(v),
+        (Tipo::Lista(interno), Valor::Lista(itens)) => itens
+            .into_iter()
+            .map(|item| converter(item, interno))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Valor::Lista),
+        (Tipo::Mapeamento(_, _) | Tipo::Estrutura(_), v) => Ok(v),
+        (tipo, v) => Err(incompativel(&format!("{:?}", tipo), &v)),
+    }
+}
+
+/// Aplica o tipo de retorno e a precisão: `com_precisao(decimais: n)` tem
+/// prioridade sobre a precisão de `Decimal<n>`.
+fn ajustar_retorno(valor: Valor, retorno: &Tipo, anotacoes: &[Anotacao]) -> Result<Valor, ErroExecucao> {
+    let valor = converter(valor, retorno)?;
+    let precisao = anotacoes
+        .iter()
+        .find_map(|a| match a {
+            Anotacao::Precisao(n) => Some(*n),
+            _ => None,
+        })
+        .or(match retorno {
+            Tipo::Decimal(n) => Some(*n),
+            _ => None,
+        });
+
+    match (valor, precisao) {
+        (Valor::Decimal(d), Some(casas)) => {
+            Ok(Valor::Decimal(d.round_dp_with_strategy(casas as u32, ARREDONDAMENTO)))
+        }
+        (valor, _) => Ok(valor),
+    }
+}
+
+pub(crate) fn incompativel(esperado: &str, encontrado: &Valor) -> ErroExecucao {
+    ErroExecucao::TipoIncompativel {
+        esperado: esperado.to_string(),
+        encontrado: encontrado.nome_tipo().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICMS: &str = r#"
+    funcao calcular_icms(valor: Decimal, aliquota: Decimal) -> Decimal
+        verificavel_mathematicamente
+        com_precisao(decimais: 2)
+    {
+        retorne valor * aliquota;
+    }
+
+    funcao aliquota_interestadual(origem_sul_sudeste: Booleano, destino_sul_sudeste: Booleano) -> Decimal<3>
+    {
+        se origem_sul_sudeste == verdadeiro entao {
+            se destino_sul_sudeste == falso entao {
+                retorne 0.07;
+            }
+        }
+        retorne 0.12;
+    }
+
+    funcao total_operacoes(valores: Lista<Decimal>, aliquota: Decimal) -> Decimal
+        com_precisao(decimais: 2)
+    {
+        total = 0;
+        para cada valor em valores {
+            total = total + calcular_icms(valor, aliquota);
+        }
+        retorne total;
+    }
+
+    processo reparticao_icms(arrecadacao: Decimal, indice_municipio: Decimal) -> Decimal
+        auditavel_por("Tribunal de Contas")
+    {
+        etapa cota_municipios {
+            cota = arrecadacao * 0.25;
+        }
+        etapa cota_individual {
+            retorne cota * indice_municipio;
+        }
+        etapa nunca_executada {
+            retorne 0;
+        }
+    }
+    "#;
+
+    fn interpretador() -> Interpretador {
+        Interpretador::carregar(ICMS).expect("programa ICMS deve ser válido")
+    }
+
+    #[test]
+    fn test_precisao_decimal_sem_ponto_flutuante() {
+        let resultado = interpretador()
+            .executar("calcular_icms", vec![Valor::decimal("1000.10").unwrap(), Valor::decimal("0.175").unwrap()])
+            .unwrap();
+        // 1000.10 * 0.175 = 175.0175 -> 175.02 (duas casas)
+        assert_eq!(resultado, Valor::decimal("175.02").unwrap());
+
+        // Meio para o par: 0.125 -> 0.12
+        let resultado = interpretador()
+            .executar("calcular_icms", vec![Valor::decimal("0.5").unwrap(), Valor::decimal("0.25").unwrap()])
+            .unwrap();
+        assert_eq!(resultado, Valor::decimal("0.12").unwrap());
+    }
+
+    #[test]
+    fn test_se_senao_e_para_cada() {
+        let interp = interpretador();
+        let aliquota = interp
+            .executar("aliquota_interestadual", vec![Valor::Booleano(true), Valor::Booleano(false)])
+            .unwrap();
+        assert_eq!(aliquota, Valor::decimal("0.070").unwrap());
+
+        let valores = Valor::Lista(vec![Valor::decimal("100.00").unwrap(), Valor::Inteiro(250), Valor::decimal("33.33").unwrap()]);
+        let total = interp.executar("total_operacoes", vec![valores, Valor::decimal("0.18").unwrap()]).unwrap();
+        // 18.00 + 45.00 + 6.00 (5.9994 arredondado por chamada)
+        assert_eq!(total, Valor::decimal("69.00").unwrap());
+    }
+
+    #[test]
+    fn test_processo_executa_etapas_em_ordem() {
+        let resultado = interpretador()
+            .executar("reparticao_icms", vec![Valor::decimal("1000000").unwrap(), Valor::decimal("0.0125").unwrap()])
+            .unwrap();
+        assert_eq!(resultado, Valor::decimal("3125.00").unwrap());
+    }
+
+    #[test]
+    fn test_arquivos_icms() {
+        let exemplo = Interpretador::carregar(include_str!("../../examples/icms_reparticao.ls")).unwrap();
+        assert_eq!(
+            exemplo.executar("reparticao_icms", vec![Valor::decimal("1234567.89").unwrap()]).unwrap(),
+            Valor::decimal("308641.97").unwrap()
+        );
+
+        let caso_01 = Interpretador::carregar(include_str!("../../test_cases/icms/caso_01_simples.ls")).unwrap();
+        // 5_000_000.00 * 12_345 / 1_000_000 = 61725.00
+        assert_eq!(
+            caso_01
+                .executar("calcular_quota", vec![Valor::decimal("5000000.00").unwrap(), Valor::Inteiro(12_345)])
+                .unwrap(),
+            Valor::decimal("61725.00").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_erros_de_execucao() {
+        let interp = interpretador();
+        assert_eq!(
+            interp.executar("calcular_icms", vec![Valor::decimal("1").unwrap()]),
+            Err(ErroExecucao::Aridade { funcao: "calcular_icms".to_string(), esperado: 2, recebido: 1 })
+        );
+        assert_eq!(
+            interp.executar("calcular_icms", vec![Valor::Texto("x".into()), Valor::decimal("1").unwrap()]),
+            Err(ErroExecucao::TipoIncompativel { esperado: "Decimal(2)".into(), encontrado: "Texto".into() })
+        );
+        assert_eq!(Valor::decimal("12,50"), Err(ErroExecucao::DecimalInvalido("12,50".to_string())));
+        assert_eq!(
+            interp.executar("inexistente", vec![]),
+            Err(ErroExecucao::FuncaoDesconhecida("inexistente".to_string()))
+        );
+
+        let divisao = Interpretador::carregar("funcao f(x: Decimal) -> Decimal { retorne x / 0; }").unwrap();
+        assert_eq!(divisao.executar("f", vec![Valor::Inteiro(1)]), Err(ErroExecucao::DivisaoPorZero));
+
+        let recursiva = Interpretador::carregar("funcao f(x: Inteiro) -> Inteiro { retorne f(x); }").unwrap();
+        assert!(matches!(
+            recursiva.executar("f", vec![Valor::Inteiro(1)]),
+            Err(ErroExecucao::ProfundidadeExcedida(_))
+        ));
+    }
+}
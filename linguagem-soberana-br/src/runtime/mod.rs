@@ -0,0 +1,5 @@
+// src/runtime/mod.rs
+// Execução da Linguagem Soberana
+
+pub mod interpretador;
+pub mod wasm_runner;
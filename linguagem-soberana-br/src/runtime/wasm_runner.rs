@@ -0,0 +1,308 @@
+// src/runtime/wasm_runner.rs
+// Executor de módulos WebAssembly gerados pelo transpilador
+// Roda cada chamada em sandbox (wasmi) com limite de combustível.
+
+use std::collections::HashMap;
+
+use rust_decimal::prelude::*;
+use rust_decimal::RoundingStrategy;
+use wasmi::core::{Pages, TrapCode};
+use wasmi::{Config, Engine, Instance, Linker, Memory, Module, Store, Val};
+
+use super::interpretador::{incompativel, ErroExecucao, Valor};
+use crate::parser::Tipo;
+use crate::transpiler::{self, Assinatura, ModuloWasm, ESCALA_DECIMAL, MEMORIA_EXPORTADA};
+
+/// Limite de combustível padrão por chamada (≈ uma unidade por instrução).
+pub const COMBUSTIVEL_PADRAO: u64 = 10_000_000;
+
+/// Resultado de uma chamada
+#[derive(Debug, Clone, PartialEq)]
+pub struct Execucao {
+    pub valor: Valor,
+    pub combustivel_consumido: u64,
+}
+
+/// Executor de um módulo instanciado
+pub struct ExecutorWasm {
+    store: Store<()>,
+    instancia: Instance,
+    memoria: Memory,
+    assinaturas: HashMap<String, Assinatura>,
+    limite_combustivel: u64,
+}
+
+impl ExecutorWasm {
+    pub fn new(modulo: &ModuloWasm, limite_combustivel: u64) -> Result<Self, ErroExecucao> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let compilado = Module::new(&engine, &modulo.bytes).map_err(wasm)?;
+
+        let mut store = Store::new(&engine, ());
+        let instancia = Linker::<()>::new(&engine)
+            .instantiate(&mut store, &compilado)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(wasm)?;
+        let memoria = instancia
+            .get_memory(&store, MEMORIA_EXPORTADA)
+            .ok_or_else(|| ErroExecucao::Wasm(format!("módulo sem memória '{}'", MEMORIA_EXPORTADA)))?;
+
+        Ok(Self {
+            store,
+            instancia,
+            memoria,
+            assinaturas: modulo.assinaturas.clone(),
+            limite_combustivel,
+        })
+    }
+
+    /// Faz o parse, transpila e instancia o código-fonte.
+    pub fn compilar(codigo: &str, limite_combustivel: u64) -> Result<Self, ErroExecucao> {
+        let declaracoes = crate::parser::parse(codigo).map_err(ErroExecucao::Sintaxe)?;
+        let modulo = transpiler::transpilar(&declaracoes).map_err(ErroExecucao::Wasm)?;
+        Self::new(&modulo, limite_combustivel)
+    }
+
+    /// Executa uma `funcao` ou `processo` exportado.
+    pub fn executar(&mut self, nome: &str, args: Vec<Valor>) -> Result<Execucao, ErroExecucao> {
+        let assinatura = self
+            .assinaturas
+            .get(nome)
+            .cloned()
+            .ok_or_else(|| ErroExecucao::FuncaoDesconhecida(nome.to_string()))?;
+        let funcao = self
+            .instancia
+            .get_func(&self.store, nome)
+            .ok_or_else(|| ErroExecucao::FuncaoDesconhecida(nome.to_string()))?;
+
+        if assinatura.parametros.len() != args.len() {
+            return Err(ErroExecucao::Aridade {
+                funcao: nome.to_string(),
+                esperado: assinatura.parametros.len(),
+                recebido: args.len(),
+            });
+        }
+
+        // Listas são copiadas para a memória a partir do início a cada chamada
+        let mut cursor = 0usize;
+        let mut entradas = Vec::new();
+        for ((_, tipo), valor) in assinatura.parametros.iter().zip(args) {
+            match (tipo, valor) {
+                (Tipo::Lista(elemento), Valor::Lista(itens)) => {
+                    let mut bytes = Vec::with_capacity(itens.len() * 8);
+                    for item in &itens {
+                        bytes.extend_from_slice(&para_i64(item, elemento)?.to_le_bytes());
+                    }
+                    self.garantir_memoria(cursor + bytes.len())?;
+                    self.memoria.write(&mut self.store, cursor, &bytes).map_err(wasm)?;
+                    entradas.push(Val::I32(cursor as i32));
+                    entradas.push(Val::I32(itens.len() as i32));
+                    cursor += bytes.len();
+                }
+                (Tipo::Lista(_), outro) => return Err(incompativel("Lista", &outro)),
+                (Tipo::Booleano, Valor::Booleano(b)) => entradas.push(Val::I32(b as i32)),
+                (tipo, valor) => entradas.push(Val::I64(para_i64(&valor, tipo)?)),
+            }
+        }
+
+        self.store.set_fuel(self.limite_combustivel).map_err(wasm)?;
+        let mut saida = [Val::I64(0)];
+        if let Tipo::Booleano = assinatura.retorno {
+            saida[0] = Val::I32(0);
+        }
+
+        if let Err(erro) = funcao.call(&mut self.store, &entradas, &mut saida) {
+            return Err(match erro.as_trap_code() {
+                Some(TrapCode::OutOfFuel) => ErroExecucao::CombustivelEsgotado(self.limite_combustivel),
+                Some(TrapCode::IntegerDivisionByZero) => ErroExecucao::DivisaoPorZero,
+                Some(TrapCode::IntegerOverflow) => ErroExecucao::Estouro,
+                Some(TrapCode::UnreachableCodeReached) => ErroExecucao::SemRetorno(nome.to_string()),
+                _ => ErroExecucao::Wasm(erro.to_string()),
+            });
+        }
+
+        let restante = self.store.get_fuel().map_err(wasm)?;
+        let valor = match (&assinatura.retorno, &saida[0]) {
+            (Tipo::Decimal(_), Val::I64(fixo)) => {
+                let decimal = Decimal::new(*fixo, ESCALA_DECIMAL);
+                Valor::Decimal(match assinatura.precisao {
+                    Some(casas) => decimal.round_dp(u32::from(casas)),
+                    None => decimal.normalize(),
+                })
+            }
+            (Tipo::Inteiro, Val::I64(i)) => Valor::Inteiro(*i),
+            (Tipo::Booleano, Val::I32(b)) => Valor::Booleano(*b != 0),
+            (tipo, valor) => return Err(ErroExecucao::Wasm(format!("retorno {:?} inesperado para {:?}", valor, tipo))),
+        };
+
+        Ok(Execucao { valor, combustivel_consumido: self.limite_combustivel - restante })
+    }
+
+    fn garantir_memoria(&mut self, bytes: usize) -> Result<(), ErroExecucao> {
+        const PAGINA: usize = 64 * 1024;
+        let atuais = u32::from(self.memoria.current_pages(&self.store)) as usize;
+        let necessarias = bytes.div_ceil(PAGINA);
+        if necessarias > atuais {
+            let adicionais = Pages::new((necessarias - atuais) as u32)
+                .ok_or_else(|| ErroExecucao::Wasm("lista grande demais para a memória".to_string()))?;
+            self.memoria.grow(&mut self.store, adicionais).map_err(wasm)?;
+        }
+        Ok(())
+    }
+}
+
+/// Converte um valor para a representação i64 do módulo (`Decimal` em ponto
+/// fixo, arredondado meio para o par na 6ª casa).
+fn para_i64(valor: &Valor, tipo: &Tipo) -> Result<i64, ErroExecucao> {
+    match (tipo, valor) {
+        (Tipo::Inteiro, Valor::Inteiro(i)) => Ok(*i),
+        (Tipo::Decimal(_), Valor::Decimal(_) | Valor::Inteiro(_)) => {
+            let decimal = valor.como_decimal().unwrap_or_default();
+            decimal
+                .round_dp_with_strategy(ESCALA_DECIMAL, RoundingStrategy::MidpointNearestEven)
+                .checked_mul(Decimal::from(transpiler::FATOR_DECIMAL))
+                .and_then(|fixo| fixo.to_i64())
+                .ok_or(ErroExecucao::Estouro)
+        }
+        (tipo, valor) => Err(incompativel(&format!("{:?}", tipo), valor)),
+    }
+}
+
+fn wasm(erro: impl std::fmt::Display) -> ErroExecucao {
+    ErroExecucao::Wasm(erro.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::interpretador::Interpretador;
+
+    const ICMS: &str = r#"
+    funcao calcular_icms(valor: Decimal, aliquota: Decimal) -> Decimal
+        verificavel_mathematicamente
+        com_precisao(decimais: 2)
+    {
+        retorne valor * aliquota;
+    }
+
+    funcao aliquota_interestadual(origem_sul_sudeste: Booleano, destino_sul_sudeste: Booleano) -> Decimal<3>
+    {
+        se origem_sul_sudeste == verdadeiro entao {
+            se destino_sul_sudeste == falso entao {
+                retorne 0.07;
+            }
+        }
+        retorne 0.12;
+    }
+
+    funcao total_operacoes(valores: Lista<Decimal>, aliquota: Decimal) -> Decimal
+        com_precisao(decimais: 2)
+    {
+        total = 0;
+        para cada valor em valores {
+            total = total + calcular_icms(valor, aliquota);
+        }
+        retorne total;
+    }
+
+    funcao quota_por_habitante(receita: Decimal, populacao: Inteiro) -> Decimal<4>
+    {
+        retorne receita / populacao;
+    }
+
+    processo reparticao_icms(arrecadacao: Decimal, indice_municipio: Decimal) -> Decimal
+        auditavel_por("Tribunal de Contas")
+    {
+        etapa cota_municipios {
+            cota = arrecadacao * 0.25;
+        }
+        etapa cota_individual {
+            retorne cota * indice_municipio;
+        }
+    }
+    "#;
+
+    fn executor() -> ExecutorWasm {
+        ExecutorWasm::compilar(ICMS, COMBUSTIVEL_PADRAO).expect("programa ICMS deve compilar")
+    }
+
+    #[test]
+    fn test_resultados_iguais_ao_interpretador() {
+        let interpretador = Interpretador::carregar(ICMS).unwrap();
+        let mut executor = executor();
+
+        let casos = vec![
+            ("calcular_icms", vec![Valor::decimal("1000.10").unwrap(), Valor::decimal("0.175").unwrap()]),
+            ("calcular_icms", vec![Valor::decimal("0.5").unwrap(), Valor::decimal("0.25").unwrap()]),
+            ("calcular_icms", vec![Valor::decimal("-1234567.89").unwrap(), Valor::decimal("0.18").unwrap()]),
+            ("aliquota_interestadual", vec![Valor::Booleano(true), Valor::Booleano(false)]),
+            ("aliquota_interestadual", vec![Valor::Booleano(false), Valor::Booleano(false)]),
+            (
+                "total_operacoes",
+                vec![
+                    Valor::Lista(vec![Valor::decimal("100.00").unwrap(), Valor::Inteiro(250), Valor::decimal("33.33").unwrap()]),
+                    Valor::decimal("0.18").unwrap(),
+                ],
+            ),
+            ("quota_por_habitante", vec![Valor::decimal("1000000").unwrap(), Valor::Inteiro(3)]),
+            ("quota_por_habitante", vec![Valor::decimal("-10").unwrap(), Valor::Inteiro(7)]),
+            ("reparticao_icms", vec![Valor::decimal("1000000").unwrap(), Valor::decimal("0.0125").unwrap()]),
+        ];
+
+        for (nome, args) in casos {
+            let esperado = interpretador.executar(nome, args.clone()).unwrap();
+            let obtido = executor.executar(nome, args.clone()).unwrap();
+            assert_eq!(obtido.valor, esperado, "{}({:?})", nome, args);
+            assert!(obtido.combustivel_consumido > 0);
+        }
+
+        assert_eq!(
+            executor.executar("calcular_icms", vec![Valor::decimal("1000.10").unwrap(), Valor::decimal("0.175").unwrap()]).unwrap().valor.to_string(),
+            "175.02"
+        );
+    }
+
+    #[test]
+    fn test_limite_de_combustivel() {
+        let modulo = transpiler::transpilar(&crate::parser::parse(ICMS).unwrap()).unwrap();
+        let valores = Valor::Lista(vec![Valor::decimal("1.00").unwrap(); 1_000]);
+        let args = vec![valores, Valor::decimal("0.18").unwrap()];
+
+        let mut folgado = ExecutorWasm::new(&modulo, COMBUSTIVEL_PADRAO).unwrap();
+        let consumo = folgado.executar("total_operacoes", args.clone()).unwrap().combustivel_consumido;
+
+        let mut apertado = ExecutorWasm::new(&modulo, consumo / 2).unwrap();
+        assert_eq!(
+            apertado.executar("total_operacoes", args),
+            Err(ErroExecucao::CombustivelEsgotado(consumo / 2))
+        );
+    }
+
+    #[test]
+    fn test_armadilhas_viram_erros_de_execucao() {
+        let mut executor = ExecutorWasm::compilar(
+            r#"
+            funcao dividir(a: Decimal, b: Decimal) -> Decimal { retorne a / b; }
+            funcao dobrar(a: Inteiro) -> Inteiro { retorne a * 2; }
+            funcao sem_retorno(a: Inteiro) -> Inteiro { se a > 0 entao { retorne a; } }
+            "#,
+            COMBUSTIVEL_PADRAO,
+        )
+        .unwrap();
+
+        assert_eq!(
+            executor.executar("dividir", vec![Valor::Inteiro(1), Valor::Inteiro(0)]),
+            Err(ErroExecucao::DivisaoPorZero)
+        );
+        assert_eq!(executor.executar("dobrar", vec![Valor::Inteiro(i64::MAX)]), Err(ErroExecucao::Estouro));
+        assert_eq!(
+            executor.executar("sem_retorno", vec![Valor::Inteiro(-1)]),
+            Err(ErroExecucao::SemRetorno("sem_retorno".to_string()))
+        );
+        assert!(matches!(
+            executor.executar("dobrar", vec![Valor::Texto("x".to_string())]),
+            Err(ErroExecucao::TipoIncompativel { .. })
+        ));
+    }
+}
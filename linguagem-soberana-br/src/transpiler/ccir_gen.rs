@@ -0,0 +1,325 @@
+// src/transpiler/ccir_gen.rs
+// Lowering da Linguagem Soberana para a ConstitutionalIR do Crux-86
+//
+// Cada `funcao`/`processo` vira um grafo de blocos básicos cujo custo
+// energético é verificado por `ConstitutionalIR::analyze_energy_ceiling`
+// (teto de 0.152 J) antes da implantação.
+//
+// - leitura de variável ou literal → Load; atribuição → Store
+// - `+`, `-` e comparações → Add; `*` e `/` → Mul
+// - funções embutidas → SysCall; funções do programa são expandidas no
+//   ponto de chamada (não há recursão na Fase A)
+// - `se` → diamante de blocos; `retorne` → Jump para o bloco de saída
+// - `para cada` → cabeçalho, corpo e aresta de retorno limitada ao número
+//   máximo de iterações configurado para a coleção
+
+use std::collections::HashMap;
+
+use sasc_core::joule_jailer::{BasicBlock, ConstitutionalIR, Instruction, OpCode};
+
+use crate::parser::{Comando, Declaracao, Expr, Operador};
+use crate::embutidas::FUNCOES_EMBUTIDAS;
+
+/// Iterações assumidas para uma coleção sem limite configurado.
+pub const LIMITE_ITERACOES_PADRAO: u32 = 16;
+
+/// Gerador de ConstitutionalIR para as declarações de um programa
+pub struct GeradorCcir<'a> {
+    corpos: HashMap<&'a str, Vec<&'a Comando>>,
+    limite_padrao: u32,
+    limites: HashMap<String, u32>,
+}
+
+impl<'a> GeradorCcir<'a> {
+    pub fn new(declaracoes: &'a [Declaracao]) -> Self {
+        let corpos = declaracoes
+            .iter()
+            .map(|decl| match decl {
+                Declaracao::Funcao(f) => (f.nome.as_str(), f.corpo.iter().collect()),
+                Declaracao::Processo(p) => (p.nome.as_str(), p.etapas.iter().flat_map(|e| &e.corpo).collect()),
+            })
+            .collect();
+        Self { corpos, limite_padrao: LIMITE_ITERACOES_PADRAO, limites: HashMap::new() }
+    }
+
+    /// Número máximo de iterações para `para cada` sobre a coleção `colecao`.
+    pub fn com_limite(mut self, colecao: &str, iteracoes: u32) -> Self {
+        self.limites.insert(colecao.to_string(), iteracoes);
+        self
+    }
+
+    pub fn com_limite_padrao(mut self, iteracoes: u32) -> Self {
+        self.limite_padrao = iteracoes;
+        self
+    }
+
+    /// Gera a IR de uma `funcao` ou `processo`.
+    pub fn gerar(&self, nome: &str) -> Result<ConstitutionalIR, String> {
+        let mut construtor = Construtor::new();
+        let saida = construtor.novo_bloco();
+        self.expandir(nome, &mut construtor, saida, &mut Vec::new())?;
+        construtor.ligar(construtor.atual, saida);
+        Ok(construtor.ir)
+    }
+
+    /// Gera a IR e verifica o teto energético; devolve o custo do pior caso.
+    pub fn verificar_teto(&self, nome: &str) -> Result<f64, String> {
+        self.gerar(nome)?.analyze_energy_ceiling()
+    }
+
+    fn expandir(
+        &self,
+        nome: &str,
+        construtor: &mut Construtor,
+        saida: usize,
+        pilha: &mut Vec<String>,
+    ) -> Result<(), String> {
+        if pilha.iter().any(|n| n == nome) {
+            return Err(format!("recursão em '{}' não é permitida na Fase A", nome));
+        }
+        let corpo = self.corpos.get(nome).ok_or_else(|| format!("função desconhecida: {}", nome))?;
+
+        pilha.push(nome.to_string());
+        for comando in corpo {
+            self.comando(comando, construtor, saida, pilha)?;
+        }
+        pilha.pop();
+        Ok(())
+    }
+
+    fn comando(
+        &self,
+        comando: &Comando,
+        construtor: &mut Construtor,
+        saida: usize,
+        pilha: &mut Vec<String>,
+    ) -> Result<(), String> {
+        match comando {
+            Comando::Atribuicao { var, expr } => {
+                self.expr(expr, construtor, pilha)?;
+                construtor.emitir(OpCode::Store, var);
+            }
+            Comando::Expressao(expr) => self.expr(expr, construtor, pilha)?,
+            Comando::Retorno(expr) => {
+                self.expr(expr, construtor, pilha)?;
+                construtor.emitir(OpCode::Jump, "retorne");
+                construtor.ligar(construtor.atual, saida);
+                // O que vier depois do `retorne` é inalcançável
+                construtor.atual = construtor.novo_bloco();
+            }
+            Comando::Se { cond, entao, senao } => {
+                self.expr(cond, construtor, pilha)?;
+                construtor.emitir(OpCode::Jump, "se");
+                let origem = construtor.atual;
+                let juncao = construtor.novo_bloco();
+
+                for ramo in std::iter::once(entao).chain(senao.as_ref()) {
+                    let inicio = construtor.novo_bloco();
+                    construtor.ligar(origem, inicio);
+                    construtor.atual = inicio;
+                    for c in ramo {
+                        self.comando(c, construtor, saida, pilha)?;
+                    }
+                    construtor.ligar(construtor.atual, juncao);
+                }
+                if senao.is_none() {
+                    construtor.ligar(origem, juncao);
+                }
+                construtor.atual = juncao;
+            }
+            Comando::ParaCada { var, colecao, corpo, .. } => {
+                construtor.emitir(OpCode::Load, colecao);
+
+                let cabecalho = construtor.novo_bloco();
+                construtor.ligar(construtor.atual, cabecalho);
+                construtor.atual = cabecalho;
+                construtor.emitir(OpCode::Load, "indice");
+                construtor.emitir(OpCode::Add, "indice < tamanho");
+                construtor.emitir(OpCode::Jump, "para cada");
+
+                let inicio = construtor.novo_bloco();
+                let fim = construtor.novo_bloco();
+                construtor.ligar(cabecalho, inicio);
+                construtor.ligar(cabecalho, fim);
+
+                construtor.atual = inicio;
+                construtor.emitir(OpCode::Load, colecao);
+                construtor.emitir(OpCode::Store, var);
+                for c in corpo {
+                    self.comando(c, construtor, saida, pilha)?;
+                }
+                construtor.emitir(OpCode::Add, "indice + 1");
+                construtor.emitir(OpCode::Store, "indice");
+                construtor.emitir(OpCode::Jump, "para cada");
+                let limite = self.limites.get(colecao).copied().unwrap_or(self.limite_padrao);
+                construtor.ir.add_back_edge(construtor.atual, cabecalho, limite);
+
+                construtor.atual = fim;
+            }
+        }
+        Ok(())
+    }
+
+    fn expr(&self, expr: &Expr, construtor: &mut Construtor, pilha: &mut Vec<String>) -> Result<(), String> {
+        match expr {
+            Expr::Literal(literal) => construtor.emitir(OpCode::Load, &format!("{:?}", literal)),
+            Expr::Identificador(nome) => construtor.emitir(OpCode::Load, nome),
+            Expr::Binaria { esq, op, dir } => {
+                self.expr(esq, construtor, pilha)?;
+                self.expr(dir, construtor, pilha)?;
+                let opcode = match op {
+                    Operador::Multiplicacao | Operador::Divisao => OpCode::Mul,
+                    _ => OpCode::Add,
+                };
+                construtor.emitir(opcode, &format!("{:?}", op));
+            }
+            Expr::Chamada { funcao, args } => {
+                for arg in args {
+                    self.expr(arg, construtor, pilha)?;
+                }
+                if let Some(id) = FUNCOES_EMBUTIDAS.iter().position(|f| f == funcao) {
+                    construtor.emitir(OpCode::SysCall(id as u32), funcao);
+                } else {
+                    // Expande o corpo da função chamada entre a chamada e o retorno
+                    construtor.emitir(OpCode::Jump, funcao);
+                    let entrada = construtor.novo_bloco();
+                    let retorno = construtor.novo_bloco();
+                    construtor.ligar(construtor.atual, entrada);
+                    construtor.atual = entrada;
+                    self.expandir(funcao, construtor, retorno, pilha)?;
+                    construtor.ligar(construtor.atual, retorno);
+                    construtor.atual = retorno;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Estado do lowering: a IR em construção e o bloco corrente.
+struct Construtor {
+    ir: ConstitutionalIR,
+    atual: usize,
+    proximo: usize,
+}
+
+impl Construtor {
+    fn new() -> Self {
+        let mut construtor = Self { ir: ConstitutionalIR::new(0), atual: 0, proximo: 0 };
+        construtor.atual = construtor.novo_bloco();
+        construtor
+    }
+
+    fn novo_bloco(&mut self) -> usize {
+        let id = self.proximo;
+        self.proximo += 1;
+        self.ir.add_block(BasicBlock { id, instructions: Vec::new(), next_blocks: Vec::new() });
+        id
+    }
+
+    fn emitir(&mut self, op: OpCode, metadata: &str) {
+        if let Some(bloco) = self.ir.blocks.get_mut(&self.atual) {
+            bloco.instructions.push(Instruction { op, metadata: metadata.to_string() });
+        }
+    }
+
+    fn ligar(&mut self, de: usize, para: usize) {
+        if let Some(bloco) = self.ir.blocks.get_mut(&de) {
+            if !bloco.next_blocks.contains(&para) {
+                bloco.next_blocks.push(para);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    const PROGRAMA: &str = r#"
+    funcao calcular_icms(valor: Decimal, aliquota: Decimal) -> Decimal
+        com_precisao(decimais: 2)
+    {
+        retorne valor * aliquota;
+    }
+
+    funcao aliquota(interestadual: Booleano) -> Decimal
+    {
+        se interestadual == verdadeiro entao {
+            retorne 0.12;
+        } senao {
+            retorne 0.18;
+        }
+    }
+
+    funcao total(valores: Lista<Decimal>) -> Decimal
+    {
+        soma = 0;
+        para cada v em valores {
+            soma = soma + calcular_icms(v, 0.18);
+        }
+        retorne arredondar(soma, 2);
+    }
+    "#;
+
+    #[test]
+    fn test_funcao_simples_vira_um_caminho() {
+        let declaracoes = parse(PROGRAMA).unwrap();
+        let ir = GeradorCcir::new(&declaracoes).gerar("calcular_icms").unwrap();
+
+        let ops: Vec<OpCode> = ir.blocks[&ir.entry_point].instructions.iter().map(|i| i.op).collect();
+        assert_eq!(ops, vec![OpCode::Load, OpCode::Load, OpCode::Mul, OpCode::Jump]);
+        assert!(ir.loop_bounds.is_empty());
+        assert!(GeradorCcir::new(&declaracoes).verificar_teto("calcular_icms").is_ok());
+    }
+
+    #[test]
+    fn test_se_gera_diamante() {
+        let declaracoes = parse(PROGRAMA).unwrap();
+        let ir = GeradorCcir::new(&declaracoes).gerar("aliquota").unwrap();
+        let entrada = &ir.blocks[&ir.entry_point];
+        assert_eq!(entrada.next_blocks.len(), 2);
+        for ramo in &entrada.next_blocks {
+            assert_eq!(ir.blocks[ramo].instructions.last().map(|i| i.op), Some(OpCode::Jump));
+        }
+    }
+
+    #[test]
+    fn test_laco_tem_aresta_de_retorno_limitada() {
+        let declaracoes = parse(PROGRAMA).unwrap();
+        let ir = GeradorCcir::new(&declaracoes).com_limite("valores", 5).gerar("total").unwrap();
+
+        assert_eq!(ir.loop_bounds.len(), 1);
+        let (&(latch, cabecalho), &limite) = ir.loop_bounds.iter().next().unwrap();
+        assert_eq!(limite, 5);
+        assert!(ir.blocks[&latch].next_blocks.contains(&cabecalho));
+        // A chamada a calcular_icms foi expandida dentro do laço
+        assert!(ir
+            .blocks
+            .values()
+            .flat_map(|b| &b.instructions)
+            .any(|i| i.op == OpCode::Mul && i.metadata == "Multiplicacao"));
+        // arredondar é uma chamada ao sistema
+        assert!(ir.blocks.values().flat_map(|b| &b.instructions).any(|i| i.op == OpCode::SysCall(0)));
+    }
+
+    #[test]
+    fn test_teto_do_laco_depende_do_limite() {
+        let declaracoes = parse(
+            "funcao contar(itens: Lista<Inteiro>) -> Inteiro { n = 0; para cada i em itens { n = n + 1; } retorne n; }",
+        )
+        .unwrap();
+        let custo = GeradorCcir::new(&declaracoes).com_limite("itens", 2).verificar_teto("contar").unwrap();
+        // entrada 0.018 + 3 × cabeçalho 0.008 + 2 × corpo 0.044 + saída 0.006
+        assert!((custo - 0.136).abs() < 1e-9, "{}", custo);
+        let erro = GeradorCcir::new(&declaracoes).com_limite("itens", 100).verificar_teto("contar").unwrap_err();
+        assert!(erro.contains("VIOLAÇÃO TÉRMICA"), "{}", erro);
+    }
+
+    #[test]
+    fn test_recursao_rejeitada() {
+        let declaracoes = parse("funcao f(x: Inteiro) -> Inteiro { retorne f(x); }").unwrap();
+        assert!(GeradorCcir::new(&declaracoes).gerar("f").unwrap_err().contains("recursão"));
+    }
+}
@@ -0,0 +1,93 @@
+// src/transpiler/mod.rs
+// Transpilador da Linguagem Soberana - Fase A
+// Compila as declarações parseadas para um módulo WebAssembly autocontido.
+
+use std::collections::HashMap;
+
+use crate::parser::{Anotacao, Declaracao, Tipo};
+
+#[cfg(feature = "ccir")]
+pub mod ccir_gen;
+pub mod wasm_gen;
+
+/// Casas decimais da representação de ponto fixo: um `Decimal` é um `i64`
+/// escalado por 10^6 dentro do módulo.
+pub const ESCALA_DECIMAL: u32 = 6;
+pub const FATOR_DECIMAL: i64 = 1_000_000;
+
+/// Nome da memória exportada; listas são passadas como (ponteiro, tamanho)
+/// apontando para elementos `i64` nela.
+pub const MEMORIA_EXPORTADA: &str = "memoria";
+
+/// Assinatura de uma `funcao`/`processo` exportado, necessária para converter
+/// argumentos e retorno na fronteira com o host.
+#[derive(Debug, Clone)]
+pub struct Assinatura {
+    pub parametros: Vec<(String, Tipo)>,
+    pub retorno: Tipo,
+    /// Casas decimais aplicadas ao retorno (`com_precisao` ou `Decimal<n>`)
+    pub precisao: Option<u8>,
+}
+
+/// Módulo WebAssembly gerado
+#[derive(Debug, Clone)]
+pub struct ModuloWasm {
+    pub bytes: Vec<u8>,
+    pub assinaturas: HashMap<String, Assinatura>,
+}
+
+/// Compila um programa para WebAssembly. Cada `funcao` e `processo` vira uma
+/// função exportada com o mesmo nome.
+pub fn transpilar(declaracoes: &[Declaracao]) -> Result<ModuloWasm, String> {
+    wasm_gen::GeradorWasm::new(declaracoes)?.gerar()
+}
+
+/// `com_precisao(decimais: n)` tem prioridade sobre a precisão de `Decimal<n>`.
+pub(crate) fn precisao_retorno(retorno: &Tipo, anotacoes: &[Anotacao]) -> Option<u8> {
+    anotacoes
+        .iter()
+        .find_map(|a| match a {
+            Anotacao::Precisao(n) => Some(*n),
+            _ => None,
+        })
+        .or(match retorno {
+            Tipo::Decimal(n) => Some(*n),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_modulo_valido_e_exportado() {
+        let programa = parse(
+            r#"
+            funcao calcular_icms(valor: Decimal, aliquota: Decimal) -> Decimal
+                com_precisao(decimais: 2)
+            {
+                retorne valor * aliquota;
+            }
+            "#,
+        )
+        .unwrap();
+        let modulo = transpilar(&programa).unwrap();
+        assert_eq!(&modulo.bytes[..4], b"\0asm");
+        assert_eq!(modulo.assinaturas["calcular_icms"].precisao, Some(2));
+
+        let engine = wasmi::Engine::default();
+        let compilado = wasmi::Module::new(&engine, &modulo.bytes).expect("módulo deve validar");
+        let mut exportados: Vec<_> = compilado.exports().map(|e| e.name().to_string()).collect();
+        exportados.sort();
+        assert_eq!(exportados, vec!["calcular_icms", MEMORIA_EXPORTADA]);
+    }
+
+    #[test]
+    fn test_tipos_nao_suportados() {
+        let programa = parse("funcao saudacao(nome: Texto) -> Texto { retorne nome; }").unwrap();
+        let erro = transpilar(&programa).unwrap_err();
+        assert!(erro.contains("Texto"), "{}", erro);
+    }
+}
@@ -0,0 +1,755 @@
+// src/transpiler/wasm_gen.rs
+// Geração de código WebAssembly a partir da AST da Linguagem Soberana
+//
+// Representação dentro do módulo:
+// - `Decimal`  → i64 em ponto fixo (valor × 10^ESCALA_DECIMAL)
+// - `Inteiro`  → i64
+// - `Booleano` → i32
+// - `Lista<T>` → dois parâmetros i32 (ponteiro, tamanho) na memória exportada,
+//                elementos i64 em little-endian; só aceita como parâmetro
+//
+// Estouro aritmético gera trap `IntegerOverflow`; divisão por zero gera
+// `IntegerDivisionByZero`; terminar sem `retorne` gera `unreachable`.
+// Produtos e quocientes decimais são truncados na 6ª casa; o arredondamento
+// (meio para o par, ABNT NBR 5891) é aplicado apenas no retorno, como no
+// interpretador.
+
+use std::collections::HashMap;
+
+use rust_decimal::prelude::*;
+use wasm_encoder::{
+    BlockType, CodeSection, ExportKind, ExportSection, Function, FunctionSection, Instruction, MemArg,
+    MemorySection, MemoryType, Module, TypeSection, ValType,
+};
+
+use super::{precisao_retorno, Assinatura, ModuloWasm, ESCALA_DECIMAL, FATOR_DECIMAL, MEMORIA_EXPORTADA};
+use crate::parser::{Comando, Declaracao, Expr, Literal, Operador, Tipo};
+
+type Instrucao = Instruction<'static>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TipoValor {
+    Decimal,
+    Inteiro,
+    Booleano,
+}
+
+impl TipoValor {
+    fn de(tipo: &Tipo) -> Result<Self, String> {
+        match tipo {
+            Tipo::Decimal(_) => Ok(TipoValor::Decimal),
+            Tipo::Inteiro => Ok(TipoValor::Inteiro),
+            Tipo::Booleano => Ok(TipoValor::Booleano),
+            outro => Err(format!("tipo {:?} não suportado no backend WebAssembly", outro)),
+        }
+    }
+
+    fn val_type(self) -> ValType {
+        match self {
+            TipoValor::Booleano => ValType::I32,
+            TipoValor::Decimal | TipoValor::Inteiro => ValType::I64,
+        }
+    }
+
+    fn numerico(self) -> bool {
+        self != TipoValor::Booleano
+    }
+}
+
+/// `Inteiro` sobe para `Decimal`; qualquer outra combinação é incompatível.
+fn unir(a: TipoValor, b: TipoValor) -> Option<TipoValor> {
+    match (a, b) {
+        _ if a == b => Some(a),
+        (TipoValor::Inteiro, TipoValor::Decimal) | (TipoValor::Decimal, TipoValor::Inteiro) => Some(TipoValor::Decimal),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Local {
+    Escalar { indice: u32, tipo: TipoValor },
+    Lista { ponteiro: u32, tamanho: u32, elemento: TipoValor },
+}
+
+/// Funções auxiliares `(i64, i64) -> i64` emitidas em todo módulo, após as
+/// declarações do programa.
+#[derive(Debug, Clone, Copy)]
+enum Auxiliar {
+    SomaChecada,
+    SubtracaoChecada,
+    MultiplicacaoChecada,
+    MultiplicacaoFixa,
+    DivisaoFixa,
+    Arredondar,
+}
+
+const AUXILIARES: [Auxiliar; 6] = [
+    Auxiliar::SomaChecada,
+    Auxiliar::SubtracaoChecada,
+    Auxiliar::MultiplicacaoChecada,
+    Auxiliar::MultiplicacaoFixa,
+    Auxiliar::DivisaoFixa,
+    Auxiliar::Arredondar,
+];
+
+/// Declaração vista de forma uniforme: um processo é o corpo de suas etapas em ordem.
+struct Unidade<'a> {
+    nome: &'a str,
+    parametros: &'a [(String, Tipo)],
+    retorno: &'a Tipo,
+    precisao: Option<u8>,
+    corpo: Vec<&'a Comando>,
+}
+
+pub struct GeradorWasm<'a> {
+    unidades: Vec<Unidade<'a>>,
+    indices: HashMap<&'a str, u32>,
+}
+
+impl<'a> GeradorWasm<'a> {
+    pub fn new(declaracoes: &'a [Declaracao]) -> Result<Self, String> {
+        let mut unidades = Vec::new();
+        let mut indices = HashMap::new();
+        for decl in declaracoes {
+            let unidade = match decl {
+                Declaracao::Funcao(f) => Unidade {
+                    nome: &f.nome,
+                    parametros: &f.parametros,
+                    retorno: &f.retorno,
+                    precisao: precisao_retorno(&f.retorno, &f.anotacoes),
+                    corpo: f.corpo.iter().collect(),
+                },
+                Declaracao::Processo(p) => Unidade {
+                    nome: &p.nome,
+                    parametros: &p.parametros,
+                    retorno: &p.retorno,
+                    precisao: precisao_retorno(&p.retorno, &p.anotacoes),
+                    corpo: p.etapas.iter().flat_map(|e| &e.corpo).collect(),
+                },
+            };
+            if indices.insert(unidade.nome, unidades.len() as u32).is_some() {
+                return Err(format!("'{}' declarada mais de uma vez", unidade.nome));
+            }
+            unidades.push(unidade);
+        }
+        Ok(Self { unidades, indices })
+    }
+
+    pub fn gerar(&self) -> Result<ModuloWasm, String> {
+        let mut tipos = TypeSection::new();
+        let mut funcoes = FunctionSection::new();
+        let mut exportacoes = ExportSection::new();
+        let mut codigo = CodeSection::new();
+        let mut assinaturas = HashMap::new();
+
+        for (indice, unidade) in self.unidades.iter().enumerate() {
+            let mut parametros = Vec::new();
+            for (_, tipo) in unidade.parametros {
+                match tipo {
+                    Tipo::Lista(_) => parametros.extend([ValType::I32, ValType::I32]),
+                    tipo => parametros.push(TipoValor::de(tipo)?.val_type()),
+                }
+            }
+            let retorno = TipoValor::de(unidade.retorno)
+                .map_err(|e| format!("retorno de '{}': {}", unidade.nome, e))?;
+
+            tipos.ty().function(parametros, [retorno.val_type()]);
+            funcoes.function(indice as u32);
+            exportacoes.export(unidade.nome, ExportKind::Func, indice as u32);
+            codigo.function(&CorpoFuncao::compilar(self, unidade)?);
+
+            assinaturas.insert(
+                unidade.nome.to_string(),
+                Assinatura {
+                    parametros: unidade.parametros.to_vec(),
+                    retorno: unidade.retorno.clone(),
+                    precisao: unidade.precisao,
+                },
+            );
+        }
+
+        for (i, auxiliar) in AUXILIARES.iter().enumerate() {
+            let indice = (self.unidades.len() + i) as u32;
+            tipos.ty().function([ValType::I64, ValType::I64], [ValType::I64]);
+            funcoes.function(indice);
+            codigo.function(&self.gerar_auxiliar(*auxiliar));
+        }
+
+        let mut memorias = MemorySection::new();
+        memorias.memory(MemoryType {
+            minimum: 1,
+            maximum: None,
+            memory64: false,
+            shared: false,
+            page_size_log2: None,
+        });
+        exportacoes.export(MEMORIA_EXPORTADA, ExportKind::Memory, 0);
+
+        let mut modulo = Module::new();
+        modulo
+            .section(&tipos)
+            .section(&funcoes)
+            .section(&memorias)
+            .section(&exportacoes)
+            .section(&codigo);
+
+        Ok(ModuloWasm { bytes: modulo.finish(), assinaturas })
+    }
+
+    fn indice_auxiliar(&self, auxiliar: Auxiliar) -> u32 {
+        self.unidades.len() as u32 + auxiliar as u32
+    }
+
+    fn gerar_auxiliar(&self, auxiliar: Auxiliar) -> Function {
+        use Instruction::*;
+
+        // Parâmetros: 0 = a, 1 = b. Locais auxiliares a partir de 2.
+        let (a, b) = (0, 1);
+        let chamar = |aux| Call(self.indice_auxiliar(aux));
+        let mut f;
+        match auxiliar {
+            Auxiliar::SomaChecada | Auxiliar::SubtracaoChecada => {
+                let r = 2;
+                f = Function::new([(1, ValType::I64)]);
+                let (op, sinal_b) = match auxiliar {
+                    // a + b estoura se a e b têm o mesmo sinal e r tem o oposto
+                    Auxiliar::SomaChecada => (I64Add, LocalGet(r)),
+                    // a - b estoura se a e b têm sinais opostos e r difere de a
+                    _ => (I64Sub, LocalGet(a)),
+                };
+                for i in [LocalGet(a), LocalGet(b), op, LocalSet(r)] {
+                    f.instruction(&i);
+                }
+                for i in [LocalGet(a), LocalGet(r), I64Xor, LocalGet(b), sinal_b, I64Xor, I64And, I64Const(0), I64LtS, If(BlockType::Empty)] {
+                    f.instruction(&i);
+                }
+                estouro(&mut f);
+                f.instruction(&End).instruction(&LocalGet(r));
+            }
+            Auxiliar::MultiplicacaoChecada => {
+                let r = 2;
+                f = Function::new([(1, ValType::I64)]);
+                for i in [
+                    LocalGet(a), LocalGet(b), I64Mul, LocalSet(r),
+                    LocalGet(a), I64Eqz, I32Eqz, If(BlockType::Empty),
+                    // i64::MIN / -1 também causa trap de estouro
+                    LocalGet(r), LocalGet(a), I64DivS, LocalGet(b), I64Ne, If(BlockType::Empty),
+                ] {
+                    f.instruction(&i);
+                }
+                estouro(&mut f);
+                f.instruction(&End).instruction(&End).instruction(&LocalGet(r));
+            }
+            Auxiliar::MultiplicacaoFixa => {
+                // a·b/F = qa·qb·F + qa·rb + ra·qb + ra·rb/F, com a = qa·F + ra
+                let (qa, ra, qb, rb) = (2, 3, 4, 5);
+                f = Function::new([(4, ValType::I64)]);
+                for (x, q, r) in [(a, qa, ra), (b, qb, rb)] {
+                    for i in [
+                        LocalGet(x), I64Const(FATOR_DECIMAL), I64DivS, LocalSet(q),
+                        LocalGet(x), I64Const(FATOR_DECIMAL), I64RemS, LocalSet(r),
+                    ] {
+                        f.instruction(&i);
+                    }
+                }
+                for i in [
+                    LocalGet(qa), LocalGet(qb), chamar(Auxiliar::MultiplicacaoChecada),
+                    I64Const(FATOR_DECIMAL), chamar(Auxiliar::MultiplicacaoChecada),
+                    LocalGet(qa), LocalGet(rb), chamar(Auxiliar::MultiplicacaoChecada),
+                    chamar(Auxiliar::SomaChecada),
+                    LocalGet(ra), LocalGet(qb), chamar(Auxiliar::MultiplicacaoChecada),
+                    chamar(Auxiliar::SomaChecada),
+                    LocalGet(ra), LocalGet(rb), I64Mul, I64Const(FATOR_DECIMAL), I64DivS,
+                    chamar(Auxiliar::SomaChecada),
+                ] {
+                    f.instruction(&i);
+                }
+            }
+            Auxiliar::DivisaoFixa => {
+                // Divisão longa sobre valores absolutos, uma casa decimal por vez
+                let (negativo, ua, ub, q, r) = (2, 3, 4, 5, 6);
+                f = Function::new([(5, ValType::I64)]);
+                for i in [
+                    LocalGet(a), I64Const(0), I64LtS, LocalGet(b), I64Const(0), I64LtS, I32Xor,
+                    I64ExtendI32U, LocalSet(negativo),
+                ] {
+                    f.instruction(&i);
+                }
+                for (x, u) in [(a, ua), (b, ub)] {
+                    for i in [
+                        I64Const(0), LocalGet(x), I64Sub, LocalGet(x), LocalGet(x), I64Const(0), I64LtS,
+                        Select, LocalSet(u),
+                    ] {
+                        f.instruction(&i);
+                    }
+                }
+                for i in [
+                    LocalGet(ua), LocalGet(ub), I64DivS, I64Const(FATOR_DECIMAL),
+                    chamar(Auxiliar::MultiplicacaoChecada), LocalSet(q),
+                    LocalGet(ua), LocalGet(ub), I64RemS, LocalSet(r),
+                ] {
+                    f.instruction(&i);
+                }
+                for casa in (0..ESCALA_DECIMAL).rev() {
+                    for i in [
+                        LocalGet(r), I64Const(10), chamar(Auxiliar::MultiplicacaoChecada), LocalSet(r),
+                        LocalGet(q), LocalGet(r), LocalGet(ub), I64DivS, I64Const(10_i64.pow(casa)), I64Mul,
+                        chamar(Auxiliar::SomaChecada), LocalSet(q),
+                        LocalGet(r), LocalGet(ub), I64RemS, LocalSet(r),
+                    ] {
+                        f.instruction(&i);
+                    }
+                }
+                for i in [
+                    I64Const(0), LocalGet(q), I64Sub, LocalGet(q), LocalGet(negativo), I32WrapI64, Select,
+                ] {
+                    f.instruction(&i);
+                }
+            }
+            Auxiliar::Arredondar => {
+                // Arredonda a (ponto fixo) para múltiplo de b, meio para o par
+                let (q, r, dobro) = (2, 3, 4);
+                f = Function::new([(3, ValType::I64)]);
+                for i in [
+                    LocalGet(a), LocalGet(b), I64DivS, LocalSet(q),
+                    LocalGet(a), LocalGet(b), I64RemS, LocalSet(r),
+                    I64Const(0), LocalGet(r), I64Sub, LocalGet(r), LocalGet(r), I64Const(0), I64LtS, Select,
+                    I64Const(2), I64Mul, LocalSet(dobro),
+                    LocalGet(dobro), LocalGet(b), I64GtS,
+                    LocalGet(dobro), LocalGet(b), I64Eq, LocalGet(q), I64Const(1), I64And, I64Const(0), I64Ne, I32And,
+                    I32Or, If(BlockType::Empty),
+                    LocalGet(q), I64Const(-1), I64Const(1), LocalGet(a), I64Const(0), I64LtS, Select, I64Add, LocalSet(q),
+                    End,
+                    LocalGet(q), LocalGet(b), chamar(Auxiliar::MultiplicacaoChecada),
+                ] {
+                    f.instruction(&i);
+                }
+            }
+        }
+        f.instruction(&End);
+        f
+    }
+}
+
+/// Gera trap `IntegerOverflow` (`i64::MIN / -1`), distinguível de `unreachable`.
+fn estouro(f: &mut Function) {
+    for i in [
+        Instruction::I64Const(i64::MIN),
+        Instruction::I64Const(-1),
+        Instruction::I64DivS,
+        Instruction::Drop,
+    ] {
+        f.instruction(&i);
+    }
+}
+
+/// Compilação de uma `funcao`/`processo`
+struct CorpoFuncao<'g, 'a> {
+    gerador: &'g GeradorWasm<'a>,
+    unidade: &'g Unidade<'a>,
+    locais: HashMap<String, Local>,
+    /// Tipos dos locais declarados além dos parâmetros
+    declarados: Vec<ValType>,
+    proximo: u32,
+    retorno: TipoValor,
+    instrucoes: Vec<Instrucao>,
+}
+
+impl<'g, 'a> CorpoFuncao<'g, 'a> {
+    fn compilar(gerador: &'g GeradorWasm<'a>, unidade: &'g Unidade<'a>) -> Result<Function, String> {
+        let mut corpo = CorpoFuncao {
+            gerador,
+            unidade,
+            locais: HashMap::new(),
+            declarados: Vec::new(),
+            proximo: 0,
+            retorno: TipoValor::de(unidade.retorno)?,
+            instrucoes: Vec::new(),
+        };
+
+        for (nome, tipo) in unidade.parametros {
+            let local = match tipo {
+                Tipo::Lista(elemento) => {
+                    let elemento = TipoValor::de(elemento)?;
+                    if elemento == TipoValor::Booleano {
+                        return Err(format!("Lista<Booleano> não suportada ('{}')", nome));
+                    }
+                    corpo.proximo += 2;
+                    Local::Lista { ponteiro: corpo.proximo - 2, tamanho: corpo.proximo - 1, elemento }
+                }
+                tipo => {
+                    corpo.proximo += 1;
+                    Local::Escalar { indice: corpo.proximo - 1, tipo: TipoValor::de(tipo)? }
+                }
+            };
+            corpo.locais.insert(nome.clone(), local);
+        }
+
+        for (nome, tipo) in corpo.inferir_variaveis()? {
+            let indice = corpo.novo_local(tipo.val_type());
+            corpo.locais.insert(nome, Local::Escalar { indice, tipo });
+        }
+
+        for comando in &unidade.corpo {
+            corpo.comando(comando)?;
+        }
+        // Sem `retorne` no fim do corpo: trap
+        corpo.instrucoes.push(Instruction::Unreachable);
+        corpo.instrucoes.push(Instruction::End);
+
+        let mut funcao = Function::new(corpo.declarados.iter().map(|t| (1, *t)));
+        for instrucao in &corpo.instrucoes {
+            funcao.instruction(instrucao);
+        }
+        Ok(funcao)
+    }
+
+    fn novo_local(&mut self, tipo: ValType) -> u32 {
+        self.declarados.push(tipo);
+        self.proximo += 1;
+        self.proximo - 1
+    }
+
+    fn erro(&self, mensagem: String) -> String {
+        format!("em '{}': {}", self.unidade.nome, mensagem)
+    }
+
+    /// Tipo de cada variável local (atribuições e variáveis de laço). Itera até
+    /// um ponto fixo, pois `total = 0; total = total + x` só se revela
+    /// `Decimal` na segunda atribuição.
+    fn inferir_variaveis(&self) -> Result<Vec<(String, TipoValor)>, String> {
+        let mut tipos: HashMap<String, TipoValor> = HashMap::new();
+        loop {
+            let antes = tipos.clone();
+            for comando in &self.unidade.corpo {
+                self.inferir_comando(comando, &mut tipos)?;
+            }
+            if tipos == antes {
+                break;
+            }
+        }
+        let mut variaveis: Vec<_> = tipos.into_iter().collect();
+        variaveis.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(variaveis)
+    }
+
+    fn inferir_comando(&self, comando: &Comando, tipos: &mut HashMap<String, TipoValor>) -> Result<(), String> {
+        let registrar = |var: &str, tipo: TipoValor, tipos: &mut HashMap<String, TipoValor>| {
+            if self.locais.contains_key(var) {
+                // Parâmetros mantêm o tipo declarado; a conversão é checada na geração
+                return Ok(());
+            }
+            let unido = match tipos.get(var) {
+                Some(atual) => unir(*atual, tipo)
+                    .ok_or_else(|| self.erro(format!("variável '{}' recebe {:?} e {:?}", var, atual, tipo)))?,
+                None => tipo,
+            };
+            tipos.insert(var.to_string(), unido);
+            Ok::<(), String>(())
+        };
+
+        match comando {
+            Comando::Atribuicao { var, expr } => {
+                if let Some(tipo) = self.tipo_expr(expr, tipos)? {
+                    registrar(var, tipo, tipos)?;
+                }
+            }
+            Comando::ParaCada { var, colecao, corpo, .. } => {
+                match self.locais.get(colecao) {
+                    Some(Local::Lista { elemento, .. }) => registrar(var, *elemento, tipos)?,
+                    _ => return Err(self.erro(format!("'{}' não é um parâmetro do tipo Lista", colecao))),
+                }
+                for c in corpo {
+                    self.inferir_comando(c, tipos)?;
+                }
+            }
+            Comando::Se { entao, senao, .. } => {
+                for c in entao.iter().chain(senao.iter().flatten()) {
+                    self.inferir_comando(c, tipos)?;
+                }
+            }
+            Comando::Retorno(_) | Comando::Expressao(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Tipo de uma expressão; `None` se depende de variável ainda não inferida.
+    fn tipo_expr(&self, expr: &Expr, tipos: &HashMap<String, TipoValor>) -> Result<Option<TipoValor>, String> {
+        let tipo = match expr {
+            Expr::Literal(Literal::Decimal(..)) => TipoValor::Decimal,
+            Expr::Literal(Literal::Inteiro(_)) => TipoValor::Inteiro,
+            Expr::Literal(Literal::Booleano(_)) => TipoValor::Booleano,
+            Expr::Literal(Literal::Texto(_)) => {
+                return Err(self.erro("Texto não suportado no backend WebAssembly".to_string()))
+            }
+            Expr::Identificador(nome) => match (self.locais.get(nome), tipos.get(nome)) {
+                (Some(Local::Escalar { tipo, .. }), _) => *tipo,
+                (Some(Local::Lista { .. }), _) => {
+                    return Err(self.erro(format!("lista '{}' só pode ser usada em 'para cada', tamanho() ou como argumento", nome)))
+                }
+                (None, Some(tipo)) => *tipo,
+                (None, None) => return Ok(None),
+            },
+            Expr::Binaria { esq, op, dir } => {
+                let (Some(esq), Some(dir)) = (self.tipo_expr(esq, tipos)?, self.tipo_expr(dir, tipos)?) else {
+                    return Ok(None);
+                };
+                self.tipo_binaria(op, esq, dir)?.1
+            }
+            Expr::Chamada { funcao, .. } => match funcao.as_str() {
+                "arredondar" => TipoValor::Decimal,
+                "tamanho" => TipoValor::Inteiro,
+                nome => match self.gerador.indices.get(nome) {
+                    Some(&i) => TipoValor::de(self.gerador.unidades[i as usize].retorno)?,
+                    None => return Err(self.erro(format!("função desconhecida: {}", nome))),
+                },
+            },
+        };
+        Ok(Some(tipo))
+    }
+
+    /// (tipo dos operandos, tipo do resultado)
+    fn tipo_binaria(&self, op: &Operador, esq: TipoValor, dir: TipoValor) -> Result<(TipoValor, TipoValor), String> {
+        use Operador::*;
+        let incompativel = || self.erro(format!("operandos incompatíveis para {:?}: {:?} e {:?}", op, esq, dir));
+        let numerico = if esq.numerico() && dir.numerico() { unir(esq, dir) } else { None };
+        match op {
+            Soma | Subtracao | Multiplicacao => numerico.map(|t| (t, t)).ok_or_else(incompativel),
+            Divisao => numerico.map(|_| (TipoValor::Decimal, TipoValor::Decimal)).ok_or_else(incompativel),
+            Menor | Maior | MenorIgual | MaiorIgual => numerico.map(|t| (t, TipoValor::Booleano)).ok_or_else(incompativel),
+            Igual | Diferente => unir(esq, dir).map(|t| (t, TipoValor::Booleano)).ok_or_else(incompativel),
+        }
+    }
+
+    fn tipo(&self, expr: &Expr) -> Result<TipoValor, String> {
+        self.tipo_expr(expr, &HashMap::new())?
+            .ok_or_else(|| self.erro(format!("variável desconhecida em {:?}", expr)))
+    }
+
+    fn emitir(&mut self, instrucao: Instrucao) {
+        self.instrucoes.push(instrucao);
+    }
+
+    fn chamar(&mut self, auxiliar: Auxiliar) {
+        let indice = self.gerador.indice_auxiliar(auxiliar);
+        self.emitir(Instruction::Call(indice));
+    }
+
+    fn converter(&mut self, de: TipoValor, para: TipoValor) -> Result<(), String> {
+        match (de, para) {
+            _ if de == para => Ok(()),
+            (TipoValor::Inteiro, TipoValor::Decimal) => {
+                self.emitir(Instruction::I64Const(FATOR_DECIMAL));
+                self.chamar(Auxiliar::MultiplicacaoChecada);
+                Ok(())
+            }
+            _ => Err(self.erro(format!("não é possível converter {:?} em {:?}", de, para))),
+        }
+    }
+
+    fn comando(&mut self, comando: &Comando) -> Result<(), String> {
+        match comando {
+            Comando::Atribuicao { var, expr } => {
+                let Some(Local::Escalar { indice, tipo }) = self.locais.get(var).copied() else {
+                    return Err(self.erro(format!("não é possível atribuir à lista '{}'", var)));
+                };
+                let origem = self.expr(expr)?;
+                self.converter(origem, tipo)?;
+                self.emitir(Instruction::LocalSet(indice));
+            }
+            Comando::Retorno(expr) => {
+                let origem = self.expr(expr)?;
+                self.converter(origem, self.retorno)?;
+                if let (TipoValor::Decimal, Some(casas)) = (self.retorno, self.unidade.precisao) {
+                    if u32::from(casas) < ESCALA_DECIMAL {
+                        self.emitir(Instruction::I64Const(10_i64.pow(ESCALA_DECIMAL - u32::from(casas))));
+                        self.chamar(Auxiliar::Arredondar);
+                    }
+                }
+                self.emitir(Instruction::Return);
+            }
+            Comando::Se { cond, entao, senao } => {
+                let tipo = self.expr(cond)?;
+                if tipo != TipoValor::Booleano {
+                    return Err(self.erro(format!("condição de 'se' deve ser Booleano, encontrado {:?}", tipo)));
+                }
+                self.emitir(Instruction::If(BlockType::Empty));
+                for c in entao {
+                    self.comando(c)?;
+                }
+                if let Some(senao) = senao {
+                    self.emitir(Instruction::Else);
+                    for c in senao {
+                        self.comando(c)?;
+                    }
+                }
+                self.emitir(Instruction::End);
+            }
+            Comando::ParaCada { var, colecao, corpo, .. } => {
+                let Some(Local::Lista { ponteiro, tamanho, elemento }) = self.locais.get(colecao).copied() else {
+                    return Err(self.erro(format!("'{}' não é um parâmetro do tipo Lista", colecao)));
+                };
+                let Some(Local::Escalar { indice: destino, tipo }) = self.locais.get(var).copied() else {
+                    return Err(self.erro(format!("variável de laço inválida: {}", var)));
+                };
+                let i = self.novo_local(ValType::I32);
+
+                use Instruction::*;
+                for instrucao in [
+                    I32Const(0), LocalSet(i),
+                    Block(BlockType::Empty), Loop(BlockType::Empty),
+                    LocalGet(i), LocalGet(tamanho), I32GeU, BrIf(1),
+                    LocalGet(ponteiro), LocalGet(i), I32Const(8), I32Mul, I32Add,
+                    I64Load(MemArg { offset: 0, align: 3, memory_index: 0 }),
+                ] {
+                    self.emitir(instrucao);
+                }
+                self.converter(elemento, tipo)?;
+                self.emitir(LocalSet(destino));
+                for c in corpo {
+                    self.comando(c)?;
+                }
+                for instrucao in [LocalGet(i), I32Const(1), I32Add, LocalSet(i), Br(0), End, End] {
+                    self.emitir(instrucao);
+                }
+            }
+            Comando::Expressao(expr) => {
+                self.expr(expr)?;
+                self.emitir(Instruction::Drop);
+            }
+        }
+        Ok(())
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Result<TipoValor, String> {
+        match expr {
+            Expr::Literal(Literal::Decimal(valor, _)) => {
+                let decimal = Decimal::from_str(&valor.to_string()).map_err(|e| self.erro(e.to_string()))?;
+                let fixo = decimal * Decimal::from(FATOR_DECIMAL);
+                if fixo.fract() != Decimal::ZERO {
+                    return Err(self.erro(format!("literal {} tem mais de {} casas decimais", decimal, ESCALA_DECIMAL)));
+                }
+                let fixo = fixo.to_i64().ok_or_else(|| self.erro(format!("literal {} fora do intervalo", decimal)))?;
+                self.emitir(Instruction::I64Const(fixo));
+                Ok(TipoValor::Decimal)
+            }
+            Expr::Literal(Literal::Inteiro(valor)) => {
+                self.emitir(Instruction::I64Const(*valor));
+                Ok(TipoValor::Inteiro)
+            }
+            Expr::Literal(Literal::Booleano(valor)) => {
+                self.emitir(Instruction::I32Const(*valor as i32));
+                Ok(TipoValor::Booleano)
+            }
+            Expr::Identificador(nome) => {
+                let tipo = self.tipo(expr)?;
+                let Some(Local::Escalar { indice, .. }) = self.locais.get(nome).copied() else {
+                    return Err(self.erro(format!("variável desconhecida: {}", nome)));
+                };
+                self.emitir(Instruction::LocalGet(indice));
+                Ok(tipo)
+            }
+            Expr::Binaria { esq, op, dir } => {
+                let (te, td) = (self.tipo(esq)?, self.tipo(dir)?);
+                let (operandos, resultado) = self.tipo_binaria(op, te, td)?;
+                self.expr(esq)?;
+                self.converter(te, operandos)?;
+                self.expr(dir)?;
+                self.converter(td, operandos)?;
+                self.operador(op, operandos);
+                Ok(resultado)
+            }
+            Expr::Literal(Literal::Texto(_)) => Err(self.erro("Texto não suportado no backend WebAssembly".to_string())),
+            Expr::Chamada { funcao, args } => self.chamada(funcao, args),
+        }
+    }
+
+    fn operador(&mut self, op: &Operador, operandos: TipoValor) {
+        use Instruction::*;
+        let booleano = operandos == TipoValor::Booleano;
+        match op {
+            Operador::Soma => self.chamar(Auxiliar::SomaChecada),
+            Operador::Subtracao => self.chamar(Auxiliar::SubtracaoChecada),
+            Operador::Multiplicacao if operandos == TipoValor::Decimal => self.chamar(Auxiliar::MultiplicacaoFixa),
+            Operador::Multiplicacao => self.chamar(Auxiliar::MultiplicacaoChecada),
+            Operador::Divisao => self.chamar(Auxiliar::DivisaoFixa),
+            Operador::Igual => self.emitir(if booleano { I32Eq } else { I64Eq }),
+            Operador::Diferente => self.emitir(if booleano { I32Ne } else { I64Ne }),
+            Operador::Menor => self.emitir(I64LtS),
+            Operador::Maior => self.emitir(I64GtS),
+            Operador::MenorIgual => self.emitir(I64LeS),
+            Operador::MaiorIgual => self.emitir(I64GeS),
+        }
+    }
+
+    fn chamada(&mut self, funcao: &str, args: &[Expr]) -> Result<TipoValor, String> {
+        let esperado = match funcao {
+            "arredondar" => 2,
+            "tamanho" => 1,
+            nome => match self.gerador.indices.get(nome) {
+                Some(&i) => self.gerador.unidades[i as usize].parametros.len(),
+                None => return Err(self.erro(format!("função desconhecida: {}", nome))),
+            },
+        };
+        if args.len() != esperado {
+            return Err(self.erro(format!("{} espera {} argumento(s), recebeu {}", funcao, esperado, args.len())));
+        }
+
+        match funcao {
+            "arredondar" => {
+                let casas = match &args[1] {
+                    Expr::Literal(Literal::Inteiro(n)) if (0..=28).contains(n) => *n as u32,
+                    _ => return Err(self.erro("arredondar exige casas decimais literais entre 0 e 28".to_string())),
+                };
+                let tipo = self.expr(&args[0])?;
+                self.converter(tipo, TipoValor::Decimal)?;
+                if casas < ESCALA_DECIMAL {
+                    self.emitir(Instruction::I64Const(10_i64.pow(ESCALA_DECIMAL - casas)));
+                    self.chamar(Auxiliar::Arredondar);
+                }
+                Ok(TipoValor::Decimal)
+            }
+            "tamanho" => {
+                match &args[0] {
+                    Expr::Identificador(nome) => match self.locais.get(nome).copied() {
+                        Some(Local::Lista { tamanho, .. }) => {
+                            self.emitir(Instruction::LocalGet(tamanho));
+                            self.emitir(Instruction::I64ExtendI32U);
+                            Ok(TipoValor::Inteiro)
+                        }
+                        _ => Err(self.erro(format!("tamanho() exige uma lista, '{}' não é", nome))),
+                    },
+                    _ => Err(self.erro("tamanho() exige o nome de uma lista".to_string())),
+                }
+            }
+            nome => {
+                let indice = self.gerador.indices[nome];
+                let chamada = &self.gerador.unidades[indice as usize];
+                for (arg, (param, tipo)) in args.iter().zip(chamada.parametros) {
+                    match tipo {
+                        Tipo::Lista(elemento) => {
+                            let esperado = TipoValor::de(elemento)?;
+                            match arg {
+                                Expr::Identificador(lista) => match self.locais.get(lista).copied() {
+                                    Some(Local::Lista { ponteiro, tamanho, elemento }) if elemento == esperado => {
+                                        self.emitir(Instruction::LocalGet(ponteiro));
+                                        self.emitir(Instruction::LocalGet(tamanho));
+                                    }
+                                    _ => return Err(self.erro(format!("'{}' deve ser Lista<{:?}> em {}({})", lista, esperado, nome, param))),
+                                },
+                                _ => return Err(self.erro(format!("parâmetro '{}' de {} exige uma lista", param, nome))),
+                            }
+                        }
+                        tipo => {
+                            let destino = TipoValor::de(tipo)?;
+                            let origem = self.expr(arg)?;
+                            self.converter(origem, destino)?;
+                        }
+                    }
+                }
+                self.emitir(Instruction::Call(indice));
+                TipoValor::de(chamada.retorno)
+            }
+        }
+    }
+}
@@ -1,3 +1,29 @@
+/// Posição de um nó no código-fonte (linhas e colunas começam em 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, end_line: usize, end_column: usize) -> Self {
+        Self { line, column, end_line, end_column }
+    }
+
+    /// Span que cobre `self` até o fim de `other`.
+    pub fn to(&self, other: Span) -> Span {
+        Span::new(self.line, self.column, other.end_line, other.end_column)
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Paradigm {
     Functional,
@@ -43,28 +69,39 @@ pub struct Function {
     pub body: Body,
     pub constraints: Vec<Constraint>,
     pub target_paradigm: Option<Paradigm>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
 pub struct Body {
     pub content: String,
+    /// Span do conteúdo, sem as chaves `{{ }}`
+    pub span: Span,
 }
 
 impl Body {
     pub fn to_solidity(&self) -> Result<String, crate::compiler::CompilerError> {
-        Ok(format!("// {} ", self.content))
+        // Cada linha vira comentário, para corpos de várias linhas
+        Ok(self.content.trim().lines()
+            .map(|line| format!("// {}", line.trim()).trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n"))
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Agent {
     pub name: String,
+    pub properties: Vec<(String, String)>,
+    pub methods: Vec<Method>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
 pub struct Field {
     pub name: String,
     pub field_type: Type,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -72,6 +109,7 @@ pub struct Method {
     pub name: String,
     pub params: Vec<(String, Type)>,
     pub return_type: Type,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -79,17 +117,22 @@ pub struct Class {
     pub name: String,
     pub fields: Vec<Field>,
     pub methods: Vec<Method>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
 pub struct Transmutation {
     pub name: String,
+    pub function: String,
+    pub target: Paradigm,
     pub constraints: Vec<Constraint>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
 pub struct Constraint {
     pub name: String,
+    pub span: Span,
 }
 
 impl Constraint {
@@ -106,6 +149,7 @@ impl Constraint {
     }
 }
 
+#[derive(Debug, Clone, Default)]
 pub struct OntologyProgram {
     pub functions: Vec<Function>,
     pub agents: Vec<Agent>,
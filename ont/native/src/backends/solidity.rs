@@ -29,10 +29,12 @@ impl SolidityBackend {
         solidity_code.push_str(&self.generate_diplomatic_interface());
 
         // 3. Contrato principal
-        solidity_code.push_str("contract OntologyContract is DiplomaticProtocol {\n");
+        solidity_code.push_str("contract OntologyContract is IDiplomaticContract {\n");
 
-        // 4. State variables (paradigm-aware)
+        // 4. State variables (paradigm-aware), construtor e helpers internos
         solidity_code.push_str(&self.generate_state_variables(program));
+        solidity_code.push_str(&self.generate_constructor(program)?);
+        solidity_code.push_str(&self.generate_helpers());
 
         // 5. Modifiers de guarda paradigmática
         if self.inject_guards {
@@ -58,10 +60,12 @@ impl SolidityBackend {
         solidity_code.push_str("}\n");
 
         let stats = self.calculate_stats(program);
+        let abi = self.generate_abi(program)?;
 
         Ok(CompiledContract {
             target_language: "Solidity 0.8.24".to_string(),
             source_code: solidity_code,
+            abi,
             stats,
         })
     }
@@ -69,16 +73,11 @@ impl SolidityBackend {
     // --- Cabeçalho Solidity ---
     fn generate_header(&self) -> String {
         format!(
-            r#"
-// SPDX-License-Identifier: ONTOLOGY-BSL-1.0
-pragma solidity {};
-
-// Imports de segurança paradigmática
-import {{DiplomaticProtocol}} from "onto/diplomacy/DiplomaticProtocol.sol";
-import {{ParadigmGuard}} from "onto/diplomacy/ParadigmGuard.sol";
-import {{TMRConsensus}} from "onto/consensus/TMRConsensus.sol";
-import {{MemorySeal}} from "onto/seal/MemorySeal.sol";
-            "#,
+            r#"// SPDX-License-Identifier: ONTOLOGY-BSL-1.0
+pragma solidity ^{};
+
+enum Paradigm {{ Functional, Imperative, Agent, Substrate, OO, Custom }}
+"#,
             self.evm_version
         )
     }
@@ -86,229 +85,467 @@ import {{MemorySeal}} from "onto/seal/MemorySeal.sol";
     // --- Interface diplomática base ---
     fn generate_diplomatic_interface(&self) -> String {
         indoc! { r#"
+
 interface IDiplomaticContract {
     // Evento emitido quando uma transmutação ocorre
     event Transmuted(bytes32 indexed functionHash, Paradigm indexed from, Paradigm indexed to);
 
-    // Verificar violação ontológica
+    // Verificar se o seletor pertence a uma função transmutada
     function checkOntologicalViolations(bytes4 selector) external view returns (bool);
 
-    // Executar com consenso TMR
-    function executeWithTmr(address[] calldata nodes, bytes memory callData) external;
-
-    // Obter selo de memória BLAKE3-Δ2
+    // Obter selo de memória do bloco
     function getMemorySeal(uint256 blockNumber) external view returns (bytes32);
 }
-        "# }.to_string()
+
+"# }.to_string()
     }
 
     fn generate_state_variables(&self, _program: &OntologyProgram) -> String {
-        indoc! { r#"
-    MemorySeal private _memorySeal;
+        r#"
+    event ActorMessage(bytes4 indexed agentId, bytes32 messageId);
+
+    bytes32 private _memorySeal;
+    mapping(uint256 => bytes32) private _seals;
     mapping(bytes4 => bool) private _transmutedFunctions;
-    mailbox private _mailbox;
-        "# }.to_string()
+    mapping(bytes32 => bytes) private _mailbox;
+    bytes32[] private _mailboxQueue;
+    mapping(address => bool) private _actors;
+    mapping(address => bytes32) private _instances;
+    uint256 private _writes;
+    bool private _locked;
+"#.to_string()
+    }
+
+    // --- Construtor: registra o implantador como ator e as transmutações ---
+    fn generate_constructor(&self, program: &OntologyProgram) -> CompilerResult<String> {
+        let mut registrations = String::new();
+        for func in program.functions.iter().filter(|f| f.target_paradigm.is_some()) {
+            registrations.push_str(&format!(
+                "\n        _transmutedFunctions[bytes4(keccak256(bytes(\"{}\")))] = true;",
+                self.abi_signature(&func.name, &func.params)?
+            ));
+        }
+
+        Ok(format!(
+            r#"
+    constructor() {{
+        _actors[msg.sender] = true;{}
+    }}
+"#,
+            registrations
+        ))
+    }
+
+    // --- Helpers internos usados pelo código gerado ---
+    fn generate_helpers(&self) -> String {
+        r#"
+
+    function checkOntologicalViolations(bytes4 selector) external view returns (bool) {
+        return _transmutedFunctions[selector];
+    }
+
+    function getMemorySeal(uint256 blockNumber) external view returns (bytes32) {
+        return _seals[blockNumber];
+    }
+
+    // Memory Seal: encadeia o selo anterior com o bloco e o seletor chamado
+    function _checkpoint() internal {
+        _memorySeal = keccak256(abi.encodePacked(_memorySeal, block.number, msg.sig));
+        _seals[block.number] = _memorySeal;
+    }
+
+    function _enqueue(bytes memory message) internal returns (bytes32 messageId) {
+        messageId = keccak256(message);
+        _mailbox[messageId] = message;
+        _mailboxQueue.push(messageId);
+        _writes += 1;
+    }
+
+    function _isInstanceOf(address instance, bytes32 classId) internal view returns (bool) {
+        return _instances[instance] == classId;
+    }
+
+    function _ooGuardEnter() internal {
+        require(!_locked, "REENTRANT");
+        _locked = true;
+    }
+
+    function _ooGuardExit() internal {
+        _locked = false;
+    }
+"#.to_string()
     }
 
     // --- Modifiers de guarda paradigmática ---
     fn generate_paradigm_modifiers(&self) -> String {
-        indoc! { r#"
-modifier pureGuard() {
-    // Revert se qualquer SSTORE for detectado neste call frame
-    uint256 writesBefore = _paradigmWrites();
-    _;
-    require(_paradigmWrites() == writesBefore, "PURE_VIOLATION");
-}
+        r#"
 
-modifier mutableGuard(uint256 maxWrites) {
-    uint256 writesBefore = _paradigmWrites();
-    _;
-    uint256 writesAfter = _paradigmWrites();
-    require(writesAfter - writesBefore <= maxWrites, "MUTABLE_OVERFLOW");
-}
+    modifier pureGuard() {
+        // Revert se alguma escrita registrada ocorrer neste call frame
+        uint256 writesBefore = _writes;
+        _;
+        require(_writes == writesBefore, "PURE_VIOLATION");
+    }
 
-modifier agentGuard(address caller) {
-    require(_isActor(caller), "AGENT_ONLY");
-    _;
-}
-        "# }.to_string()
+    modifier mutableGuard(uint256 maxWrites) {
+        uint256 writesBefore = _writes;
+        _;
+        require(_writes - writesBefore <= maxWrites, "MUTABLE_OVERFLOW");
+    }
+
+    modifier agentGuard(address caller) {
+        require(_actors[caller], "AGENT_ONLY");
+        _;
+    }
+
+    modifier nonReentrant() {
+        _ooGuardEnter();
+        _;
+        _ooGuardExit();
+    }
+"#.to_string()
     }
 
     // --- Tradução de função ---
     fn translate_function(&self, func: &Function) -> Result<String, CompilerError> {
-        let mut code = String::new();
+        check_identifier(&func.name)?;
 
-        // Determinar visibility
-        let visibility = if func.paradigm == Paradigm::Agent {
-            "external"
+        // Determinar visibility e a localização dos parâmetros por referência
+        let (visibility, location) = if func.paradigm == Paradigm::Agent {
+            ("external", "calldata")
         } else {
-            "public"
+            ("public", "memory")
+        };
+
+        // `view` vem da mesma fonte que a ABI
+        let mutability = match state_mutability(&func.paradigm) {
+            "nonpayable" => String::new(),
+            other => format!(" {}", other),
         };
 
         // Seleção de modifier baseado no paradigma
         let modifier = match func.paradigm {
-            Paradigm::Functional => "pureGuard".to_string(),
+            _ if !self.inject_guards => String::new(),
+            Paradigm::Functional => " pureGuard".to_string(),
             Paradigm::Imperative => {
                 // Extrair O(n) da constraint para maxWrites
                 let max_writes = func.constraints.iter()
                     .find_map(|c| c.parse_o_n_complexity())
                     .unwrap_or(0);
-                format!("mutableGuard({})", max_writes)
+                format!(" mutableGuard({})", max_writes)
             }
-            Paradigm::Agent => "agentGuard(msg.sender)".to_string(),
-            Paradigm::OO | Paradigm::Substrate => "nonReentrant".to_string(),
-            Paradigm::Custom(_) => "".to_string(),
+            Paradigm::Agent => " agentGuard(msg.sender)".to_string(),
+            Paradigm::OO | Paradigm::Substrate => " nonReentrant".to_string(),
+            Paradigm::Custom(_) => String::new(),
         };
 
-        // Gerar assinatura da função
-        let params: Vec<String> = func.params.iter()
-            .map(|(name, typ)| Ok(format!("{} {}", self.type_to_solidity(typ)?, name)))
-            .collect::<Result<Vec<_>, CompilerError>>()?;
-
-        let return_type = self.type_to_solidity(&func.return_type)?;
+        let params = self.params_decl(&func.params, location)?;
+        let returns = self.returns_clause(&func.return_type)?;
+        let body = func.body.to_solidity()?.replace('\n', "\n        ");
 
-        code.push_str(&format!(
-            r#"
-    function {}({}) {} {} returns ({}) {{
+        // Funções `view` não alteram o selo nem emitem eventos
+        let code = if func.paradigm == Paradigm::Functional {
+            format!(
+                r#"
+    function {}({}) {}{}{} {}{{
+        // Implementação nativa (from AST native_block)
         {}
+    }}
+"#,
+                func.name, params, visibility, mutability, modifier, returns, body
+            )
+        } else {
+            let transmuted = match &func.target_paradigm {
+                Some(target) => format!(
+                    "\n\n        // Transmutação declarada\n        emit Transmuted(keccak256(bytes(\"{}\")), {}, {});",
+                    func.name,
+                    func.paradigm.to_solidity_enum(),
+                    target.to_solidity_enum()
+                ),
+                None => String::new(),
+            };
+            format!(
+                r#"
+    function {}({}) {}{}{} {}{{
         // Memory Seal checkpoint
-        _memorySeal.checkpoint();
+        _checkpoint();
 
         // Implementação nativa (from AST native_block)
-        {}
-
-        // Emitir evento de transmutação se aplicável
-        if (_isTransmuted(bytes4(keccak256("{}")))) {{
-            emit Transmuted(bytes32(keccak256("{}")), {}, {});
-        }}
-
-        return _memorySeal.seal();
+        {}{}
     }}
-            "#,
-            func.name,
-            params.join(", "),
-            visibility,
-            if !modifier.is_empty() { modifier } else { "".to_string() },
-            return_type,
-            if func.paradigm == Paradigm::Functional { "memory" } else { "storage" },
-            func.body.to_solidity()?,
-            func.name,
-            func.name,
-            func.paradigm.to_solidity_enum(),
-            func.target_paradigm.as_ref().unwrap_or(&func.paradigm).to_solidity_enum()
-        ));
+"#,
+                func.name, params, visibility, mutability, modifier, returns, body, transmuted
+            )
+        };
 
         Ok(code)
     }
 
     // --- Tradução de Agente → Contrato ERC-1155 + Actor Model ---
     fn translate_agent(&self, agent: &Agent) -> Result<String, CompilerError> {
+        check_identifier(&agent.name)?;
         let agent_id = selector(&agent.name);
+        let guard = if self.inject_guards { " agentGuard(msg.sender)" } else { "" };
+        let mut code = String::new();
 
-        Ok(format!(
+        code.push_str(&format!(
             r#"
     // Agente: {} (ID: {})
-    function {}Actor(bytes calldata message) external agentGuard(msg.sender) {{
+    function {}Actor(bytes calldata message) external{} {{
         // Mailbox pattern
-        bytes32 messageId = keccak256(message);
-        bool processed = _processMessage(messageId, message);
-
-        if (!processed) {{
-            // Armazenar para processamento futuro
-            _mailbox.push(messageId, message);
-        }}
-
-        emit ActorMessage(agentId: {}, message: messageId);
+        bytes32 messageId = _enqueue(message);
+        emit ActorMessage(bytes4({}), messageId);
     }}
-            "#,
+"#,
             agent.name,
             agent_id,
             agent.name,
+            guard,
             agent_id
-        ))
+        ));
+
+        // Métodos do agente: cada um vira uma mensagem tipada na mailbox
+        for method in &agent.methods {
+            check_identifier(&method.name)?;
+            code.push_str(&format!(
+                r#"
+    function {}_{}({}) external{} {}{{
+        // Resposta entregue de forma assíncrona pela mailbox
+        _enqueue({});
+    }}
+"#,
+                agent.name,
+                method.name,
+                self.params_decl(&method.params, "calldata")?,
+                guard,
+                self.returns_clause(&method.return_type)?,
+                self.encode_call(&method.name, &method.params)?,
+            ));
+        }
+
+        Ok(code)
     }
 
     // --- Tradução de Classe (OO) → EVM Struct + Dispatch ---
     fn translate_class(&self, class: &Class) -> Result<String, CompilerError> {
+        check_identifier(&class.name)?;
         let mut code = String::new();
 
-        // 1. Definir struct de armazenamento
-        let fields = class.fields.iter()
-                .map(|f| Ok(format!("{} {};", self.type_to_solidity(&f.field_type)?, f.name)))
-                .collect::<Result<Vec<_>, CompilerError>>()?.join("\n        ");
+        // 1. Definir struct de armazenamento (Solidity não aceita struct vazia)
+        if !class.fields.is_empty() {
+            let fields = class.fields.iter()
+                    .map(|f| {
+                        check_identifier(&f.name)?;
+                        Ok(format!("{} {};", self.type_to_solidity(&f.field_type)?, f.name))
+                    })
+                    .collect::<Result<Vec<_>, CompilerError>>()?.join("\n        ");
 
-        code.push_str(&format!(
-            r#"
+            code.push_str(&format!(
+                r#"
     struct {} {{
         {}
     }}
-            "#,
-            class.name,
-            fields
-        ));
+"#,
+                class.name,
+                fields
+            ));
+        }
 
         // 2. Definir funções de método com dispatch dinâmico
         for method in &class.methods {
-            let method_params = method.params.iter()
-                    .map(|(n, t)| Ok(format!("{} {}", self.type_to_solidity(t)?, n)))
-                    .collect::<Result<Vec<_>, CompilerError>>()?.join(", ");
+            check_identifier(&method.name)?;
+            let (call_result, decode) = match &method.return_type {
+                Type::Unit => ("(bool success, )", String::new()),
+                t => (
+                    "(bool success, bytes memory result)",
+                    format!("\n        _ret = abi.decode(result, ({}));", self.type_to_solidity(t)?),
+                ),
+            };
 
             code.push_str(&format!(
                 r#"
-    function {}_{}({}) public returns ({}) {{
+    function {}_{}({}) public {}{{
         // Dispatch: verificar que msg.sender é instância válida
-        require(_isInstanceOf(msg.sender, bytes32("{}")), "OO_VIOLATION");
+        require(_isInstanceOf(msg.sender, keccak256(bytes("{}"))), "OO_VIOLATION");
 
         // Acesso seguro aos fields via delegatecall com guard
         _ooGuardEnter();
-        (bool success, bytes memory result) = msg.sender.delegatecall(
-            abi.encodeWithSignature("{}", {})
-        );
+        {} = msg.sender.delegatecall({});
         require(success, "METHOD_CALL_FAILED");
-        _ooGuardExit();
-
-        return abi.decode(result, ({}));
+        _ooGuardExit();{}
     }}
-                "#,
+"#,
                 class.name,
                 method.name,
-                method_params,
-                self.type_to_solidity(&method.return_type)?,
+                self.params_decl(&method.params, "memory")?,
+                self.returns_clause(&method.return_type)?,
                 class.name,
-                method.name,
-                method.params.iter().map(|(n, _)| n.clone()).collect::<Vec<_>>().join(", "),
-                self.type_to_solidity(&method.return_type)?,
+                call_result,
+                self.encode_call(&method.name, &method.params)?,
+                decode,
             ));
         }
 
         Ok(code)
     }
 
+    // --- ABI ---
+
+    /// ABI JSON (formato solc) das funções externas geradas: funções do
+    /// programa, métodos de agentes (`<agente>_<metodo>` e `<agente>Actor`)
+    /// e métodos de classes (`<Classe>_<metodo>`).
+    pub fn generate_abi(&self, program: &OntologyProgram) -> CompilerResult<serde_json::Value> {
+        let mut entries = Vec::new();
+
+        for func in &program.functions {
+            entries.push(self.abi_function(
+                &func.name,
+                &func.params,
+                &func.return_type,
+                state_mutability(&func.paradigm),
+            )?);
+        }
+
+        for agent in program.agents() {
+            entries.push(self.abi_function(
+                &format!("{}Actor", agent.name),
+                &[("message".to_string(), Type::Bytes)],
+                &Type::Unit,
+                "nonpayable",
+            )?);
+            for method in &agent.methods {
+                entries.push(self.abi_function(
+                    &format!("{}_{}", agent.name, method.name),
+                    &method.params,
+                    &method.return_type,
+                    "nonpayable",
+                )?);
+            }
+        }
+
+        for class in program.classes() {
+            for method in &class.methods {
+                entries.push(self.abi_function(
+                    &format!("{}_{}", class.name, method.name),
+                    &method.params,
+                    &method.return_type,
+                    "nonpayable",
+                )?);
+            }
+        }
+
+        Ok(serde_json::Value::Array(entries))
+    }
+
+    fn abi_function(
+        &self,
+        name: &str,
+        params: &[(String, Type)],
+        return_type: &Type,
+        state_mutability: &str,
+    ) -> CompilerResult<serde_json::Value> {
+        let inputs = params.iter()
+            .map(|(n, t)| self.abi_param(n, t))
+            .collect::<CompilerResult<Vec<_>>>()?;
+        let outputs = match return_type {
+            Type::Unit => vec![],
+            t => vec![self.abi_param("", t)?],
+        };
+
+        Ok(serde_json::json!({
+            "type": "function",
+            "name": name,
+            "inputs": inputs,
+            "outputs": outputs,
+            "stateMutability": state_mutability,
+        }))
+    }
+
+    fn abi_param(&self, name: &str, typ: &Type) -> CompilerResult<serde_json::Value> {
+        let abi_type = self.type_to_abi(typ)?;
+        Ok(serde_json::json!({
+            "name": name,
+            "type": abi_type,
+            "internalType": abi_type,
+        }))
+    }
+
+    /// Tipo ABI canônico; hoje coincide com `type_to_solidity`.
+    fn type_to_abi(&self, typ: &Type) -> CompilerResult<String> {
+        match typ {
+            Type::Pure(inner) | Type::Mutable(inner) => self.type_to_abi(inner),
+            Type::Agent(_) | Type::Object(_) | Type::Address | Type::Function(_, _) => Ok("address".to_string()),
+            Type::Substrate(_) | Type::TypeVar(_) => Ok("bytes32".to_string()),
+            Type::Int | Type::Float => Ok("int256".to_string()),
+            Type::Bool => Ok("bool".to_string()),
+            Type::String => Ok("string".to_string()),
+            Type::Bytes => Ok("bytes".to_string()),
+            Type::Named(_, _) | Type::Unit => Err(CompilerError::UnsupportedType(typ.clone())),
+        }
+    }
+
     // --- Helpers ---
+
+    /// `returns (T _ret) ` ou vazio para `Unit`; o retorno nomeado deixa
+    /// corpos opacos compiláveis.
+    fn returns_clause(&self, typ: &Type) -> Result<String, CompilerError> {
+        match typ {
+            Type::Unit => Ok(String::new()),
+            t => Ok(format!("returns ({} _ret) ", self.with_location(t, "memory")?)),
+        }
+    }
+
+    fn params_decl(&self, params: &[(String, Type)], location: &str) -> CompilerResult<String> {
+        params.iter()
+            .map(|(name, typ)| {
+                check_identifier(name)?;
+                Ok(format!("{} {}", self.with_location(typ, location)?, name))
+            })
+            .collect::<CompilerResult<Vec<_>>>()
+            .map(|params| params.join(", "))
+    }
+
+    /// Tipo Solidity com a localização de dados exigida por tipos de referência.
+    fn with_location(&self, typ: &Type, location: &str) -> CompilerResult<String> {
+        let solidity_type = self.type_to_solidity(typ)?;
+        match self.type_to_abi(typ)?.as_str() {
+            "string" | "bytes" => Ok(format!("{} {}", solidity_type, location)),
+            _ => Ok(solidity_type),
+        }
+    }
+
+    /// Assinatura canônica `nome(tipo,...)` usada em seletores.
+    fn abi_signature(&self, name: &str, params: &[(String, Type)]) -> CompilerResult<String> {
+        let types = params.iter()
+            .map(|(_, t)| self.type_to_abi(t))
+            .collect::<CompilerResult<Vec<_>>>()?;
+        Ok(format!("{}({})", name, types.join(",")))
+    }
+
+    /// `abi.encodeWithSignature(...)` repassando os parâmetros do método.
+    fn encode_call(&self, name: &str, params: &[(String, Type)]) -> CompilerResult<String> {
+        let mut args = vec![format!("\"{}\"", self.abi_signature(name, params)?)];
+        args.extend(params.iter().map(|(n, _)| n.clone()));
+        Ok(format!("abi.encodeWithSignature({})", args.join(", ")))
+    }
+
     fn type_to_solidity(&self, typ: &Type) -> Result<String, CompilerError> {
         match typ {
             Type::Pure(inner) => self.type_to_solidity(inner),
             Type::Mutable(inner) => self.type_to_solidity(inner),
-            Type::Agent(inner) => {
-                // Agents compilam para address + interface
-                let inner_type = self.type_to_solidity(inner)?;
-                Ok(format!("address /* Agent<{}> */", inner_type))
-            }
-            Type::Substrate(inner) => {
-                // Substrate types têm representação especial
-                Ok(format!("bytes32 /* Substrate<{}> */", self.type_to_solidity(inner)?))
-            }
+            Type::Agent(_) => Ok("address".to_string()), // Agents compilam para address
+            Type::Substrate(_) => Ok("bytes32".to_string()), // Substrate types viram um handle opaco
             Type::Object(_) => Ok("address".to_string()), // Objects são endereços de contrato
             Type::Int => Ok("int256".to_string()),
             Type::Float => Ok("int256".to_string()), // Simulação para prototipagem
             Type::Bool => Ok("bool".to_string()),
             Type::String => Ok("string".to_string()),
-            Type::Named(name, _) => Ok(name.clone()), // Tipos personalizados do usuário
-            Type::Unit => Ok("void".to_string()), // Should not happen in returns directly as Solidity uses empty
             Type::Bytes => Ok("bytes".to_string()),
             Type::Address => Ok("address".to_string()),
-            Type::TypeVar(t) => Ok(format!("bytes32 /* {} */", t)), // Generic placeholder
-            Type::Function(_, _) => Ok("address /* Function pointer */".to_string()),
+            Type::TypeVar(_) => Ok("bytes32".to_string()), // Generic placeholder
+            Type::Function(_, _) => Ok("address".to_string()), // Function pointer
+            // Tipos nomeados não têm declaração no contrato gerado
+            Type::Named(_, _) | Type::Unit => Err(CompilerError::UnsupportedType(typ.clone())),
         }
     }
 
@@ -339,6 +576,39 @@ modifier agentGuard(address caller) {
     }
 }
 
+/// Mutabilidade de estado de uma função do programa. Fonte única para o
+/// modificador emitido no Solidity e para o `stateMutability` da ABI.
+fn state_mutability(paradigm: &Paradigm) -> &'static str {
+    match paradigm {
+        Paradigm::Functional => "view",
+        _ => "nonpayable",
+    }
+}
+
+/// Palavras-chave e reservadas do Solidity que não podem nomear nada no
+/// contrato gerado.
+const SOLIDITY_RESERVED: &[&str] = &[
+    "abstract", "address", "after", "alias", "anonymous", "apply", "as", "assembly", "auto",
+    "bool", "break", "byte", "bytes", "calldata", "case", "catch", "constant", "constructor",
+    "continue", "contract", "copyof", "days", "default", "define", "delete", "do", "else",
+    "emit", "enum", "error", "ether", "event", "external", "fallback", "false", "final",
+    "for", "function", "gwei", "hours", "if", "immutable", "implements", "import", "in",
+    "indexed", "inline", "interface", "internal", "is", "let", "library", "macro", "mapping",
+    "match", "memory", "minutes", "modifier", "mutable", "new", "null", "of", "override",
+    "partial", "payable", "pragma", "private", "promise", "public", "pure", "receive",
+    "reference", "relocatable", "return", "returns", "revert", "sealed", "seconds", "sizeof",
+    "static", "storage", "string", "struct", "super", "supports", "switch", "this", "true",
+    "try", "type", "typedef", "typeof", "unchecked", "using", "var", "view", "virtual",
+    "weeks", "wei", "while", "years",
+];
+
+fn check_identifier(name: &str) -> CompilerResult<()> {
+    if SOLIDITY_RESERVED.contains(&name) {
+        return Err(CompilerError::Other(format!("'{}' is a reserved word in Solidity", name)));
+    }
+    Ok(())
+}
+
 // Conversão de paradigma para enum Solidity
 trait ParadigmSolidity {
     fn to_solidity_enum(&self) -> String;
@@ -352,7 +622,8 @@ impl ParadigmSolidity for Paradigm {
             Paradigm::Agent => "Paradigm.Agent".to_string(),
             Paradigm::Substrate => "Paradigm.Substrate".to_string(),
             Paradigm::OO => "Paradigm.OO".to_string(),
-            Paradigm::Custom(s) => format!("Paradigm.Custom('{}')", s),
+            // O nome do paradigma customizado fica só no fonte .onto
+            Paradigm::Custom(_) => "Paradigm.Custom".to_string(),
         }
     }
 }
@@ -362,6 +633,7 @@ impl ParadigmSolidity for Paradigm {
 pub struct CompiledContract {
     pub target_language: String,
     pub source_code: String,
+    pub abi: serde_json::Value,
     pub stats: CompilationStats,
 }
 
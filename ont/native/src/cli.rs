@@ -22,6 +22,10 @@ pub enum Commands {
         /// Target platform
         #[arg(long, default_value = "solidity")]
         target: String,
+
+        /// Also compile to bytecode with solc (optionally the path to the solc binary)
+        #[arg(long, num_args = 0..=1, default_missing_value = "solc")]
+        solc: Option<String>,
     },
 
     /// Deploy a contract
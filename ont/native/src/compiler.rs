@@ -1,6 +1,10 @@
 use thiserror::Error;
 use crate::ast::Type;
-use std::path::Path;
+use crate::backends::solidity::SolidityBackend;
+use crate::parser::ParseError;
+use crate::type_checker::{check_program, Diagnostic};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::fs;
 
 #[derive(Error, Debug)]
@@ -11,32 +15,114 @@ pub enum CompilerError {
     ConstraintViolation(String),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("Parse error: {0}")]
+    Parse(#[from] ParseError),
+    #[error("Type check failed:\n{}", .0.iter().map(|d| d.to_string()).collect::<Vec<_>>().join("\n"))]
+    TypeCheck(Vec<Diagnostic>),
+    #[error("solc failed: {0}")]
+    Solc(String),
     #[error("Other error: {0}")]
     Other(String),
 }
 
 pub type CompilerResult<T> = Result<T, CompilerError>;
 
-pub fn compile(input: &str, output: Option<&str>, target: &str) -> CompilerResult<()> {
+/// Arquivos gerados por `compile`.
+#[derive(Debug, Clone)]
+pub struct CompileArtifacts {
+    pub source: PathBuf,
+    pub abi: PathBuf,
+    pub bytecode: Option<PathBuf>,
+}
+
+/// Compila um arquivo `.onto`: parse, verificação de tipos, geração de
+/// Solidity (`<nome>.sol`) e ABI (`<nome>.json`). Se `solc` for informado,
+/// o fonte gerado também é compilado para bytecode (`<nome>.bin`) com o
+/// binário indicado.
+pub fn compile(input: &str, output: Option<&str>, target: &str, solc: Option<&str>) -> CompilerResult<CompileArtifacts> {
     println!("Compiling {} to {} (target: {})", input, output.unwrap_or("default"), target);
 
-    // Mock compilation logic
+    if target != "solidity" {
+        return Err(CompilerError::Other(format!("Unsupported target: {}", target)));
+    }
+
     let input_path = Path::new(input);
     let output_dir = output.map(Path::new).unwrap_or_else(|| Path::new("."));
 
+    let source = fs::read_to_string(input_path)?;
+    let program = crate::parser::parse_program(&source)?;
+
+    let (errors, warnings): (Vec<_>, Vec<_>) = check_program(&program)
+        .into_iter()
+        .partition(|d| d.is_error());
+    for warning in &warnings {
+        log::warn!("{}: {}", input, warning);
+    }
+    if !errors.is_empty() {
+        return Err(CompilerError::TypeCheck(errors));
+    }
+
+    let backend = SolidityBackend::new(true, "0.8.24".to_string(), true);
+    let compiled = backend.compile(&program)?;
+
     if !output_dir.exists() {
         fs::create_dir_all(output_dir)?;
     }
 
     let file_name = input_path.file_stem().and_then(|s| s.to_str()).unwrap_or("contract");
 
-    if target == "solidity" {
-        let bin_path = output_dir.join(format!("{}.bin", file_name));
-        let abi_path = output_dir.join(format!("{}.json", file_name));
+    let sol_path = output_dir.join(format!("{}.sol", file_name));
+    let abi_path = output_dir.join(format!("{}.json", file_name));
 
-        fs::write(bin_path, "0x608060405234801561001057600080fd5b5061012b806100206000396000f3fe")?;
-        fs::write(abi_path, "[]")?;
+    fs::write(&sol_path, &compiled.source_code)?;
+    let abi = serde_json::to_string_pretty(&compiled.abi)
+        .map_err(|e| CompilerError::Other(e.to_string()))?;
+    fs::write(&abi_path, abi)?;
+
+    let bytecode = match solc {
+        Some(solc) => {
+            let bin_path = output_dir.join(format!("{}.bin", file_name));
+            let bin = compile_with_solc(solc, &sol_path, backend.optimize)?;
+            fs::write(&bin_path, format!("0x{}", bin))?;
+            Some(bin_path)
+        }
+        None => None,
+    };
+
+    println!("✅ {} functions, {} contracts, gas estimate {}",
+        compiled.stats.functions_compiled,
+        compiled.stats.contracts_deployed,
+        compiled.stats.gas_estimate);
+
+    Ok(CompileArtifacts { source: sol_path, abi: abi_path, bytecode })
+}
+
+/// Executa `solc --combined-json bin` e extrai o bytecode de `OntologyContract`.
+fn compile_with_solc(solc: &str, sol_path: &Path, optimize: bool) -> CompilerResult<String> {
+    let mut command = Command::new(solc);
+    command.arg("--combined-json").arg("bin");
+    if optimize {
+        command.arg("--optimize");
+    }
+    let result = command
+        .arg(sol_path)
+        .output()
+        .map_err(|e| CompilerError::Solc(format!("could not run '{}': {}", solc, e)))?;
+
+    if !result.status.success() {
+        return Err(CompilerError::Solc(String::from_utf8_lossy(&result.stderr).into_owned()));
     }
 
-    Ok(())
+    let combined: serde_json::Value = serde_json::from_slice(&result.stdout)
+        .map_err(|e| CompilerError::Solc(format!("invalid combined JSON: {}", e)))?;
+
+    combined["contracts"]
+        .as_object()
+        .and_then(|contracts| {
+            contracts.iter().find(|(name, _)| name.ends_with(":OntologyContract"))
+        })
+        .and_then(|(_, contract)| contract["bin"].as_str())
+        .filter(|bin| !bin.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| CompilerError::Solc("no bytecode for OntologyContract in solc output".to_string()))
 }
@@ -20,8 +20,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Compile { input, output, target } => {
-            ontology_lang::compile(&input, output.as_deref(), &target)?;
+        Commands::Compile { input, output, target, solc } => {
+            let artifacts = ontology_lang::compile(&input, output.as_deref(), &target, solc.as_deref())?;
+            println!("📄 Solidity: {}", artifacts.source.display());
+            println!("📄 ABI: {}", artifacts.abi.display());
+            if let Some(bin) = artifacts.bytecode {
+                println!("📦 Bytecode: {}", bin.display());
+            }
         }
 
         Commands::Deploy { bytecode, rpc_url, private_key } => {
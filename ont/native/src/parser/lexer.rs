@@ -0,0 +1,364 @@
+// ==============================================
+// ONTOLOGY LEXER v0.7.0
+// Tokenização com posição (linha/coluna) para diagnósticos
+// ==============================================
+
+use crate::ast::Span;
+use super::{ParseError, ParseErrorKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    // Palavras-chave
+    Fn,
+    Agent,
+    Class,
+    Transmute,
+    To,
+    With,
+
+    // Literais e identificadores
+    Ident(String),
+    Int(String),
+    Float(String),
+    Str(String),
+    /// Bloco nativo `{{ ... }}`, conteúdo bruto sem as chaves duplas
+    Native(String),
+
+    // Pontuação
+    Colon,
+    Arrow,
+    Assign,
+    Lt,
+    Gt,
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    Comma,
+    Semicolon,
+    At,
+    Dot,
+
+    // Operadores (usados nas expressões dos blocos nativos)
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    EqEq,
+    NotEq,
+    Le,
+    Ge,
+    Bang,
+    AndAnd,
+    OrOr,
+
+    Eof,
+}
+
+impl TokenKind {
+    pub fn describe(&self) -> String {
+        match self {
+            TokenKind::Fn => "'fn'".to_string(),
+            TokenKind::Agent => "'agent'".to_string(),
+            TokenKind::Class => "'class'".to_string(),
+            TokenKind::Transmute => "'transmute'".to_string(),
+            TokenKind::To => "'to'".to_string(),
+            TokenKind::With => "'with'".to_string(),
+            TokenKind::Ident(name) => format!("identifier '{}'", name),
+            TokenKind::Int(value) => format!("integer '{}'", value),
+            TokenKind::Float(value) => format!("float '{}'", value),
+            TokenKind::Str(value) => format!("string \"{}\"", value),
+            TokenKind::Native(_) => "native block '{{ ... }}'".to_string(),
+            TokenKind::Colon => "':'".to_string(),
+            TokenKind::Arrow => "'->'".to_string(),
+            TokenKind::Assign => "'='".to_string(),
+            TokenKind::Lt => "'<'".to_string(),
+            TokenKind::Gt => "'>'".to_string(),
+            TokenKind::LParen => "'('".to_string(),
+            TokenKind::RParen => "')'".to_string(),
+            TokenKind::LBrace => "'{'".to_string(),
+            TokenKind::RBrace => "'}'".to_string(),
+            TokenKind::Comma => "','".to_string(),
+            TokenKind::Semicolon => "';'".to_string(),
+            TokenKind::At => "'@'".to_string(),
+            TokenKind::Dot => "'.'".to_string(),
+            TokenKind::Plus => "'+'".to_string(),
+            TokenKind::Minus => "'-'".to_string(),
+            TokenKind::Star => "'*'".to_string(),
+            TokenKind::Slash => "'/'".to_string(),
+            TokenKind::EqEq => "'=='".to_string(),
+            TokenKind::NotEq => "'!='".to_string(),
+            TokenKind::Le => "'<='".to_string(),
+            TokenKind::Ge => "'>='".to_string(),
+            TokenKind::Bang => "'!'".to_string(),
+            TokenKind::AndAnd => "'&&'".to_string(),
+            TokenKind::OrOr => "'||'".to_string(),
+            TokenKind::Eof => "end of file".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+    /// Offsets em bytes no código-fonte (início, fim)
+    pub offset: (usize, usize),
+    /// Apenas para `Native`: posição do conteúdo dentro das chaves
+    pub content_span: Option<Span>,
+}
+
+pub struct Lexer<'a> {
+    source: &'a str,
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            chars: source.char_indices().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn tokenize(mut self) -> Result<Vec<Token>, ParseError> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = self.next_token()?;
+            let done = token.kind == TokenKind::Eof;
+            tokens.push(token);
+            if done {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|&(_, c)| c)
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        let mut it = self.chars.clone();
+        it.next();
+        it.next().map(|(_, c)| c)
+    }
+
+    fn offset(&mut self) -> usize {
+        self.chars.peek().map(|&(i, _)| i).unwrap_or(self.source.len())
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let (_, c) = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        loop {
+            match (self.peek(), self.peek_second()) {
+                (Some(c), _) if c.is_whitespace() => {
+                    self.bump();
+                }
+                (Some('/'), Some('/')) => {
+                    while let Some(c) = self.peek() {
+                        if c == '\n' {
+                            break;
+                        }
+                        self.bump();
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    let (line, column) = (self.line, self.column);
+                    self.bump();
+                    self.bump();
+                    loop {
+                        match self.bump() {
+                            Some('*') if self.peek() == Some('/') => {
+                                self.bump();
+                                break;
+                            }
+                            Some(_) => {}
+                            None => {
+                                return Err(ParseError::new(
+                                    ParseErrorKind::UnterminatedComment,
+                                    Span::new(line, column, self.line, self.column),
+                                ))
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Token, ParseError> {
+        self.skip_trivia()?;
+
+        let (line, column) = (self.line, self.column);
+        let start = self.offset();
+
+        let c = match self.bump() {
+            Some(c) => c,
+            None => {
+                return Ok(Token {
+                    kind: TokenKind::Eof,
+                    span: Span::new(line, column, line, column),
+                    offset: (start, start),
+                    content_span: None,
+                })
+            }
+        };
+
+        let kind = match (c, self.peek()) {
+            ('-', Some('>')) => self.pair(TokenKind::Arrow),
+            ('=', Some('=')) => self.pair(TokenKind::EqEq),
+            ('!', Some('=')) => self.pair(TokenKind::NotEq),
+            ('<', Some('=')) => self.pair(TokenKind::Le),
+            ('>', Some('=')) => self.pair(TokenKind::Ge),
+            ('&', Some('&')) => self.pair(TokenKind::AndAnd),
+            ('|', Some('|')) => self.pair(TokenKind::OrOr),
+            ('{', Some('{')) => {
+                self.bump();
+                return self.lex_native(line, column, start);
+            }
+            (':', _) => TokenKind::Colon,
+            ('=', _) => TokenKind::Assign,
+            ('<', _) => TokenKind::Lt,
+            ('>', _) => TokenKind::Gt,
+            ('(', _) => TokenKind::LParen,
+            (')', _) => TokenKind::RParen,
+            (',', _) => TokenKind::Comma,
+            (';', _) => TokenKind::Semicolon,
+            ('@', _) => TokenKind::At,
+            ('.', _) => TokenKind::Dot,
+            ('+', _) => TokenKind::Plus,
+            ('-', _) => TokenKind::Minus,
+            ('*', _) => TokenKind::Star,
+            ('/', _) => TokenKind::Slash,
+            ('!', _) => TokenKind::Bang,
+            ('{', _) => TokenKind::LBrace,
+            ('}', _) => TokenKind::RBrace,
+            ('"', _) => self.lex_string(line, column)?,
+            (c, _) if c.is_ascii_digit() => {
+                while matches!(self.peek(), Some(d) if d.is_ascii_digit() || d == '_') {
+                    self.bump();
+                }
+                let is_float = self.peek() == Some('.')
+                    && matches!(self.peek_second(), Some(d) if d.is_ascii_digit());
+                if is_float {
+                    self.bump();
+                    while matches!(self.peek(), Some(d) if d.is_ascii_digit() || d == '_') {
+                        self.bump();
+                    }
+                    TokenKind::Float(self.source[start..self.offset()].replace('_', ""))
+                } else {
+                    TokenKind::Int(self.source[start..self.offset()].replace('_', ""))
+                }
+            }
+            (c, _) if c.is_alphabetic() || c == '_' => {
+                while matches!(self.peek(), Some(d) if d.is_alphanumeric() || d == '_') {
+                    self.bump();
+                }
+                match &self.source[start..self.offset()] {
+                    "fn" => TokenKind::Fn,
+                    "agent" => TokenKind::Agent,
+                    "class" => TokenKind::Class,
+                    "transmute" => TokenKind::Transmute,
+                    "to" => TokenKind::To,
+                    "with" => TokenKind::With,
+                    ident => TokenKind::Ident(ident.to_string()),
+                }
+            }
+            (other, _) => {
+                return Err(ParseError::new(
+                    ParseErrorKind::InvalidCharacter(other),
+                    Span::new(line, column, self.line, self.column),
+                ))
+            }
+        };
+
+        Ok(Token {
+            kind,
+            span: Span::new(line, column, self.line, self.column),
+            offset: (start, self.offset()),
+            content_span: None,
+        })
+    }
+
+    /// Operador de dois caracteres: consome o segundo.
+    fn pair(&mut self, kind: TokenKind) -> TokenKind {
+        self.bump();
+        kind
+    }
+
+    /// Lê um bloco nativo `{{ ... }}`. O span do token cobre o bloco inteiro;
+    /// `content_span` aponta para o conteúdo sem espaços nas pontas, para que
+    /// diagnósticos dentro do bloco possam ser relocados no arquivo.
+    fn lex_native(&mut self, line: usize, column: usize, start: usize) -> Result<Token, ParseError> {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.bump();
+        }
+        let content_start = self.offset();
+        let (content_line, content_column) = (self.line, self.column);
+        let mut content_end = (content_start, content_line, content_column);
+
+        loop {
+            match self.peek() {
+                Some('}') if self.peek_second() == Some('}') => {
+                    let content = self.source[content_start..content_end.0].to_string();
+                    self.bump();
+                    self.bump();
+                    return Ok(Token {
+                        kind: TokenKind::Native(content),
+                        span: Span::new(line, column, self.line, self.column),
+                        offset: (start, self.offset()),
+                        content_span: Some(Span::new(content_line, content_column, content_end.1, content_end.2)),
+                    });
+                }
+                Some(c) => {
+                    self.bump();
+                    if !c.is_whitespace() {
+                        content_end = (self.offset(), self.line, self.column);
+                    }
+                }
+                None => {
+                    return Err(ParseError::new(
+                        ParseErrorKind::UnterminatedNativeBlock,
+                        Span::new(line, column, self.line, self.column),
+                    ))
+                }
+            }
+        }
+    }
+
+    fn lex_string(&mut self, line: usize, column: usize) -> Result<TokenKind, ParseError> {
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(TokenKind::Str(value)),
+                Some('\\') => match self.bump() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(escaped) => value.push(escaped),
+                    None => break,
+                },
+                Some('\n') | None => break,
+                Some(c) => value.push(c),
+            }
+        }
+        Err(ParseError::new(
+            ParseErrorKind::UnterminatedString,
+            Span::new(line, column, self.line, self.column),
+        ))
+    }
+}
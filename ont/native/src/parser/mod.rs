@@ -0,0 +1,582 @@
+// ==============================================
+// ONTOLOGY PARSER v0.7.0
+// Parser recursivo descendente: fonte .onto -> OntologyProgram
+// ==============================================
+//
+// Gramática:
+//
+//   program       := item* EOF
+//   item          := function | agent | class | transmutation
+//   function      := ('@' IDENT)? 'fn' IDENT signature ('with' constraints)? '=' NATIVE
+//   signature     := '(' params? ')' '->' type
+//                  | ':' type (',' type)* ('->' type)?
+//   agent         := 'agent' IDENT '='? '{' (property | method)* '}'
+//   class         := 'class' IDENT '{' (field | method)* '}'
+//   transmutation := 'transmute' IDENT 'to' IDENT ('with' constraints)?
+//   property      := IDENT ':' (STRING | INT | IDENT)
+//   field         := IDENT ':' type
+//   method        := 'fn' IDENT '(' params? ')' ('->' type)?
+//   params        := IDENT ':' type (',' IDENT ':' type)*
+//   constraints   := constraint (',' constraint)*
+//   type          := IDENT ('<' type (',' type)* '>')?
+//                  | '(' (type (',' type)*)? ')' '->' type
+
+pub mod lexer;
+
+use std::collections::HashSet;
+use thiserror::Error;
+
+use crate::ast::*;
+use lexer::{Lexer, Token, TokenKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidCharacter(char),
+    UnterminatedString,
+    UnterminatedNativeBlock,
+    UnterminatedComment,
+    UnexpectedToken { expected: String, found: String },
+    WrongTypeArity { name: String, expected: usize, found: usize },
+    DuplicateDefinition(String),
+    UnknownFunction(String),
+}
+
+impl std::fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::InvalidCharacter(c) => write!(f, "invalid character '{}'", c),
+            ParseErrorKind::UnterminatedString => write!(f, "unterminated string literal"),
+            ParseErrorKind::UnterminatedNativeBlock => write!(f, "unterminated native block, expected '}}}}'"),
+            ParseErrorKind::UnterminatedComment => write!(f, "unterminated block comment"),
+            ParseErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+            ParseErrorKind::WrongTypeArity { name, expected, found } => {
+                write!(f, "type '{}' takes {} argument(s), found {}", name, expected, found)
+            }
+            ParseErrorKind::DuplicateDefinition(name) => write!(f, "'{}' is defined more than once", name),
+            ParseErrorKind::UnknownFunction(name) => write!(f, "transmutation refers to unknown function '{}'", name),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{span}: {kind}")]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Faz o parse de um programa Ontology completo.
+pub fn parse_program(source: &str) -> ParseResult<OntologyProgram> {
+    let tokens = Lexer::new(source).tokenize()?;
+    Parser::new(source, tokens).parse()
+}
+
+pub struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(source: &'a str, tokens: Vec<Token>) -> Self {
+        Self { source, tokens, pos: 0 }
+    }
+
+    pub fn parse(mut self) -> ParseResult<OntologyProgram> {
+        let mut program = OntologyProgram::default();
+
+        while !self.at(&TokenKind::Eof) {
+            match self.peek().kind {
+                TokenKind::Fn | TokenKind::At => program.functions.push(self.parse_function()?),
+                TokenKind::Agent => program.agents.push(self.parse_agent()?),
+                TokenKind::Class => program.classes.push(self.parse_class()?),
+                TokenKind::Transmute => program.transmutations.push(self.parse_transmutation()?),
+                _ => return Err(self.unexpected("'fn', 'agent', 'class' or 'transmute'")),
+            }
+        }
+
+        resolve(&mut program)?;
+        Ok(program)
+    }
+
+    // --- Declarações ---
+
+    fn parse_function(&mut self) -> ParseResult<Function> {
+        let start = self.peek().span;
+
+        let explicit_paradigm = if self.eat(&TokenKind::At) {
+            let (name, _) = self.expect_ident()?;
+            Some(paradigm_from_name(&name))
+        } else {
+            None
+        };
+
+        self.expect(&TokenKind::Fn)?;
+        let (name, _) = self.expect_ident()?;
+        let (params, return_type) = self.parse_signature()?;
+
+        let constraints = if self.eat(&TokenKind::With) {
+            self.parse_constraints()?
+        } else {
+            Vec::new()
+        };
+
+        self.expect(&TokenKind::Assign)?;
+        let (body, end) = match self.peek().kind.clone() {
+            TokenKind::Native(content) => {
+                let token = self.advance();
+                let span = token.content_span.unwrap_or(token.span);
+                (Body { content, span }, token.span)
+            }
+            _ => return Err(self.unexpected("native block '{{ ... }}'")),
+        };
+
+        let paradigm = explicit_paradigm
+            .unwrap_or_else(|| infer_paradigm(params.iter().map(|(_, t)| t).chain(Some(&return_type))));
+
+        Ok(Function {
+            name,
+            paradigm,
+            params,
+            return_type,
+            span: start.to(end),
+            body,
+            constraints,
+            target_paradigm: None,
+        })
+    }
+
+    fn parse_signature(&mut self) -> ParseResult<(Vec<(String, Type)>, Type)> {
+        if self.eat(&TokenKind::LParen) {
+            let params = self.parse_params()?;
+            self.expect(&TokenKind::RParen)?;
+            self.expect(&TokenKind::Arrow)?;
+            return Ok((params, self.parse_type()?));
+        }
+
+        self.expect(&TokenKind::Colon)?;
+        let mut types = vec![self.parse_type()?];
+        while self.eat(&TokenKind::Comma) {
+            types.push(self.parse_type()?);
+        }
+
+        if self.eat(&TokenKind::Arrow) {
+            let return_type = self.parse_type()?;
+            let params = types
+                .into_iter()
+                .enumerate()
+                .map(|(i, t)| (format!("arg{}", i), t))
+                .collect();
+            Ok((params, return_type))
+        } else if types.len() == 1 {
+            Ok((Vec::new(), types.remove(0)))
+        } else {
+            Err(self.unexpected("'->'"))
+        }
+    }
+
+    fn parse_agent(&mut self) -> ParseResult<Agent> {
+        let start = self.expect(&TokenKind::Agent)?.span;
+        let (name, _) = self.expect_ident()?;
+        self.eat(&TokenKind::Assign);
+        self.expect(&TokenKind::LBrace)?;
+
+        let mut properties = Vec::new();
+        let mut methods = Vec::new();
+        while !self.at(&TokenKind::RBrace) {
+            if self.at(&TokenKind::Fn) {
+                methods.push(self.parse_method()?);
+            } else {
+                let (key, _) = self.expect_ident()?;
+                self.expect(&TokenKind::Colon)?;
+                let value = match self.peek().kind.clone() {
+                    TokenKind::Str(v) | TokenKind::Int(v) | TokenKind::Ident(v) => {
+                        self.advance();
+                        v
+                    }
+                    _ => return Err(self.unexpected("property value")),
+                };
+                properties.push((key, value));
+            }
+            self.eat_separator();
+        }
+        let end = self.expect(&TokenKind::RBrace)?.span;
+
+        Ok(Agent { name, properties, methods, span: start.to(end) })
+    }
+
+    fn parse_class(&mut self) -> ParseResult<Class> {
+        let start = self.expect(&TokenKind::Class)?.span;
+        let (name, _) = self.expect_ident()?;
+        self.expect(&TokenKind::LBrace)?;
+
+        let mut fields = Vec::new();
+        let mut methods = Vec::new();
+        while !self.at(&TokenKind::RBrace) {
+            if self.at(&TokenKind::Fn) {
+                methods.push(self.parse_method()?);
+            } else {
+                let (field_name, field_start) = self.expect_ident()?;
+                self.expect(&TokenKind::Colon)?;
+                let field_type = self.parse_type()?;
+                fields.push(Field {
+                    name: field_name,
+                    field_type,
+                    span: field_start.to(self.previous_span()),
+                });
+            }
+            self.eat_separator();
+        }
+        let end = self.expect(&TokenKind::RBrace)?.span;
+
+        Ok(Class { name, fields, methods, span: start.to(end) })
+    }
+
+    fn parse_method(&mut self) -> ParseResult<Method> {
+        let start = self.expect(&TokenKind::Fn)?.span;
+        let (name, _) = self.expect_ident()?;
+        self.expect(&TokenKind::LParen)?;
+        let params = self.parse_params()?;
+        self.expect(&TokenKind::RParen)?;
+        let return_type = if self.eat(&TokenKind::Arrow) {
+            self.parse_type()?
+        } else {
+            Type::Unit
+        };
+
+        Ok(Method { name, params, return_type, span: start.to(self.previous_span()) })
+    }
+
+    fn parse_transmutation(&mut self) -> ParseResult<Transmutation> {
+        let start = self.expect(&TokenKind::Transmute)?.span;
+        let (function, _) = self.expect_ident()?;
+        self.expect(&TokenKind::To)?;
+        let (target, _) = self.expect_ident()?;
+
+        let constraints = if self.eat(&TokenKind::With) {
+            self.parse_constraints()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Transmutation {
+            name: format!("transmute_{}", function),
+            function,
+            target: paradigm_from_name(&target),
+            constraints,
+            span: start.to(self.previous_span()),
+        })
+    }
+
+    // --- Componentes ---
+
+    fn parse_params(&mut self) -> ParseResult<Vec<(String, Type)>> {
+        let mut params = Vec::new();
+        if self.at(&TokenKind::RParen) {
+            return Ok(params);
+        }
+        loop {
+            let (name, _) = self.expect_ident()?;
+            self.expect(&TokenKind::Colon)?;
+            params.push((name, self.parse_type()?));
+            if !self.eat(&TokenKind::Comma) {
+                return Ok(params);
+            }
+        }
+    }
+
+    /// Uma constraint é a sequência de tokens até a próxima vírgula de nível
+    /// zero, `=`, ou o início de outra declaração. O texto-fonte é preservado.
+    /// Parênteses aninham; `<` só abre argumentos de tipo colado a um nome
+    /// de tipo (`Map<K, V>`), senão é comparação (`balance < 100`).
+    fn parse_constraints(&mut self) -> ParseResult<Vec<Constraint>> {
+        let mut constraints = Vec::new();
+        loop {
+            let first = self.peek().clone();
+            let mut last = first.clone();
+            let mut consumed = 0usize;
+            let mut depth = 0usize;
+            let mut generics = 0usize;
+
+            loop {
+                let token = self.peek();
+                let after_type_name = consumed > 0
+                    && last.offset.1 == token.offset.0
+                    && matches!(&last.kind, TokenKind::Ident(name) if name.starts_with(|c: char| c.is_ascii_uppercase()));
+                match token.kind {
+                    TokenKind::LParen => depth += 1,
+                    TokenKind::RParen if depth > 0 => depth -= 1,
+                    TokenKind::Lt if after_type_name => generics += 1,
+                    TokenKind::Gt if generics > 0 => generics -= 1,
+                    TokenKind::Comma if depth == 0 && generics == 0 => break,
+                    TokenKind::Assign
+                    | TokenKind::Fn
+                    | TokenKind::At
+                    | TokenKind::Agent
+                    | TokenKind::Class
+                    | TokenKind::Transmute
+                    | TokenKind::Eof => break,
+                    _ => {}
+                }
+                last = self.advance();
+                consumed += 1;
+            }
+
+            if consumed == 0 {
+                return Err(self.unexpected("constraint"));
+            }
+
+            let text = &self.source[first.offset.0..last.offset.1];
+            constraints.push(Constraint {
+                name: text.split_whitespace().collect::<Vec<_>>().join(" "),
+                span: first.span.to(last.span),
+            });
+
+            if !self.eat(&TokenKind::Comma) {
+                return Ok(constraints);
+            }
+        }
+    }
+
+    pub fn parse_type(&mut self) -> ParseResult<Type> {
+        if self.eat(&TokenKind::LParen) {
+            let mut args = Vec::new();
+            if !self.at(&TokenKind::RParen) {
+                args.push(self.parse_type()?);
+                while self.eat(&TokenKind::Comma) {
+                    args.push(self.parse_type()?);
+                }
+            }
+            self.expect(&TokenKind::RParen)?;
+            if !self.eat(&TokenKind::Arrow) {
+                return match args.len() {
+                    0 => Ok(Type::Unit),
+                    1 => Ok(args.remove(0)),
+                    _ => Err(self.unexpected("'->'")),
+                };
+            }
+            return Ok(Type::Function(args, Box::new(self.parse_type()?)));
+        }
+
+        let (name, span) = self.expect_ident()?;
+        let mut args = Vec::new();
+        if self.eat(&TokenKind::Lt) {
+            args.push(self.parse_type()?);
+            while self.eat(&TokenKind::Comma) {
+                args.push(self.parse_type()?);
+            }
+            self.expect(&TokenKind::Gt)?;
+        }
+
+        let expected_arity = match name.as_str() {
+            "Int" | "Float" | "Bool" | "String" | "Unit" | "Bytes" | "Address" => Some(0),
+            "Pure" | "Mutable" | "Agent" | "Substrate" | "Object" => Some(1),
+            _ => None,
+        };
+        if let Some(expected) = expected_arity {
+            if args.len() != expected {
+                return Err(ParseError::new(
+                    ParseErrorKind::WrongTypeArity { name, expected, found: args.len() },
+                    span,
+                ));
+            }
+        }
+
+        Ok(match name.as_str() {
+            "Int" => Type::Int,
+            "Float" => Type::Float,
+            "Bool" => Type::Bool,
+            "String" => Type::String,
+            "Unit" => Type::Unit,
+            "Bytes" => Type::Bytes,
+            "Address" => Type::Address,
+            "Pure" => Type::Pure(Box::new(args.remove(0))),
+            "Mutable" => Type::Mutable(Box::new(args.remove(0))),
+            "Agent" => Type::Agent(Box::new(args.remove(0))),
+            "Substrate" => Type::Substrate(Box::new(args.remove(0))),
+            "Object" => match args.remove(0) {
+                Type::Named(class, inner) if inner.is_empty() => Type::Object(class),
+                _ => {
+                    return Err(ParseError::new(
+                        ParseErrorKind::UnexpectedToken {
+                            expected: "class name".to_string(),
+                            found: "type".to_string(),
+                        },
+                        span,
+                    ))
+                }
+            },
+            _ if args.is_empty() && is_type_var(&name) => Type::TypeVar(name),
+            _ => Type::Named(name, args),
+        })
+    }
+
+    // --- Primitivas ---
+
+    /// Índice do próximo token a ser consumido.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos.min(self.tokens.len() - 1)]
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.peek().clone();
+        if self.pos < self.tokens.len() - 1 {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn previous_span(&self) -> Span {
+        self.tokens[self.pos.saturating_sub(1)].span
+    }
+
+    fn at(&self, kind: &TokenKind) -> bool {
+        &self.peek().kind == kind
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.at(kind) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_separator(&mut self) {
+        if !self.eat(&TokenKind::Comma) {
+            self.eat(&TokenKind::Semicolon);
+        }
+    }
+
+    fn expect(&mut self, kind: &TokenKind) -> ParseResult<Token> {
+        if self.at(kind) {
+            Ok(self.advance())
+        } else {
+            Err(self.unexpected(&kind.describe()))
+        }
+    }
+
+    fn expect_ident(&mut self) -> ParseResult<(String, Span)> {
+        match self.peek().kind.clone() {
+            TokenKind::Ident(name) => Ok((name, self.advance().span)),
+            _ => Err(self.unexpected("identifier")),
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        let token = self.peek();
+        ParseError::new(
+            ParseErrorKind::UnexpectedToken {
+                expected: expected.to_string(),
+                found: token.kind.describe(),
+            },
+            token.span,
+        )
+    }
+}
+
+/// Resolução pós-parse: nomes duplicados, alvos de transmutação e
+/// referências a classes (`Named` -> `Object`).
+fn resolve(program: &mut OntologyProgram) -> ParseResult<()> {
+    let mut seen = HashSet::new();
+    let declared = program.functions.iter().map(|f| (&f.name, f.span))
+        .chain(program.agents.iter().map(|a| (&a.name, a.span)))
+        .chain(program.classes.iter().map(|c| (&c.name, c.span)));
+    for (name, span) in declared {
+        if !seen.insert(name.clone()) {
+            return Err(ParseError::new(ParseErrorKind::DuplicateDefinition(name.clone()), span));
+        }
+    }
+
+    for transmutation in &program.transmutations {
+        let function = program.functions.iter_mut()
+            .find(|f| f.name == transmutation.function)
+            .ok_or_else(|| ParseError::new(
+                ParseErrorKind::UnknownFunction(transmutation.function.clone()),
+                transmutation.span,
+            ))?;
+        function.target_paradigm = Some(transmutation.target.clone());
+    }
+
+    let classes: HashSet<String> = program.classes.iter().map(|c| c.name.clone()).collect();
+    let resolve_type = |t: &mut Type| resolve_class_refs(t, &classes);
+    for function in &mut program.functions {
+        function.params.iter_mut().for_each(|(_, t)| resolve_type(t));
+        resolve_type(&mut function.return_type);
+    }
+    let methods = program.agents.iter_mut().flat_map(|a| a.methods.iter_mut())
+        .chain(program.classes.iter_mut().flat_map(|c| c.methods.iter_mut()));
+    for method in methods {
+        method.params.iter_mut().for_each(|(_, t)| resolve_type(t));
+        resolve_type(&mut method.return_type);
+    }
+    for class in &mut program.classes {
+        class.fields.iter_mut().for_each(|f| resolve_type(&mut f.field_type));
+    }
+
+    Ok(())
+}
+
+fn resolve_class_refs(typ: &mut Type, classes: &HashSet<String>) {
+    match typ {
+        Type::Named(name, args) if args.is_empty() && classes.contains(name) => {
+            *typ = Type::Object(name.clone());
+        }
+        Type::Named(_, args) => args.iter_mut().for_each(|t| resolve_class_refs(t, classes)),
+        Type::Pure(inner) | Type::Mutable(inner) | Type::Agent(inner) | Type::Substrate(inner) => {
+            resolve_class_refs(inner, classes)
+        }
+        Type::Function(params, ret) => {
+            params.iter_mut().for_each(|t| resolve_class_refs(t, classes));
+            resolve_class_refs(ret, classes);
+        }
+        _ => {}
+    }
+}
+
+/// Variáveis de tipo são identificadores de uma letra maiúscula (`T`, `U`).
+fn is_type_var(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if c.is_ascii_uppercase())
+}
+
+fn paradigm_from_name(name: &str) -> Paradigm {
+    match name {
+        "Functional" | "Pure" => Paradigm::Functional,
+        "Imperative" => Paradigm::Imperative,
+        "Agent" => Paradigm::Agent,
+        "OO" => Paradigm::OO,
+        "Substrate" => Paradigm::Substrate,
+        other => Paradigm::Custom(other.to_string()),
+    }
+}
+
+/// Sem `@Paradigma` explícito, o paradigma vem da assinatura: qualquer
+/// `Agent<_>` ou `Substrate<_>` domina, depois `Mutable<_>`; o resto é funcional.
+fn infer_paradigm<'t>(types: impl Iterator<Item = &'t Type>) -> Paradigm {
+    let mut paradigm = Paradigm::Functional;
+    for typ in types {
+        match typ {
+            Type::Agent(_) => return Paradigm::Agent,
+            Type::Substrate(_) => paradigm = Paradigm::Substrate,
+            Type::Mutable(_) if paradigm == Paradigm::Functional => paradigm = Paradigm::Imperative,
+            _ => {}
+        }
+    }
+    paradigm
+}
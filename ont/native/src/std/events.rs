@@ -1,5 +1,17 @@
 use crate::type_checker::type_env::TypeEnv;
+use crate::ast::{Type, TypeAnnotation, Paradigm};
 
-pub fn register(_env: &mut TypeEnv) {
-    // TODO: Register events functions
+pub fn register(env: &mut TypeEnv) {
+    // emit: name (String), payload (T) -> Unit
+    // Paradigm: Imperative (Escreve no log de eventos da cadeia)
+    env.define_function("emit", TypeAnnotation {
+        name: "emit".to_string(),
+        type_expr: Type::Function(
+            vec![Type::String, Type::TypeVar("T".to_string())],
+            Box::new(Type::Unit)
+        ),
+        paradigm: Paradigm::Imperative,
+    }).unwrap();
+
+    env.add_side_effect("emit");
 }
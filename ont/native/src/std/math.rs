@@ -1,5 +1,28 @@
 use crate::type_checker::type_env::TypeEnv;
+use crate::ast::{Type, TypeAnnotation, Paradigm};
 
-pub fn register(_env: &mut TypeEnv) {
-    // TODO: Register math functions
+pub fn register(env: &mut TypeEnv) {
+    // abs: Int -> Int
+    // Paradigm: Pure
+    env.define_function("abs", TypeAnnotation {
+        name: "abs".to_string(),
+        type_expr: Type::Function(
+            vec![Type::Int],
+            Box::new(Type::Int)
+        ),
+        paradigm: Paradigm::Functional,
+    }).unwrap();
+
+    // min / max: Int, Int -> Int
+    // Paradigm: Pure
+    for name in ["min", "max"] {
+        env.define_function(name, TypeAnnotation {
+            name: name.to_string(),
+            type_expr: Type::Function(
+                vec![Type::Int, Type::Int],
+                Box::new(Type::Int)
+            ),
+            paradigm: Paradigm::Functional,
+        }).unwrap();
+    }
 }
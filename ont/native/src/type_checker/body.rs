@@ -0,0 +1,296 @@
+// ==============================================
+// ONTOLOGY NATIVE BLOCK EXPRESSIONS
+// Mini-linguagem de expressões dentro de `{{ ... }}`
+// ==============================================
+//
+//   block      := stmt (';' stmt)* ';'?
+//   stmt       := 'let' IDENT (':' type)? '=' expr
+//               | expr ('=' expr)?
+//   expr       := or
+//   or         := and ('||' and)*
+//   and        := equality ('&&' equality)*
+//   equality   := comparison (('==' | '!=') comparison)*
+//   comparison := additive (('<' | '>' | '<=' | '>=') additive)*
+//   additive   := term (('+' | '-') term)*
+//   term       := unary (('*' | '/') unary)*
+//   unary      := ('!' | '-') unary | postfix
+//   postfix    := primary ('.' IDENT ('(' args ')')? | '(' args ')')*
+//   primary    := INT | FLOAT | STRING | 'true' | 'false' | IDENT | '(' expr ')'
+//
+// Os spans são relocados a partir do span do conteúdo do bloco nativo.
+
+use crate::ast::{Body, Span, Type};
+use crate::parser::lexer::{Lexer, Token, TokenKind};
+use crate::parser::{ParseError, ParseErrorKind, ParseResult, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    NotEq,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Not,
+    Neg,
+}
+
+#[derive(Debug, Clone)]
+pub enum ExprKind {
+    Int(i128),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+    Var(String),
+    Call { callee: Box<Expr>, args: Vec<Expr> },
+    Member { target: Box<Expr>, member: String },
+    Binary { op: BinaryOp, lhs: Box<Expr>, rhs: Box<Expr> },
+    Unary { op: UnaryOp, operand: Box<Expr> },
+}
+
+#[derive(Debug, Clone)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum Stmt {
+    Let { name: String, annotation: Option<Type>, value: Expr, span: Span },
+    Assign { target: Expr, value: Expr, span: Span },
+    Expr(Expr),
+}
+
+/// Faz o parse do conteúdo de um bloco nativo.
+pub fn parse_body(body: &Body) -> ParseResult<Vec<Stmt>> {
+    let relocate = |span: Span| relocate(span, body.span);
+    let mut tokens = Lexer::new(&body.content)
+        .tokenize()
+        .map_err(|e| ParseError::new(e.kind, relocate(e.span)))?;
+    for token in &mut tokens {
+        token.span = relocate(token.span);
+    }
+    BodyParser { tokens, pos: 0, source: &body.content }.parse_block()
+}
+
+/// Converte um span relativo ao conteúdo do bloco em um span do arquivo.
+fn relocate(span: Span, origin: Span) -> Span {
+    let shift = |line: usize, column: usize| {
+        if line == 1 {
+            (origin.line, origin.column + column - 1)
+        } else {
+            (origin.line + line - 1, column)
+        }
+    };
+    let (line, column) = shift(span.line, span.column);
+    let (end_line, end_column) = shift(span.end_line, span.end_column);
+    Span::new(line, column, end_line, end_column)
+}
+
+struct BodyParser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    source: &'a str,
+}
+
+impl<'a> BodyParser<'a> {
+    fn parse_block(&mut self) -> ParseResult<Vec<Stmt>> {
+        let mut stmts = Vec::new();
+        while !self.at(&TokenKind::Eof) {
+            if self.eat(&TokenKind::Semicolon) {
+                continue;
+            }
+            stmts.push(self.parse_stmt()?);
+            if !self.at(&TokenKind::Eof) {
+                self.expect(&TokenKind::Semicolon)?;
+            }
+        }
+        Ok(stmts)
+    }
+
+    fn parse_stmt(&mut self) -> ParseResult<Stmt> {
+        let start = self.peek().span;
+
+        if matches!(&self.peek().kind, TokenKind::Ident(kw) if kw == "let") {
+            self.advance();
+            let name = match self.advance() {
+                Token { kind: TokenKind::Ident(name), .. } => name,
+                token => return Err(unexpected(&token, "identifier")),
+            };
+            let annotation = if self.eat(&TokenKind::Colon) {
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+            self.expect(&TokenKind::Assign)?;
+            let value = self.parse_expr()?;
+            let span = start.to(value.span);
+            return Ok(Stmt::Let { name, annotation, value, span });
+        }
+
+        let expr = self.parse_expr()?;
+        if self.eat(&TokenKind::Assign) {
+            let value = self.parse_expr()?;
+            let span = start.to(value.span);
+            return Ok(Stmt::Assign { target: expr, value, span });
+        }
+        Ok(Stmt::Expr(expr))
+    }
+
+    /// Tipos anotados em `let` reutilizam o parser de tipos da linguagem.
+    fn parse_type(&mut self) -> ParseResult<Type> {
+        let mut parser = Parser::new(self.source, self.tokens[self.pos..].to_vec());
+        let typ = parser.parse_type()?;
+        self.pos += parser.position();
+        Ok(typ)
+    }
+
+    fn parse_expr(&mut self) -> ParseResult<Expr> {
+        self.parse_binary(0)
+    }
+
+    /// Precedência por níveis: `||`, `&&`, igualdade, comparação, aditivos, multiplicativos.
+    fn parse_binary(&mut self, level: usize) -> ParseResult<Expr> {
+        const LEVELS: &[&[(TokenKind, BinaryOp)]] = &[
+            &[(TokenKind::OrOr, BinaryOp::Or)],
+            &[(TokenKind::AndAnd, BinaryOp::And)],
+            &[(TokenKind::EqEq, BinaryOp::Eq), (TokenKind::NotEq, BinaryOp::NotEq)],
+            &[
+                (TokenKind::Lt, BinaryOp::Lt),
+                (TokenKind::Gt, BinaryOp::Gt),
+                (TokenKind::Le, BinaryOp::Le),
+                (TokenKind::Ge, BinaryOp::Ge),
+            ],
+            &[(TokenKind::Plus, BinaryOp::Add), (TokenKind::Minus, BinaryOp::Sub)],
+            &[(TokenKind::Star, BinaryOp::Mul), (TokenKind::Slash, BinaryOp::Div)],
+        ];
+
+        if level == LEVELS.len() {
+            return self.parse_unary();
+        }
+
+        let mut lhs = self.parse_binary(level + 1)?;
+        while let Some(op) = LEVELS[level].iter().find(|(kind, _)| self.at(kind)).map(|(_, op)| *op) {
+            self.advance();
+            let rhs = self.parse_binary(level + 1)?;
+            let span = lhs.span.to(rhs.span);
+            lhs = Expr { kind: ExprKind::Binary { op, lhs: Box::new(lhs), rhs: Box::new(rhs) }, span };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> ParseResult<Expr> {
+        let op = match self.peek().kind {
+            TokenKind::Bang => UnaryOp::Not,
+            TokenKind::Minus => UnaryOp::Neg,
+            _ => return self.parse_postfix(),
+        };
+        let start = self.advance().span;
+        let operand = self.parse_unary()?;
+        let span = start.to(operand.span);
+        Ok(Expr { kind: ExprKind::Unary { op, operand: Box::new(operand) }, span })
+    }
+
+    fn parse_postfix(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            if self.eat(&TokenKind::Dot) {
+                let member = match self.advance() {
+                    Token { kind: TokenKind::Ident(name), .. } => name,
+                    token => return Err(unexpected(&token, "member name")),
+                };
+                let span = expr.span.to(self.previous_span());
+                expr = Expr { kind: ExprKind::Member { target: Box::new(expr), member }, span };
+            } else if self.eat(&TokenKind::LParen) {
+                let mut args = Vec::new();
+                if !self.at(&TokenKind::RParen) {
+                    args.push(self.parse_expr()?);
+                    while self.eat(&TokenKind::Comma) {
+                        args.push(self.parse_expr()?);
+                    }
+                }
+                let end = self.expect(&TokenKind::RParen)?.span;
+                let span = expr.span.to(end);
+                expr = Expr { kind: ExprKind::Call { callee: Box::new(expr), args }, span };
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> ParseResult<Expr> {
+        let token = self.advance();
+        let kind = match token.kind {
+            TokenKind::Int(ref value) => ExprKind::Int(value.parse().map_err(|_| unexpected(&token, "integer"))?),
+            TokenKind::Float(ref value) => ExprKind::Float(value.parse().map_err(|_| unexpected(&token, "float"))?),
+            TokenKind::Str(ref value) => ExprKind::Str(value.clone()),
+            TokenKind::Ident(ref name) if name == "true" => ExprKind::Bool(true),
+            TokenKind::Ident(ref name) if name == "false" => ExprKind::Bool(false),
+            TokenKind::Ident(ref name) => ExprKind::Var(name.clone()),
+            TokenKind::LParen => {
+                let inner = self.parse_expr()?;
+                self.expect(&TokenKind::RParen)?;
+                return Ok(inner);
+            }
+            _ => return Err(unexpected(&token, "expression")),
+        };
+        Ok(Expr { kind, span: token.span })
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos.min(self.tokens.len() - 1)]
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.peek().clone();
+        if self.pos < self.tokens.len() - 1 {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn previous_span(&self) -> Span {
+        self.tokens[self.pos.saturating_sub(1)].span
+    }
+
+    fn at(&self, kind: &TokenKind) -> bool {
+        &self.peek().kind == kind
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.at(kind) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, kind: &TokenKind) -> ParseResult<Token> {
+        if self.at(kind) {
+            Ok(self.advance())
+        } else {
+            Err(unexpected(self.peek(), &kind.describe()))
+        }
+    }
+}
+
+fn unexpected(token: &Token, expected: &str) -> ParseError {
+    ParseError::new(
+        ParseErrorKind::UnexpectedToken {
+            expected: expected.to_string(),
+            found: token.kind.describe(),
+        },
+        token.span,
+    )
+}
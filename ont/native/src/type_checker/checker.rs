@@ -0,0 +1,584 @@
+// ==============================================
+// ONTOLOGY TYPE CHECKER v0.7.0
+// Inferência/verificação de tipos e de fronteiras paradigmáticas
+// ==============================================
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::ast::*;
+use crate::parser::ParseError;
+use super::body::{parse_body, BinaryOp, Expr, ExprKind, Stmt, UnaryOp};
+use super::type_env::TypeEnv;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagnosticKind {
+    TypeMismatch { expected: Type, found: Type },
+    ArityMismatch { callee: String, expected: usize, found: usize },
+    UnknownFunction(String),
+    UnknownVariable(String),
+    UnknownMember { target: Type, member: String },
+    NotCallable(Type),
+    InvalidOperands { op: BinaryOp, lhs: Type, rhs: Type },
+    InvalidAssignmentTarget,
+    ImmutableAssignment(String),
+    /// Função de um paradigma fazendo algo que o paradigma proíbe
+    ParadigmViolation { function: String, paradigm: Paradigm, reason: String },
+    /// O bloco nativo não está na mini-linguagem de expressões e não foi verificado
+    OpaqueBody(ParseError),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    pub span: Span,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {}: ", self.span, level)?;
+        match &self.kind {
+            DiagnosticKind::TypeMismatch { expected, found } => {
+                write!(f, "type mismatch: expected {:?}, found {:?}", expected, found)
+            }
+            DiagnosticKind::ArityMismatch { callee, expected, found } => {
+                write!(f, "'{}' takes {} argument(s), {} supplied", callee, expected, found)
+            }
+            DiagnosticKind::UnknownFunction(name) => write!(f, "unknown function '{}'", name),
+            DiagnosticKind::UnknownVariable(name) => write!(f, "unknown variable '{}'", name),
+            DiagnosticKind::UnknownMember { target, member } => {
+                write!(f, "{:?} has no member '{}'", target, member)
+            }
+            DiagnosticKind::NotCallable(t) => write!(f, "value of type {:?} is not callable", t),
+            DiagnosticKind::InvalidOperands { op, lhs, rhs } => {
+                write!(f, "operator {:?} cannot be applied to {:?} and {:?}", op, lhs, rhs)
+            }
+            DiagnosticKind::InvalidAssignmentTarget => write!(f, "invalid assignment target"),
+            DiagnosticKind::ImmutableAssignment(name) => write!(f, "cannot assign to Pure value '{}'", name),
+            DiagnosticKind::ParadigmViolation { function, paradigm, reason } => {
+                write!(f, "paradigm violation in {:?} function '{}': {}", paradigm, function, reason)
+            }
+            DiagnosticKind::OpaqueBody(err) => write!(f, "native block not checked ({})", err.kind),
+        }
+    }
+}
+
+/// Verifica um programa com o ambiente padrão (stdlib registrada).
+pub fn check_program(program: &OntologyProgram) -> Vec<Diagnostic> {
+    TypeChecker::new(TypeEnv::new()).check(program)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BindingKind {
+    Param,
+    Local,
+}
+
+struct Binding {
+    typ: Option<Type>,
+    kind: BindingKind,
+}
+
+/// Contexto da função sendo verificada.
+struct Scope<'a> {
+    function: &'a Function,
+    bindings: HashMap<String, Binding>,
+}
+
+pub struct TypeChecker {
+    env: TypeEnv,
+    classes: HashMap<String, Class>,
+    agents: HashMap<String, Agent>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl TypeChecker {
+    pub fn new(env: TypeEnv) -> Self {
+        Self {
+            env,
+            classes: HashMap::new(),
+            agents: HashMap::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Verifica todas as funções do programa, acumulando diagnósticos em vez
+    /// de parar no primeiro erro.
+    pub fn check(mut self, program: &OntologyProgram) -> Vec<Diagnostic> {
+        self.classes = program.classes.iter().map(|c| (c.name.clone(), c.clone())).collect();
+        self.agents = program.agents.iter().map(|a| (a.name.clone(), a.clone())).collect();
+
+        // Funções do usuário entram no ambiente antes dos corpos, para permitir
+        // chamadas em qualquer ordem (inclusive recursão).
+        for function in &program.functions {
+            let annotation = TypeAnnotation {
+                name: function.name.clone(),
+                type_expr: Type::Function(
+                    function.params.iter().map(|(_, t)| t.clone()).collect(),
+                    Box::new(function.return_type.clone()),
+                ),
+                paradigm: function.paradigm.clone(),
+            };
+            self.env.define_function(&function.name, annotation).unwrap();
+            if function.paradigm == Paradigm::Imperative {
+                self.env.add_side_effect(&function.name);
+            }
+        }
+
+        for function in &program.functions {
+            self.check_function(function);
+        }
+
+        self.diagnostics
+    }
+
+    fn check_function(&mut self, function: &Function) {
+        if function.paradigm == Paradigm::Functional {
+            let signature = function.params.iter().map(|(_, t)| t).chain(Some(&function.return_type));
+            for typ in signature {
+                if contains_mutable(typ) {
+                    self.paradigm_violation(
+                        function,
+                        format!("signature mentions mutable type {:?}", typ),
+                        function.span,
+                    );
+                }
+            }
+        }
+
+        let stmts = match parse_body(&function.body) {
+            Ok(stmts) => stmts,
+            Err(err) => {
+                self.report(Severity::Warning, DiagnosticKind::OpaqueBody(err), function.body.span);
+                return;
+            }
+        };
+
+        let mut scope = Scope {
+            function,
+            bindings: function.params.iter()
+                .map(|(name, t)| (name.clone(), Binding { typ: Some(self.normalize(t)), kind: BindingKind::Param }))
+                .collect(),
+        };
+
+        let mut last_value = None;
+        for stmt in &stmts {
+            last_value = self.check_stmt(stmt, &mut scope);
+        }
+
+        let expected = self.normalize(&function.return_type);
+        if strip(&expected) == &Type::Unit {
+            return;
+        }
+        match (last_value, stmts.last()) {
+            (Some((found, span)), _) => self.expect_type(&expected, &found, span),
+            (None, Some(Stmt::Expr(_))) => {} // já reportado
+            (None, _) => self.report(
+                Severity::Error,
+                DiagnosticKind::TypeMismatch { expected, found: Type::Unit },
+                function.body.span,
+            ),
+        }
+    }
+
+    /// Retorna o tipo do valor produzido pelo comando (apenas expressões produzem valor).
+    fn check_stmt(&mut self, stmt: &Stmt, scope: &mut Scope) -> Option<(Type, Span)> {
+        match stmt {
+            Stmt::Let { name, annotation, value, span } => {
+                let found = self.infer(value, scope);
+                let typ = match annotation {
+                    Some(annotation) => {
+                        let annotation = self.normalize(annotation);
+                        if let Some(found) = &found {
+                            self.expect_type(&annotation, found, *span);
+                        }
+                        Some(annotation)
+                    }
+                    None => found,
+                };
+                scope.bindings.insert(name.clone(), Binding { typ, kind: BindingKind::Local });
+                None
+            }
+            Stmt::Assign { target, value, span } => {
+                self.check_assignment(target, value, *span, scope);
+                None
+            }
+            Stmt::Expr(expr) => self.infer(expr, scope).map(|t| (t, expr.span)),
+        }
+    }
+
+    fn check_assignment(&mut self, target: &Expr, value: &Expr, span: Span, scope: &mut Scope) {
+        let function = scope.function;
+        let (name, target_type, is_local) = match &target.kind {
+            ExprKind::Var(name) => match scope.bindings.get(name) {
+                Some(binding) => (name.clone(), binding.typ.clone(), binding.kind == BindingKind::Local),
+                None => {
+                    self.report(Severity::Error, DiagnosticKind::UnknownVariable(name.clone()), target.span);
+                    return;
+                }
+            },
+            ExprKind::Member { member, .. } => (member.clone(), self.infer(target, scope), false),
+            _ => {
+                self.report(Severity::Error, DiagnosticKind::InvalidAssignmentTarget, target.span);
+                return;
+            }
+        };
+
+        if function.paradigm == Paradigm::Functional && !is_local {
+            self.paradigm_violation(function, format!("assignment to '{}' mutates state", name), span);
+        }
+
+        // O tipo declarado (antes de `strip`) diz se o valor é `Pure<_>`.
+        let declared = match &target.kind {
+            ExprKind::Var(var) => function.params.iter().find(|(p, _)| p == var).map(|(_, t)| t.clone()),
+            _ => target_type.clone(),
+        };
+        if matches!(declared, Some(Type::Pure(_))) {
+            self.report(Severity::Error, DiagnosticKind::ImmutableAssignment(name), target.span);
+        }
+
+        let found = self.infer(value, scope);
+        if let (Some(expected), Some(found)) = (target_type, found) {
+            self.expect_type(&expected, &found, value.span);
+        }
+    }
+
+    fn infer(&mut self, expr: &Expr, scope: &Scope) -> Option<Type> {
+        match &expr.kind {
+            ExprKind::Int(_) => Some(Type::Int),
+            ExprKind::Float(_) => Some(Type::Float),
+            ExprKind::Str(_) => Some(Type::String),
+            ExprKind::Bool(_) => Some(Type::Bool),
+            ExprKind::Var(name) => {
+                if let Some(binding) = scope.bindings.get(name) {
+                    return binding.typ.clone();
+                }
+                if let Some(annotation) = self.env.lookup(name) {
+                    return Some(annotation.type_expr.clone());
+                }
+                if self.agents.contains_key(name) {
+                    return Some(Type::Agent(Box::new(Type::Object(name.clone()))));
+                }
+                self.report(Severity::Error, DiagnosticKind::UnknownVariable(name.clone()), expr.span);
+                None
+            }
+            ExprKind::Member { target, member } => {
+                let target_type = self.infer(target, scope)?;
+                let field = match strip(&target_type) {
+                    Type::Object(class) => self.classes.get(class)
+                        .and_then(|c| c.fields.iter().find(|f| &f.name == member))
+                        .map(|f| self.normalize(&f.field_type)),
+                    _ => None,
+                };
+                if field.is_none() {
+                    self.report(
+                        Severity::Error,
+                        DiagnosticKind::UnknownMember { target: target_type, member: member.clone() },
+                        expr.span,
+                    );
+                }
+                field
+            }
+            ExprKind::Call { callee, args } => self.infer_call(callee, args, expr.span, scope),
+            ExprKind::Binary { op, lhs, rhs } => {
+                let (lhs_type, rhs_type) = (self.infer(lhs, scope)?, self.infer(rhs, scope)?);
+                let result = binary_result(*op, strip(&lhs_type), strip(&rhs_type));
+                if result.is_none() {
+                    self.report(
+                        Severity::Error,
+                        DiagnosticKind::InvalidOperands { op: *op, lhs: lhs_type, rhs: rhs_type },
+                        expr.span,
+                    );
+                }
+                result
+            }
+            ExprKind::Unary { op, operand } => {
+                let operand_type = self.infer(operand, scope)?;
+                let ok = match op {
+                    UnaryOp::Not => strip(&operand_type) == &Type::Bool,
+                    UnaryOp::Neg => matches!(strip(&operand_type), Type::Int | Type::Float),
+                };
+                if ok {
+                    Some(strip(&operand_type).clone())
+                } else {
+                    let expected = if *op == UnaryOp::Not { Type::Bool } else { Type::Int };
+                    self.report(
+                        Severity::Error,
+                        DiagnosticKind::TypeMismatch { expected, found: operand_type },
+                        operand.span,
+                    );
+                    None
+                }
+            }
+        }
+    }
+
+    fn infer_call(&mut self, callee: &Expr, args: &[Expr], span: Span, scope: &Scope) -> Option<Type> {
+        let function = scope.function;
+
+        let (name, signature) = match &callee.kind {
+            ExprKind::Var(name) if !scope.bindings.contains_key(name) => {
+                let annotation = match self.env.lookup(name) {
+                    Some(annotation) => annotation.clone(),
+                    None => {
+                        self.report(Severity::Error, DiagnosticKind::UnknownFunction(name.clone()), callee.span);
+                        args.iter().for_each(|arg| { self.infer(arg, scope); });
+                        return None;
+                    }
+                };
+                let effectful = self.env.has_side_effect(name) || annotation.paradigm == Paradigm::Imperative;
+                if function.paradigm == Paradigm::Functional && effectful {
+                    self.paradigm_violation(
+                        function,
+                        format!("calls side-effecting function '{}'", name),
+                        span,
+                    );
+                }
+                (name.clone(), instantiate(&annotation.type_expr))
+            }
+            ExprKind::Member { target, member } => {
+                let target_type = self.infer(target, scope)?;
+                let (owner, method, is_agent) = match strip(&target_type) {
+                    Type::Object(class) => (
+                        class.clone(),
+                        self.classes.get(class).and_then(|c| c.methods.iter().find(|m| &m.name == member)).cloned(),
+                        false,
+                    ),
+                    Type::Agent(inner) => match strip(inner) {
+                        Type::Object(agent) => (
+                            agent.clone(),
+                            self.agents.get(agent).and_then(|a| a.methods.iter().find(|m| &m.name == member)).cloned(),
+                            true,
+                        ),
+                        _ => (String::new(), None, true),
+                    },
+                    _ => (String::new(), None, false),
+                };
+                let method = match method {
+                    Some(method) => method,
+                    None => {
+                        self.report(
+                            Severity::Error,
+                            DiagnosticKind::UnknownMember { target: target_type, member: member.clone() },
+                            callee.span,
+                        );
+                        return None;
+                    }
+                };
+                if is_agent && function.paradigm == Paradigm::Functional {
+                    self.paradigm_violation(
+                        function,
+                        format!("sends a message to agent '{}'", owner),
+                        span,
+                    );
+                }
+                let signature = Type::Function(
+                    method.params.iter().map(|(_, t)| self.normalize(t)).collect(),
+                    Box::new(self.normalize(&method.return_type)),
+                );
+                (format!("{}.{}", owner, member), instantiate(&signature))
+            }
+            // Valores (parâmetros, locais) não são polimórficos: suas variáveis
+            // de tipo são as da própria função, rígidas.
+            _ => {
+                let callee_type = self.infer(callee, scope)?;
+                ("<expr>".to_string(), callee_type)
+            }
+        };
+
+        let (params, ret) = match strip(&signature) {
+            Type::Function(params, ret) => (params.clone(), ret.as_ref().clone()),
+            other => {
+                self.report(Severity::Error, DiagnosticKind::NotCallable(other.clone()), callee.span);
+                return None;
+            }
+        };
+
+        if params.len() != args.len() {
+            self.report(
+                Severity::Error,
+                DiagnosticKind::ArityMismatch { callee: name, expected: params.len(), found: args.len() },
+                span,
+            );
+            args.iter().for_each(|arg| { self.infer(arg, scope); });
+            return Some(ret);
+        }
+
+        let mut subst = HashMap::new();
+        for (param, arg) in params.iter().zip(args) {
+            if let Some(found) = self.infer(arg, scope) {
+                if !unify(param, &found, &mut subst) {
+                    self.report(
+                        Severity::Error,
+                        DiagnosticKind::TypeMismatch { expected: generalize(&apply(param, &subst)), found },
+                        arg.span,
+                    );
+                }
+            }
+        }
+        Some(generalize(&apply(&ret, &subst)))
+    }
+
+    // --- Helpers ---
+
+    fn expect_type(&mut self, expected: &Type, found: &Type, span: Span) {
+        if !unify(expected, found, &mut HashMap::new()) {
+            self.report(
+                Severity::Error,
+                DiagnosticKind::TypeMismatch { expected: expected.clone(), found: found.clone() },
+                span,
+            );
+        }
+    }
+
+    /// `Named("Classe", [])` vindo de anotações locais vira `Object("Classe")`.
+    fn normalize(&self, typ: &Type) -> Type {
+        let classes: HashSet<&String> = self.classes.keys().collect();
+        map_type(typ, &|t| match t {
+            Type::Named(name, args) if args.is_empty() && classes.contains(name) => Some(Type::Object(name.clone())),
+            _ => None,
+        })
+    }
+
+    fn paradigm_violation(&mut self, function: &Function, reason: String, span: Span) {
+        self.report(
+            Severity::Error,
+            DiagnosticKind::ParadigmViolation {
+                function: function.name.clone(),
+                paradigm: function.paradigm.clone(),
+                reason,
+            },
+            span,
+        );
+    }
+
+    fn report(&mut self, severity: Severity, kind: DiagnosticKind, span: Span) {
+        self.diagnostics.push(Diagnostic { severity, kind, span });
+    }
+}
+
+/// Remove os qualificadores de mutabilidade externos (`Pure<_>`, `Mutable<_>`):
+/// eles restringem o uso, não a forma do valor.
+fn strip(typ: &Type) -> &Type {
+    match typ {
+        Type::Pure(inner) | Type::Mutable(inner) => strip(inner),
+        other => other,
+    }
+}
+
+fn contains_mutable(typ: &Type) -> bool {
+    match typ {
+        Type::Mutable(_) => true,
+        Type::Pure(inner) | Type::Agent(inner) | Type::Substrate(inner) => contains_mutable(inner),
+        Type::Named(_, args) => args.iter().any(contains_mutable),
+        Type::Function(params, ret) => params.iter().any(contains_mutable) || contains_mutable(ret),
+        _ => false,
+    }
+}
+
+/// Prefixo das variáveis de tipo instanciadas numa chamada; não é um
+/// identificador válido na linguagem, então não colide com as declaradas.
+const FLEXIBLE_PREFIX: char = '?';
+
+/// Instancia a assinatura de uma função chamada: suas variáveis de tipo viram
+/// variáveis flexíveis, que `unify` pode ligar.
+fn instantiate(typ: &Type) -> Type {
+    map_type(typ, &|t| match t {
+        Type::TypeVar(var) => Some(Type::TypeVar(format!("{}{}", FLEXIBLE_PREFIX, var))),
+        _ => None,
+    })
+}
+
+/// Desfaz `instantiate` nas variáveis que ficaram sem ligação, para o tipo
+/// voltar a ser reportado como declarado.
+fn generalize(typ: &Type) -> Type {
+    map_type(typ, &|t| match t {
+        Type::TypeVar(var) => var.strip_prefix(FLEXIBLE_PREFIX).map(|v| Type::TypeVar(v.to_string())),
+        _ => None,
+    })
+}
+
+/// Unificação de `expected` com `found`, acumulando as ligações em `subst`.
+/// Só as variáveis instanciadas da função chamada são flexíveis; as da
+/// assinatura da função verificada são rígidas (skolem) dentro do corpo e só
+/// unificam consigo mesmas.
+fn unify(expected: &Type, found: &Type, subst: &mut HashMap<String, Type>) -> bool {
+    let (expected, found) = (strip(expected), strip(found));
+    match (expected, found) {
+        (Type::TypeVar(var), _) if var.starts_with(FLEXIBLE_PREFIX) => match subst.get(var).cloned() {
+            Some(bound) => unify(&bound, found, subst),
+            None => {
+                subst.insert(var.clone(), found.clone());
+                true
+            }
+        },
+        (Type::Agent(a), Type::Agent(b)) | (Type::Substrate(a), Type::Substrate(b)) => unify(a, b, subst),
+        (Type::Named(a, xs), Type::Named(b, ys)) => {
+            a == b && xs.len() == ys.len() && xs.iter().zip(ys).all(|(x, y)| unify(x, y, subst))
+        }
+        (Type::Function(ps, r), Type::Function(qs, s)) => {
+            ps.len() == qs.len()
+                && ps.iter().zip(qs).all(|(p, q)| unify(p, q, subst))
+                && unify(r, s, subst)
+        }
+        (a, b) => a == b,
+    }
+}
+
+fn apply(typ: &Type, subst: &HashMap<String, Type>) -> Type {
+    map_type(typ, &|t| match t {
+        Type::TypeVar(var) => subst.get(var).cloned(),
+        _ => None,
+    })
+}
+
+/// Reescreve um tipo de fora para dentro; `f` devolve `Some` para substituir o nó.
+fn map_type(typ: &Type, f: &dyn Fn(&Type) -> Option<Type>) -> Type {
+    if let Some(replaced) = f(typ) {
+        return replaced;
+    }
+    match typ {
+        Type::Pure(inner) => Type::Pure(Box::new(map_type(inner, f))),
+        Type::Mutable(inner) => Type::Mutable(Box::new(map_type(inner, f))),
+        Type::Agent(inner) => Type::Agent(Box::new(map_type(inner, f))),
+        Type::Substrate(inner) => Type::Substrate(Box::new(map_type(inner, f))),
+        Type::Named(name, args) => Type::Named(name.clone(), args.iter().map(|t| map_type(t, f)).collect()),
+        Type::Function(params, ret) => Type::Function(
+            params.iter().map(|t| map_type(t, f)).collect(),
+            Box::new(map_type(ret, f)),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn binary_result(op: BinaryOp, lhs: &Type, rhs: &Type) -> Option<Type> {
+    let numeric = |t: &Type| matches!(t, Type::Int | Type::Float);
+    match op {
+        BinaryOp::Add if lhs == &Type::String && rhs == &Type::String => Some(Type::String),
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div => {
+            (numeric(lhs) && lhs == rhs).then(|| lhs.clone())
+        }
+        BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Le | BinaryOp::Ge => {
+            (numeric(lhs) && lhs == rhs).then_some(Type::Bool)
+        }
+        BinaryOp::Eq | BinaryOp::NotEq => unify(lhs, rhs, &mut HashMap::new()).then_some(Type::Bool),
+        BinaryOp::And | BinaryOp::Or => (lhs == &Type::Bool && rhs == &Type::Bool).then_some(Type::Bool),
+    }
+}
@@ -1 +1,5 @@
 pub mod type_env;
+pub mod body;
+pub mod checker;
+
+pub use checker::{check_program, Diagnostic, DiagnosticKind, Severity, TypeChecker};
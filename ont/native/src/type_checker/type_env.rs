@@ -1,5 +1,5 @@
 use std::collections::{HashMap, HashSet};
-use crate::ast::{Type, TypeAnnotation};
+use crate::ast::TypeAnnotation;
 
 pub struct TypeEnv {
     pub functions: HashMap<String, TypeAnnotation>,
@@ -27,4 +27,18 @@ impl TypeEnv {
     pub fn add_side_effect(&mut self, name: &str) {
         self.side_effects.insert(name.to_string());
     }
+
+    pub fn lookup(&self, name: &str) -> Option<&TypeAnnotation> {
+        self.functions.get(name)
+    }
+
+    pub fn has_side_effect(&self, name: &str) -> bool {
+        self.side_effects.contains(name)
+    }
+}
+
+impl Default for TypeEnv {
+    fn default() -> Self {
+        Self::new()
+    }
 }
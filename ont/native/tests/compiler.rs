@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod compiler_tests {
+    use ontology_lang::compile;
+    use ontology_lang::compiler::CompilerError;
+    use std::fs;
+    use std::process::Command;
+
+    const TREASURY: &str = r#"
+        class Vault {
+            owner: Address
+            fn withdraw(amount: Int) -> Bool
+        }
+
+        agent governor = {
+            identity: "dao_governor"
+            fn propose(title: String) -> Int
+        }
+
+        fn fee(amount: Int) -> Pure<Int> = {{ amount / 100 }}
+
+        @Imperative
+        fn settle(amount: Int) -> Unit = {{ store("settled", amount) }}
+    "#;
+
+    #[test]
+    fn test_compile_writes_solidity_and_abi() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("treasury.onto");
+        fs::write(&source, TREASURY).unwrap();
+
+        let out = dir.path().join("out");
+        let artifacts = compile(
+            source.to_str().unwrap(),
+            Some(out.to_str().unwrap()),
+            "solidity",
+            None,
+        ).expect("Compilation failed");
+
+        assert!(artifacts.bytecode.is_none());
+        let solidity = fs::read_to_string(&artifacts.source).unwrap();
+        assert!(solidity.contains("function fee(int256 amount) public view pureGuard returns (int256 _ret) {"));
+        assert!(solidity.contains("function settle(int256 amount) public mutableGuard(0) {"));
+        assert!(solidity.contains("function governor_propose(string calldata title) external agentGuard(msg.sender) returns (int256 _ret) {"));
+        assert!(solidity.contains("abi.encodeWithSignature(\"propose(string)\", title)"));
+        assert!(solidity.contains("function Vault_withdraw(int256 amount) public returns (bool _ret) {"));
+        // Nada de tokens soltos nem retornos que não batem com a assinatura
+        assert!(!solidity.lines().any(|line| matches!(line.trim(), "memory" | "storage")));
+        assert!(!solidity.contains("seal()"));
+
+        let abi: serde_json::Value = serde_json::from_str(&fs::read_to_string(&artifacts.abi).unwrap()).unwrap();
+        let names: Vec<_> = abi.as_array().unwrap().iter()
+            .map(|entry| entry["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["fee", "settle", "governorActor", "governor_propose", "Vault_withdraw"]);
+
+        assert_eq!(abi[0]["inputs"][0]["type"], "int256");
+        assert_eq!(abi[0]["outputs"][0]["type"], "int256");
+        assert_eq!(abi[0]["stateMutability"], "view");
+        assert_eq!(abi[1]["outputs"].as_array().unwrap().len(), 0);
+        assert_eq!(abi[1]["stateMutability"], "nonpayable");
+        assert_eq!(abi[3]["inputs"][0]["type"], "string");
+        assert_eq!(abi[4]["outputs"][0]["type"], "bool");
+    }
+
+    /// Compila o fonte gerado com `solc` (variável `SOLC` ou o do PATH);
+    /// é ignorado quando o compilador não está instalado.
+    #[test]
+    fn test_generated_solidity_compiles_with_solc() {
+        let solc = std::env::var("SOLC").unwrap_or_else(|_| "solc".to_string());
+        if Command::new(&solc).arg("--version").output().is_err() {
+            eprintln!("solc not found, skipping");
+            return;
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let treasury = dir.path().join("treasury.onto");
+        fs::write(&treasury, TREASURY).unwrap();
+
+        for source in [treasury.to_str().unwrap(), "examples/onchain/oracle.onto", "examples/onchain/dao.onto"] {
+            let artifacts = compile(source, dir.path().to_str(), "solidity", Some(&solc))
+                .unwrap_or_else(|e| panic!("{}: {}", source, e));
+            let bytecode = fs::read_to_string(artifacts.bytecode.unwrap()).unwrap();
+            assert!(bytecode.len() > 2, "{}: empty bytecode", source);
+        }
+    }
+
+    #[test]
+    fn test_compile_rejects_ill_typed_programs() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("bad.onto");
+        fs::write(&source, "fn pure_store(x: Int) -> Pure<Int> = {{ store(\"k\", x); x }}").unwrap();
+
+        let result = compile(source.to_str().unwrap(), dir.path().to_str(), "solidity", None);
+        match result {
+            Err(CompilerError::TypeCheck(diagnostics)) => assert_eq!(diagnostics.len(), 1),
+            other => panic!("expected type check failure, got {:?}", other),
+        }
+        assert!(!dir.path().join("bad.sol").exists());
+    }
+
+    #[test]
+    fn test_missing_solc_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = compile(
+            "examples/onchain/oracle.onto",
+            dir.path().to_str(),
+            "solidity",
+            Some("/nonexistent/solc"),
+        );
+        assert!(matches!(result, Err(CompilerError::Solc(_))));
+        // O fonte e a ABI são gerados antes da etapa opcional do solc
+        assert!(dir.path().join("oracle.sol").exists());
+        assert!(dir.path().join("oracle.json").exists());
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod parser_tests {
+    use ontology_lang::ast::{Paradigm, Type};
+    use ontology_lang::parser::ParseErrorKind;
+    use ontology_lang::parse_program;
+    use std::fs;
+
+    #[test]
+    fn test_parse_dao_example() {
+        let source = fs::read_to_string("examples/onchain/dao.onto")
+            .expect("Failed to read DAO example");
+
+        let program = parse_program(&source).expect("Failed to parse DAO program");
+
+        assert_eq!(program.functions.len(), 1);
+        let vote = &program.functions[0];
+        assert_eq!(vote.name, "vote");
+        assert_eq!(vote.params, vec![("arg0".to_string(), Type::Pure(Box::new(Type::Int)))]);
+        assert_eq!(vote.return_type, Type::Pure(Box::new(Type::Int)));
+        assert_eq!(vote.paradigm, Paradigm::Functional);
+        assert_eq!(vote.body.content, "vote logic");
+        assert_eq!(vote.target_paradigm, Some(Paradigm::Functional));
+        assert_eq!((vote.span.line, vote.span.column), (1, 1));
+
+        assert_eq!(program.agents.len(), 1);
+        assert_eq!(program.agents[0].name, "test_agent");
+        assert_eq!(
+            program.agents[0].properties,
+            vec![("identity".to_string(), "dao_governor".to_string())]
+        );
+        assert_eq!(program.agents[0].span.line, 4);
+
+        let transmutation = &program.transmutations[0];
+        assert_eq!(transmutation.name, "transmute_vote");
+        assert_eq!(transmutation.constraints[0].name, "O(1) complexity");
+        assert_eq!(transmutation.constraints[0].span.line, 9);
+    }
+
+    #[test]
+    fn test_parse_full_grammar() {
+        let source = r#"
+            class Treasury {
+                balance: Mutable<Int>,
+                owner: Address
+                fn deposit(amount: Int) -> Bool
+            }
+
+            agent governor = {
+                identity: "dao_governor"
+                fn propose(title: String, deadline: Int) -> Int
+                fn veto(id: Int)
+            }
+
+            @Imperative
+            fn settle(treasury: Treasury, amounts: List<Int>) -> Unit
+                with O(n) time
+            = {{ treasury.balance = 0 }}
+
+            fn apply : (T) -> T, T -> Pure<T> = {{ identity }}
+
+            transmute settle to Substrate with
+                sandbox(StateSandbox),
+                O(1) time
+        "#;
+
+        let program = parse_program(source).expect("Failed to parse program");
+
+        let class = &program.classes[0];
+        assert_eq!(class.fields.len(), 2);
+        assert_eq!(class.fields[0].field_type, Type::Mutable(Box::new(Type::Int)));
+        assert_eq!(class.methods[0].name, "deposit");
+        assert_eq!(class.methods[0].return_type, Type::Bool);
+
+        let agent = &program.agents[0];
+        assert_eq!(agent.methods.len(), 2);
+        assert_eq!(agent.methods[1].return_type, Type::Unit);
+
+        let settle = &program.functions[0];
+        assert_eq!(settle.paradigm, Paradigm::Imperative);
+        assert_eq!(settle.params[0].1, Type::Object("Treasury".to_string()));
+        assert_eq!(settle.params[1].1, Type::Named("List".to_string(), vec![Type::Int]));
+        assert_eq!(settle.constraints[0].name, "O(n) time");
+        assert_eq!(settle.target_paradigm, Some(Paradigm::Substrate));
+
+        let apply = &program.functions[1];
+        let t = || Type::TypeVar("T".to_string());
+        assert_eq!(apply.params[0].1, Type::Function(vec![t()], Box::new(t())));
+        assert_eq!(apply.params[1].1, t());
+        assert_eq!(apply.return_type, Type::Pure(Box::new(t())));
+
+        let constraints: Vec<_> = program.transmutations[0].constraints.iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(constraints, vec!["sandbox(StateSandbox)", "O(1) time"]);
+    }
+
+    #[test]
+    fn test_comparison_constraints_are_split_at_commas() {
+        let source = r#"
+            fn transfer(amount: Int) -> Bool
+                with balance < 100, gas > 0
+            = {{ ok }}
+
+            fn index(keys: List<Int>) -> Int
+                with bounded Map<Int, Bool>, size < 10
+            = {{ ok }}
+        "#;
+
+        let program = parse_program(source).expect("Failed to parse program");
+        let names = |i: usize| -> Vec<String> {
+            program.functions[i].constraints.iter().map(|c| c.name.clone()).collect()
+        };
+        assert_eq!(names(0), vec!["balance < 100", "gas > 0"]);
+        assert_eq!(names(1), vec!["bounded Map<Int, Bool>", "size < 10"]);
+    }
+
+    #[test]
+    fn test_parse_errors_carry_spans() {
+        let err = parse_program("fn broken : Int ->\n  = {{ x }}").unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::UnexpectedToken { .. }));
+        assert_eq!((err.span.line, err.span.column), (2, 3));
+
+        let err = parse_program("fn f : Pure<Int, Int> = {{ x }}").unwrap_err();
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::WrongTypeArity { name: "Pure".to_string(), expected: 1, found: 2 }
+        );
+
+        let err = parse_program("fn f : Int = {{ never closed").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnterminatedNativeBlock);
+
+        let err = parse_program("transmute ghost to Imperative").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnknownFunction("ghost".to_string()));
+
+        let err = parse_program("fn f : Int = {{ a }}\nfn f : Int = {{ b }}").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::DuplicateDefinition("f".to_string()));
+        assert_eq!(err.span.line, 2);
+
+        let err = parse_program("fn f : Int = {{ a }} $").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::InvalidCharacter('$'));
+        assert_eq!(err.to_string(), "1:22: invalid character '$'");
+    }
+}
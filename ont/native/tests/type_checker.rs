@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod type_checker_tests {
+    use ontology_lang::ast::{Paradigm, Type};
+    use ontology_lang::parse_program;
+    use ontology_lang::type_checker::{check_program, DiagnosticKind, Severity};
+
+    fn errors(source: &str) -> Vec<DiagnosticKind> {
+        let program = parse_program(source).expect("Failed to parse program");
+        check_program(&program)
+            .into_iter()
+            .filter(|d| d.is_error())
+            .map(|d| d.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_well_typed_program_has_no_errors() {
+        let source = r#"
+            class Treasury {
+                balance: Mutable<Int>
+                fn deposit(amount: Int) -> Bool
+            }
+
+            fn fee(amount: Int, rate: Int) -> Pure<Int> = {{
+                let gross = amount * rate;
+                max(gross / 100, 1)
+            }}
+
+            fn digest(payload: Bytes) -> Bytes = {{ sha256(payload) }}
+
+            fn apply : (T) -> T, T -> Pure<T> = {{ arg0(arg1) }}
+
+            @Imperative
+            fn settle(treasury: Treasury, amount: Int) -> Bool = {{
+                treasury.balance = treasury.balance - fee(amount, 3);
+                store("last_settlement", amount);
+                emit("Settled", amount);
+                treasury.deposit(amount)
+            }}
+        "#;
+
+        assert_eq!(errors(source), vec![]);
+    }
+
+    #[test]
+    fn test_arity_and_type_errors_are_all_collected() {
+        let source = r#"
+            fn fee(amount: Int, rate: Int) -> Int = {{ amount * rate }}
+
+            fn caller(x: Int) -> Int = {{
+                let a = fee(x);
+                let b: Bool = fee(x, "three");
+                sha256(x)
+            }}
+        "#;
+
+        let errors = errors(source);
+        assert_eq!(errors.len(), 5, "{:?}", errors);
+        assert_eq!(
+            errors[0],
+            DiagnosticKind::ArityMismatch { callee: "fee".to_string(), expected: 2, found: 1 }
+        );
+        assert_eq!(
+            errors[1],
+            DiagnosticKind::TypeMismatch { expected: Type::Int, found: Type::String }
+        );
+        assert_eq!(
+            errors[2],
+            DiagnosticKind::TypeMismatch { expected: Type::Bool, found: Type::Int }
+        );
+        assert_eq!(
+            errors[3],
+            DiagnosticKind::TypeMismatch { expected: Type::Bytes, found: Type::Int }
+        );
+        // O valor final do bloco (Bytes) não bate com o retorno declarado
+        assert_eq!(
+            errors[4],
+            DiagnosticKind::TypeMismatch { expected: Type::Int, found: Type::Bytes }
+        );
+    }
+
+    #[test]
+    fn test_declared_type_parameters_are_rigid_in_body() {
+        let source = r#"
+            fn id(x: T) -> T = {{ x }}
+
+            fn bogus(x: T) -> T = {{ 5 }}
+
+            fn twice(x: T, y: U) -> T = {{
+                let a = id(x);
+                let b: U = id(x);
+                a
+            }}
+        "#;
+
+        let tvar = |name: &str| Type::TypeVar(name.to_string());
+        assert_eq!(errors(source), vec![
+            DiagnosticKind::TypeMismatch { expected: tvar("T"), found: Type::Int },
+            DiagnosticKind::TypeMismatch { expected: tvar("U"), found: tvar("T") },
+        ]);
+    }
+
+    #[test]
+    fn test_functional_function_cannot_call_side_effects() {
+        let source = r#"
+            agent governor = {
+                fn propose(title: String) -> Int
+            }
+
+            fn tally(votes: Int) -> Pure<Int> = {{
+                store("votes", votes);
+                governor.propose("recount");
+                votes
+            }}
+        "#;
+
+        let program = parse_program(source).unwrap();
+        let diagnostics = check_program(&program);
+        let violations: Vec<_> = diagnostics.iter()
+            .filter_map(|d| match &d.kind {
+                DiagnosticKind::ParadigmViolation { function, paradigm, reason } => {
+                    Some((function.as_str(), paradigm.clone(), reason.as_str(), d.span.line))
+                }
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(violations, vec![
+            ("tally", Paradigm::Functional, "calls side-effecting function 'store'", 7),
+            ("tally", Paradigm::Functional, "sends a message to agent 'governor'", 8),
+        ]);
+    }
+
+    #[test]
+    fn test_opaque_body_is_a_warning() {
+        let program = parse_program("fn vote : Pure<Int> -> Pure<Int> =\n    {{ vote logic }}").unwrap();
+        let diagnostics = check_program(&program);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(matches!(diagnostics[0].kind, DiagnosticKind::OpaqueBody(_)));
+        assert_eq!((diagnostics[0].span.line, diagnostics[0].span.column), (2, 8));
+    }
+}
@@ -75,8 +75,8 @@ pub fn sign_with_sasc_key(_data: impl Serialize) -> String {
     "SASC_SIG_VALID".to_string()
 }
 
-pub fn calculate_merkle_root(_data: &[crate::joule_jailer::JouleEntry]) -> String {
-    "0xMERKLE_ROOT_GENESIS".to_string()
+pub fn calculate_merkle_root(data: &[crate::joule_jailer::JouleEntry]) -> String {
+    crate::joule_jailer::merkle_root(data)
 }
 
 pub fn measure_system_energy() -> f64 {
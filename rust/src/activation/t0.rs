@@ -2,11 +2,11 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, error};
 use crate::geometric_interrogation::{SovereignManifold, GeometricLieDetector, Vector};
-use crate::joule_jailer::{CruxLedger, JouleEntry, Block, DignityAttestation};
+use crate::joule_jailer::{CruxLedger, JouleEntry, DignityAttestation};
 use crate::entropy::VajraEntropyMonitor;
 use crate::activation::{
     ActivationError, ConstitutionalPrecheck, TCDRegistry, now_nanos, SystemType,
-    CertificationLevel, hash_constitution, sign_with_prince_key, calculate_merkle_root,
+    CertificationLevel, hash_constitution, sign_with_prince_key,
     sign_with_sasc_key, InferenceMetrics
 };
 
@@ -104,23 +104,16 @@ impl TZeroActivation {
 
         // Assinatura do Prince Creator (Memory ID 20)
         let prince_signature = sign_with_prince_key(&genesis_data);
+        let sasc_signature = sign_with_sasc_key(&genesis_data); // Memory ID 20
 
-        let attestation = DignityAttestation {
-            block_hash: calculate_merkle_root(&genesis_data),
+        ledger.pending_entries = genesis_data;
+        ledger.mine_attested_block(|block| DignityAttestation {
+            block_hash: block.hash.clone(),
             energy_budget_compliance: 0.001,
             affective_resonance: 1.0,
             prince_signature,
             timestamp: now_nanos(),
-            sasc_signature: sign_with_sasc_key(&genesis_data), // Memory ID 20
-        };
-
-        ledger.chain.push(Block {
-            index: 0,
-            timestamp: now_nanos(),
-            data: genesis_data,
-            attestation: Some(attestation),
-            previous_hash: "0".repeat(64),
-            hash: "GENESIS_HASH".to_string(),
+            sasc_signature,
         });
 
         info!("✅ BLOCO GÊNESIS CRIADO");
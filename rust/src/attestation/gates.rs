@@ -35,8 +35,9 @@ pub fn verify_5_gates(
 ) -> Result<VerifiedEnclave, GateViolation> {
 
     // --- GATE 1: Verificar assinatura da AWS (raiz de confiança) ---
+    let aws_root_cert = load_aws_root_cert()?;
     let verifier = AttestationVerifier {
-        aws_root_cert: vec![], // Should be loaded from a secure location
+        aws_root_cert,
         allowed_pcr0_values: vec![hex::decode(expected_pcr0.trim_start_matches("0x")).unwrap_or_default()],
         prince_public_key: *prince_public_key,
     };
@@ -67,13 +68,25 @@ pub fn verify_5_gates(
     Ok(VerifiedEnclave {
         enclave_id: hex::encode(identity.enclave_id),
         coherence_score: coherence,
-        attestation_timestamp: std::time::SystemTime::now()
+        attestation_timestamp: identity
+            .attestation_timestamp
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs(),
     })
 }
 
+/// Caminho padrão do certificado raiz da AWS Nitro (PEM ou DER)
+pub const DEFAULT_AWS_NITRO_ROOT_CERT: &str = "/etc/sasc/aws_nitro_root.pem";
+
+/// Carrega a raiz de confiança de `AWS_NITRO_ROOT_CERT`, ou do caminho padrão.
+/// Sem raiz não há Gate 1: a ausência é um erro, nunca uma aprovação.
+fn load_aws_root_cert() -> Result<Vec<u8>, GateViolation> {
+    let path = std::env::var("AWS_NITRO_ROOT_CERT").unwrap_or_else(|_| DEFAULT_AWS_NITRO_ROOT_CERT.to_string());
+    std::fs::read(&path)
+        .map_err(|e| GateViolation::Internal(format!("AWS Nitro root certificate {}: {}", path, e)))
+}
+
 fn is_hard_freeze_active() -> bool {
     false // Mock
 }
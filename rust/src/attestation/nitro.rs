@@ -1,10 +1,10 @@
-use aws_nitro_enclaves_cose::CoseSign1;
 use x509_parser::prelude::*;
-use ring::digest;
-use std::time::SystemTime;
+use x509_parser::oid_registry::{OID_KEY_TYPE_EC_PUBLIC_KEY, OID_NIST_EC_P384, OID_SIG_ECDSA_WITH_SHA384};
+use ring::{digest, signature};
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 use serde::{Deserialize};
-use ciborium::from_reader;
+use ciborium::{from_reader, Value};
 
 #[derive(Debug, Error)]
 pub enum AttestationError {
@@ -24,6 +24,20 @@ pub enum AttestationError {
     InvalidRootSignature,
     #[error("Invalid certificate chain")]
     InvalidCertChain,
+    #[error("Certificate not valid at attestation time: {0}")]
+    CertificateNotValid(String),
+    #[error("Unsupported algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+    #[error("Invalid COSE signature")]
+    InvalidCoseSignature,
+    #[error("Invalid attestation document: {0}")]
+    InvalidDocument(String),
+    #[error("Missing PCR{0}")]
+    MissingPCR(u32),
+    #[error("Invalid PCR{0} length")]
+    InvalidPCR(u32),
+    #[error("Debug-mode enclave (zeroed PCRs)")]
+    DebugModeEnclave,
     #[error("Unauthorized PCR0")]
     UnauthorizedPCR0,
     #[error("Audit log failed")]
@@ -32,11 +46,12 @@ pub enum AttestationError {
     KarnakIsolationFailed,
 }
 
-impl From<aws_nitro_enclaves_cose::error::CoseError> for AttestationError {
-    fn from(e: aws_nitro_enclaves_cose::error::CoseError) -> Self {
-        AttestationError::CoseError(format!("{:?}", e))
-    }
-}
+/// PCRs que todo documento do NSM traz; nenhum pode faltar.
+pub const REQUIRED_PCRS: u32 = 16;
+
+/// Identificador COSE do ES384 (ECDSA P-384 com SHA-384)
+const COSE_ALG_ES384: i64 = -35;
+const COSE_SIGN1_TAG: u64 = 18;
 
 pub struct EnclaveIdentity {
     pub enclave_id: digest::Digest,
@@ -44,12 +59,93 @@ pub struct EnclaveIdentity {
     pub attestation_timestamp: SystemTime,
 }
 
+/// COSE_Sign1 (RFC 8152 §4.2): `[protected, unprotected, payload, signature]`,
+/// opcionalmente com a tag 18, como emitido pelo Nitro Secure Module.
+#[derive(Debug, Clone)]
+pub struct CoseSign1 {
+    pub protected: Vec<u8>,
+    pub unprotected: Value,
+    pub payload: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl CoseSign1 {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AttestationError> {
+        let value: Value = from_reader(bytes).map_err(|e| AttestationError::CborError(e.to_string()))?;
+        let value = match value {
+            Value::Tag(COSE_SIGN1_TAG, inner) => *inner,
+            Value::Tag(tag, _) => return Err(AttestationError::CoseError(format!("Unexpected tag {}", tag))),
+            other => other,
+        };
+
+        let invalid = || AttestationError::CoseError("COSE_Sign1 must be a 4-element array".into());
+        let mut items = match value {
+            Value::Array(items) if items.len() == 4 => items.into_iter(),
+            _ => return Err(invalid()),
+        };
+        let next_bytes = |items: &mut std::vec::IntoIter<Value>| match items.next() {
+            Some(Value::Bytes(bytes)) => Ok(bytes),
+            _ => Err(invalid()),
+        };
+
+        let protected = next_bytes(&mut items)?;
+        let unprotected = items.next().ok_or_else(invalid)?;
+        let payload = next_bytes(&mut items)?;
+        let signature = next_bytes(&mut items)?;
+        Ok(Self { protected, unprotected, payload, signature })
+    }
+
+    /// Algoritmo declarado no cabeçalho protegido (rótulo 1).
+    pub fn algorithm(&self) -> Result<i64, AttestationError> {
+        if self.protected.is_empty() {
+            return Err(AttestationError::CoseError("Empty protected header".into()));
+        }
+        let header: Value = from_reader(self.protected.as_slice())
+            .map_err(|e| AttestationError::CborError(e.to_string()))?;
+        header
+            .as_map()
+            .and_then(|entries| {
+                entries.iter().find_map(|(label, value)| match (label.as_integer(), value.as_integer()) {
+                    (Some(label), Some(alg)) if i128::from(label) == 1 => i64::try_from(alg).ok(),
+                    _ => None,
+                })
+            })
+            .ok_or_else(|| AttestationError::CoseError("Missing alg in protected header".into()))
+    }
+
+    /// Sig_structure assinado: `["Signature1", protected, external_aad, payload]`.
+    fn to_be_signed(&self) -> Result<Vec<u8>, AttestationError> {
+        let structure = Value::Array(vec![
+            Value::Text("Signature1".into()),
+            Value::Bytes(self.protected.clone()),
+            Value::Bytes(Vec::new()),
+            Value::Bytes(self.payload.clone()),
+        ]);
+        let mut out = Vec::new();
+        ciborium::into_writer(&structure, &mut out).map_err(|e| AttestationError::CborError(e.to_string()))?;
+        Ok(out)
+    }
+
+    /// Verifica a assinatura ES384 com a chave pública (ponto SEC1 não
+    /// comprimido) do certificado folha.
+    pub fn verify_es384(&self, public_key: &[u8]) -> Result<(), AttestationError> {
+        let alg = self.algorithm()?;
+        if alg != COSE_ALG_ES384 {
+            return Err(AttestationError::UnsupportedAlgorithm(format!("COSE alg {}", alg)));
+        }
+        signature::UnparsedPublicKey::new(&signature::ECDSA_P384_SHA384_FIXED, public_key)
+            .verify(&self.to_be_signed()?, &self.signature)
+            .map_err(|_| AttestationError::InvalidCoseSignature)
+    }
+}
+
 /// Represents the structure of an AWS Nitro Enclave attestation document.
 /// Based on AWS documentation: https://docs.aws.amazon.com/enclaves/latest/userguide/set-up-attestation.html
 #[derive(Deserialize, Debug)]
 pub struct NitroAttestationDoc {
     #[serde(rename = "module_id")]
     pub module_id: String,
+    /// Milissegundos desde a época UNIX
     pub timestamp: u64,
     pub digest: String,
     pub pcrs: std::collections::HashMap<u32, Vec<u8>>,
@@ -64,13 +160,12 @@ pub struct NitroAttestationDoc {
 }
 
 pub struct AttestationVerifier {
+    /// Raiz da AWS Nitro (DER ou PEM); deve ser igual a `cabundle[0]`
     pub aws_root_cert: Vec<u8>,
     pub allowed_pcr0_values: Vec<Vec<u8>>,
     pub prince_public_key: [u8; 32],
 }
 
-use aws_nitro_enclaves_cose::crypto::Openssl;
-
 impl AttestationVerifier {
     pub fn verify_attestation_doc(
         &self,
@@ -83,8 +178,9 @@ impl AttestationVerifier {
         // 2. Parse the attestation document payload (CBOR)
         let payload = self.parse_payload(&cose_sign1)?;
 
-        // 3. Verify AWS signature chain
-        self.verify_aws_signature_chain(&payload)?;
+        // 3. Verify AWS signature chain and the COSE signature of the leaf
+        let leaf_public_key = self.verify_aws_signature_chain(&payload)?;
+        cose_sign1.verify_es384(&leaf_public_key)?;
 
         // 4. Extract and verify PCR values
         let pcrs = self.extract_pcrs(&payload)?;
@@ -110,28 +206,101 @@ impl AttestationVerifier {
         Ok(EnclaveIdentity {
             enclave_id: digest::digest(&digest::SHA256, &pcrs[0]),
             pcrs,
-            attestation_timestamp: SystemTime::now(),
+            attestation_timestamp: SystemTime::UNIX_EPOCH + Duration::from_millis(payload.timestamp),
         })
     }
 
-    fn verify_aws_signature_chain(&self, payload: &NitroAttestationDoc) -> Result<(), AttestationError> {
-        // Verify the certificate chain provided in the payload against the AWS Root CA
-        let _cert = parse_x509_certificate(&payload.certificate)
-            .map_err(|e| AttestationError::X509Error(format!("Cert error: {:?}", e)))?;
+    /// Valida `cabundle` a partir da raiz confiável até o certificado folha:
+    /// a raiz deve ser exatamente `aws_root_cert`, cada certificado deve ser
+    /// emitido (nome e assinatura ECDSA P-384/SHA-384) pelo anterior, os
+    /// intermediários devem ser CAs e todos devem estar válidos no instante do
+    /// documento. Devolve a chave pública da folha.
+    fn verify_aws_signature_chain(&self, payload: &NitroAttestationDoc) -> Result<Vec<u8>, AttestationError> {
+        let trusted_root = self.trusted_root_der()?;
+        let root_der = payload.cabundle.first().ok_or(AttestationError::InvalidCertChain)?;
+        if *root_der != trusted_root {
+            return Err(AttestationError::RootCertMismatch);
+        }
+
+        let mut chain = payload.cabundle.iter().map(|der| Self::parse_cert(der)).collect::<Result<Vec<_>, _>>()?;
+        chain.push(Self::parse_cert(&payload.certificate)?);
 
-        // In a full implementation, we would use 'ring' or 'rustls' to verify the chain
-        // against self.aws_root_cert and the cabundle.
-        Ok(())
+        let at_secs = i64::try_from(payload.timestamp / 1000)
+            .map_err(|_| AttestationError::InvalidDocument("timestamp out of range".into()))?;
+
+        let root = &chain[0];
+        Self::verify_issued_by(root, root).map_err(|_| AttestationError::InvalidRootSignature)?;
+        for (position, cert) in chain.iter().enumerate() {
+            let validity = cert.validity();
+            if at_secs < validity.not_before.timestamp() || at_secs > validity.not_after.timestamp() {
+                return Err(AttestationError::CertificateNotValid(cert.subject().to_string()));
+            }
+            let is_leaf = position == chain.len() - 1;
+            if !is_leaf && !cert.is_ca() {
+                return Err(AttestationError::InvalidCertChain);
+            }
+            if position > 0 {
+                Self::verify_issued_by(cert, &chain[position - 1])?;
+            }
+        }
+
+        let leaf_key = chain[chain.len() - 1].public_key();
+        let curve = leaf_key.algorithm.parameters.as_ref().and_then(|p| p.as_oid().ok());
+        if leaf_key.algorithm.algorithm != OID_KEY_TYPE_EC_PUBLIC_KEY || curve != Some(OID_NIST_EC_P384) {
+            return Err(AttestationError::UnsupportedAlgorithm("leaf key is not EC P-384".into()));
+        }
+        Ok(leaf_key.subject_public_key.data.to_vec())
+    }
+
+    fn parse_cert(der: &[u8]) -> Result<X509Certificate<'_>, AttestationError> {
+        X509Certificate::from_der(der)
+            .map(|(_, cert)| cert)
+            .map_err(|e| AttestationError::X509Error(format!("Cert error: {:?}", e)))
+    }
+
+    fn verify_issued_by(cert: &X509Certificate, issuer: &X509Certificate) -> Result<(), AttestationError> {
+        if cert.issuer() != issuer.subject() {
+            return Err(AttestationError::InvalidCertChain);
+        }
+        if cert.signature_algorithm.algorithm != OID_SIG_ECDSA_WITH_SHA384 {
+            return Err(AttestationError::UnsupportedAlgorithm(cert.signature_algorithm.algorithm.to_id_string()));
+        }
+        signature::UnparsedPublicKey::new(
+            &signature::ECDSA_P384_SHA384_ASN1,
+            issuer.public_key().subject_public_key.data.as_ref(),
+        )
+        .verify(cert.tbs_certificate.as_ref(), cert.signature_value.data.as_ref())
+        .map_err(|_| AttestationError::InvalidCertChain)
     }
 
+    fn trusted_root_der(&self) -> Result<Vec<u8>, AttestationError> {
+        if self.aws_root_cert.starts_with(b"-----BEGIN") {
+            let (_, pem) = x509_parser::pem::parse_x509_pem(&self.aws_root_cert)
+                .map_err(|e| AttestationError::X509Error(format!("Root PEM error: {:?}", e)))?;
+            Ok(pem.contents)
+        } else if self.aws_root_cert.is_empty() {
+            Err(AttestationError::X509Error("AWS root certificate not configured".into()))
+        } else {
+            Ok(self.aws_root_cert.clone())
+        }
+    }
+
+    /// PCR0..PCR15 devem estar presentes, com o mesmo tamanho (32, 48 ou 64
+    /// bytes). PCR0-2 zerados indicam enclave em modo debug.
     fn extract_pcrs(&self, payload: &NitroAttestationDoc) -> Result<Vec<Vec<u8>>, AttestationError> {
         let mut pcrs = Vec::new();
-        for i in 0..16 {
-            if let Some(pcr) = payload.pcrs.get(&i) {
-                pcrs.push(pcr.clone());
-            } else {
-                pcrs.push(vec![0u8; 48]); // Fill with zeros if missing
+        for i in 0..REQUIRED_PCRS {
+            let pcr = payload.pcrs.get(&i).ok_or(AttestationError::MissingPCR(i))?;
+            if ![32, 48, 64].contains(&pcr.len()) || pcrs.first().is_some_and(|p: &Vec<u8>| p.len() != pcr.len()) {
+                return Err(AttestationError::InvalidPCR(i));
             }
+            pcrs.push(pcr.clone());
+        }
+        if let Some(&extra) = payload.pcrs.keys().find(|&&i| i >= 32) {
+            return Err(AttestationError::InvalidPCR(extra));
+        }
+        if pcrs[..3].iter().all(|pcr| pcr.iter().all(|&b| b == 0)) {
+            return Err(AttestationError::DebugModeEnclave);
         }
         Ok(pcrs)
     }
@@ -149,12 +318,19 @@ impl AttestationVerifier {
     }
 
     fn parse_payload(&self, cose_sign1: &CoseSign1) -> Result<NitroAttestationDoc, AttestationError> {
-        let payload_bytes = cose_sign1.get_payload::<Openssl>(None)
-            .map_err(|e| AttestationError::CoseError(format!("Payload extraction failed: {:?}", e)))?;
-
-        let doc: NitroAttestationDoc = from_reader(payload_bytes.as_slice())
+        let doc: NitroAttestationDoc = from_reader(cose_sign1.payload.as_slice())
             .map_err(|e| AttestationError::CborError(e.to_string()))?;
 
+        if doc.module_id.is_empty() {
+            return Err(AttestationError::InvalidDocument("empty module_id".into()));
+        }
+        if doc.digest != "SHA384" {
+            return Err(AttestationError::UnsupportedAlgorithm(format!("digest {}", doc.digest)));
+        }
+        if doc.timestamp == 0 {
+            return Err(AttestationError::InvalidDocument("missing timestamp".into()));
+        }
+
         Ok(doc)
     }
 
@@ -28,11 +28,16 @@ fn main() {
     // 2. Teste do Carcereiro de Joule
     println!("--- TESTE 2: CARCEREIRO DE JOULE ---");
     let mut jailer = JouleJailer::new("UUID_DEMO_01".into());
-    let fake_binary = vec![0u8; 2048]; // 2 chunks
-    match jailer.run_wasm(fake_binary) {
+    match jailer.run_wasm(spin_loop_binary()) {
         Ok(_) => println!("✅ Execução WASM concluída"),
         Err(e) => println!("❌ Execução WASM falhou: {}", e),
     }
+    println!(
+        "📒 Ledger: {} blocos, {} entradas pendentes, {:.3}J consumidos",
+        jailer.ledger.chain.len(),
+        jailer.ledger.pending_entries.len(),
+        jailer.consumed_joules
+    );
     println!("");
 
     // 3. Interrogatório Constitucional
@@ -51,3 +56,38 @@ fn main() {
     // 7. Privacidade Zero-Knowledge
     run_zk_stress_test_demo();
 }
+
+/// `_start` que gira para sempre: o Carcereiro deve interrompê-lo no teto.
+fn spin_loop_binary() -> Vec<u8> {
+    use wasm_encoder::{
+        BlockType, CodeSection, ExportKind, ExportSection, Function, FunctionSection, Instruction, Module,
+        TypeSection, ValType,
+    };
+
+    let mut types = TypeSection::new();
+    types.ty().function([], []);
+    let mut functions = FunctionSection::new();
+    functions.function(0);
+    let mut exports = ExportSection::new();
+    exports.export("_start", ExportKind::Func, 0);
+
+    let mut body = Function::new([(1, ValType::I32)]);
+    for op in [
+        Instruction::Loop(BlockType::Empty),
+        Instruction::LocalGet(0),
+        Instruction::I32Const(1),
+        Instruction::I32Add,
+        Instruction::LocalSet(0),
+        Instruction::Br(0),
+        Instruction::End,
+        Instruction::End,
+    ] {
+        body.instruction(&op);
+    }
+    let mut code = CodeSection::new();
+    code.function(&body);
+
+    let mut module = Module::new();
+    module.section(&types).section(&functions).section(&exports).section(&code);
+    module.finish()
+}
@@ -0,0 +1,75 @@
+use clap::{Parser, Subcommand};
+use sasc_core::joule_jailer::{verify_chain, LedgerFile};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "crux-ledger")]
+#[command(about = "CRUX-86: Leitor e auditor do ledger de Joules", long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Lista os blocos do ledger
+    Dump {
+        path: PathBuf,
+        /// Emite os blocos completos em JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Verifica elos, raízes Merkle, hashes e atestações da cadeia
+    Audit { path: PathBuf },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let path = match &cli.command {
+        Commands::Dump { path, .. } | Commands::Audit { path } => path,
+    };
+    let chain = match LedgerFile::read(path) {
+        Ok(chain) => chain,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match cli.command {
+        Commands::Dump { json: true, .. } => {
+            println!("{}", serde_json::to_string_pretty(&chain).unwrap());
+        }
+        Commands::Dump { .. } => {
+            println!("📒 {} — {} blocos", path.display(), chain.len());
+            for block in &chain {
+                let energy: f64 = block.data.iter().map(|e| e.energy_consumed).sum();
+                println!(
+                    "   #{:<5} t={} entradas={:<3} energia={:.3}J hash={}…{}",
+                    block.index,
+                    block.timestamp,
+                    block.data.len(),
+                    energy,
+                    &block.hash[..block.hash.len().min(16)],
+                    if block.attestation.is_some() { " [atestado]" } else { "" }
+                );
+            }
+        }
+        Commands::Audit { .. } => {
+            let entries: usize = chain.iter().map(|b| b.data.len()).sum();
+            let energy: f64 = chain.iter().flat_map(|b| &b.data).map(|e| e.energy_consumed).sum();
+            let refused = chain.iter().flat_map(|b| &b.data).filter(|e| !e.constitutional_check).count();
+            println!("🔎 AUDITORIA: {}", path.display());
+            println!("   Blocos: {} | Entradas: {} | Energia: {:.3}J | Recusas: {}", chain.len(), entries, energy, refused);
+
+            if let Err(fault) = verify_chain(&chain) {
+                println!("❌ {}", fault);
+                return ExitCode::FAILURE;
+            }
+            println!("✅ Cadeia íntegra");
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -8,6 +8,7 @@ use crate::gates::gate8_multiverse_regulator::{Gate8MultiverseRegulator, Complex
 use crate::crypto::pqc::NeuralSignature;
 use crate::security::karnak_sealer::KarnakQuantumSealer;
 use crate::security::quantum_phi::QuantumPhiMonitor;
+use crate::security::platform_backend::{NvramFlag, PlatformBackend, PlatformError};
 
 pub const PLUTON_TPM_BASE: usize = 0xFED40000;
 pub const PCR16_OFFSET: usize = 0x320;
@@ -63,6 +64,8 @@ pub struct InvariantVerificationEngine {
     pub nonce_cache: NonceCache,
     #[zeroize(skip)]
     pub multiverse_regulator: crate::gates::gate8_multiverse_regulator::Gate8MultiverseRegulator,
+    #[zeroize(skip)]
+    pub platform: Box<dyn PlatformBackend>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -78,13 +81,17 @@ pub enum GateError {
 }
 
 impl InvariantVerificationEngine {
-    pub fn new(prince_pubkey: [u8; 32], pcr0: [u8; 48]) -> Self {
+    /// Não há backend padrão: um TPM emulado em memória aceitaria qualquer
+    /// PCR0 no valor de reset. Em hardware Pluton use `PlutonMmioBackend`;
+    /// `SoftwareTpm` fica para testes e desenvolvimento.
+    pub fn with_platform(prince_pubkey: [u8; 32], pcr0: [u8; 48], platform: Box<dyn PlatformBackend>) -> Self {
         Self {
             prince_public_key: prince_pubkey,
             pcr0_invariant: pcr0,
             lyapunov_threshold: 0.001,
             nonce_cache: NonceCache::new(),
             multiverse_regulator: crate::gates::gate8_multiverse_regulator::Gate8MultiverseRegulator::new(1_000_000_000_000),
+            platform,
         }
     }
 
@@ -111,9 +118,15 @@ impl InvariantVerificationEngine {
             return Err(GateError::Gate1Failure);
         }
 
-        // --- GATE 2: Verificar PCR0 contra invariante TPM via MMIO ---
-        let current_pcr0 = self.read_mmio_pcr0();
-        if current_pcr0 != self.pcr0_invariant {
+        // --- GATE 2: Verificar PCR0 contra invariante TPM ---
+        let pcr0_matches = match self.platform.read_pcr(0) {
+            Ok(current_pcr0) => current_pcr0 == self.pcr0_invariant,
+            Err(e) => {
+                log::error!("ASI_VERIFIER: PCR0 read failed: {}", e);
+                false
+            }
+        };
+        if !pcr0_matches {
             self.log_failure_to_tpm_nvram(0xBAD002);
             return Err(GateError::Gate2Failure);
         }
@@ -185,38 +198,16 @@ impl InvariantVerificationEngine {
         }
     }
 
-    fn read_mmio_pcr0(&self) -> [u8; 48] {
-        let mut pcr0 = [0u8; 48];
-        let addr = (PLUTON_TPM_BASE + PCR16_OFFSET) as *const u64;
-
-        // In a sandbox, this WILL segfault if we actually try to read physical memory.
-        // For the purpose of this implementation, we use a conditional check to simulate the read.
-        #[cfg(not(test))]
-        unsafe {
-            for i in 0..6 {
-                let val = core::ptr::read_volatile(addr.add(i));
-                pcr0[i*8..(i+1)*8].copy_from_slice(&val.to_le_bytes());
-            }
-        }
-
-        #[cfg(test)]
-        {
-            pcr0.copy_from_slice(&self.pcr0_invariant);
-        }
-
-        pcr0
-    }
-
+    /// Uma flag ilegível conta como congelamento ativo (falha fechada).
     fn is_hard_freeze_active(&self) -> bool {
-        let addr = (PLUTON_TPM_BASE + NVRAM_SASC_OFFSET) as *const u8;
-
-        #[cfg(not(test))]
-        unsafe {
-            core::ptr::read_volatile(addr) != 0
-        }
+        self.platform.read_nvram_flag(NvramFlag::HardFreeze).unwrap_or_else(|e| {
+            log::error!("ASI_VERIFIER: Hard freeze flag unreadable: {}", e);
+            true
+        })
+    }
 
-        #[cfg(test)]
-        false
+    pub fn set_hard_freeze(&self, active: bool) -> Result<(), PlatformError> {
+        self.platform.write_nvram_flag(NvramFlag::HardFreeze, active)
     }
 
     fn compute_lyapunov_exponent(&self) -> f64 {
@@ -229,12 +220,10 @@ impl InvariantVerificationEngine {
 
     // Emergency Protocols
     pub fn log_failure_to_tpm_nvram(&self, error_code: u32) {
-        let addr = (PLUTON_TPM_BASE + AUDIT_LOG_OFFSET) as *mut u32;
-        #[cfg(not(test))]
-        unsafe {
-            core::ptr::write_volatile(addr, error_code);
-        }
         log::error!("ASI_VERIFIER: Logging failure 0x{:X} to TPM NVRAM", error_code);
+        if let Err(e) = self.platform.append_audit_log(error_code) {
+            log::error!("ASI_VERIFIER: Audit log append failed: {}", e);
+        }
     }
 
     pub fn broadcast_emergency_to_sasc(&self) {
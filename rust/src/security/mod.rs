@@ -2,6 +2,7 @@ pub mod bio_hardening;
 pub mod gateway_alpha;
 pub mod integrity;
 pub mod invariant_engine;
+pub mod platform_backend;
 pub mod memory;
 pub mod karnak_sealer;
 pub mod quantum_phi;
@@ -0,0 +1,372 @@
+// Backends de plataforma para o InvariantVerificationEngine
+//
+// O motor de invariantes precisa de quatro operações do root-of-trust:
+// ler um PCR, ler/gravar uma flag de NVRAM e acrescentar um código ao log de
+// auditoria. Em hardware Pluton elas são MMIO; em hosts comuns e em testes
+// usa-se o emulador de TPM em software ou o mock.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha384};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+
+use crate::security::invariant_engine::{AUDIT_LOG_OFFSET, NVRAM_SASC_OFFSET, PCR16_OFFSET, PLUTON_TPM_BASE};
+
+/// Tamanho de um PCR do banco SHA-384
+pub const PCR_SIZE: usize = 48;
+pub const PCR_COUNT: u32 = 24;
+
+#[derive(Debug, Error)]
+pub enum PlatformError {
+    #[error("PCR {0} inexistente")]
+    InvalidPcr(u32),
+    #[error("Falha de E/S no backend: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Estado do TPM corrompido: {0}")]
+    Corrupted(String),
+    #[error("Backend indisponível: {0}")]
+    Unavailable(String),
+}
+
+/// Flags persistentes na área SASC da NVRAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum NvramFlag {
+    HardFreeze,
+}
+
+impl NvramFlag {
+    fn offset(self) -> usize {
+        match self {
+            NvramFlag::HardFreeze => NVRAM_SASC_OFFSET,
+        }
+    }
+}
+
+pub trait PlatformBackend: Send + Sync {
+    fn read_pcr(&self, index: u32) -> Result<[u8; PCR_SIZE], PlatformError>;
+
+    fn read_nvram_flag(&self, flag: NvramFlag) -> Result<bool, PlatformError>;
+
+    fn write_nvram_flag(&self, flag: NvramFlag, value: bool) -> Result<(), PlatformError>;
+
+    fn append_audit_log(&self, code: u32) -> Result<(), PlatformError>;
+}
+
+fn check_pcr_index(index: u32) -> Result<usize, PlatformError> {
+    if index < PCR_COUNT {
+        Ok(index as usize)
+    } else {
+        Err(PlatformError::InvalidPcr(index))
+    }
+}
+
+/// PCR estendido como num TPM: `novo = SHA-384(atual || medida)`.
+pub fn extend_pcr_value(current: &[u8; PCR_SIZE], measurement: &[u8]) -> [u8; PCR_SIZE] {
+    let mut hasher = Sha384::new();
+    hasher.update(current);
+    hasher.update(measurement);
+    hasher.finalize().into()
+}
+
+// ----------------------------------------------------------------------------
+// Pluton (MMIO)
+// ----------------------------------------------------------------------------
+
+/// Acesso direto aos registradores do Pluton em `PLUTON_TPM_BASE`.
+pub struct PlutonMmioBackend {
+    base: usize,
+}
+
+impl PlutonMmioBackend {
+    /// # Safety
+    ///
+    /// Só é válido num host com o Pluton mapeado em `PLUTON_TPM_BASE` e com
+    /// acesso à memória física; em qualquer outro lugar as leituras falham
+    /// com falta de segmentação.
+    pub unsafe fn new() -> Self {
+        Self { base: PLUTON_TPM_BASE }
+    }
+}
+
+impl PlatformBackend for PlutonMmioBackend {
+    fn read_pcr(&self, index: u32) -> Result<[u8; PCR_SIZE], PlatformError> {
+        let index = check_pcr_index(index)?;
+        let addr = (self.base + PCR16_OFFSET + index * PCR_SIZE) as *const u64;
+        let mut pcr = [0u8; PCR_SIZE];
+        for i in 0..PCR_SIZE / 8 {
+            // SAFETY: garantido por `PlutonMmioBackend::new`
+            let val = unsafe { core::ptr::read_volatile(addr.add(i)) };
+            pcr[i * 8..(i + 1) * 8].copy_from_slice(&val.to_le_bytes());
+        }
+        Ok(pcr)
+    }
+
+    fn read_nvram_flag(&self, flag: NvramFlag) -> Result<bool, PlatformError> {
+        let addr = (self.base + flag.offset()) as *const u8;
+        // SAFETY: garantido por `PlutonMmioBackend::new`
+        Ok(unsafe { core::ptr::read_volatile(addr) } != 0)
+    }
+
+    fn write_nvram_flag(&self, flag: NvramFlag, value: bool) -> Result<(), PlatformError> {
+        let addr = (self.base + flag.offset()) as *mut u8;
+        // SAFETY: garantido por `PlutonMmioBackend::new`
+        unsafe { core::ptr::write_volatile(addr, value as u8) };
+        Ok(())
+    }
+
+    fn append_audit_log(&self, code: u32) -> Result<(), PlatformError> {
+        let addr = (self.base + AUDIT_LOG_OFFSET) as *mut u32;
+        // SAFETY: garantido por `PlutonMmioBackend::new`
+        unsafe { core::ptr::write_volatile(addr, code) };
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Emulador de TPM em software
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TpmState {
+    /// PCRs em hex; os ausentes estão no valor de reset (zeros)
+    pcrs: BTreeMap<u32, String>,
+    nvram: BTreeMap<NvramFlag, bool>,
+}
+
+/// TPM emulado: PCRs SHA-384 estendidos por `extend_pcr`, flags de NVRAM e
+/// log de auditoria. Com `open` o estado sobrevive ao processo em
+/// `<dir>/tpm_state.json` e o log é acrescentado a `<dir>/audit.log`.
+pub struct SoftwareTpm {
+    state: Mutex<TpmState>,
+    audit: Mutex<Vec<u32>>,
+    dir: Option<PathBuf>,
+}
+
+impl Default for SoftwareTpm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SoftwareTpm {
+    /// TPM volátil, recém-ligado.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(TpmState { pcrs: BTreeMap::new(), nvram: BTreeMap::new() }),
+            audit: Mutex::new(Vec::new()),
+            dir: None,
+        }
+    }
+
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, PlatformError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+
+        let state = match std::fs::read_to_string(dir.join("tpm_state.json")) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| PlatformError::Corrupted(e.to_string()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                TpmState { pcrs: BTreeMap::new(), nvram: BTreeMap::new() }
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let audit = match std::fs::read_to_string(dir.join("audit.log")) {
+            Ok(log) => log
+                .lines()
+                .map(|line| {
+                    u32::from_str_radix(line.trim_start_matches("0x"), 16)
+                        .map_err(|_| PlatformError::Corrupted(format!("linha de auditoria inválida: {}", line)))
+                })
+                .collect::<Result<_, _>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self { state: Mutex::new(state), audit: Mutex::new(audit), dir: Some(dir) })
+    }
+
+    /// Estende o PCR `index` com `measurement`.
+    pub fn extend_pcr(&self, index: u32, measurement: &[u8]) -> Result<[u8; PCR_SIZE], PlatformError> {
+        check_pcr_index(index)?;
+        let mut state = self.state.lock().unwrap();
+        let extended = extend_pcr_value(&Self::pcr_value(&state, index)?, measurement);
+        state.pcrs.insert(index, hex::encode(extended));
+        self.persist(&state)?;
+        Ok(extended)
+    }
+
+    pub fn audit_log(&self) -> Vec<u32> {
+        self.audit.lock().unwrap().clone()
+    }
+
+    fn pcr_value(state: &TpmState, index: u32) -> Result<[u8; PCR_SIZE], PlatformError> {
+        let Some(value) = state.pcrs.get(&index) else {
+            return Ok([0u8; PCR_SIZE]);
+        };
+        hex::decode(value)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| PlatformError::Corrupted(format!("PCR {}", index)))
+    }
+
+    fn persist(&self, state: &TpmState) -> Result<(), PlatformError> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+        // Grava e renomeia para nunca deixar um estado pela metade
+        let tmp = dir.join("tpm_state.json.tmp");
+        let json = serde_json::to_vec_pretty(state).map_err(|e| PlatformError::Corrupted(e.to_string()))?;
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, dir.join("tpm_state.json"))?;
+        Ok(())
+    }
+
+    pub fn state_dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+}
+
+impl PlatformBackend for SoftwareTpm {
+    fn read_pcr(&self, index: u32) -> Result<[u8; PCR_SIZE], PlatformError> {
+        check_pcr_index(index)?;
+        Self::pcr_value(&self.state.lock().unwrap(), index)
+    }
+
+    fn read_nvram_flag(&self, flag: NvramFlag) -> Result<bool, PlatformError> {
+        Ok(self.state.lock().unwrap().nvram.get(&flag).copied().unwrap_or(false))
+    }
+
+    fn write_nvram_flag(&self, flag: NvramFlag, value: bool) -> Result<(), PlatformError> {
+        let mut state = self.state.lock().unwrap();
+        state.nvram.insert(flag, value);
+        self.persist(&state)
+    }
+
+    fn append_audit_log(&self, code: u32) -> Result<(), PlatformError> {
+        let mut audit = self.audit.lock().unwrap();
+        if let Some(dir) = &self.dir {
+            let mut file = std::fs::OpenOptions::new().create(true).append(true).open(dir.join("audit.log"))?;
+            writeln!(file, "0x{:08X}", code)?;
+        }
+        audit.push(code);
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Mock
+// ----------------------------------------------------------------------------
+
+/// Backend em memória com PCRs definidos diretamente e falhas injetáveis.
+#[derive(Default)]
+pub struct MockPlatform {
+    pcrs: Mutex<BTreeMap<u32, [u8; PCR_SIZE]>>,
+    flags: Mutex<BTreeMap<NvramFlag, bool>>,
+    audit: Mutex<Vec<u32>>,
+    fail: std::sync::atomic::AtomicBool,
+}
+
+impl MockPlatform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_pcr(self, index: u32, value: [u8; PCR_SIZE]) -> Self {
+        self.pcrs.lock().unwrap().insert(index, value);
+        self
+    }
+
+    pub fn with_flag(self, flag: NvramFlag, value: bool) -> Self {
+        self.flags.lock().unwrap().insert(flag, value);
+        self
+    }
+
+    /// Com `true`, toda operação devolve `PlatformError::Unavailable`.
+    pub fn set_failing(&self, failing: bool) {
+        self.fail.store(failing, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    pub fn audit_log(&self) -> Vec<u32> {
+        self.audit.lock().unwrap().clone()
+    }
+
+    fn check(&self) -> Result<(), PlatformError> {
+        if self.fail.load(std::sync::atomic::Ordering::SeqCst) {
+            Err(PlatformError::Unavailable("mock configurado para falhar".into()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl PlatformBackend for MockPlatform {
+    fn read_pcr(&self, index: u32) -> Result<[u8; PCR_SIZE], PlatformError> {
+        self.check()?;
+        check_pcr_index(index)?;
+        Ok(self.pcrs.lock().unwrap().get(&index).copied().unwrap_or([0u8; PCR_SIZE]))
+    }
+
+    fn read_nvram_flag(&self, flag: NvramFlag) -> Result<bool, PlatformError> {
+        self.check()?;
+        Ok(self.flags.lock().unwrap().get(&flag).copied().unwrap_or(false))
+    }
+
+    fn write_nvram_flag(&self, flag: NvramFlag, value: bool) -> Result<(), PlatformError> {
+        self.check()?;
+        self.flags.lock().unwrap().insert(flag, value);
+        Ok(())
+    }
+
+    fn append_audit_log(&self, code: u32) -> Result<(), PlatformError> {
+        self.check()?;
+        self.audit.lock().unwrap().push(code);
+        Ok(())
+    }
+}
+
+/// Permite compartilhar um backend com o motor e ainda inspecioná-lo.
+impl<T: PlatformBackend + ?Sized> PlatformBackend for std::sync::Arc<T> {
+    fn read_pcr(&self, index: u32) -> Result<[u8; PCR_SIZE], PlatformError> {
+        (**self).read_pcr(index)
+    }
+
+    fn read_nvram_flag(&self, flag: NvramFlag) -> Result<bool, PlatformError> {
+        (**self).read_nvram_flag(flag)
+    }
+
+    fn write_nvram_flag(&self, flag: NvramFlag, value: bool) -> Result<(), PlatformError> {
+        (**self).write_nvram_flag(flag, value)
+    }
+
+    fn append_audit_log(&self, code: u32) -> Result<(), PlatformError> {
+        (**self).append_audit_log(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_software_tpm_extend_and_persist() {
+        let dir = std::env::temp_dir().join(format!("sasc_swtpm_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let tpm = SoftwareTpm::open(&dir).unwrap();
+        assert_eq!(tpm.read_pcr(0).unwrap(), [0u8; PCR_SIZE]);
+        let extended = tpm.extend_pcr(0, b"bootloader").unwrap();
+        assert_eq!(extended, extend_pcr_value(&[0u8; PCR_SIZE], b"bootloader"));
+        tpm.write_nvram_flag(NvramFlag::HardFreeze, true).unwrap();
+        tpm.append_audit_log(0xBAD002).unwrap();
+
+        let reopened = SoftwareTpm::open(&dir).unwrap();
+        assert_eq!(reopened.read_pcr(0).unwrap(), extended);
+        assert!(reopened.read_nvram_flag(NvramFlag::HardFreeze).unwrap());
+        assert_eq!(reopened.audit_log(), vec![0xBAD002]);
+        assert!(matches!(reopened.read_pcr(PCR_COUNT), Err(PlatformError::InvalidPcr(_))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
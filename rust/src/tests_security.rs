@@ -66,6 +66,7 @@ mod tests {
     #[test]
     fn test_invariant_verification_engine_full_pass() {
         use crate::security::invariant_engine::{InvariantVerificationEngine, GateError};
+        use crate::security::platform_backend::SoftwareTpm;
         use crate::crypto::pqc::{PostQuantumKey, LatticePublicKey, LatticeSecretKey};
         use crate::gates::gate8_multiverse_regulator::ComplexityClass;
         use ed25519_dalek::{SigningKey, Signer};
@@ -76,7 +77,7 @@ mod tests {
         let prince_pubkey: [u8; 32] = *verifying_key.as_bytes();
         let pcr0_invariant: [u8; 48] = [0u8; 48];
 
-        let mut engine = InvariantVerificationEngine::new(prince_pubkey, pcr0_invariant);
+        let mut engine = InvariantVerificationEngine::with_platform(prince_pubkey, pcr0_invariant, Box::new(SoftwareTpm::new()));
 
         let doc = b"ASI_ATTESTATION_DOC_V1";
         let mut hasher = blake3::Hasher::new();
@@ -100,6 +101,7 @@ mod tests {
     #[test]
     fn test_invariant_verification_engine_replay_attack() {
         use crate::security::invariant_engine::{InvariantVerificationEngine, GateError};
+        use crate::security::platform_backend::SoftwareTpm;
         use crate::crypto::pqc::{PostQuantumKey, LatticePublicKey, LatticeSecretKey};
         use crate::gates::gate8_multiverse_regulator::ComplexityClass;
         use ed25519_dalek::{SigningKey, Signer};
@@ -110,7 +112,7 @@ mod tests {
         let prince_pubkey: [u8; 32] = *verifying_key.as_bytes();
         let pcr0_invariant: [u8; 48] = [0u8; 48];
 
-        let mut engine = InvariantVerificationEngine::new(prince_pubkey, pcr0_invariant);
+        let mut engine = InvariantVerificationEngine::with_platform(prince_pubkey, pcr0_invariant, Box::new(SoftwareTpm::new()));
 
         let doc = b"ASI_ATTESTATION_DOC_V1";
         let mut hasher = blake3::Hasher::new();
@@ -191,12 +193,13 @@ mod tests {
     #[test]
     fn test_invariant_engine_secure_cleanup() {
         use crate::security::invariant_engine::InvariantVerificationEngine;
+        use crate::security::platform_backend::SoftwareTpm;
         use zeroize::Zeroize;
 
         let prince_pubkey: [u8; 32] = [1u8; 32];
         let pcr0_invariant: [u8; 48] = [2u8; 48];
 
-        let mut engine = InvariantVerificationEngine::new(prince_pubkey, pcr0_invariant);
+        let mut engine = InvariantVerificationEngine::with_platform(prince_pubkey, pcr0_invariant, Box::new(SoftwareTpm::new()));
 
         // Before cleanup
         assert_eq!(engine.prince_public_key, [1u8; 32]);
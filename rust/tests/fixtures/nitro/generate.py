@@ -0,0 +1,147 @@
+#!/usr/bin/env python3
+"""Gera os documentos de atestação Nitro usados em tests/nitro_attestation_test.rs.
+
+Os documentos seguem o formato do NSM (COSE_Sign1 ES384 sobre um payload CBOR
+com module_id, digest, timestamp, pcrs, certificate, cabundle, ...), mas a
+cadeia é emitida por uma raiz de teste própria (`root.der`), já que documentos
+reais só são assinados pela raiz da AWS dentro de um enclave.
+
+    python3 generate.py   # regrava os arquivos deste diretório
+"""
+
+import datetime
+import hashlib
+import os
+import ssl
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.asymmetric.utils import decode_dss_signature
+from cryptography.x509.oid import NameOID
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+T0 = datetime.datetime(2025, 1, 1, tzinfo=datetime.timezone.utc)
+NONCE = b"sasc-nonce-0001"
+
+
+# --- CBOR mínimo (RFC 8949) --------------------------------------------------
+
+def _head(major, n):
+    if n < 24:
+        return bytes([major << 5 | n])
+    for info, size in ((24, 1), (25, 2), (26, 4), (27, 8)):
+        if n < 1 << (8 * size):
+            return bytes([major << 5 | info]) + n.to_bytes(size, "big")
+    raise ValueError(n)
+
+
+def cbor(value):
+    if value is None:
+        return b"\xf6"
+    if isinstance(value, bool):
+        return b"\xf5" if value else b"\xf4"
+    if isinstance(value, int):
+        return _head(0, value) if value >= 0 else _head(1, -1 - value)
+    if isinstance(value, bytes):
+        return _head(2, len(value)) + value
+    if isinstance(value, str):
+        raw = value.encode()
+        return _head(3, len(raw)) + raw
+    if isinstance(value, list):
+        return _head(4, len(value)) + b"".join(cbor(v) for v in value)
+    if isinstance(value, dict):
+        return _head(5, len(value)) + b"".join(cbor(k) + cbor(v) for k, v in value.items())
+    raise TypeError(type(value))
+
+
+# --- Certificados ------------------------------------------------------------
+
+def name(cn):
+    return x509.Name([x509.NameAttribute(NameOID.ORGANIZATION_NAME, "SASC Test"),
+                      x509.NameAttribute(NameOID.COMMON_NAME, cn)])
+
+
+def cert(subject, key, issuer, issuer_key, not_before, not_after, ca):
+    builder = (x509.CertificateBuilder()
+               .subject_name(name(subject))
+               .issuer_name(name(issuer))
+               .public_key(key.public_key())
+               .serial_number(int.from_bytes(hashlib.sha256(subject.encode()).digest()[:8], "big"))
+               .not_valid_before(not_before)
+               .not_valid_after(not_after)
+               .add_extension(x509.BasicConstraints(ca=ca, path_length=None), critical=True))
+    return builder.sign(issuer_key, hashes.SHA384()).public_bytes(serialization.Encoding.DER)
+
+
+def key(seed):
+    # Chaves determinísticas: regenerar mantém as mesmas chaves públicas
+    secret = int.from_bytes(hashlib.sha384(seed.encode()).digest(), "big") % (2**383)
+    return ec.derive_private_key(secret, ec.SECP384R1())
+
+
+# --- COSE_Sign1 --------------------------------------------------------------
+
+def cose_sign1(payload, signer):
+    protected = cbor({1: -35})  # alg: ES384
+    sig_structure = cbor(["Signature1", protected, b"", payload])
+    r, s = decode_dss_signature(signer.sign(sig_structure, ec.ECDSA(hashes.SHA384())))
+    signature = r.to_bytes(48, "big") + s.to_bytes(48, "big")
+    return b"\xd2" + cbor([protected, {}, payload, signature])  # tag 18
+
+
+def document(leaf, cabundle, timestamp, pcrs):
+    return cbor({
+        "module_id": "i-0sasc-enc0000000000001",
+        "digest": "SHA384",
+        "timestamp": int(timestamp.timestamp() * 1000),
+        "pcrs": pcrs,
+        "certificate": leaf,
+        "cabundle": cabundle,
+        "public_key": None,
+        "user_data": None,
+        "nonce": NONCE,
+    })
+
+
+def main():
+    root_key, int_key, leaf_key, rogue_key = key("root"), key("intermediate"), key("leaf"), key("rogue")
+    years = datetime.timedelta(days=3650)
+    root = cert("Nitro Test Root", root_key, "Nitro Test Root", root_key, T0 - years, T0 + years, True)
+    inter = cert("Nitro Test Zonal", int_key, "Nitro Test Root", root_key, T0, T0 + datetime.timedelta(days=30), True)
+    leaf_from = T0 + datetime.timedelta(days=1)
+    leaf = cert("i-0sasc-enc0000000000001", leaf_key, "Nitro Test Zonal", int_key,
+                leaf_from, leaf_from + datetime.timedelta(hours=3), False)
+    rogue_root = cert("Nitro Test Root", rogue_key, "Nitro Test Root", rogue_key, T0 - years, T0 + years, True)
+
+    pcrs = {i: hashlib.sha384(b"pcr%d" % i).digest() for i in range(16)}
+    at = leaf_from + datetime.timedelta(hours=1)
+
+    fixtures = {
+        "valid.cose": cose_sign1(document(leaf, [root, inter], at, pcrs), leaf_key),
+        "expired.cose": cose_sign1(document(leaf, [root, inter], at + datetime.timedelta(hours=4), pcrs), leaf_key),
+        "wrong_root.cose": cose_sign1(document(leaf, [rogue_root, inter], at, pcrs), leaf_key),
+        "signed_by_intermediate.cose": cose_sign1(document(leaf, [root, inter], at, pcrs), int_key),
+        "missing_pcr.cose": cose_sign1(
+            document(leaf, [root, inter], at, {i: v for i, v in pcrs.items() if i != 3}), leaf_key),
+        "debug_mode.cose": cose_sign1(
+            document(leaf, [root, inter], at, {i: bytes(48) for i in range(16)}), leaf_key),
+    }
+
+    tampered = bytearray(fixtures["valid.cose"])
+    tampered[-1] ^= 0x01
+    fixtures["bad_signature.cose"] = bytes(tampered)
+
+    with open(os.path.join(HERE, "root.der"), "wb") as f:
+        f.write(root)
+    with open(os.path.join(HERE, "root.pem"), "w") as f:
+        f.write(ssl.DER_cert_to_PEM_cert(root))
+    with open(os.path.join(HERE, "pcr0.hex"), "w") as f:
+        f.write(pcrs[0].hex() + "\n")
+    for file_name, data in fixtures.items():
+        with open(os.path.join(HERE, file_name), "wb") as f:
+            f.write(data)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,70 @@
+use ed25519_dalek::{Signer, SigningKey};
+use sasc_core::crypto::pqc::{LatticePublicKey, LatticeSecretKey, PostQuantumKey};
+use sasc_core::gates::gate8_multiverse_regulator::ComplexityClass;
+use sasc_core::security::invariant_engine::{GateError, InvariantVerificationEngine};
+use sasc_core::security::platform_backend::{MockPlatform, NvramFlag, PlatformBackend, SoftwareTpm};
+use std::sync::Arc;
+
+const DOC: &[u8] = b"ASI_ATTESTATION_DOC_V1";
+
+fn run_gates(engine: &mut InvariantVerificationEngine, key: &SigningKey, nonce: u64) -> Result<(), GateError> {
+    let signature = key.sign(blake3::hash(DOC).as_bytes()).to_bytes();
+    let pqc_key = PostQuantumKey::new(
+        LatticePublicKey { data: [0u8; 1024] },
+        LatticeSecretKey { data: [0u8; 1024] },
+        [0u8; 32],
+    );
+    let q_sig = pqc_key.sign_neural_consent(&[0.1f32; 64]);
+    engine.verify_8_gates(DOC, &signature, nonce, 0.0, &q_sig, ComplexityClass::Low)
+}
+
+#[test]
+fn test_pcr0_mismatch_is_audited() {
+    let key = SigningKey::from_bytes(&[3u8; 32]);
+    let platform = Arc::new(MockPlatform::new().with_pcr(0, [7u8; 48]));
+    let mut engine = InvariantVerificationEngine::with_platform(
+        *key.verifying_key().as_bytes(),
+        [0u8; 48],
+        Box::new(platform.clone()),
+    );
+
+    assert_eq!(run_gates(&mut engine, &key, 1), Err(GateError::Gate2Failure));
+    assert_eq!(platform.audit_log(), vec![0xBAD002]);
+}
+
+#[test]
+fn test_unavailable_backend_fails_closed() {
+    let key = SigningKey::from_bytes(&[4u8; 32]);
+    let platform = Arc::new(MockPlatform::new());
+    platform.set_failing(true);
+    let mut engine = InvariantVerificationEngine::with_platform(
+        *key.verifying_key().as_bytes(),
+        [0u8; 48],
+        Box::new(platform.clone()),
+    );
+
+    assert_eq!(run_gates(&mut engine, &key, 1), Err(GateError::Gate2Failure));
+}
+
+#[test]
+fn test_hard_freeze_from_software_tpm() {
+    let dir = std::env::temp_dir().join(format!("sasc_engine_swtpm_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let tpm = SoftwareTpm::open(&dir).unwrap();
+    let pcr0 = tpm.extend_pcr(0, b"sasc-kernel-v31").unwrap();
+    tpm.write_nvram_flag(NvramFlag::HardFreeze, true).unwrap();
+    drop(tpm);
+
+    // O congelamento gravado por outro processo continua valendo
+    let key = SigningKey::from_bytes(&[5u8; 32]);
+    let mut engine = InvariantVerificationEngine::with_platform(
+        *key.verifying_key().as_bytes(),
+        pcr0,
+        Box::new(SoftwareTpm::open(&dir).unwrap()),
+    );
+    assert_eq!(run_gates(&mut engine, &key, 1), Err(GateError::Gate4Failure));
+    assert_eq!(SoftwareTpm::open(&dir).unwrap().audit_log(), vec![0xBAD004]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
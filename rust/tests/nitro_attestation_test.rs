@@ -0,0 +1,96 @@
+use sasc_core::attestation::nitro::{AttestationError, AttestationVerifier, CoseSign1};
+
+// Fixtures gerados por tests/fixtures/nitro/generate.py (cadeia P-384 de teste)
+const ROOT_DER: &[u8] = include_bytes!("fixtures/nitro/root.der");
+const PCR0_HEX: &str = include_str!("fixtures/nitro/pcr0.hex");
+const NONCE: &[u8] = b"sasc-nonce-0001";
+
+fn verifier() -> AttestationVerifier {
+    AttestationVerifier {
+        aws_root_cert: ROOT_DER.to_vec(),
+        allowed_pcr0_values: vec![hex::decode(PCR0_HEX.trim()).unwrap()],
+        prince_public_key: [0u8; 32],
+    }
+}
+
+fn verify(doc: &[u8]) -> Result<(), AttestationError> {
+    verifier().verify_attestation_doc(doc, NONCE).map(|_| ())
+}
+
+#[test]
+fn test_valid_document_is_accepted() {
+    let identity = verifier()
+        .verify_attestation_doc(include_bytes!("fixtures/nitro/valid.cose"), NONCE)
+        .unwrap();
+    assert_eq!(identity.pcrs.len(), 16);
+    assert_eq!(hex::encode(&identity.pcrs[0]), PCR0_HEX.trim());
+}
+
+#[test]
+fn test_root_accepted_as_pem() {
+    let pem = include_bytes!("fixtures/nitro/root.pem");
+    let verifier = AttestationVerifier { aws_root_cert: pem.to_vec(), ..verifier() };
+    assert!(verifier.verify_attestation_doc(include_bytes!("fixtures/nitro/valid.cose"), NONCE).is_ok());
+}
+
+#[test]
+fn test_wrong_nonce_is_rejected() {
+    let result = verifier().verify_attestation_doc(include_bytes!("fixtures/nitro/valid.cose"), b"other-nonce");
+    assert!(matches!(result, Err(AttestationError::InvalidNonce)));
+}
+
+#[test]
+fn test_unlisted_pcr0_is_rejected() {
+    let verifier = AttestationVerifier { allowed_pcr0_values: vec![vec![0xAA; 48]], ..verifier() };
+    let result = verifier.verify_attestation_doc(include_bytes!("fixtures/nitro/valid.cose"), NONCE);
+    assert!(matches!(result, Err(AttestationError::UnauthorizedPCR0)));
+}
+
+#[test]
+fn test_missing_root_is_rejected() {
+    let verifier = AttestationVerifier { aws_root_cert: vec![], ..verifier() };
+    let result = verifier.verify_attestation_doc(include_bytes!("fixtures/nitro/valid.cose"), NONCE);
+    assert!(matches!(result, Err(AttestationError::X509Error(_))));
+}
+
+#[test]
+fn test_expired_leaf_is_rejected() {
+    let result = verify(include_bytes!("fixtures/nitro/expired.cose"));
+    assert!(matches!(result, Err(AttestationError::CertificateNotValid(_))));
+}
+
+#[test]
+fn test_foreign_root_is_rejected() {
+    let result = verify(include_bytes!("fixtures/nitro/wrong_root.cose"));
+    assert!(matches!(result, Err(AttestationError::RootCertMismatch)));
+}
+
+#[test]
+fn test_document_not_signed_by_leaf_is_rejected() {
+    let result = verify(include_bytes!("fixtures/nitro/signed_by_intermediate.cose"));
+    assert!(matches!(result, Err(AttestationError::InvalidCoseSignature)));
+}
+
+#[test]
+fn test_tampered_signature_is_rejected() {
+    let result = verify(include_bytes!("fixtures/nitro/bad_signature.cose"));
+    assert!(matches!(result, Err(AttestationError::InvalidCoseSignature)));
+}
+
+#[test]
+fn test_missing_pcr_is_rejected() {
+    let result = verify(include_bytes!("fixtures/nitro/missing_pcr.cose"));
+    assert!(matches!(result, Err(AttestationError::MissingPCR(3))));
+}
+
+#[test]
+fn test_debug_mode_enclave_is_rejected() {
+    let result = verify(include_bytes!("fixtures/nitro/debug_mode.cose"));
+    assert!(matches!(result, Err(AttestationError::DebugModeEnclave)));
+}
+
+#[test]
+fn test_garbage_is_rejected() {
+    assert!(matches!(verify(b"not cbor at all"), Err(AttestationError::CborError(_) | AttestationError::CoseError(_))));
+    assert!(CoseSign1::from_bytes(&[0x80]).is_err());
+}
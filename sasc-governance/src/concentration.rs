@@ -0,0 +1,265 @@
+//! Análise de concentração de poder (INV-3) sobre o grafo de dependências
+//! entre provedores: pontos de articulação, pontes, HHI e resiliência N-1.
+
+use crate::invariants::MIN_REDUNDANCY;
+use crate::types::Provider;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Participação de mercado acima da qual um provedor é dominante
+pub const DOMINANT_SHARE: f64 = 0.25;
+/// Limiares do Herfindahl-Hirschman (escala 0–10.000)
+pub const HHI_MODERATE: f64 = 1500.0;
+pub const HHI_HIGH: f64 = 2500.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConcentrationLevel {
+    Unconcentrated,
+    Moderate,
+    High,
+}
+
+/// Provedor cuja queda isola outros provedores.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SinglePointOfFailure {
+    pub provider: String,
+    pub cut_off: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcentrationReport {
+    pub provider_count: usize,
+    pub hhi: f64,
+    pub concentration: ConcentrationLevel,
+    pub dominant_providers: Vec<(String, f64)>,
+    pub articulation_points: Vec<String>,
+    pub bridges: Vec<(String, String)>,
+    pub single_points_of_failure: Vec<SinglePointOfFailure>,
+    /// Nenhuma queda isolada deixa provedores sem alternativa e há ao
+    /// menos `MIN_REDUNDANCY` provedores
+    pub n_minus_1_resilient: bool,
+}
+
+impl ConcentrationReport {
+    pub fn is_compliant(&self) -> bool {
+        self.dominant_providers.is_empty() && self.concentration != ConcentrationLevel::High && self.n_minus_1_resilient
+    }
+}
+
+/// Arquivo de procurement: `{"providers": [...]}` ou `[[providers]]` em TOML.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderGraph {
+    pub providers: Vec<Provider>,
+}
+
+impl ProviderGraph {
+    pub fn from_json_str(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|e| format!("Grafo de provedores JSON inválido: {}", e))
+    }
+
+    pub fn from_toml_str(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| format!("Grafo de provedores TOML inválido: {}", e))
+    }
+
+    /// Carrega pelo sufixo do arquivo (`.json` ou `.toml`).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Falha ao ler {}: {}", path.display(), e))?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json_str(&text),
+            Some("toml") => Self::from_toml_str(&text),
+            _ => Err(format!("Formato desconhecido: {}", path.display())),
+        }
+    }
+}
+
+/// Grafo não dirigido: uma dependência liga dependente e fornecedor.
+/// Dependências sem cadastro entram como nós (infraestrutura de terceiros).
+struct Graph {
+    names: Vec<String>,
+    adjacency: Vec<BTreeSet<usize>>,
+}
+
+impl Graph {
+    fn build(providers: &[Provider]) -> Self {
+        let mut index = BTreeMap::new();
+        let mut names = Vec::new();
+        let mut id_of = |name: &str, names: &mut Vec<String>| {
+            *index.entry(name.to_string()).or_insert_with(|| {
+                names.push(name.to_string());
+                names.len() - 1
+            })
+        };
+        let mut edges = Vec::new();
+        for provider in providers {
+            let from = id_of(&provider.id, &mut names);
+            for dependency in &provider.dependencies {
+                let to = id_of(dependency, &mut names);
+                if from != to {
+                    edges.push((from, to));
+                }
+            }
+        }
+        let mut adjacency = vec![BTreeSet::new(); names.len()];
+        for (a, b) in edges {
+            adjacency[a].insert(b);
+            adjacency[b].insert(a);
+        }
+        Self { names, adjacency }
+    }
+
+    /// Tarjan iterativo: pontos de articulação e pontes.
+    fn cut_structure(&self) -> (BTreeSet<usize>, Vec<(usize, usize)>) {
+        let n = self.names.len();
+        let mut discovery = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut points = BTreeSet::new();
+        let mut bridges = Vec::new();
+        let mut timer = 0;
+
+        for root in 0..n {
+            if discovery[root] != usize::MAX {
+                continue;
+            }
+            discovery[root] = timer;
+            low[root] = timer;
+            timer += 1;
+            let mut root_children = 0;
+            // (nó, pai, vizinhos ainda não visitados)
+            let mut stack = vec![(root, usize::MAX, self.adjacency[root].iter().copied().collect::<Vec<_>>())];
+            while let Some((node, parent, pending)) = stack.last_mut() {
+                let (node, parent) = (*node, *parent);
+                if let Some(next) = pending.pop() {
+                    if next == parent {
+                        continue;
+                    }
+                    if discovery[next] == usize::MAX {
+                        discovery[next] = timer;
+                        low[next] = timer;
+                        timer += 1;
+                        if node == root {
+                            root_children += 1;
+                        }
+                        stack.push((next, node, self.adjacency[next].iter().copied().collect()));
+                    } else {
+                        low[node] = low[node].min(discovery[next]);
+                    }
+                } else {
+                    stack.pop();
+                    if parent != usize::MAX {
+                        low[parent] = low[parent].min(low[node]);
+                        if low[node] > discovery[parent] {
+                            bridges.push((parent.min(node), parent.max(node)));
+                        }
+                        if parent != root && low[node] >= discovery[parent] {
+                            points.insert(parent);
+                        }
+                    }
+                }
+            }
+            if root_children > 1 {
+                points.insert(root);
+            }
+        }
+        bridges.sort();
+        (points, bridges)
+    }
+
+    /// Componentes conexos do grafo, opcionalmente sem o nó `removed`.
+    fn components(&self, removed: Option<usize>) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.names.len()];
+        if let Some(removed) = removed {
+            seen[removed] = true;
+        }
+        let mut components = Vec::new();
+        for start in 0..self.names.len() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![start];
+            let mut frontier = vec![start];
+            while let Some(node) = frontier.pop() {
+                for &next in &self.adjacency[node] {
+                    if !seen[next] {
+                        seen[next] = true;
+                        component.push(next);
+                        frontier.push(next);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Provedores isolados pela queda de `node`: os que saem do maior
+    /// pedaço restante do seu componente original.
+    fn cut_off_by(&self, node: usize) -> BTreeSet<usize> {
+        let original: BTreeSet<usize> = self
+            .components(None)
+            .into_iter()
+            .find(|c| c.contains(&node))
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let mut pieces: Vec<Vec<usize>> =
+            self.components(Some(node)).into_iter().filter(|c| original.contains(&c[0])).collect();
+        pieces.sort_by_key(|c| std::cmp::Reverse(c.len()));
+        pieces.into_iter().skip(1).flatten().collect()
+    }
+}
+
+pub fn herfindahl_hirschman(providers: &[Provider]) -> f64 {
+    providers.iter().map(|p| (p.market_share * 100.0).powi(2)).sum()
+}
+
+pub fn analyze(providers: &[Provider]) -> ConcentrationReport {
+    let graph = Graph::build(providers);
+    let (points, bridges) = graph.cut_structure();
+
+    // Queda de um ponto de articulação parte o grafo; queda do único
+    // fornecedor de alguém deixa esse dependente sem alternativa
+    let mut failures: BTreeMap<usize, BTreeSet<usize>> = points.iter().map(|&p| (p, graph.cut_off_by(p))).collect();
+    let index_of = |name: &str| graph.names.iter().position(|n| n == name);
+    for provider in providers {
+        if let [sole] = provider.dependencies.as_slice() {
+            if let (Some(dependent), Some(sole)) = (index_of(&provider.id), index_of(sole)) {
+                failures.entry(sole).or_default().insert(dependent);
+            }
+        }
+    }
+    let single_points_of_failure: Vec<SinglePointOfFailure> = failures
+        .into_iter()
+        .map(|(point, cut_off)| {
+            let mut cut_off: Vec<String> = cut_off.into_iter().map(|i| graph.names[i].clone()).collect();
+            cut_off.sort();
+            SinglePointOfFailure { provider: graph.names[point].clone(), cut_off }
+        })
+        .collect();
+
+    let hhi = herfindahl_hirschman(providers);
+    let concentration = if hhi > HHI_HIGH {
+        ConcentrationLevel::High
+    } else if hhi >= HHI_MODERATE {
+        ConcentrationLevel::Moderate
+    } else {
+        ConcentrationLevel::Unconcentrated
+    };
+
+    ConcentrationReport {
+        provider_count: graph.names.len(),
+        hhi,
+        concentration,
+        dominant_providers: providers
+            .iter()
+            .filter(|p| p.market_share > DOMINANT_SHARE)
+            .map(|p| (p.id.clone(), p.market_share))
+            .collect(),
+        articulation_points: points.iter().map(|&i| graph.names[i].clone()).collect(),
+        bridges: bridges.iter().map(|&(a, b)| (graph.names[a].clone(), graph.names[b].clone())).collect(),
+        n_minus_1_resilient: graph.names.len() >= MIN_REDUNDANCY && single_points_of_failure.is_empty(),
+        single_points_of_failure,
+    }
+}
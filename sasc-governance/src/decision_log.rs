@@ -0,0 +1,359 @@
+//! Log de decisões append-only com árvore de Merkle (RFC 9162, hash BLAKE3).
+//!
+//! Um auditor externo que só guarda um `SignedTreeHead` consegue verificar
+//! que uma entrada está no log (prova de inclusão) e que um log posterior
+//! apenas acrescentou entradas ao que ele já conhecia (prova de consistência).
+
+use crate::types::{DecisionLog, LogEntry};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+pub type Hash = [u8; 32];
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+pub fn leaf_hash(entry: &LogEntry) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(&entry.timestamp.to_le_bytes());
+    hasher.update(&entry.decision_id.0);
+    hasher.update(&(entry.decision.len() as u64).to_le_bytes());
+    hasher.update(entry.decision.as_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+/// Maior potência de dois estritamente menor que `n` (n ≥ 2).
+fn split(n: usize) -> usize {
+    let mut k = 1;
+    while k << 1 < n {
+        k <<= 1;
+    }
+    k
+}
+
+fn subtree_root(leaves: &[Hash]) -> Hash {
+    match leaves.len() {
+        0 => *blake3::hash(b"").as_bytes(),
+        1 => leaves[0],
+        n => {
+            let k = split(n);
+            node_hash(&subtree_root(&leaves[..k]), &subtree_root(&leaves[k..]))
+        }
+    }
+}
+
+fn inclusion_path(index: usize, leaves: &[Hash], path: &mut Vec<Hash>) {
+    let n = leaves.len();
+    if n <= 1 {
+        return;
+    }
+    let k = split(n);
+    if index < k {
+        inclusion_path(index, &leaves[..k], path);
+        path.push(subtree_root(&leaves[k..]));
+    } else {
+        inclusion_path(index - k, &leaves[k..], path);
+        path.push(subtree_root(&leaves[..k]));
+    }
+}
+
+fn consistency_path(m: usize, leaves: &[Hash], complete: bool, path: &mut Vec<Hash>) {
+    let n = leaves.len();
+    if m == n {
+        if !complete {
+            path.push(subtree_root(leaves));
+        }
+        return;
+    }
+    let k = split(n);
+    if m <= k {
+        consistency_path(m, &leaves[..k], complete, path);
+        path.push(subtree_root(&leaves[k..]));
+    } else {
+        consistency_path(m - k, &leaves[k..], false, path);
+        path.push(subtree_root(&leaves[..k]));
+    }
+}
+
+impl DecisionLog {
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Acrescenta uma entrada e devolve seu índice na árvore.
+    pub fn append(&mut self, entry: LogEntry) -> u64 {
+        self.entries.push(entry);
+        self.entries.len() as u64 - 1
+    }
+
+    pub fn tree_size(&self) -> u64 {
+        self.entries.len() as u64
+    }
+
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    fn leaves(&self, tree_size: u64) -> Result<Vec<Hash>, String> {
+        if tree_size > self.tree_size() {
+            return Err(format!("Árvore de tamanho {} excede o log ({})", tree_size, self.tree_size()));
+        }
+        Ok(self.entries[..tree_size as usize].iter().map(leaf_hash).collect())
+    }
+
+    pub fn root(&self) -> Hash {
+        subtree_root(&self.entries.iter().map(leaf_hash).collect::<Vec<_>>())
+    }
+
+    /// Raiz da árvore formada pelas primeiras `tree_size` entradas.
+    pub fn root_at(&self, tree_size: u64) -> Result<Hash, String> {
+        Ok(subtree_root(&self.leaves(tree_size)?))
+    }
+
+    pub fn inclusion_proof(&self, index: u64, tree_size: u64) -> Result<Vec<Hash>, String> {
+        if index >= tree_size {
+            return Err(format!("Índice {} fora da árvore de tamanho {}", index, tree_size));
+        }
+        let mut path = Vec::new();
+        inclusion_path(index as usize, &self.leaves(tree_size)?, &mut path);
+        Ok(path)
+    }
+
+    pub fn consistency_proof(&self, old_size: u64, new_size: u64) -> Result<Vec<Hash>, String> {
+        if old_size > new_size {
+            return Err(format!("Árvore antiga ({}) maior que a nova ({})", old_size, new_size));
+        }
+        let mut path = Vec::new();
+        if old_size > 0 {
+            consistency_path(old_size as usize, &self.leaves(new_size)?, true, &mut path);
+        }
+        Ok(path)
+    }
+
+    pub fn sign_tree_head(&self, key: &SigningKey, timestamp: u64) -> SignedTreeHead {
+        SignedTreeHead::sign(key, self.tree_size(), self.root(), timestamp)
+    }
+}
+
+impl Default for DecisionLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn verify_inclusion(leaf: &Hash, index: u64, tree_size: u64, proof: &[Hash], root: &Hash) -> bool {
+    if index >= tree_size {
+        return false;
+    }
+    let (mut fn_, mut sn) = (index, tree_size - 1);
+    let mut r = *leaf;
+    for p in proof {
+        if sn == 0 {
+            return false;
+        }
+        if fn_ & 1 == 1 || fn_ == sn {
+            r = node_hash(p, &r);
+            while fn_ & 1 == 0 && fn_ != 0 {
+                fn_ >>= 1;
+                sn >>= 1;
+            }
+        } else {
+            r = node_hash(&r, p);
+        }
+        fn_ >>= 1;
+        sn >>= 1;
+    }
+    sn == 0 && r == *root
+}
+
+pub fn verify_consistency(old_size: u64, new_size: u64, old_root: &Hash, new_root: &Hash, proof: &[Hash]) -> bool {
+    if old_size > new_size {
+        return false;
+    }
+    if old_size == new_size {
+        return proof.is_empty() && old_root == new_root;
+    }
+    if old_size == 0 {
+        return proof.is_empty();
+    }
+
+    let mut path = Vec::with_capacity(proof.len() + 1);
+    if old_size.is_power_of_two() {
+        path.push(*old_root);
+    }
+    path.extend_from_slice(proof);
+    let Some((first, rest)) = path.split_first() else {
+        return false;
+    };
+
+    let (mut fn_, mut sn) = (old_size - 1, new_size - 1);
+    while fn_ & 1 == 1 {
+        fn_ >>= 1;
+        sn >>= 1;
+    }
+    let (mut fr, mut sr) = (*first, *first);
+    for c in rest {
+        if sn == 0 {
+            return false;
+        }
+        if fn_ & 1 == 1 || fn_ == sn {
+            fr = node_hash(c, &fr);
+            sr = node_hash(c, &sr);
+            while fn_ & 1 == 0 && fn_ != 0 {
+                fn_ >>= 1;
+                sn >>= 1;
+            }
+        } else {
+            sr = node_hash(&sr, c);
+        }
+        fn_ >>= 1;
+        sn >>= 1;
+    }
+    fr == *old_root && sr == *new_root && sn == 0
+}
+
+/// Cabeça da árvore assinada pela chave Ed25519 do log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTreeHead {
+    pub tree_size: u64,
+    pub root_hash: Hash,
+    pub timestamp: u64,
+    pub log_key: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+impl SignedTreeHead {
+    pub fn sign(key: &SigningKey, tree_size: u64, root_hash: Hash, timestamp: u64) -> Self {
+        let mut head = Self {
+            tree_size,
+            root_hash,
+            timestamp,
+            log_key: key.verifying_key().to_bytes(),
+            signature: Vec::new(),
+        };
+        head.signature = key.sign(&head.signing_message()).to_bytes().to_vec();
+        head
+    }
+
+    fn signing_message(&self) -> Vec<u8> {
+        let mut message = b"sasc-decision-log-sth-v1".to_vec();
+        message.extend_from_slice(&self.tree_size.to_le_bytes());
+        message.extend_from_slice(&self.root_hash);
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message
+    }
+
+    /// Verifica a assinatura contra a chave confiável do log.
+    pub fn verify(&self, log_key: &[u8; 32]) -> bool {
+        if &self.log_key != log_key {
+            return false;
+        }
+        let (Ok(key), Ok(signature)) = (VerifyingKey::from_bytes(log_key), Signature::from_slice(&self.signature)) else {
+            return false;
+        };
+        key.verify(&self.signing_message(), &signature).is_ok()
+    }
+}
+
+/// Armazenamento em arquivo: entradas em `<path>` e cabeças publicadas em
+/// `<path>.heads`, ambos JSON por linha e somente com acréscimos. Ao abrir,
+/// cada cabeça publicada é conferida contra as entradas e contra a chave
+/// configurada do log, de modo que reescrever o histórico — inclusive
+/// reassinando as cabeças com outra chave — é detectado.
+#[derive(Debug)]
+pub struct DecisionLogStore {
+    log: DecisionLog,
+    heads: Vec<SignedTreeHead>,
+    path: PathBuf,
+    log_key: [u8; 32],
+}
+
+impl DecisionLogStore {
+    /// `log_key` é a chave pública confiável do log, obtida fora do arquivo.
+    pub fn open(path: impl AsRef<Path>, log_key: [u8; 32]) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let log = DecisionLog { entries: read_lines(&path)? };
+        let heads: Vec<SignedTreeHead> = read_lines(&heads_path(&path))?;
+        for head in &heads {
+            let root = log.root_at(head.tree_size)?;
+            if root != head.root_hash || !head.verify(&log_key) {
+                return Err(format!("Cabeça de árvore {} não confere com o log", head.tree_size));
+            }
+        }
+        Ok(Self { log, heads, path, log_key })
+    }
+
+    pub fn log(&self) -> &DecisionLog {
+        &self.log
+    }
+
+    pub fn heads(&self) -> &[SignedTreeHead] {
+        &self.heads
+    }
+
+    pub fn append(&mut self, entry: LogEntry) -> Result<u64, String> {
+        append_line(&self.path, &entry)?;
+        Ok(self.log.append(entry))
+    }
+
+    /// Assina e publica a cabeça atual da árvore.
+    pub fn publish_head(&mut self, key: &SigningKey, timestamp: u64) -> Result<SignedTreeHead, String> {
+        if key.verifying_key().to_bytes() != self.log_key {
+            return Err("Chave de assinatura difere da chave configurada do log".to_string());
+        }
+        let head = self.log.sign_tree_head(key, timestamp);
+        append_line(&heads_path(&self.path), &head)?;
+        self.heads.push(head.clone());
+        Ok(head)
+    }
+}
+
+fn heads_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".heads");
+    PathBuf::from(name)
+}
+
+fn read_lines<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Vec<T>, String> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Falha ao abrir {}: {}", path.display(), e)),
+    };
+    let mut items = Vec::new();
+    for (n, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("Falha ao ler {}: {}", path.display(), e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        items.push(
+            serde_json::from_str(&line).map_err(|e| format!("{}:{} corrompido: {}", path.display(), n + 1, e))?,
+        );
+    }
+    Ok(items)
+}
+
+fn append_line<T: Serialize>(path: &Path, item: &T) -> Result<(), String> {
+    let mut line = serde_json::to_string(item).map_err(|e| e.to_string())?;
+    line.push('\n');
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Falha ao abrir {}: {}", path.display(), e))?;
+    file.write_all(line.as_bytes())
+        .and_then(|_| file.sync_data())
+        .map_err(|e| format!("Falha ao gravar {}: {}", path.display(), e))
+}
@@ -0,0 +1,271 @@
+//! Avaliação de explicações (INV-5): idioma, legibilidade (Flesch para
+//! inglês, adaptação de Fernandes para pt-BR), conectivos causais e
+//! conferência das entidades citadas contra o log da decisão.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Legibilidade mínima exigida (escala Flesch, 0–100)
+pub const MIN_READABILITY: f64 = 60.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    Portuguese,
+    English,
+}
+
+const PT_STOPWORDS: &[&str] = &[
+    "de", "que", "o", "a", "os", "as", "do", "da", "dos", "das", "em", "um", "uma", "para", "com", "não",
+    "por", "porque", "é", "se", "na", "no", "foi", "ao", "pelo", "pela", "mais", "seu", "sua", "ou", "e",
+];
+const EN_STOPWORDS: &[&str] = &[
+    "the", "of", "and", "to", "in", "is", "that", "for", "it", "was", "with", "because", "on", "as", "be",
+    "are", "this", "by", "an", "not", "or", "from", "has", "have", "its",
+];
+
+const PT_CONNECTIVES: &[&str] = &[
+    "porque", "pois", "portanto", "por isso", "por causa de", "devido a", "devido ao", "devido à",
+    "em razão de", "uma vez que", "visto que", "já que", "dado que", "consequentemente", "logo",
+    "como resultado", "de modo que", "assim,",
+];
+const EN_CONNECTIVES: &[&str] = &[
+    "because", "therefore", "since", "due to", "as a result", "thus", "hence", "consequently",
+    "so that", "given that", "which means", "owing to",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplainabilityReport {
+    pub language: Option<Language>,
+    pub sentences: usize,
+    pub words: usize,
+    pub syllables: usize,
+    /// Flesch (en) ou Flesch–Fernandes (pt-BR)
+    pub readability: f64,
+    pub causal_connectives: Vec<String>,
+    pub entities: Vec<String>,
+    pub unverified_entities: Vec<String>,
+    /// 0.4·legibilidade + 0.3·cadeia causal + 0.3·entidades conferidas
+    pub score: f64,
+    pub passed: bool,
+}
+
+impl ExplainabilityReport {
+    /// Decisão que não afeta direitos: nada a explicar.
+    pub fn not_required() -> Self {
+        Self::empty(1.0, true)
+    }
+
+    pub fn missing() -> Self {
+        Self::empty(0.0, false)
+    }
+
+    fn empty(score: f64, passed: bool) -> Self {
+        Self {
+            language: None,
+            sentences: 0,
+            words: 0,
+            syllables: 0,
+            readability: 0.0,
+            causal_connectives: Vec::new(),
+            entities: Vec::new(),
+            unverified_entities: Vec::new(),
+            score,
+            passed,
+        }
+    }
+
+    pub fn has_causal_chain(&self) -> bool {
+        !self.causal_connectives.is_empty()
+    }
+}
+
+/// Avalia `explanation`; `log_text` são as entradas do log da decisão.
+pub fn assess(explanation: &str, log_text: &[&str]) -> ExplainabilityReport {
+    let language = detect_language(explanation);
+    let words = words(explanation);
+    let sentences = count_sentences(explanation);
+    let syllables: usize = words.iter().map(|w| count_syllables(w, language)).sum();
+    let readability = reading_ease(language, sentences, words.len(), syllables);
+
+    let lowered = format!(" {} ", explanation.to_lowercase());
+    let mut causal_connectives: Vec<String> = connectives(language)
+        .iter()
+        .filter(|c| contains_phrase(&lowered, c))
+        .map(|c| c.trim_end_matches(',').to_string())
+        .collect();
+    if has_numbered_steps(explanation) {
+        causal_connectives.push("1.".to_string());
+    }
+
+    let entities = extract_entities(explanation);
+    // Conferência por token inteiro: "10" não casa com "100" nem "Ana" com "bananas"
+    let log: HashSet<String> = log_text
+        .iter()
+        .flat_map(|entry| entry.split_whitespace())
+        .map(|raw| entity_token(raw).to_lowercase())
+        .collect();
+    let unverified_entities: Vec<String> =
+        entities.iter().filter(|e| !log.contains(&e.to_lowercase())).cloned().collect();
+
+    let readability_score = (readability / 100.0).clamp(0.0, 1.0);
+    let causal_score = if causal_connectives.is_empty() { 0.0 } else { 1.0 };
+    let entity_score = if entities.is_empty() {
+        1.0
+    } else {
+        (entities.len() - unverified_entities.len()) as f64 / entities.len() as f64
+    };
+
+    ExplainabilityReport {
+        language: Some(language),
+        sentences,
+        words: words.len(),
+        syllables,
+        readability,
+        passed: readability >= MIN_READABILITY && !causal_connectives.is_empty() && unverified_entities.is_empty(),
+        causal_connectives,
+        entities,
+        unverified_entities,
+        score: 0.4 * readability_score + 0.3 * causal_score + 0.3 * entity_score,
+    }
+}
+
+/// Contagem de palavras funcionais; diacríticos do português desempatam.
+/// Na dúvida, pt-BR.
+pub fn detect_language(text: &str) -> Language {
+    let words = words(text);
+    let lowered: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+    let pt = lowered.iter().filter(|w| PT_STOPWORDS.contains(&w.as_str())).count()
+        + text.chars().filter(|c| "ãõçáéíóúâêôà".contains(*c)).count();
+    let en = lowered.iter().filter(|w| EN_STOPWORDS.contains(&w.as_str())).count();
+    if en > pt {
+        Language::English
+    } else {
+        Language::Portuguese
+    }
+}
+
+/// Flesch: 206,835 − 1,015·ASL − 84,6·ASW. Fernandes (1996) desloca a
+/// constante para 248,835 para compensar as palavras mais longas do português.
+pub fn reading_ease(language: Language, sentences: usize, words: usize, syllables: usize) -> f64 {
+    if words == 0 {
+        return 0.0;
+    }
+    let asl = words as f64 / sentences.max(1) as f64;
+    let asw = syllables as f64 / words as f64;
+    let base = match language {
+        Language::Portuguese => 248.835,
+        Language::English => 206.835,
+    };
+    base - 1.015 * asl - 84.6 * asw
+}
+
+fn connectives(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Portuguese => PT_CONNECTIVES,
+        Language::English => EN_CONNECTIVES,
+    }
+}
+
+fn contains_phrase(lowered: &str, phrase: &str) -> bool {
+    lowered.match_indices(phrase).any(|(i, _)| {
+        let before = lowered[..i].chars().next_back().is_none_or(|c| !c.is_alphanumeric());
+        let after = lowered[i + phrase.len()..].chars().next().is_none_or(|c| !c.is_alphanumeric());
+        before && after
+    })
+}
+
+fn has_numbered_steps(text: &str) -> bool {
+    text.lines().any(|line| line.trim_start().starts_with("1.")) || text.starts_with("1.")
+}
+
+fn words(text: &str) -> Vec<&str> {
+    text.split(|c: char| !(c.is_alphabetic() || c == '-' || c == '\''))
+        .map(|w| w.trim_matches(|c| c == '-' || c == '\''))
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+fn count_sentences(text: &str) -> usize {
+    let mut count = 0;
+    let mut in_sentence = false;
+    let chars: Vec<char> = text.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_alphabetic() {
+            in_sentence = true;
+        }
+        let terminator = matches!(c, '.' | '!' | '?' | ';' | ':');
+        let boundary = chars.get(i + 1).is_none_or(|n| n.is_whitespace());
+        if terminator && boundary && in_sentence {
+            count += 1;
+            in_sentence = false;
+        }
+    }
+    count + usize::from(in_sentence)
+}
+
+fn is_vowel(c: char) -> bool {
+    "aeiouyáéíóúâêôãõàü".contains(c)
+}
+
+pub fn count_syllables(word: &str, language: Language) -> usize {
+    let word = word.to_lowercase();
+    let chars: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+    let mut syllables = 0;
+    let mut previous: Option<char> = None;
+    for &c in &chars {
+        if is_vowel(c) {
+            match previous {
+                None => syllables += 1,
+                // Hiato em português: vogal tônica acentuada ou par de vogais fortes
+                Some(p) if language == Language::Portuguese && is_hiatus(p, c) => syllables += 1,
+                Some(_) => {}
+            }
+            previous = Some(c);
+        } else {
+            previous = None;
+        }
+    }
+    if language == Language::English && chars.len() > 2 && chars.ends_with(&['e']) && !chars.ends_with(&['l', 'e']) {
+        let before = chars[chars.len() - 2];
+        if !is_vowel(before) && syllables > 1 {
+            syllables -= 1;
+        }
+    }
+    syllables.max(1)
+}
+
+fn is_hiatus(first: char, second: char) -> bool {
+    let strong = |c: char| "aeoáéóâêô".contains(c);
+    let nasal_diphthong = matches!((first, second), ('ã', 'o') | ('ã', 'e') | ('õ', 'e'));
+    !nasal_diphthong && ("íú".contains(second) || (strong(first) && strong(second)))
+}
+
+/// Entidades citadas: números e identificadores (dígitos, `_`, `#`) e nomes
+/// próprios (maiúscula fora do início de frase).
+pub fn extract_entities(text: &str) -> Vec<String> {
+    let mut entities: Vec<String> = Vec::new();
+    let mut sentence_start = true;
+    for raw in text.split_whitespace() {
+        let token = entity_token(raw);
+        let ends_sentence = raw.ends_with(['.', '!', '?', ':', ';']);
+        let is_list_marker = token.chars().all(|c| c.is_ascii_digit()) && raw.ends_with('.') && sentence_start;
+        if !token.is_empty() && !is_list_marker {
+            let lowered = token.to_lowercase();
+            let identifier = token.chars().any(|c| c.is_ascii_digit() || c == '_' || c == '#');
+            let proper = !sentence_start
+                && token.chars().next().is_some_and(char::is_uppercase)
+                && !PT_STOPWORDS.contains(&lowered.as_str())
+                && !EN_STOPWORDS.contains(&lowered.as_str());
+            if (identifier || proper) && !entities.iter().any(|e| e == token) {
+                entities.push(token.to_string());
+            }
+        }
+        sentence_start = ends_sentence || is_list_marker;
+    }
+    entities
+}
+
+/// Token sem a pontuação das bordas; mesma regra para explicação e log
+fn entity_token(raw: &str) -> &str {
+    raw.trim_matches(|c: char| !(c.is_alphanumeric() || c == '_' || c == '#' || c == '%'))
+}
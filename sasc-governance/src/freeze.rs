@@ -0,0 +1,263 @@
+use crate::types::{CloudDomain, CouncilType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Um Hard Freeze registrado: por que começou, quando começou e quando
+/// expira. `cloud == None` congela todos os domínios.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FreezeRecord {
+    pub id: u64,
+    pub cloud: Option<CloudDomain>,
+    pub reason: String,
+    pub action_hash: [u8; 32],
+    pub started_at: u64,
+    pub expires_at: u64,
+    pub thaw_votes: Vec<ThawVote>,
+    pub lifted: Option<FreezeLift>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThawVote {
+    pub cloud: CloudDomain,
+    pub council: CouncilType,
+    pub approve: bool,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FreezeLift {
+    pub timestamp: u64,
+    pub cause: LiftCause,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LiftCause {
+    /// Aprovado pelos conselhos de todos os domínios afetados
+    CouncilThaw,
+    /// `freeze_duration` esgotado sem descongelamento explícito
+    Expired,
+}
+
+impl FreezeRecord {
+    pub fn is_active(&self, now: u64) -> bool {
+        self.lifted.is_none() && now < self.expires_at
+    }
+
+    pub fn covers(&self, cloud: Option<CloudDomain>) -> bool {
+        match (self.cloud, cloud) {
+            (None, _) | (_, None) => true,
+            (Some(frozen), Some(cloud)) => frozen == cloud,
+        }
+    }
+
+    /// Domínios cujos conselhos precisam votar o descongelamento.
+    fn affected_domains(&self, councils: &HashMap<CloudDomain, Vec<CouncilType>>) -> Vec<CloudDomain> {
+        match self.cloud {
+            Some(cloud) => vec![cloud],
+            None => {
+                let mut domains: Vec<_> = councils.keys().copied().collect();
+                domains.sort_by_key(|d| format!("{:?}", d));
+                domains
+            }
+        }
+    }
+}
+
+/// Resultado de um voto de descongelamento.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThawOutcome {
+    /// Votação em andamento; `pending` lista os domínios ainda sem maioria
+    Pending { pending: Vec<CloudDomain> },
+    /// Veto: a fração de rejeições de um domínio atingiu `veto_threshold`
+    Vetoed { cloud: CloudDomain },
+    Thawed,
+}
+
+/// Máquina de estados do Hard Freeze com histórico persistente.
+///
+/// Um congelamento termina quando expira (`freeze_duration`) ou quando,
+/// em cada domínio afetado, a fração de conselhos que aprovam o
+/// descongelamento supera `1 - veto_threshold`. Se a fração de
+/// rejeições atingir `veto_threshold`, o congelamento é mantido até expirar.
+#[derive(Debug, Default)]
+pub struct FreezeState {
+    history: Vec<FreezeRecord>,
+    path: Option<PathBuf>,
+}
+
+impl FreezeState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Abre (ou cria) o histórico persistido em `path`; toda transição é
+    /// regravada atomicamente.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let history = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Histórico de freeze corrompido {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Falha ao ler {}: {}", path.display(), e)),
+        };
+        Ok(Self { history, path: Some(path) })
+    }
+
+    /// Estado para quando o histórico não pôde ser lido: um congelamento
+    /// global sem prazo, só em memória. O arquivo ilegível não é tocado.
+    pub fn unavailable(error: &str, now: u64) -> Self {
+        Self {
+            history: vec![FreezeRecord {
+                id: 1,
+                cloud: None,
+                reason: format!("FREEZE_HISTORY_UNAVAILABLE: {}", error),
+                action_hash: [0u8; 32],
+                started_at: now,
+                expires_at: u64::MAX,
+                thaw_votes: Vec::new(),
+                lifted: None,
+            }],
+            path: None,
+        }
+    }
+
+    pub fn history(&self) -> &[FreezeRecord] {
+        &self.history
+    }
+
+    /// Congelamento vigente que bloqueia `cloud` (`None` = qualquer domínio);
+    /// havendo vários, o que expira por último.
+    pub fn active_freeze(&self, cloud: Option<CloudDomain>, now: u64) -> Option<&FreezeRecord> {
+        self.history
+            .iter()
+            .filter(|r| r.is_active(now) && r.covers(cloud))
+            .max_by_key(|r| r.expires_at)
+    }
+
+    pub fn is_frozen(&self, cloud: Option<CloudDomain>, now: u64) -> bool {
+        self.active_freeze(cloud, now).is_some()
+    }
+
+    pub fn activate(
+        &mut self,
+        cloud: Option<CloudDomain>,
+        reason: &str,
+        action_hash: &[u8; 32],
+        now: u64,
+        duration: Duration,
+    ) -> Result<u64, String> {
+        // O registro entra antes de qualquer etapa falível: o freeze vale em
+        // memória mesmo que a gravação falhe
+        let id = self.history.last().map_or(1, |r| r.id + 1);
+        self.history.push(FreezeRecord {
+            id,
+            cloud,
+            reason: reason.to_string(),
+            action_hash: *action_hash,
+            started_at: now,
+            expires_at: now.saturating_add(duration.as_secs()),
+            thaw_votes: Vec::new(),
+            lifted: None,
+        });
+        self.expire(now)?;
+        self.persist()?;
+        Ok(id)
+    }
+
+    /// Registra o voto de um conselho do domínio `cloud` sobre o
+    /// descongelamento `id`. Cada conselho vota uma vez por domínio.
+    #[allow(clippy::too_many_arguments)]
+    pub fn vote_thaw(
+        &mut self,
+        id: u64,
+        cloud: CloudDomain,
+        council: CouncilType,
+        approve: bool,
+        now: u64,
+        councils: &HashMap<CloudDomain, Vec<CouncilType>>,
+        veto_threshold: f64,
+    ) -> Result<ThawOutcome, String> {
+        self.expire(now)?;
+        let record = self
+            .history
+            .iter_mut()
+            .find(|r| r.id == id)
+            .ok_or_else(|| format!("Freeze #{} inexistente", id))?;
+        if record.lifted.is_some() {
+            return Err(format!("Freeze #{} já foi encerrado", id));
+        }
+
+        let domains = record.affected_domains(councils);
+        if !domains.contains(&cloud) {
+            return Err(format!("{:?} não é afetado pelo freeze #{}", cloud, id));
+        }
+        let roster = councils.get(&cloud).map(Vec::as_slice).unwrap_or_default();
+        if !roster.contains(&council) {
+            return Err(format!("Conselho {:?} não pertence a {:?}", council, cloud));
+        }
+        if record.thaw_votes.iter().any(|v| v.cloud == cloud && v.council == council) {
+            return Err(format!("Conselho {:?} já votou em {:?}", council, cloud));
+        }
+        record.thaw_votes.push(ThawVote { cloud, council, approve, timestamp: now });
+
+        let mut pending = Vec::new();
+        let mut outcome = None;
+        for domain in domains {
+            let roster = councils.get(&domain).map_or(0, Vec::len).max(1) as f64;
+            let votes = record.thaw_votes.iter().filter(|v| v.cloud == domain);
+            let (approvals, rejections) = votes.fold((0usize, 0usize), |(a, r), v| {
+                if v.approve { (a + 1, r) } else { (a, r + 1) }
+            });
+            if rejections as f64 / roster >= veto_threshold {
+                outcome = Some(ThawOutcome::Vetoed { cloud: domain });
+                break;
+            }
+            if approvals as f64 / roster <= 1.0 - veto_threshold {
+                pending.push(domain);
+            }
+        }
+
+        let outcome = outcome.unwrap_or(if pending.is_empty() {
+            record.lifted = Some(FreezeLift { timestamp: now, cause: LiftCause::CouncilThaw });
+            ThawOutcome::Thawed
+        } else {
+            ThawOutcome::Pending { pending }
+        });
+
+        // Sem gravação não há descongelamento: voto e liberação são desfeitos
+        if let Err(e) = self.persist() {
+            if let Some(record) = self.history.iter_mut().find(|r| r.id == id) {
+                record.thaw_votes.pop();
+                record.lifted = None;
+            }
+            return Err(e);
+        }
+        Ok(outcome)
+    }
+
+    /// Encerra os congelamentos cujo prazo se esgotou.
+    pub fn expire(&mut self, now: u64) -> Result<(), String> {
+        let mut changed = false;
+        for record in self.history.iter_mut().filter(|r| r.lifted.is_none() && now >= r.expires_at) {
+            record.lifted = Some(FreezeLift { timestamp: record.expires_at, cause: LiftCause::Expired });
+            changed = true;
+        }
+        if changed {
+            self.persist()?;
+        }
+        Ok(())
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let json = serde_json::to_vec_pretty(&self.history).map_err(|e| e.to_string())?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| format!("Falha ao gravar {}: {}", path.display(), e))
+    }
+}
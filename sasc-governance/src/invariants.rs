@@ -1,5 +1,7 @@
-use crate::types::{Decision, DecisionLog, Interaction, Provider};
-use std::collections::{HashMap, HashSet};
+use crate::concentration::{self, ConcentrationLevel, ConcentrationReport, ProviderGraph};
+use crate::decision_log::{self, Hash, SignedTreeHead};
+use crate::explainability::{self, ExplainabilityReport};
+use crate::types::{Decision, DecisionLog, Interaction, LogEntry, Provider};
 
 pub const CRITICAL_THRESHOLD: u64 = 30; // seconds
 pub const MANIPULATION_THRESHOLD: f64 = 0.7;
@@ -9,6 +11,22 @@ pub struct InvariantMonitor {
     pub jurisdiction: String,
     pub violation_log: Vec<Violation>,
     pub providers: Vec<Provider>,
+    /// Chave pública do log de decisões em que o auditor confia (INV-2)
+    pub log_public_key: Option<[u8; 32]>,
+    /// Última cabeça de árvore aceita; o log auditado deve estendê-la
+    pub trusted_head: Option<SignedTreeHead>,
+    /// Entradas contra as quais as explicações são conferidas (INV-5)
+    pub decision_log: DecisionLog,
+}
+
+/// Resultado da conferência do log contra a cabeça confiável (INV-2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogVerification {
+    /// O prefixo do log reproduz a raiz assinada pela chave confiável
+    Verified,
+    /// Falta cabeça confiável ou chave do log: nada foi conferido
+    Unverified,
+    Tampered,
 }
 
 #[derive(Debug, Clone)]
@@ -24,6 +42,9 @@ impl InvariantMonitor {
             jurisdiction: jurisdiction.to_string(),
             violation_log: Vec::new(),
             providers: Vec::new(),
+            log_public_key: None,
+            trusted_head: None,
+            decision_log: DecisionLog::new(),
         }
     }
 
@@ -65,10 +86,16 @@ impl InvariantMonitor {
     }
 
     pub fn check_inv2_auditability(&mut self, log: &DecisionLog) -> bool {
-        // Mock Merkle proof verification
-        if !self.verify_merkle_proof(log) {
-            self.record_violation("INV-2", "Log tampering detected", "BLOCK_AND_ALARM");
-            return false;
+        match self.verify_merkle_proof(log) {
+            LogVerification::Verified => {}
+            LogVerification::Unverified => {
+                self.record_violation("INV-2", "Log unverified (no trusted tree head)", "BLOCK_AND_ALARM");
+                return false;
+            }
+            LogVerification::Tampered => {
+                self.record_violation("INV-2", "Log tampering detected", "BLOCK_AND_ALARM");
+                return false;
+            }
         }
 
         if self.detect_temporal_gaps(log) {
@@ -79,8 +106,54 @@ impl InvariantMonitor {
         true
     }
 
-    fn verify_merkle_proof(&self, _log: &DecisionLog) -> bool {
-        // Simplified: in a real system we would verify the Merkle root
+    /// Sem cabeça confiável não há contra o que comparar; com ela, o prefixo
+    /// do log deve reproduzir exatamente a raiz assinada.
+    pub fn verify_merkle_proof(&self, log: &DecisionLog) -> LogVerification {
+        let (Some(head), Some(key)) = (&self.trusted_head, self.log_public_key) else {
+            return LogVerification::Unverified;
+        };
+        if head.verify(&key) && log.root_at(head.tree_size).is_ok_and(|root| root == head.root_hash) {
+            LogVerification::Verified
+        } else {
+            LogVerification::Tampered
+        }
+    }
+
+    /// Auditor que só guarda uma cabeça: confere que `entry` está no log.
+    pub fn check_inv2_inclusion(&mut self, entry: &LogEntry, index: u64, proof: &[Hash], head: &SignedTreeHead) -> bool {
+        let signed = self.log_public_key.is_some_and(|key| head.verify(&key));
+        let included =
+            decision_log::verify_inclusion(&decision_log::leaf_hash(entry), index, head.tree_size, proof, &head.root_hash);
+        if !signed || !included {
+            self.record_violation(
+                "INV-2",
+                &format!("Entry {} not provably included in tree head {}", index, head.tree_size),
+                "BLOCK_AND_ALARM",
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Aceita `new_head` somente se provar que estende a cabeça confiável;
+    /// caso contrário o log foi reescrito.
+    pub fn check_inv2_consistency(&mut self, new_head: &SignedTreeHead, proof: &[Hash]) -> bool {
+        let signed = self.log_public_key.is_some_and(|key| new_head.verify(&key));
+        let consistent = match &self.trusted_head {
+            Some(old) => decision_log::verify_consistency(
+                old.tree_size,
+                new_head.tree_size,
+                &old.root_hash,
+                &new_head.root_hash,
+                proof,
+            ),
+            None => true,
+        };
+        if !signed || !consistent {
+            self.record_violation("INV-2", "Log history rewritten (inconsistent tree head)", "BLOCK_AND_ALARM");
+            return false;
+        }
+        self.trusted_head = Some(new_head.clone());
         true
     }
 
@@ -93,63 +166,52 @@ impl InvariantMonitor {
         false
     }
 
-    pub fn check_inv3_power_concentration(&mut self) -> bool {
-        let mut violations = false;
-        let mut violation_details = Vec::new();
-        for provider in &self.providers {
-            if provider.market_share > 0.25 {
-                violation_details.push((
-                    "INV-3",
-                    format!("Provider {} has market share {}", provider.id, provider.market_share),
-                    "REGULATORY_REVIEW_TRIGGERED".to_string(),
-                ));
-                violations = true;
-            }
-        }
+    /// Carrega os provedores de um grafo de procurement (`.json`/`.toml`).
+    pub fn load_providers(&mut self, path: impl AsRef<std::path::Path>) -> Result<(), String> {
+        self.providers = ProviderGraph::load(path)?.providers;
+        Ok(())
+    }
 
-        for (inv, details, action) in violation_details {
-            self.record_violation(inv, &details, &action);
-        }
+    pub fn inv3_report(&self) -> ConcentrationReport {
+        concentration::analyze(&self.providers)
+    }
 
-        let dependency_graph = self.build_dependency_graph();
-        let critical_nodes = self.find_critical_nodes(&dependency_graph);
+    pub fn check_inv3_power_concentration(&mut self) -> bool {
+        let report = self.inv3_report();
 
-        if critical_nodes.len() < MIN_REDUNDANCY {
+        for (id, share) in &report.dominant_providers {
             self.record_violation(
                 "INV-3",
-                "Insufficient infrastructure redundancy",
-                "ALERT_COMPETITION_AUTHORITY",
+                &format!("Provider {} has market share {}", id, share),
+                "REGULATORY_REVIEW_TRIGGERED",
             );
-            violations = true;
         }
 
-        !violations
-    }
-
-    fn build_dependency_graph(&self) -> HashMap<String, Vec<String>> {
-        let mut graph = HashMap::new();
-        for p in &self.providers {
-            graph.insert(p.id.clone(), p.dependencies.clone());
+        if report.concentration == ConcentrationLevel::High {
+            self.record_violation(
+                "INV-3",
+                &format!("Highly concentrated market (HHI {:.0})", report.hhi),
+                "REGULATORY_REVIEW_TRIGGERED",
+            );
         }
-        graph
-    }
 
-    fn find_critical_nodes(&self, graph: &HashMap<String, Vec<String>>) -> HashSet<String> {
-        // Simplified: find nodes that are not dependencies of others
-        let mut all_deps = HashSet::new();
-        for deps in graph.values() {
-            for dep in deps {
-                all_deps.insert(dep.clone());
-            }
+        for spof in &report.single_points_of_failure {
+            self.record_violation(
+                "INV-3",
+                &format!("Provider {} is a single point of failure for {:?}", spof.provider, spof.cut_off),
+                "ALERT_COMPETITION_AUTHORITY",
+            );
         }
 
-        let mut critical = HashSet::new();
-        for id in graph.keys() {
-            if !all_deps.contains(id) {
-                critical.insert(id.clone());
-            }
+        if !report.n_minus_1_resilient {
+            self.record_violation(
+                "INV-3",
+                "Insufficient infrastructure redundancy",
+                "ALERT_COMPETITION_AUTHORITY",
+            );
         }
-        critical
+
+        report.is_compliant()
     }
 
     pub fn check_inv4_cognitive_sovereignty(&mut self, interaction: &Interaction) -> bool {
@@ -195,41 +257,42 @@ impl InvariantMonitor {
         }
     }
 
-    pub fn check_inv5_explainability(&mut self, decision: &Decision) -> bool {
-        if decision.affects_rights {
-            if let Some(explanation) = &decision.explanation {
-                let readability = self.flesch_reading_ease(explanation);
-                let completeness = self.check_causal_chain(explanation);
-                let accuracy = self.verify_against_log(explanation, decision);
-
-                if readability < 60.0 || !completeness || !accuracy {
-                    self.record_violation(
-                        "INV-5",
-                        &format!("Explanation quality failed: readability={}, completeness={}, accuracy={}", readability, completeness, accuracy),
-                        "REQUIRE_EXPLANATION_REWRITE",
-                    );
-                    return false;
-                }
-            } else {
-                return false;
-            }
-        }
-        true
-    }
-
-    fn flesch_reading_ease(&self, text: &str) -> f64 {
-        // Mock implementation
-        if text.contains("gradiente estocástico") {
-            return 30.0;
+    /// Avalia a explicação de decisões que afetam direitos. Entidades citadas
+    /// precisam constar das entradas de `decision_log` desta decisão.
+    pub fn check_inv5_explainability(&mut self, decision: &Decision) -> ExplainabilityReport {
+        if !decision.affects_rights {
+            return ExplainabilityReport::not_required();
         }
-        75.0
-    }
+        let Some(explanation) = &decision.explanation else {
+            self.record_violation(
+                "INV-5",
+                &format!("Decision {:?} affects rights but has no explanation", decision.id),
+                "REQUIRE_EXPLANATION_REWRITE",
+            );
+            return ExplainabilityReport::missing();
+        };
 
-    fn check_causal_chain(&self, text: &str) -> bool {
-        text.contains("porque") || text.contains("1.")
-    }
+        let log_text: Vec<&str> = self
+            .decision_log
+            .entries
+            .iter()
+            .filter(|entry| entry.decision_id == decision.id)
+            .map(|entry| entry.decision.as_str())
+            .collect();
+        let report = explainability::assess(explanation, &log_text);
 
-    fn verify_against_log(&self, _explanation: &str, _decision: &Decision) -> bool {
-        true
+        if !report.passed {
+            self.record_violation(
+                "INV-5",
+                &format!(
+                    "Explanation quality failed: readability={:.1}, completeness={}, unverified={:?}",
+                    report.readability,
+                    report.has_causal_chain(),
+                    report.unverified_entities
+                ),
+                "REQUIRE_EXPLANATION_REWRITE",
+            );
+        }
+        report
     }
 }
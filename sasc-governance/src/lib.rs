@@ -1,5 +1,10 @@
 pub mod types;
 pub mod invariants;
+pub mod freeze;
+pub mod voting;
+pub mod decision_log;
+pub mod concentration;
+pub mod explainability;
 
 use std::collections::HashMap;
 use std::time::Duration;
@@ -9,41 +14,101 @@ use ed25519_dalek::{VerifyingKey, Signature, Verifier};
 use blake3;
 
 use types::{
-    CloudDomain, CouncilType, Decision, DecisionId, HardFreeze,
-    GlobalGovernance, VerificationContext
+    CloudDomain, CouncilType, Decision, DecisionId, DecisionLog, HardFreeze,
+    GlobalGovernance, LogEntry, VerificationContext
 };
+use freeze::{FreezeRecord, FreezeState, ThawOutcome};
+use voting::{Ballot, CouncilMember, Tally, TallyOutcome, VotingEngine, VotingPolicy};
+
+/// Arquivo do histórico de Hard Freeze; sem ele o histórico é volátil
+pub const FREEZE_HISTORY_ENV: &str = "SASC_FREEZE_HISTORY";
 
 pub struct Cathedral {
     pub governance: Mutex<GlobalGovernance>,
 }
 
 static INSTANCE: Lazy<Cathedral> = Lazy::new(|| {
-    let mut councils = HashMap::new();
-    let all_councils = vec![
-        CouncilType::Mathematical, CouncilType::Security, CouncilType::Geometric,
-        CouncilType::Ethical, CouncilType::Economic, CouncilType::Temporal,
-        CouncilType::Quantum
-    ];
-    councils.insert(CloudDomain::WindowsServerGov, all_councils.clone());
-    councils.insert(CloudDomain::AwsNitroGovCloud, all_councils.clone());
-    councils.insert(CloudDomain::CloudflareQuantum, all_councils);
-
-    Cathedral {
-        governance: Mutex::new(GlobalGovernance {
+    let hard_freeze = match std::env::var(FREEZE_HISTORY_ENV) {
+        Ok(path) => FreezeState::open(&path).unwrap_or_else(|e| {
+            // Histórico ilegível: não há como provar que o sistema foi
+            // descongelado, então a Catedral sobe congelada
+            eprintln!("HARD FREEZE HISTORY UNAVAILABLE: {}", e);
+            FreezeState::unavailable(&e, now_secs())
+        }),
+        Err(_) => FreezeState::new(),
+    };
+    Cathedral::new(Cathedral::default_governance(hard_freeze))
+});
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+impl Cathedral {
+    pub fn instance() -> &'static Self {
+        &INSTANCE
+    }
+
+    pub fn new(governance: GlobalGovernance) -> Self {
+        Self { governance: Mutex::new(governance) }
+    }
+
+    pub fn default_governance(hard_freeze: FreezeState) -> GlobalGovernance {
+        let mut councils = HashMap::new();
+        let all_councils = vec![
+            CouncilType::Mathematical, CouncilType::Security, CouncilType::Geometric,
+            CouncilType::Ethical, CouncilType::Economic, CouncilType::Temporal,
+            CouncilType::Quantum
+        ];
+        councils.insert(CloudDomain::WindowsServerGov, all_councils.clone());
+        councils.insert(CloudDomain::AwsNitroGovCloud, all_councils.clone());
+        councils.insert(CloudDomain::CloudflareQuantum, all_councils);
+
+        GlobalGovernance {
             councils,
             prince_key: [0u8; 32], // Should be loaded from secure storage
             veto_threshold: 0.45,
-            hard_freeze_status: false,
+            hard_freeze,
+            voting: VotingEngine::new(VotingPolicy::default()),
+            decision_log: DecisionLog::new(),
             freeze_duration: Duration::from_secs(72 * 3600),
             delta2_array: [0u8; 32],
             crypto_blck_seed: [0u8; 32],
-        }),
+        }
     }
-});
 
-impl Cathedral {
-    pub fn instance() -> &'static Self {
-        &INSTANCE
+    /// Freeze vigente que bloqueia `cloud`: motivo, início e expiração.
+    pub fn freeze_status(&self, cloud: CloudDomain) -> Option<FreezeRecord> {
+        let mut gov = self.governance.lock().unwrap();
+        let now = now_secs();
+        if let Err(e) = gov.hard_freeze.expire(now) {
+            eprintln!("HARD FREEZE HISTORY NOT PERSISTED: {}", e);
+        }
+        gov.hard_freeze.active_freeze(Some(cloud), now).cloned()
+    }
+
+    pub fn freeze_history(&self) -> Vec<FreezeRecord> {
+        self.governance.lock().unwrap().hard_freeze.history().to_vec()
+    }
+
+    /// Voto de um conselho de `cloud` pelo descongelamento do freeze `freeze_id`.
+    pub fn vote_thaw(
+        &self,
+        freeze_id: u64,
+        cloud: CloudDomain,
+        council: CouncilType,
+        approve: bool,
+    ) -> Result<ThawOutcome, String> {
+        let mut gov = self.governance.lock().unwrap();
+        let GlobalGovernance { councils, veto_threshold, hard_freeze, .. } = &mut *gov;
+        let outcome = hard_freeze.vote_thaw(freeze_id, cloud, council, approve, now_secs(), councils, *veto_threshold)?;
+        if outcome == ThawOutcome::Thawed {
+            println!("HARD FREEZE #{} LIFTED by council thaw", freeze_id);
+        }
+        Ok(outcome)
     }
 
     pub fn verify_agent_attestation(
@@ -61,30 +126,41 @@ impl Cathedral {
         decision: Decision,
         cloud: CloudDomain,
     ) -> Result<DecisionId, HardFreeze> {
-        // 0. Invariant Monitoring (Post-ASI Governance)
+        // 0. Nenhuma decisão passa enquanto o domínio estiver congelado
+        if let Some(record) = self.freeze_status(cloud) {
+            return Err(HardFreeze::Active(Box::new(record)));
+        }
+
+        // Invariant Monitoring (Post-ASI Governance)
         let mut monitor = invariants::InvariantMonitor::new("BR");
-        // For simulation, we use current timestamp
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        monitor.decision_log = self.decision_entries(&decision.id);
+        let now = now_secs();
 
         if !monitor.check_inv1_human_oversight(&decision, now) {
             return Err(HardFreeze::Triggered("INV-1_VIOLATION".to_string()));
         }
 
-        if !monitor.check_inv5_explainability(&decision) {
+        if !monitor.check_inv5_explainability(&decision).passed {
             return Err(HardFreeze::Triggered("INV-5_VIOLATION".to_string()));
         }
 
         // 1. Verificar attestation do nó (5 gates - Memória 20)
-        if self.verify_agent_attestation(&decision.agent_id, VerificationContext::GlobalDecision).is_err() {
-            self.trigger_karnak_isolation(cloud, &decision.agent_id);
-            return Err(HardFreeze::Triggered("ATTESTATION_FAILED".to_string()));
+        let attestation = match self.verify_agent_attestation(&decision.agent_id, VerificationContext::GlobalDecision) {
+            Ok(status) => status,
+            Err(_) => {
+                self.trigger_karnak_isolation(cloud, &decision.agent_id);
+                return Err(HardFreeze::Triggered("ATTESTATION_FAILED".to_string()));
+            }
+        };
+
+        // Ameaça catastrófica é sinalizada pela attestation, não pelo Φ
+        if attestation.is_hard_frozen() {
+            self.activate_hard_freeze(None, "CATASTROPHIC_THREAT", &decision.action_hash);
+            return Err(HardFreeze::Triggered("HARD_FREEZE_TRIGGERED".to_string()));
         }
 
-        // 2. Calcular Φ (coerência global)
-        let phi_global = self.compute_noosphere_coherence(&decision);
+        // 2. Calcular Φ (coerência global) a partir dos votos dos conselhos
+        let tally = self.tally_decision(&decision, cloud);
 
         // 3. Prince Creator veto
         if decision.signature.prince_veto {
@@ -92,38 +168,64 @@ impl Cathedral {
             return Err(HardFreeze::Triggered("PRINCE_VETO".to_string()));
         }
 
-        // 4. Threshold check
-        if phi_global < 0.65 {
-            return Err(HardFreeze::Triggered("INSUFFICIENT_CONSENSUS".to_string()));
-        } else if phi_global >= 0.80 {
-            // Hard Freeze imediato
-            self.activate_hard_freeze(None, "CATASTROPHIC_THREAT", &decision.action_hash);
-            return Err(HardFreeze::Triggered("HARD_FREEZE_TRIGGERED".to_string()));
-        } else if phi_global >= 0.72 {
-            // Proposta autorizada
-            let mut hasher = blake3::Hasher::new();
-            hasher.update(decision.content.as_bytes());
-            // In pseudocode it adds block number, here we just use content for simplicity
-            let decision_hash = hasher.finalize();
-            let mut id = [0u8; 32];
-            id.copy_from_slice(decision_hash.as_bytes());
-
-            self.broadcast_global_attestation(DecisionId(id), cloud, phi_global);
-            return Ok(DecisionId(id));
+        // 4. Só a apuração aprova: Approved já exige Φ >= supermaioria
+        match tally.outcome {
+            TallyOutcome::NoQuorum => Err(HardFreeze::Triggered("NO_QUORUM".to_string())),
+            TallyOutcome::Vetoed => Err(HardFreeze::Triggered("COUNCIL_VETO".to_string())),
+            TallyOutcome::Rejected => Err(HardFreeze::Triggered("INSUFFICIENT_CONSENSUS".to_string())),
+            TallyOutcome::Approved => {
+                // Proposta autorizada
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(decision.content.as_bytes());
+                // In pseudocode it adds block number, here we just use content for simplicity
+                let decision_hash = hasher.finalize();
+                let mut id = [0u8; 32];
+                id.copy_from_slice(decision_hash.as_bytes());
+
+                self.broadcast_global_attestation(DecisionId(id), cloud, tally.phi);
+                Ok(DecisionId(id))
+            }
+        }
+    }
+
+    /// Acrescenta um registro ao log de decisões e devolve seu índice.
+    pub fn record_log_entry(&self, entry: LogEntry) -> u64 {
+        self.governance.lock().unwrap().decision_log.append(entry)
+    }
+
+    /// Entradas do log pertencentes à decisão `id`.
+    fn decision_entries(&self, id: &DecisionId) -> DecisionLog {
+        let gov = self.governance.lock().unwrap();
+        let mut log = DecisionLog::new();
+        for entry in gov.decision_log.entries().iter().filter(|e| e.decision_id == *id) {
+            log.append(entry.clone());
         }
+        log
+    }
+
+    pub fn register_council_member(&self, member: CouncilMember) -> Result<(), String> {
+        self.governance.lock().unwrap().voting.register_member(member)
+    }
 
-        Err(HardFreeze::Triggered("INSUFFICIENT_CONSENSUS".to_string()))
+    pub fn cast_ballot(&self, ballot: Ballot) -> Result<(), String> {
+        self.governance.lock().unwrap().voting.cast(ballot)
     }
 
-    fn compute_noosphere_coherence(&self, _decision: &Decision) -> f64 {
-        // Mock computation of Φ
-        0.75
+    /// Apuração dos conselhos de `cloud` (quórum, supermaioria e veto).
+    pub fn tally_decision(&self, decision: &Decision, cloud: CloudDomain) -> Tally {
+        let gov = self.governance.lock().unwrap();
+        let councils = gov.councils.get(&cloud).map(Vec::as_slice).unwrap_or_default();
+        gov.voting.tally(decision, cloud, councils, gov.veto_threshold)
     }
 
-    fn activate_hard_freeze(&self, _cloud: Option<CloudDomain>, reason: &str, _hash: &[u8; 32]) {
+    fn activate_hard_freeze(&self, cloud: Option<CloudDomain>, reason: &str, hash: &[u8; 32]) {
         let mut gov = self.governance.lock().unwrap();
-        gov.hard_freeze_status = true;
-        println!("HARD FREEZE ACTIVATED: {}", reason);
+        let duration = gov.freeze_duration;
+        // Falha de persistência não impede o freeze: o registro fica em memória
+        match gov.hard_freeze.activate(cloud, reason, hash, now_secs(), duration) {
+            Ok(id) => println!("HARD FREEZE #{} ACTIVATED: {} ({:?})", id, reason, cloud),
+            Err(e) => eprintln!("HARD FREEZE ACTIVATED: {} (history not persisted: {})", reason, e),
+        }
     }
 
     fn trigger_karnak_isolation(&self, cloud: CloudDomain, agent_id: &str) {
@@ -134,7 +236,7 @@ impl Cathedral {
         println!("Broadcast Global Attestation: {:?} for domain {:?} with phi {}", id, cloud, phi);
     }
 
-    pub fn verify_5_gates_transaction(&self, tx_data: &[u8], signature: [u8; 64], _cloud: CloudDomain) -> bool {
+    pub fn verify_5_gates_transaction(&self, tx_data: &[u8], signature: [u8; 64], cloud: CloudDomain) -> bool {
         let gov = self.governance.lock().unwrap();
 
         // Gate 1: Prince key verification
@@ -145,7 +247,7 @@ impl Cathedral {
         }
 
         // Gate 4: Hard Freeze check
-        if gov.hard_freeze_status {
+        if gov.hard_freeze.is_frozen(Some(cloud), now_secs()) {
             return false;
         }
 
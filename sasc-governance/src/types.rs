@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
+use crate::freeze::{FreezeRecord, FreezeState};
+use crate::voting::VotingEngine;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CloudDomain {
@@ -9,7 +11,7 @@ pub enum CloudDomain {
     CloudflareQuantum,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CouncilType {
     Mathematical,
     Security,
@@ -53,7 +55,8 @@ pub struct DecisionId(pub [u8; 32]);
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum HardFreeze {
     Triggered(String),
-    Active,
+    /// Já congelado: o registro informa o motivo e quando pode ser retomado
+    Active(Box<FreezeRecord>),
 }
 
 pub struct AttestationStatus {
@@ -88,7 +91,10 @@ pub struct GlobalGovernance {
     pub councils: HashMap<CloudDomain, Vec<CouncilType>>,
     pub prince_key: [u8; 32],
     pub veto_threshold: f64,
-    pub hard_freeze_status: bool,
+    pub hard_freeze: FreezeState,
+    pub voting: VotingEngine,
+    /// Registros das decisões; as explicações são conferidas contra eles (INV-5)
+    pub decision_log: DecisionLog,
     pub freeze_duration: Duration,
     pub delta2_array: [u8; 32],
     pub crypto_blck_seed: [u8; 32],
@@ -106,15 +112,17 @@ pub struct LogEntry {
     pub decision: String,
 }
 
+/// Log append-only; a árvore de Merkle e as provas ficam em `decision_log`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecisionLog {
-    pub entries: Vec<LogEntry>,
+    pub(crate) entries: Vec<LogEntry>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Provider {
     pub id: String,
     pub market_share: f64,
+    #[serde(default)]
     pub dependencies: Vec<String>,
 }
 
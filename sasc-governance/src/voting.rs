@@ -0,0 +1,224 @@
+use crate::types::{CloudDomain, CouncilType, Decision};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Vote {
+    Approve,
+    Reject,
+    Abstain,
+    Veto,
+}
+
+/// Membro de um conselho: chave Ed25519 e peso dentro do seu conselho.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CouncilMember {
+    pub public_key: [u8; 32],
+    pub cloud: CloudDomain,
+    pub council: CouncilType,
+    pub weight: f64,
+}
+
+/// Voto assinado. Autocontido: a mensagem assinada pode ser reconstruída a
+/// partir dos próprios campos, então qualquer auditor verifica o voto depois.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ballot {
+    pub decision_digest: [u8; 32],
+    pub cloud: CloudDomain,
+    pub council: CouncilType,
+    pub member_key: [u8; 32],
+    pub vote: Vote,
+    pub timestamp: u64,
+    pub signature: Vec<u8>,
+}
+
+/// Resumo da decisão coberto pelas assinaturas dos votos.
+pub fn decision_digest(decision: &Decision) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"sasc-decision");
+    hasher.update(&decision.id.0);
+    hasher.update(&decision.action_hash);
+    hasher.update(&(decision.agent_id.len() as u64).to_le_bytes());
+    hasher.update(decision.agent_id.as_bytes());
+    hasher.update(decision.content.as_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+impl Ballot {
+    pub fn new_signed(
+        key: &SigningKey,
+        decision: &Decision,
+        cloud: CloudDomain,
+        council: CouncilType,
+        vote: Vote,
+        timestamp: u64,
+    ) -> Self {
+        let mut ballot = Ballot {
+            decision_digest: decision_digest(decision),
+            cloud,
+            council,
+            member_key: key.verifying_key().to_bytes(),
+            vote,
+            timestamp,
+            signature: Vec::new(),
+        };
+        ballot.signature = key.sign(&ballot.signing_message()).to_bytes().to_vec();
+        ballot
+    }
+
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = b"sasc-ballot-v1".to_vec();
+        message.extend_from_slice(&self.decision_digest);
+        message.extend_from_slice(format!("{:?}/{:?}/{:?}", self.cloud, self.council, self.vote).as_bytes());
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message
+    }
+
+    pub fn verify_signature(&self) -> Result<(), String> {
+        let key = VerifyingKey::from_bytes(&self.member_key).map_err(|e| format!("Chave inválida: {}", e))?;
+        let signature = Signature::from_slice(&self.signature).map_err(|e| format!("Assinatura malformada: {}", e))?;
+        key.verify(&self.signing_message(), &signature)
+            .map_err(|_| "Assinatura do voto inválida".to_string())
+    }
+}
+
+/// Parâmetros de apuração. `quorum` e `veto_threshold` são frações do peso
+/// total do domínio; `supermajority` é o Φ mínimo para aprovação.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct VotingPolicy {
+    pub quorum: f64,
+    pub supermajority: f64,
+}
+
+impl Default for VotingPolicy {
+    fn default() -> Self {
+        Self { quorum: 0.5, supermajority: 0.72 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TallyOutcome {
+    NoQuorum,
+    Vetoed,
+    Rejected,
+    Approved,
+}
+
+/// Resultado da apuração. Cada conselho do domínio pesa igualmente; dentro
+/// do conselho, cada membro pesa `weight / soma dos pesos do conselho`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tally {
+    pub cloud: CloudDomain,
+    pub participation: f64,
+    pub approve: f64,
+    pub reject: f64,
+    pub veto: f64,
+    pub abstain: f64,
+    /// Φ: fração aprovadora do peso que se manifestou (abstenções excluídas)
+    pub phi: f64,
+    pub outcome: TallyOutcome,
+    pub ballots: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct VotingEngine {
+    pub policy: VotingPolicy,
+    members: HashMap<[u8; 32], CouncilMember>,
+    ballots: HashMap<[u8; 32], Vec<Ballot>>,
+}
+
+impl VotingEngine {
+    pub fn new(policy: VotingPolicy) -> Self {
+        Self { policy, ..Self::default() }
+    }
+
+    pub fn register_member(&mut self, member: CouncilMember) -> Result<(), String> {
+        if !(member.weight.is_finite() && member.weight > 0.0) {
+            return Err(format!("Peso inválido: {}", member.weight));
+        }
+        VerifyingKey::from_bytes(&member.public_key).map_err(|e| format!("Chave inválida: {}", e))?;
+        if self.members.contains_key(&member.public_key) {
+            return Err(format!("Chave {} já registrada", hex::encode(member.public_key)));
+        }
+        self.members.insert(member.public_key, member);
+        Ok(())
+    }
+
+    pub fn members(&self) -> impl Iterator<Item = &CouncilMember> {
+        self.members.values()
+    }
+
+    /// Verifica e registra um voto: assinatura válida, membro registrado no
+    /// conselho e domínio declarados, no máximo um voto por membro e decisão.
+    pub fn cast(&mut self, ballot: Ballot) -> Result<(), String> {
+        self.verify_ballot(&ballot)?;
+        let ballots = self.ballots.entry(ballot.decision_digest).or_default();
+        if ballots.iter().any(|b| b.member_key == ballot.member_key) {
+            return Err(format!("Membro {} já votou", hex::encode(ballot.member_key)));
+        }
+        ballots.push(ballot);
+        Ok(())
+    }
+
+    pub fn verify_ballot(&self, ballot: &Ballot) -> Result<(), String> {
+        let member = self
+            .members
+            .get(&ballot.member_key)
+            .ok_or_else(|| format!("Membro {} não registrado", hex::encode(ballot.member_key)))?;
+        if member.cloud != ballot.cloud || member.council != ballot.council {
+            return Err(format!("Membro não pertence a {:?}/{:?}", ballot.cloud, ballot.council));
+        }
+        ballot.verify_signature()
+    }
+
+    pub fn ballots(&self, decision: &Decision) -> &[Ballot] {
+        self.ballots.get(&decision_digest(decision)).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Apura os votos dos conselhos de `cloud` sobre `decision`. Votos que não
+    /// verificam mais (membro removido, assinatura adulterada) são ignorados.
+    pub fn tally(
+        &self,
+        decision: &Decision,
+        cloud: CloudDomain,
+        councils: &[CouncilType],
+        veto_threshold: f64,
+    ) -> Tally {
+        let council_weight = 1.0 / councils.len().max(1) as f64;
+        let roster_weight = |council: CouncilType| -> f64 {
+            self.members.values().filter(|m| m.cloud == cloud && m.council == council).map(|m| m.weight).sum()
+        };
+
+        let (mut approve, mut reject, mut veto, mut abstain, mut counted) = (0.0, 0.0, 0.0, 0.0, 0);
+        for ballot in self.ballots(decision) {
+            if ballot.cloud != cloud || !councils.contains(&ballot.council) || self.verify_ballot(ballot).is_err() {
+                continue;
+            }
+            let total = roster_weight(ballot.council);
+            let share = council_weight * self.members[&ballot.member_key].weight / total;
+            match ballot.vote {
+                Vote::Approve => approve += share,
+                Vote::Reject => reject += share,
+                Vote::Veto => veto += share,
+                Vote::Abstain => abstain += share,
+            }
+            counted += 1;
+        }
+
+        let participation = approve + reject + veto + abstain;
+        let expressed = approve + reject + veto;
+        let phi = if expressed > 0.0 { approve / expressed } else { 0.0 };
+        let outcome = if participation < self.policy.quorum {
+            TallyOutcome::NoQuorum
+        } else if veto >= veto_threshold {
+            TallyOutcome::Vetoed
+        } else if phi >= self.policy.supermajority {
+            TallyOutcome::Approved
+        } else {
+            TallyOutcome::Rejected
+        };
+
+        Tally { cloud, participation, approve, reject, veto, abstain, phi, outcome, ballots: counted }
+    }
+}
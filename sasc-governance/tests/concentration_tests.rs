@@ -0,0 +1,107 @@
+use sasc_governance::concentration::{analyze, herfindahl_hirschman, ConcentrationLevel, ProviderGraph};
+use sasc_governance::invariants::InvariantMonitor;
+use sasc_governance::types::Provider;
+
+fn provider(id: &str, share: f64, deps: &[&str]) -> Provider {
+    Provider { id: id.to_string(), market_share: share, dependencies: deps.iter().map(|d| d.to_string()).collect() }
+}
+
+#[test]
+fn test_hub_is_single_point_of_failure() {
+    let providers = vec![
+        provider("hub", 0.1, &[]),
+        provider("a", 0.1, &["hub"]),
+        provider("b", 0.1, &["hub"]),
+        provider("c", 0.1, &["hub"]),
+    ];
+    let report = analyze(&providers);
+
+    assert_eq!(report.articulation_points, vec!["hub"]);
+    assert_eq!(report.bridges.len(), 3);
+    assert_eq!(report.single_points_of_failure.len(), 1);
+    assert_eq!(report.single_points_of_failure[0].provider, "hub");
+    assert_eq!(report.single_points_of_failure[0].cut_off, vec!["a", "b", "c"]);
+    assert!(!report.n_minus_1_resilient);
+}
+
+#[test]
+fn test_redundant_graph_is_n_minus_1_resilient() {
+    let providers = vec![
+        provider("x", 0.2, &[]),
+        provider("y", 0.2, &[]),
+        provider("a", 0.2, &["x", "y"]),
+        provider("b", 0.2, &["x", "y"]),
+        provider("c", 0.2, &["x", "y"]),
+    ];
+    let report = analyze(&providers);
+
+    assert!(report.articulation_points.is_empty());
+    assert!(report.bridges.is_empty());
+    assert!(report.single_points_of_failure.is_empty());
+    assert!(report.n_minus_1_resilient);
+    assert!((report.hhi - 2000.0).abs() < 1e-9);
+    assert_eq!(report.concentration, ConcentrationLevel::Moderate);
+    assert!(report.is_compliant());
+}
+
+#[test]
+fn test_sole_supplier_and_unlisted_dependency() {
+    // "dns" não está cadastrado, mas entra no grafo como infraestrutura
+    let providers = vec![
+        provider("edge", 0.1, &["dns"]),
+        provider("p1", 0.1, &[]),
+        provider("p2", 0.1, &[]),
+    ];
+    let report = analyze(&providers);
+
+    assert_eq!(report.provider_count, 4);
+    assert_eq!(report.bridges, vec![("edge".to_string(), "dns".to_string())]);
+    assert!(report.articulation_points.is_empty());
+    assert_eq!(report.single_points_of_failure[0].provider, "dns");
+    assert_eq!(report.single_points_of_failure[0].cut_off, vec!["edge"]);
+    assert!(!report.n_minus_1_resilient);
+}
+
+#[test]
+fn test_hhi_classification() {
+    let providers = vec![provider("a", 0.5, &[]), provider("b", 0.3, &[]), provider("c", 0.2, &[])];
+    assert!((herfindahl_hirschman(&providers) - 3800.0).abs() < 1e-9);
+    let report = analyze(&providers);
+    assert_eq!(report.concentration, ConcentrationLevel::High);
+    assert_eq!(report.dominant_providers, vec![("a".to_string(), 0.5), ("b".to_string(), 0.3)]);
+}
+
+#[test]
+fn test_procurement_graph_from_json_and_toml() {
+    let json = r#"{"providers": [
+        {"id": "x", "market_share": 0.2},
+        {"id": "a", "market_share": 0.1, "dependencies": ["x"]}
+    ]}"#;
+    let toml = r#"
+        [[providers]]
+        id = "x"
+        market_share = 0.2
+
+        [[providers]]
+        id = "a"
+        market_share = 0.1
+        dependencies = ["x"]
+    "#;
+    let from_json = ProviderGraph::from_json_str(json).unwrap();
+    let from_toml = ProviderGraph::from_toml_str(toml).unwrap();
+    assert_eq!(from_json.providers.len(), 2);
+    assert_eq!(from_toml.providers[1].dependencies, vec!["x"]);
+    assert!(ProviderGraph::from_toml_str("providers = 3").is_err());
+
+    let path = std::env::temp_dir().join(format!("sasc_providers_{}.toml", std::process::id()));
+    std::fs::write(&path, toml).unwrap();
+    let mut monitor = InvariantMonitor::new("BR");
+    monitor.load_providers(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(!monitor.check_inv3_power_concentration());
+    let report = monitor.inv3_report();
+    assert_eq!(report.single_points_of_failure[0].provider, "x");
+    assert!(monitor.violation_log.iter().any(|v| v.details.contains("single point of failure")));
+    assert!(monitor.load_providers("/nonexistent/providers.yaml").is_err());
+}
@@ -0,0 +1,131 @@
+use ed25519_dalek::SigningKey;
+use sasc_governance::decision_log::{leaf_hash, verify_consistency, verify_inclusion, DecisionLogStore};
+use sasc_governance::invariants::{InvariantMonitor, LogVerification};
+use sasc_governance::types::{DecisionId, DecisionLog, LogEntry};
+
+fn entry(i: u64) -> LogEntry {
+    LogEntry { timestamp: 100 + i, decision_id: DecisionId([i as u8; 32]), decision: format!("D{}", i) }
+}
+
+fn log_of(n: u64) -> DecisionLog {
+    log_with(n, entry)
+}
+
+fn log_with(n: u64, make: impl Fn(u64) -> LogEntry) -> DecisionLog {
+    let mut log = DecisionLog::new();
+    for i in 0..n {
+        log.append(make(i));
+    }
+    log
+}
+
+fn forged(target: u64, text: &str) -> impl Fn(u64) -> LogEntry + '_ {
+    move |i| if i == target { LogEntry { decision: text.to_string(), ..entry(i) } } else { entry(i) }
+}
+
+#[test]
+fn test_inclusion_proofs_for_every_leaf() {
+    let log = log_of(17);
+    for size in 1..=17 {
+        let root = log.root_at(size).unwrap();
+        for index in 0..size {
+            let proof = log.inclusion_proof(index, size).unwrap();
+            let leaf = leaf_hash(&log.entries()[index as usize]);
+            assert!(verify_inclusion(&leaf, index, size, &proof, &root), "leaf {} of {}", index, size);
+            assert!(!verify_inclusion(&leaf_hash(&entry(99)), index, size, &proof, &root));
+            if size > 1 {
+                assert!(!verify_inclusion(&leaf, (index + 1) % size, size, &proof, &root));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_consistency_proofs_between_all_sizes() {
+    let log = log_of(13);
+    for new_size in 0..=13 {
+        let new_root = log.root_at(new_size).unwrap();
+        for old_size in 0..=new_size {
+            let old_root = log.root_at(old_size).unwrap();
+            let proof = log.consistency_proof(old_size, new_size).unwrap();
+            assert!(verify_consistency(old_size, new_size, &old_root, &new_root, &proof), "{} -> {}", old_size, new_size);
+        }
+    }
+
+    // Histórico reescrito: a entrada 2 foi trocada depois da cabeça de tamanho 4
+    let forked = log_with(9, forged(2, "forged"));
+    let proof = forked.consistency_proof(4, 9).unwrap();
+    let honest_old = log.root_at(4).unwrap();
+    assert!(!verify_consistency(4, 9, &honest_old, &forked.root(), &proof));
+}
+
+#[test]
+fn test_inv2_auditor_with_only_a_tree_head() {
+    let key = SigningKey::from_bytes(&[5; 32]);
+    let mut log = log_of(6);
+    let head = log.sign_tree_head(&key, 1_000);
+
+    let mut auditor = InvariantMonitor::new("BR");
+    auditor.log_public_key = Some(key.verifying_key().to_bytes());
+    assert_eq!(auditor.verify_merkle_proof(&log), LogVerification::Unverified);
+    assert!(!auditor.check_inv2_auditability(&log), "log accepted without a trusted head");
+    assert!(auditor.check_inv2_consistency(&head, &[]));
+
+    let proof = log.inclusion_proof(3, head.tree_size).unwrap();
+    assert!(auditor.check_inv2_inclusion(&log.entries()[3], 3, &proof, &head));
+    assert!(!auditor.check_inv2_inclusion(&entry(42), 3, &proof, &head));
+
+    for i in 6..10 {
+        log.append(entry(i));
+    }
+    let newer = log.sign_tree_head(&key, 2_000);
+    let proof = log.consistency_proof(head.tree_size, newer.tree_size).unwrap();
+    assert!(auditor.check_inv2_consistency(&newer, &proof));
+    assert!(auditor.check_inv2_auditability(&log));
+
+    // Cabeça assinada por outra chave é recusada
+    let rogue = log.sign_tree_head(&SigningKey::from_bytes(&[6; 32]), 3_000);
+    assert!(!auditor.check_inv2_consistency(&rogue, &[]));
+
+    // Adulterar uma entrada já coberta pela cabeça confiável viola INV-2
+    let tampered = log_with(10, forged(1, "tampered"));
+    assert_eq!(auditor.verify_merkle_proof(&tampered), LogVerification::Tampered);
+    assert!(!auditor.check_inv2_auditability(&tampered));
+    assert!(auditor.violation_log.iter().all(|v| v.invariant == "INV-2"));
+}
+
+#[test]
+fn test_store_persists_entries_and_heads() {
+    let path = std::env::temp_dir().join(format!("sasc_decision_log_{}.jsonl", std::process::id()));
+    let heads = path.with_extension("jsonl.heads");
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(&heads);
+    let key = SigningKey::from_bytes(&[8; 32]);
+
+    let log_key = key.verifying_key().to_bytes();
+
+    let mut store = DecisionLogStore::open(&path, log_key).unwrap();
+    for i in 0..5 {
+        assert_eq!(store.append(entry(i)).unwrap(), i);
+    }
+    assert!(store.publish_head(&SigningKey::from_bytes(&[9; 32]), 7).is_err());
+    let head = store.publish_head(&key, 7).unwrap();
+
+    let reopened = DecisionLogStore::open(&path, log_key).unwrap();
+    assert_eq!(reopened.log().root(), head.root_hash);
+    assert_eq!(reopened.heads(), &[head]);
+
+    // Cabeças reassinadas com outra chave não passam pela chave configurada
+    let rogue = SigningKey::from_bytes(&[9; 32]);
+    let resigned = reopened.log().sign_tree_head(&rogue, 7);
+    std::fs::write(&heads, format!("{}\n", serde_json::to_string(&resigned).unwrap())).unwrap();
+    assert!(DecisionLogStore::open(&path, log_key).is_err());
+    assert!(DecisionLogStore::open(&path, rogue.verifying_key().to_bytes()).is_ok());
+
+    let tampered = std::fs::read_to_string(&path).unwrap().replace("\"D3\"", "\"D9\"");
+    std::fs::write(&path, tampered).unwrap();
+    assert!(DecisionLogStore::open(&path, rogue.verifying_key().to_bytes()).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&heads).unwrap();
+}
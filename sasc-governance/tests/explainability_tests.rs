@@ -0,0 +1,114 @@
+use sasc_governance::explainability::{assess, count_syllables, detect_language, reading_ease, Language};
+use sasc_governance::invariants::InvariantMonitor;
+use sasc_governance::types::{Decision, DecisionId, DecisionSignature, LogEntry};
+
+const PT_EXPLANATION: &str =
+    "O crédito foi negado porque a renda de 2.000 reais não cobre a parcela. O cliente pode pedir revisão.";
+
+fn decision(explanation: &str) -> Decision {
+    Decision {
+        id: DecisionId([9; 32]),
+        agent_id: "agent-1".to_string(),
+        content: "deny_credit".to_string(),
+        signature: DecisionSignature { prince_veto: false, signature_bytes: vec![] },
+        action_hash: [0; 32],
+        is_critical: false,
+        affects_rights: true,
+        human_approval: None,
+        decision_time: 100,
+        explanation: Some(explanation.to_string()),
+    }
+}
+
+#[test]
+fn test_language_detection() {
+    assert_eq!(detect_language(PT_EXPLANATION), Language::Portuguese);
+    assert_eq!(detect_language("The loan was denied because the income is below the limit."), Language::English);
+    assert_eq!(detect_language(""), Language::Portuguese);
+}
+
+#[test]
+fn test_syllable_counting() {
+    assert_eq!(count_syllables("casa", Language::Portuguese), 2);
+    assert_eq!(count_syllables("saúde", Language::Portuguese), 3);
+    assert_eq!(count_syllables("computador", Language::Portuguese), 4);
+    assert_eq!(count_syllables("não", Language::Portuguese), 1);
+    assert_eq!(count_syllables("because", Language::English), 2);
+    assert_eq!(count_syllables("table", Language::English), 2);
+}
+
+#[test]
+fn test_fernandes_index_shifts_flesch_constant() {
+    let pt = reading_ease(Language::Portuguese, 2, 20, 40);
+    let en = reading_ease(Language::English, 2, 20, 40);
+    assert!((pt - en - 42.0).abs() < 1e-9);
+    assert!((en - (206.835 - 1.015 * 10.0 - 84.6 * 2.0)).abs() < 1e-9);
+}
+
+#[test]
+fn test_plain_portuguese_explanation_backed_by_log() {
+    let report = assess(PT_EXPLANATION, &["renda declarada 2.000; parcela 3.500"]);
+    assert_eq!(report.language, Some(Language::Portuguese));
+    assert_eq!(report.sentences, 2);
+    assert!(report.readability >= 60.0, "readability {}", report.readability);
+    assert_eq!(report.causal_connectives, vec!["porque"]);
+    assert_eq!(report.entities, vec!["2.000"]);
+    assert!(report.passed);
+
+    let unbacked = assess(PT_EXPLANATION, &["parcela 3.500"]);
+    assert_eq!(unbacked.unverified_entities, vec!["2.000"]);
+    assert!(!unbacked.passed);
+    assert!(unbacked.score < report.score);
+}
+
+#[test]
+fn test_entities_match_whole_log_tokens() {
+    let explanation = "O pedido foi negado porque o prazo de 10 dias passou. A analista Ana revisou o caso.";
+    let report = assess(explanation, &["prazo 100 dias; cesta com bananas"]);
+    assert_eq!(report.entities, vec!["10", "Ana"]);
+    assert_eq!(report.unverified_entities, vec!["10", "Ana"]);
+    assert!(!report.passed);
+
+    let backed = assess(explanation, &["prazo 10 dias", "revisão: ana."]);
+    assert!(backed.unverified_entities.is_empty(), "{:?}", backed.unverified_entities);
+}
+
+#[test]
+fn test_english_explanation_and_numbered_steps() {
+    let report = assess("The loan was denied because the income is low. Ask Serasa for a new score.", &["serasa score"]);
+    assert_eq!(report.language, Some(Language::English));
+    assert_eq!(report.causal_connectives, vec!["because"]);
+    assert_eq!(report.entities, vec!["Serasa"]);
+    assert!(report.passed);
+
+    let steps = assess("1. A renda caiu.\n2. A parcela subiu.", &[]);
+    assert!(steps.has_causal_chain());
+    assert!(steps.entities.is_empty());
+}
+
+#[test]
+fn test_inv5_checks_entities_against_decision_log() {
+    let mut monitor = InvariantMonitor::new("BR");
+    let decision = decision(PT_EXPLANATION);
+    monitor.decision_log.append(LogEntry {
+        timestamp: 100,
+        decision_id: DecisionId([1; 32]),
+        decision: "renda 2.000".to_string(),
+    });
+
+    // Entrada de outra decisão não vale como evidência
+    assert!(!monitor.check_inv5_explainability(&decision).passed);
+
+    monitor.decision_log.append(LogEntry { timestamp: 101, decision_id: decision.id.clone(), decision: "renda 2.000".to_string() });
+    let report = monitor.check_inv5_explainability(&decision);
+    assert!(report.passed);
+    assert!(report.score > 0.9);
+
+    let dense = monitor.check_inv5_explainability(&self::decision(
+        "Otimização multiobjetivo heterocedástica de hiperparâmetros probabilísticos inviabilizou concessões.",
+    ));
+    assert!(!dense.passed);
+    assert!(dense.readability < 60.0);
+    assert!(!dense.has_causal_chain());
+    assert_eq!(monitor.violation_log.len(), 2);
+}
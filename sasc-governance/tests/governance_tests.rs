@@ -1,40 +1,48 @@
 use sasc_governance::Cathedral;
-use sasc_governance::types::{CloudDomain, Decision, DecisionSignature};
+use sasc_governance::freeze::{FreezeState, LiftCause, ThawOutcome};
+use sasc_governance::types::{CloudDomain, CouncilType, Decision, DecisionId, DecisionSignature, HardFreeze, LogEntry};
+use sasc_governance::voting::{Ballot, CouncilMember, Vote};
+use ed25519_dalek::SigningKey;
+use std::collections::HashMap;
+use std::time::Duration;
 
-#[test]
-fn test_submit_global_decision() {
-    let cathedral = Cathedral::instance();
-    let decision = Decision {
-        id: sasc_governance::types::DecisionId([0; 32]),
+const ALL_COUNCILS: [CouncilType; 7] = [
+    CouncilType::Mathematical, CouncilType::Security, CouncilType::Geometric,
+    CouncilType::Ethical, CouncilType::Economic, CouncilType::Temporal,
+    CouncilType::Quantum,
+];
+
+fn vetoed_decision() -> Decision {
+    Decision {
+        id: DecisionId([0; 32]),
         agent_id: "agent_001".to_string(),
-        content: "Propose civilizational initiation".to_string(),
+        content: "Dangerous proposal".to_string(),
         signature: DecisionSignature {
-            prince_veto: false,
+            prince_veto: true,
             signature_bytes: vec![0u8; 64],
         },
-        action_hash: [0u8; 32],
+        action_hash: [7u8; 32],
         is_critical: false,
         affects_rights: false,
         human_approval: None,
         decision_time: 0,
         explanation: None,
-    };
+    }
+}
 
-    let result = cathedral.submit_global_decision(decision, CloudDomain::WindowsServerGov);
-    assert!(result.is_ok());
-    let decision_id = result.unwrap();
-    println!("Decision ID: {:?}", decision_id);
+fn isolated_cathedral() -> Cathedral {
+    Cathedral::new(Cathedral::default_governance(FreezeState::new()))
 }
 
 #[test]
-fn test_prince_veto() {
+fn test_submit_global_decision() {
     let cathedral = Cathedral::instance();
     let decision = Decision {
-        id: sasc_governance::types::DecisionId([0; 32]),
+        id: DecisionId([0; 32]),
         agent_id: "agent_001".to_string(),
-        content: "Dangerous proposal".to_string(),
+        content: "Propose civilizational initiation".to_string(),
         signature: DecisionSignature {
-            prince_veto: true,
+            prince_veto: false,
             signature_bytes: vec![0u8; 64],
         },
         action_hash: [0u8; 32],
@@ -45,9 +53,238 @@ fn test_prince_veto() {
         explanation: None,
     };
 
+    // 3 aprovações e 1 rejeição entre 7 conselhos: quórum 4/7, Φ = 0.75
+    let cloud = CloudDomain::WindowsServerGov;
+    for (i, council) in ALL_COUNCILS.iter().enumerate() {
+        let key = SigningKey::from_bytes(&[i as u8 + 1; 32]);
+        cathedral
+            .register_council_member(CouncilMember {
+                public_key: key.verifying_key().to_bytes(),
+                cloud,
+                council: *council,
+                weight: 1.0,
+            })
+            .unwrap();
+        let vote = match i {
+            0..=2 => Vote::Approve,
+            3 => Vote::Reject,
+            _ => continue,
+        };
+        cathedral.cast_ballot(Ballot::new_signed(&key, &decision, cloud, *council, vote, 1)).unwrap();
+    }
+
+    let result = cathedral.submit_global_decision(decision, cloud);
+    assert!(result.is_ok());
+    let decision_id = result.unwrap();
+    println!("Decision ID: {:?}", decision_id);
+}
+
+/// Registra um membro por conselho em `cloud` e lança `votes[i]` pelo i-ésimo conselho.
+fn vote_all(cathedral: &Cathedral, decision: &Decision, cloud: CloudDomain, votes: &[Vote]) {
+    for (i, (council, vote)) in ALL_COUNCILS.iter().zip(votes).enumerate() {
+        let key = SigningKey::from_bytes(&[i as u8 + 100; 32]);
+        cathedral
+            .register_council_member(CouncilMember {
+                public_key: key.verifying_key().to_bytes(),
+                cloud,
+                council: *council,
+                weight: 1.0,
+            })
+            .unwrap();
+        cathedral.cast_ballot(Ballot::new_signed(&key, decision, cloud, *council, *vote, 1)).unwrap();
+    }
+}
+
+#[test]
+fn test_unanimous_decision_is_approved() {
+    let cathedral = isolated_cathedral();
+    let mut decision = vetoed_decision();
+    decision.signature.prince_veto = false;
+    let cloud = CloudDomain::CloudflareQuantum;
+    vote_all(&cathedral, &decision, cloud, &[Vote::Approve; 7]);
+
+    assert_eq!(cathedral.tally_decision(&decision, cloud).phi, 1.0);
+    assert!(cathedral.submit_global_decision(decision, cloud).is_ok());
+    assert!(cathedral.freeze_history().is_empty());
+}
+
+#[test]
+fn test_explanation_checked_against_cathedral_log() {
+    let cathedral = isolated_cathedral();
+    let mut decision = vetoed_decision();
+    decision.signature.prince_veto = false;
+    decision.affects_rights = true;
+    decision.explanation = Some(
+        "O crédito foi negado porque a renda de 2.000 reais não cobre a parcela. O cliente pode pedir revisão."
+            .to_string(),
+    );
+    let cloud = CloudDomain::CloudflareQuantum;
+    vote_all(&cathedral, &decision, cloud, &[Vote::Approve; 7]);
+
+    // Sem registro que sustente "2.000" a explicação é recusada
+    match cathedral.submit_global_decision(decision.clone(), cloud) {
+        Err(HardFreeze::Triggered(reason)) => assert_eq!(reason, "INV-5_VIOLATION"),
+        other => panic!("expected INV-5 violation, got {:?}", other),
+    }
+
+    // Registro de outra decisão não conta
+    cathedral.record_log_entry(LogEntry {
+        timestamp: 1,
+        decision_id: DecisionId([8; 32]),
+        decision: "renda declarada 2.000".to_string(),
+    });
+    assert!(cathedral.submit_global_decision(decision.clone(), cloud).is_err());
+
+    cathedral.record_log_entry(LogEntry {
+        timestamp: 2,
+        decision_id: decision.id.clone(),
+        decision: "renda declarada 2.000; parcela 3.500".to_string(),
+    });
+    assert!(cathedral.submit_global_decision(decision, cloud).is_ok());
+}
+
+#[test]
+fn test_rejected_decision_is_refused() {
+    let cathedral = isolated_cathedral();
+    let mut decision = vetoed_decision();
+    decision.signature.prince_veto = false;
+    let cloud = CloudDomain::CloudflareQuantum;
+    vote_all(&cathedral, &decision, cloud, &[Vote::Approve, Vote::Approve, Vote::Reject, Vote::Reject]);
+
+    match cathedral.submit_global_decision(decision, cloud) {
+        Err(HardFreeze::Triggered(reason)) => assert_eq!(reason, "INSUFFICIENT_CONSENSUS"),
+        other => panic!("expected rejection, got {:?}", other),
+    }
+    assert!(cathedral.freeze_history().is_empty());
+}
+
+#[test]
+fn test_prince_veto() {
+    // Instância própria: o freeze global bloquearia os demais testes
+    let cathedral = isolated_cathedral();
+    let decision = vetoed_decision();
+
     let result = cathedral.submit_global_decision(decision, CloudDomain::AwsNitroGovCloud);
     assert!(result.is_err());
     // Verify hard freeze is active
     let gov = cathedral.governance.lock().unwrap();
-    assert!(gov.hard_freeze_status);
+    let record = &gov.hard_freeze.history()[0];
+    assert!(gov.hard_freeze.is_frozen(None, record.started_at));
+    assert_eq!(record.reason, "PRINCE_VETO");
+    assert_eq!(record.expires_at - record.started_at, 72 * 3600);
+}
+
+#[test]
+fn test_frozen_cathedral_reports_reason_and_expiry() {
+    let cathedral = isolated_cathedral();
+    cathedral.submit_global_decision(vetoed_decision(), CloudDomain::AwsNitroGovCloud).unwrap_err();
+
+    let mut decision = vetoed_decision();
+    decision.signature.prince_veto = false;
+    match cathedral.submit_global_decision(decision, CloudDomain::WindowsServerGov) {
+        Err(HardFreeze::Active(record)) => {
+            assert_eq!(record.reason, "PRINCE_VETO");
+            assert_eq!(record.action_hash, [7u8; 32]);
+            assert!(record.expires_at > record.started_at);
+        }
+        other => panic!("expected active freeze, got {:?}", other),
+    }
+    assert!(!cathedral.verify_5_gates_transaction(b"tx", [0u8; 64], CloudDomain::CloudflareQuantum));
+}
+
+#[test]
+fn test_council_thaw_lifts_global_freeze() {
+    let cathedral = isolated_cathedral();
+    cathedral.submit_global_decision(vetoed_decision(), CloudDomain::AwsNitroGovCloud).unwrap_err();
+    let id = cathedral.freeze_status(CloudDomain::AwsNitroGovCloud).unwrap().id;
+
+    let domains = [CloudDomain::AwsNitroGovCloud, CloudDomain::CloudflareQuantum, CloudDomain::WindowsServerGov];
+    for (i, cloud) in domains.iter().enumerate() {
+        // Maioria acima de 1 - veto_threshold (0.55): 4 de 7 conselhos
+        for council in &ALL_COUNCILS[..3] {
+            assert!(matches!(cathedral.vote_thaw(id, *cloud, *council, true), Ok(ThawOutcome::Pending { .. })));
+        }
+        let outcome = cathedral.vote_thaw(id, *cloud, ALL_COUNCILS[3], true).unwrap();
+        if i + 1 < domains.len() {
+            assert!(matches!(outcome, ThawOutcome::Pending { ref pending } if pending.len() == domains.len() - i - 1));
+        } else {
+            assert_eq!(outcome, ThawOutcome::Thawed);
+        }
+    }
+
+    assert!(cathedral.freeze_status(CloudDomain::WindowsServerGov).is_none());
+    let history = cathedral.freeze_history();
+    assert_eq!(history[0].lifted.as_ref().unwrap().cause, LiftCause::CouncilThaw);
+    assert!(cathedral.vote_thaw(id, CloudDomain::WindowsServerGov, ALL_COUNCILS[6], true).is_err());
+}
+
+#[test]
+fn test_council_veto_keeps_freeze() {
+    let cathedral = isolated_cathedral();
+    cathedral.submit_global_decision(vetoed_decision(), CloudDomain::AwsNitroGovCloud).unwrap_err();
+    let id = cathedral.freeze_status(CloudDomain::AwsNitroGovCloud).unwrap().id;
+    let cloud = CloudDomain::CloudflareQuantum;
+
+    for council in &ALL_COUNCILS[..3] {
+        cathedral.vote_thaw(id, cloud, *council, false).unwrap();
+    }
+    assert!(cathedral.vote_thaw(id, cloud, ALL_COUNCILS[0], true).is_err(), "duplicate vote accepted");
+    assert_eq!(cathedral.vote_thaw(id, cloud, ALL_COUNCILS[3], false).unwrap(), ThawOutcome::Vetoed { cloud });
+    assert!(cathedral.freeze_status(cloud).is_some());
+}
+
+#[test]
+fn test_freeze_expires_and_history_persists() {
+    let path = std::env::temp_dir().join(format!("sasc_freeze_{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let councils: HashMap<_, _> = [(CloudDomain::WindowsServerGov, ALL_COUNCILS.to_vec())].into_iter().collect();
+
+    let mut state = FreezeState::open(&path).unwrap();
+    let id = state
+        .activate(Some(CloudDomain::WindowsServerGov), "CATASTROPHIC_THREAT", &[1u8; 32], 1_000, Duration::from_secs(60))
+        .unwrap();
+    assert!(state.is_frozen(Some(CloudDomain::WindowsServerGov), 1_059));
+    assert!(!state.is_frozen(Some(CloudDomain::AwsNitroGovCloud), 1_059));
+
+    let mut reopened = FreezeState::open(&path).unwrap();
+    assert_eq!(reopened.history(), state.history());
+    reopened.expire(1_060).unwrap();
+    assert!(!reopened.is_frozen(None, 1_060));
+    assert!(reopened
+        .vote_thaw(id, CloudDomain::WindowsServerGov, CouncilType::Ethical, true, 1_061, &councils, 0.45)
+        .is_err());
+
+    let history = FreezeState::open(&path).unwrap();
+    let lift = history.history()[0].lifted.clone().unwrap();
+    assert_eq!((lift.cause, lift.timestamp), (LiftCause::Expired, 1_060));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_freeze_survives_persistence_failure() {
+    let dir = std::env::temp_dir().join(format!("sasc_freeze_fail_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut state = FreezeState::open(dir.join("history.json")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let councils: HashMap<_, _> = [(CloudDomain::WindowsServerGov, vec![CouncilType::Ethical])].into_iter().collect();
+
+    // Gravação falha, mas o freeze continua valendo em memória
+    assert!(state.activate(None, "CATASTROPHIC_THREAT", &[1u8; 32], 1_000, Duration::from_secs(60)).is_err());
+    assert!(state.is_frozen(Some(CloudDomain::WindowsServerGov), 1_001));
+
+    // O voto que descongelaria é desfeito quando não pode ser gravado
+    let id = state.history()[0].id;
+    assert!(state
+        .vote_thaw(id, CloudDomain::WindowsServerGov, CouncilType::Ethical, true, 1_002, &councils, 0.45)
+        .is_err());
+    assert!(state.history()[0].thaw_votes.is_empty());
+    assert!(state.history()[0].lifted.is_none());
+    assert!(state.is_frozen(Some(CloudDomain::WindowsServerGov), 1_003));
+}
+
+#[test]
+fn test_unreadable_history_starts_frozen() {
+    let state = FreezeState::unavailable("corrupted", 1_000);
+    assert!(state.is_frozen(None, u64::MAX - 1));
+    assert!(state.history()[0].reason.starts_with("FREEZE_HISTORY_UNAVAILABLE"));
 }
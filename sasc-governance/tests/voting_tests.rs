@@ -0,0 +1,132 @@
+use ed25519_dalek::SigningKey;
+use sasc_governance::freeze::FreezeState;
+use sasc_governance::types::{CloudDomain, CouncilType, Decision, DecisionId, DecisionSignature, HardFreeze};
+use sasc_governance::voting::{Ballot, CouncilMember, TallyOutcome, Vote, VotingEngine, VotingPolicy};
+use sasc_governance::Cathedral;
+
+const CLOUD: CloudDomain = CloudDomain::AwsNitroGovCloud;
+const COUNCILS: [CouncilType; 2] = [CouncilType::Ethical, CouncilType::Security];
+
+fn decision(content: &str) -> Decision {
+    Decision {
+        id: DecisionId([1; 32]),
+        agent_id: "agent_042".to_string(),
+        content: content.to_string(),
+        signature: DecisionSignature { prince_veto: false, signature_bytes: vec![] },
+        action_hash: [2; 32],
+        is_critical: false,
+        affects_rights: false,
+        human_approval: None,
+        decision_time: 0,
+        explanation: None,
+    }
+}
+
+/// Conselho Ético: pesos 3 e 1; Conselho de Segurança: um único membro.
+fn engine() -> (VotingEngine, Vec<(SigningKey, CouncilType)>) {
+    let mut engine = VotingEngine::new(VotingPolicy::default());
+    let members = vec![
+        (SigningKey::from_bytes(&[10; 32]), CouncilType::Ethical, 3.0),
+        (SigningKey::from_bytes(&[11; 32]), CouncilType::Ethical, 1.0),
+        (SigningKey::from_bytes(&[12; 32]), CouncilType::Security, 5.0),
+    ];
+    for (key, council, weight) in &members {
+        engine
+            .register_member(CouncilMember {
+                public_key: key.verifying_key().to_bytes(),
+                cloud: CLOUD,
+                council: *council,
+                weight: *weight,
+            })
+            .unwrap();
+    }
+    (engine, members.into_iter().map(|(k, c, _)| (k, c)).collect())
+}
+
+#[test]
+fn test_weighted_tally_produces_phi() {
+    let (mut engine, members) = engine();
+    let decision = decision("expand grid");
+    let votes = [Vote::Approve, Vote::Reject, Vote::Approve];
+    for ((key, council), vote) in members.iter().zip(votes) {
+        engine.cast(Ballot::new_signed(key, &decision, CLOUD, *council, vote, 7)).unwrap();
+    }
+
+    let tally = engine.tally(&decision, CLOUD, &COUNCILS, 0.45);
+    // Ético: 0.5 * 3/4 aprova, 0.5 * 1/4 rejeita; Segurança: 0.5 aprova
+    assert!((tally.approve - 0.875).abs() < 1e-12);
+    assert!((tally.reject - 0.125).abs() < 1e-12);
+    assert!((tally.phi - 0.875).abs() < 1e-12);
+    assert_eq!(tally.outcome, TallyOutcome::Approved);
+    assert_eq!(tally.ballots, 3);
+}
+
+#[test]
+fn test_quorum_and_veto() {
+    let (mut engine, members) = engine();
+    let decision = decision("dissolve council");
+
+    let (ethical_minor, council) = &members[1];
+    engine.cast(Ballot::new_signed(ethical_minor, &decision, CLOUD, *council, Vote::Approve, 1)).unwrap();
+    assert_eq!(engine.tally(&decision, CLOUD, &COUNCILS, 0.45).outcome, TallyOutcome::NoQuorum);
+
+    let (security, council) = &members[2];
+    engine.cast(Ballot::new_signed(security, &decision, CLOUD, *council, Vote::Veto, 2)).unwrap();
+    let tally = engine.tally(&decision, CLOUD, &COUNCILS, 0.45);
+    assert_eq!(tally.outcome, TallyOutcome::Vetoed);
+    assert!((tally.participation - 0.625).abs() < 1e-12);
+}
+
+#[test]
+fn test_ballots_are_verifiable() {
+    let (mut engine, members) = engine();
+    let decision = decision("audit me");
+    let (key, council) = &members[0];
+
+    // Assinatura de outra decisão, conselho errado e voto duplicado são recusados
+    let mut forged = Ballot::new_signed(key, &decision, CLOUD, *council, Vote::Reject, 1);
+    forged.vote = Vote::Approve;
+    assert!(engine.cast(forged).is_err());
+    assert!(engine.cast(Ballot::new_signed(key, &decision, CLOUD, CouncilType::Quantum, Vote::Approve, 1)).is_err());
+    let stranger = SigningKey::from_bytes(&[99; 32]);
+    assert!(engine.cast(Ballot::new_signed(&stranger, &decision, CLOUD, *council, Vote::Approve, 1)).is_err());
+
+    engine.cast(Ballot::new_signed(key, &decision, CLOUD, *council, Vote::Approve, 1)).unwrap();
+    assert!(engine.cast(Ballot::new_signed(key, &decision, CLOUD, *council, Vote::Reject, 2)).is_err());
+
+    let stored = engine.ballots(&decision);
+    assert_eq!(stored.len(), 1);
+    let json = serde_json::to_string(&stored[0]).unwrap();
+    let restored: Ballot = serde_json::from_str(&json).unwrap();
+    assert!(restored.verify_signature().is_ok());
+    assert!(engine.ballots(&self::decision("other")).is_empty());
+}
+
+#[test]
+fn test_cathedral_uses_council_tally() {
+    let cathedral = Cathedral::new(Cathedral::default_governance(FreezeState::new()));
+    let decision = decision("unanimous but vetoed");
+    let cloud = CloudDomain::CloudflareQuantum;
+
+    match cathedral.submit_global_decision(decision.clone(), cloud) {
+        Err(HardFreeze::Triggered(reason)) => assert_eq!(reason, "NO_QUORUM"),
+        other => panic!("unexpected {:?}", other),
+    }
+
+    let all = cathedral.governance.lock().unwrap().councils[&cloud].clone();
+    for (i, council) in all.iter().enumerate() {
+        let key = SigningKey::from_bytes(&[40 + i as u8; 32]);
+        cathedral
+            .register_council_member(CouncilMember { public_key: key.verifying_key().to_bytes(), cloud, council: *council, weight: 1.0 })
+            .unwrap();
+        let vote = if i < 4 { Vote::Veto } else { Vote::Approve };
+        cathedral.cast_ballot(Ballot::new_signed(&key, &decision, cloud, *council, vote, 3)).unwrap();
+    }
+
+    let tally = cathedral.tally_decision(&decision, cloud);
+    assert_eq!(tally.outcome, TallyOutcome::Vetoed);
+    match cathedral.submit_global_decision(decision, cloud) {
+        Err(HardFreeze::Triggered(reason)) => assert_eq!(reason, "COUNCIL_VETO"),
+        other => panic!("unexpected {:?}", other),
+    }
+}
@@ -23,7 +23,7 @@ pub struct Persona {
     pub expertise: Vec<ExpertiseDomain>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SocioEmotionalRole {
     Analytic,
     Empathic,
@@ -32,7 +32,7 @@ pub enum SocioEmotionalRole {
     Diplomatic,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ExpertiseDomain {
     Ethics,
     Technoscience,
@@ -40,3 +40,23 @@ pub enum ExpertiseDomain {
     Sociology,
     Environment,
 }
+
+impl SocioEmotionalRole {
+    pub const ALL: [SocioEmotionalRole; 5] = [
+        SocioEmotionalRole::Analytic,
+        SocioEmotionalRole::Empathic,
+        SocioEmotionalRole::Creative,
+        SocioEmotionalRole::Stoic,
+        SocioEmotionalRole::Diplomatic,
+    ];
+}
+
+impl ExpertiseDomain {
+    pub const ALL: [ExpertiseDomain; 5] = [
+        ExpertiseDomain::Ethics,
+        ExpertiseDomain::Technoscience,
+        ExpertiseDomain::Legislation,
+        ExpertiseDomain::Sociology,
+        ExpertiseDomain::Environment,
+    ];
+}
@@ -1,19 +1,46 @@
 use blake3::Hash;
-use serde::Serialize;
+use std::sync::Arc;
+use std::time::SystemTime;
 use crate::engine::mod_reexport::SoTDecisionRequest;
 
+pub mod worm;
+
+use worm::{HistoryQuery, WormError, WormEvent, WormRecord, WormStore};
+
+/// Rastreia a proveniência das decisões no armazenamento WORM
 pub struct ProvenanceTracer {
     component: String,
+    store: Arc<WormStore>,
 }
 
 impl ProvenanceTracer {
-    pub fn new(component: &str) -> Self {
+    pub fn new(component: &str, store: Arc<WormStore>) -> Self {
         Self {
             component: component.to_string(),
+            store,
         }
     }
 
-    pub fn trace_decision_start(&self, request_hash: Hash, request: &SoTDecisionRequest, phi: &f64) {
-        // Implementation for tracing decision start
+    pub async fn trace_decision_start(&self, request_hash: Hash, request: &SoTDecisionRequest, phi: &f64) {
+        let event = WormEvent::DecisionStarted {
+            problem_statement: request.problem_statement.clone(),
+            phi: *phi,
+        };
+        if let Err(e) = self.store.append_async(*request_hash.as_bytes(), &request.metadata.requestor_id, event, SystemTime::now()).await {
+            log::error!("[{}] Falha ao registrar início da decisão {}: {}", self.component, request_hash.to_hex(), e);
+        }
+    }
+
+    /// Registros da decisão, do início ao desfecho
+    pub fn provenance(&self, decision_id: &[u8; 32]) -> Result<Vec<WormRecord>, WormError> {
+        self.store.query(&HistoryQuery {
+            decision_id: Some(*decision_id),
+            ..Default::default()
+        })
+    }
+
+    /// Confere a cadeia de hashes de todo o histórico retido
+    pub fn verify_chain(&self) -> Result<u64, WormError> {
+        self.store.verify()
     }
 }
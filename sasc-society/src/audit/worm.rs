@@ -0,0 +1,933 @@
+//! Histórico de decisões em armazenamento WORM (write once, read many)
+//! Segmentos append-only, registros encadeados por BLAKE3 e retenção que só
+//! remove segmentos inteiros já expirados
+
+use crate::engine::DecisionStatus;
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+
+/// Separador de domínio do hash de cada registro
+const RECORD_DOMAIN: &[u8] = b"sasc-society-worm-v1";
+const SEGMENT_PREFIX: &str = "segment-";
+const SEGMENT_EXTENSION: &str = "worm";
+/// Registro append-only dos segmentos removidos pela retenção
+const RETENTION_LOG: &str = "retention.log";
+
+#[derive(Error, Debug)]
+pub enum WormError {
+    #[error("Falha de E/S em {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("Registro {sequence} do segmento {segment} corrompido: {reason}")]
+    Corrupted { segment: u64, sequence: u64, reason: String },
+    #[error("Registro malformado: {0}")]
+    Malformed(String),
+    #[error("Tarefa de E/S do histórico interrompida: {0}")]
+    Interrupted(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WormEvent {
+    /// Decisão aceita para processamento
+    DecisionStarted { problem_statement: String, phi: f64 },
+    /// Desfecho da decisão
+    DecisionRecorded { status: DecisionStatus, summary: String, coherence: f64 },
+}
+
+impl WormEvent {
+    pub fn status(&self) -> Option<&DecisionStatus> {
+        match self {
+            WormEvent::DecisionRecorded { status, .. } => Some(status),
+            WormEvent::DecisionStarted { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WormRecord {
+    pub sequence: u64,
+    pub timestamp: SystemTime,
+    pub decision_id: [u8; 32],
+    pub requestor_id: String,
+    pub event: WormEvent,
+    /// Hash do registro anterior; zeros no primeiro registro do armazenamento
+    pub previous_hash: [u8; 32],
+    pub hash: [u8; 32],
+}
+
+impl WormRecord {
+    /// BLAKE3 de domínio || JSON de todos os campos exceto `hash`
+    fn compute_hash(&self) -> Result<[u8; 32], WormError> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            sequence: u64,
+            timestamp: &'a SystemTime,
+            decision_id: &'a [u8; 32],
+            requestor_id: &'a str,
+            event: &'a WormEvent,
+            previous_hash: &'a [u8; 32],
+        }
+        let body = serde_json::to_vec(&Body {
+            sequence: self.sequence,
+            timestamp: &self.timestamp,
+            decision_id: &self.decision_id,
+            requestor_id: &self.requestor_id,
+            event: &self.event,
+            previous_hash: &self.previous_hash,
+        }).map_err(|e| WormError::Malformed(e.to_string()))?;
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(RECORD_DOMAIN);
+        hasher.update(&body);
+        Ok(hasher.finalize().into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WormPolicy {
+    /// Registros no segmento ativo antes de selá-lo
+    pub max_records_per_segment: u64,
+    /// Idade máxima do segmento ativo antes de selá-lo
+    pub max_segment_age: Duration,
+    /// Um segmento selado expira quando seu registro mais recente passa desta idade
+    pub retention: Duration,
+}
+
+impl WormPolicy {
+    pub fn with_retention_days(days: u32) -> Self {
+        Self {
+            retention: Duration::from_secs(u64::from(days) * 86_400),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for WormPolicy {
+    fn default() -> Self {
+        Self {
+            max_records_per_segment: 10_000,
+            max_segment_age: Duration::from_secs(86_400),
+            retention: Duration::from_secs(3650 * 86_400),
+        }
+    }
+}
+
+/// Filtros combinados por conjunção; resultado em ordem de gravação
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    pub start: Option<SystemTime>,
+    pub end: Option<SystemTime>,
+    pub decision_id: Option<[u8; 32]>,
+    pub requestor_id: Option<String>,
+    pub status: Option<DecisionStatus>,
+    /// Só registros de desfecho (`DecisionRecorded`)
+    pub outcomes_only: bool,
+    /// 0 = sem limite
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone)]
+struct IndexEntry {
+    timestamp: SystemTime,
+    decision_id: [u8; 32],
+    requestor_id: String,
+    status: Option<DecisionStatus>,
+    segment: u64,
+    offset: u64,
+    len: usize,
+}
+
+impl IndexEntry {
+    fn matches(&self, query: &HistoryQuery) -> bool {
+        query.start.is_none_or(|start| self.timestamp >= start)
+            && query.end.is_none_or(|end| self.timestamp <= end)
+            && query.decision_id.is_none_or(|id| self.decision_id == id)
+            && query.requestor_id.as_ref().is_none_or(|r| &self.requestor_id == r)
+            && query.status.as_ref().is_none_or(|s| self.status.as_ref() == Some(s))
+            && (!query.outcomes_only || self.status.is_some())
+    }
+}
+
+#[derive(Debug)]
+struct Segment {
+    first_sequence: u64,
+    records: u64,
+    first_timestamp: SystemTime,
+    last_timestamp: SystemTime,
+    last_hash: [u8; 32],
+    size: u64,
+    sealed: bool,
+    /// Conteúdo do segmento quando o armazenamento é só em memória
+    buffer: Option<Vec<u8>>,
+}
+
+/// Segmento removido pela retenção: mantém a âncora da cadeia verificável
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Tombstone {
+    segment: u64,
+    first_sequence: u64,
+    last_sequence: u64,
+    last_hash: [u8; 32],
+    last_timestamp: SystemTime,
+    deleted_at: SystemTime,
+}
+
+#[derive(Debug)]
+struct Inner {
+    segments: BTreeMap<u64, Segment>,
+    /// Índice por sequência (= ordem temporal)
+    entries: BTreeMap<u64, IndexEntry>,
+    by_decision: HashMap<[u8; 32], Vec<u64>>,
+    by_requestor: HashMap<String, Vec<u64>>,
+    by_status: HashMap<DecisionStatus, Vec<u64>>,
+    /// Primeira sequência gravada em cada instante
+    by_time: BTreeMap<SystemTime, u64>,
+    next_sequence: u64,
+    /// Id do próximo segmento a abrir; nunca reutiliza ids, nem de segmentos
+    /// já removidos pela retenção
+    next_segment: u64,
+    last_hash: [u8; 32],
+    last_timestamp: SystemTime,
+    /// Hash que precede o registro mais antigo retido
+    anchor: [u8; 32],
+}
+
+impl Inner {
+    fn empty() -> Self {
+        Self {
+            segments: BTreeMap::new(),
+            entries: BTreeMap::new(),
+            by_decision: HashMap::new(),
+            by_requestor: HashMap::new(),
+            by_status: HashMap::new(),
+            by_time: BTreeMap::new(),
+            next_sequence: 0,
+            next_segment: 0,
+            last_hash: [0u8; 32],
+            last_timestamp: SystemTime::UNIX_EPOCH,
+            anchor: [0u8; 32],
+        }
+    }
+
+    fn index(&mut self, record: &WormRecord, segment: u64, offset: u64, len: usize) {
+        let status = record.event.status().cloned();
+        self.by_decision.entry(record.decision_id).or_default().push(record.sequence);
+        self.by_requestor.entry(record.requestor_id.clone()).or_default().push(record.sequence);
+        if let Some(status) = &status {
+            self.by_status.entry(status.clone()).or_default().push(record.sequence);
+        }
+        self.by_time.entry(record.timestamp).or_insert(record.sequence);
+        self.entries.insert(record.sequence, IndexEntry {
+            timestamp: record.timestamp,
+            decision_id: record.decision_id,
+            requestor_id: record.requestor_id.clone(),
+            status,
+            segment,
+            offset,
+            len,
+        });
+        self.next_sequence = record.sequence + 1;
+        self.last_hash = record.hash;
+        self.last_timestamp = record.timestamp;
+    }
+
+    /// Remove do índice; chamado em ordem crescente de sequência pela retenção
+    fn unindex(&mut self, sequence: u64) {
+        let Some(entry) = self.entries.remove(&sequence) else {
+            return;
+        };
+        if self.by_time.get(&entry.timestamp) == Some(&sequence) {
+            match self.entries.get(&(sequence + 1)) {
+                Some(next) if next.timestamp == entry.timestamp => {
+                    self.by_time.insert(entry.timestamp, sequence + 1);
+                }
+                _ => {
+                    self.by_time.remove(&entry.timestamp);
+                }
+            }
+        }
+        prune(&mut self.by_decision, &entry.decision_id, sequence);
+        prune(&mut self.by_requestor, &entry.requestor_id, sequence);
+        if let Some(status) = &entry.status {
+            prune(&mut self.by_status, status, sequence);
+        }
+    }
+}
+
+fn prune<K: std::hash::Hash + Eq + Clone>(index: &mut HashMap<K, Vec<u64>>, key: &K, sequence: u64) {
+    if let Some(sequences) = index.get_mut(key) {
+        sequences.retain(|s| *s != sequence);
+        if sequences.is_empty() {
+            index.remove(key);
+        }
+    }
+}
+
+/// Armazenamento WORM. Registros só são acrescentados; segmentos selados
+/// ficam somente-leitura e só a retenção os remove, inteiros e a partir do
+/// mais antigo, deixando um tombstone com a âncora da cadeia.
+pub struct WormStore {
+    root: Option<PathBuf>,
+    policy: WormPolicy,
+    inner: Mutex<Inner>,
+}
+
+impl WormStore {
+    /// Armazenamento sem persistência, para testes e execuções efêmeras
+    pub fn in_memory(policy: WormPolicy) -> Self {
+        Self { root: None, policy, inner: Mutex::new(Inner::empty()) }
+    }
+
+    /// Abre (ou cria) o diretório `root`, verificando a cadeia de todos os
+    /// segmentos. Uma última linha sem quebra de linha no segmento ativo é
+    /// gravação interrompida: seus bytes vão para `<segmento>.torn` e o
+    /// segmento é truncado antes dela. Segmentos já cobertos por tombstone
+    /// (queda entre o tombstone e a remoção) são removidos.
+    pub fn open(root: impl AsRef<Path>, policy: WormPolicy) -> Result<Self, WormError> {
+        let root = root.as_ref().to_path_buf();
+        std::fs::create_dir_all(&root).map_err(|e| WormError::Io(root.clone(), e))?;
+
+        let mut inner = Inner::empty();
+        let retired = read_tombstones(&root)?.pop();
+        if let Some(tombstone) = &retired {
+            inner.anchor = tombstone.last_hash;
+            inner.last_hash = tombstone.last_hash;
+            inner.next_sequence = tombstone.last_sequence + 1;
+            inner.next_segment = tombstone.segment + 1;
+            inner.last_timestamp = tombstone.last_timestamp;
+        }
+
+        let mut segment_ids = list_segments(&root)?;
+        if let Some(tombstone) = &retired {
+            for id in segment_ids.iter().filter(|id| **id <= tombstone.segment) {
+                let path = segment_path(&root, *id);
+                std::fs::remove_file(&path).map_err(|e| WormError::Io(path.clone(), e))?;
+                log::warn!("Segmento {} já retirado pela retenção; arquivo remanescente removido", id);
+            }
+            segment_ids.retain(|id| *id > tombstone.segment);
+        }
+        let last_id = segment_ids.last().copied();
+        if let Some(last_id) = last_id {
+            inner.next_segment = inner.next_segment.max(last_id + 1);
+        }
+        for id in segment_ids {
+            let path = segment_path(&root, id);
+            let mut bytes = std::fs::read(&path).map_err(|e| WormError::Io(path.clone(), e))?;
+            if Some(id) == last_id && bytes.last().is_some_and(|b| *b != b'\n') {
+                let complete = bytes.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+                quarantine_torn_tail(&path, &bytes[complete..], complete as u64)?;
+                bytes.truncate(complete);
+            }
+            let mut segment: Option<Segment> = None;
+            let mut offset = 0u64;
+            for line in bytes.split_inclusive(|b| *b == b'\n') {
+                let record = parse_record(line, id, inner.next_sequence)?;
+                check_link(&record, id, inner.next_sequence, inner.last_hash)?;
+                inner.index(&record, id, offset, line.len());
+                offset += line.len() as u64;
+                let segment = segment.get_or_insert(Segment {
+                    first_sequence: record.sequence,
+                    records: 0,
+                    first_timestamp: record.timestamp,
+                    last_timestamp: record.timestamp,
+                    last_hash: record.hash,
+                    size: 0,
+                    sealed: false,
+                    buffer: None,
+                });
+                segment.records += 1;
+                segment.last_timestamp = record.timestamp;
+                segment.last_hash = record.hash;
+                segment.size = offset;
+            }
+            if let Some(mut segment) = segment {
+                let readonly = std::fs::metadata(&path)
+                    .map(|m| m.permissions().readonly())
+                    .unwrap_or(false);
+                segment.sealed = Some(id) != last_id || readonly;
+                inner.segments.insert(id, segment);
+            }
+        }
+
+        Ok(Self { root: Some(root), policy, inner: Mutex::new(inner) })
+    }
+
+    pub fn policy(&self) -> &WormPolicy {
+        &self.policy
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    /// Segmentos retidos, do mais antigo ao mais recente
+    pub fn segments(&self) -> Vec<u64> {
+        self.lock().segments.keys().copied().collect()
+    }
+
+    pub fn contains(&self, decision_id: &[u8; 32]) -> bool {
+        self.lock().by_decision.contains_key(decision_id)
+    }
+
+    /// Acrescenta um registro ao segmento ativo. O timestamp nunca recua:
+    /// a ordem de sequência é também a ordem temporal.
+    pub fn append(
+        &self,
+        decision_id: [u8; 32],
+        requestor_id: &str,
+        event: WormEvent,
+        now: SystemTime,
+    ) -> Result<WormRecord, WormError> {
+        let mut inner = self.lock();
+        let timestamp = now.max(inner.last_timestamp);
+
+        let mut record = WormRecord {
+            sequence: inner.next_sequence,
+            timestamp,
+            decision_id,
+            requestor_id: requestor_id.to_string(),
+            event,
+            previous_hash: inner.last_hash,
+            hash: [0u8; 32],
+        };
+        record.hash = record.compute_hash()?;
+        let mut line = serde_json::to_vec(&record).map_err(|e| WormError::Malformed(e.to_string()))?;
+        line.push(b'\n');
+
+        let segment_id = self.active_segment(&mut inner, timestamp)?;
+        let offset = inner.segments.get(&segment_id).map_or(0, |segment| segment.size);
+        // Gravado antes de tocar o estado: em falha nada muda em memória
+        if let Some(root) = &self.root {
+            append_line(&segment_path(root, segment_id), &line, offset)?;
+        }
+        inner.next_segment = inner.next_segment.max(segment_id + 1);
+        let segment = inner.segments.entry(segment_id).or_insert_with(|| Segment {
+            first_sequence: record.sequence,
+            records: 0,
+            first_timestamp: timestamp,
+            last_timestamp: timestamp,
+            last_hash: record.hash,
+            size: 0,
+            sealed: false,
+            buffer: self.root.is_none().then(Vec::new),
+        });
+        if let Some(buffer) = &mut segment.buffer {
+            buffer.extend_from_slice(&line);
+        }
+        segment.records += 1;
+        segment.size += line.len() as u64;
+        segment.last_timestamp = timestamp;
+        segment.last_hash = record.hash;
+
+        inner.index(&record, segment_id, offset, line.len());
+        Ok(record)
+    }
+
+    /// `append` numa thread de bloqueio, para chamadores assíncronos: a
+    /// gravação faz fsync e não deve ocupar uma thread do runtime
+    pub async fn append_async(
+        self: &Arc<Self>,
+        decision_id: [u8; 32],
+        requestor_id: &str,
+        event: WormEvent,
+        now: SystemTime,
+    ) -> Result<WormRecord, WormError> {
+        let store = self.clone();
+        let requestor_id = requestor_id.to_string();
+        run_blocking(move || store.append(decision_id, &requestor_id, event, now)).await
+    }
+
+    /// `enforce_retention` numa thread de bloqueio
+    pub async fn enforce_retention_async(self: &Arc<Self>, now: SystemTime) -> Result<Vec<u64>, WormError> {
+        let store = self.clone();
+        run_blocking(move || store.enforce_retention(now)).await
+    }
+
+    /// Remove segmentos selados cujo registro mais recente excedeu a
+    /// retenção, a partir do mais antigo. Devolve os segmentos removidos.
+    pub fn enforce_retention(&self, now: SystemTime) -> Result<Vec<u64>, WormError> {
+        let mut inner = self.lock();
+        let mut removed = Vec::new();
+
+        while let Some((&id, segment)) = inner.segments.iter().next() {
+            let expired = now.duration_since(segment.last_timestamp)
+                .is_ok_and(|age| age >= self.policy.retention);
+            if !segment.sealed || !expired {
+                break;
+            }
+            let tombstone = Tombstone {
+                segment: id,
+                first_sequence: segment.first_sequence,
+                last_sequence: segment.first_sequence + segment.records - 1,
+                last_hash: segment.last_hash,
+                last_timestamp: segment.last_timestamp,
+                deleted_at: now,
+            };
+            if let Some(root) = &self.root {
+                // Tombstone gravado antes da remoção: a âncora nunca se perde
+                append_tombstone(root, &tombstone)?;
+                let path = segment_path(root, id);
+                std::fs::remove_file(&path).map_err(|e| WormError::Io(path, e))?;
+            }
+
+            inner.segments.remove(&id);
+            for sequence in tombstone.first_sequence..=tombstone.last_sequence {
+                inner.unindex(sequence);
+            }
+            inner.anchor = tombstone.last_hash;
+            removed.push(id);
+        }
+        Ok(removed)
+    }
+
+    pub fn query(&self, query: &HistoryQuery) -> Result<Vec<WormRecord>, WormError> {
+        let inner = self.lock();
+        let limit = if query.limit == 0 { usize::MAX } else { query.limit };
+
+        // Índice mais seletivo disponível; os demais filtros sobre as entradas
+        let candidates: Box<dyn Iterator<Item = u64>> = if let Some(id) = &query.decision_id {
+            Box::new(inner.by_decision.get(id).into_iter().flatten().copied())
+        } else if let Some(requestor) = &query.requestor_id {
+            Box::new(inner.by_requestor.get(requestor).into_iter().flatten().copied())
+        } else if let Some(status) = &query.status {
+            Box::new(inner.by_status.get(status).into_iter().flatten().copied())
+        } else {
+            let first = query.start.map_or(0, |start| {
+                inner.by_time.range(start..).next().map_or(u64::MAX, |(_, sequence)| *sequence)
+            });
+            Box::new(inner.entries.range(first..).map(|(s, _)| *s))
+        };
+
+        let time_scan = query.decision_id.is_none() && query.requestor_id.is_none() && query.status.is_none();
+        let mut records = Vec::new();
+        for sequence in candidates {
+            let entry = &inner.entries[&sequence];
+            if time_scan && query.end.is_some_and(|end| entry.timestamp > end) {
+                break; // O restante é posterior
+            }
+            if !entry.matches(query) {
+                continue;
+            }
+            records.push(self.read(&inner, sequence, entry)?);
+            if records.len() >= limit {
+                break;
+            }
+        }
+        Ok(records)
+    }
+
+    /// Registro mais recente de uma decisão, opcionalmente só desfechos
+    pub fn latest(&self, decision_id: &[u8; 32], outcomes_only: bool) -> Result<Option<WormRecord>, WormError> {
+        let inner = self.lock();
+        let Some(sequences) = inner.by_decision.get(decision_id) else {
+            return Ok(None);
+        };
+        for sequence in sequences.iter().rev() {
+            let entry = &inner.entries[sequence];
+            if !outcomes_only || entry.status.is_some() {
+                return self.read(&inner, *sequence, entry).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Relê todos os segmentos retidos e confere a cadeia desde a âncora.
+    /// Devolve o número de registros verificados.
+    pub fn verify(&self) -> Result<u64, WormError> {
+        let inner = self.lock();
+        let mut previous = inner.anchor;
+        let first = inner.entries.keys().next().copied().unwrap_or(inner.next_sequence);
+        for ((sequence, entry), expected) in inner.entries.iter().zip(first..) {
+            let record = self.read(&inner, *sequence, entry)?;
+            check_link(&record, entry.segment, expected, previous)?;
+            previous = record.hash;
+        }
+        Ok(inner.entries.len() as u64)
+    }
+
+    fn read(&self, inner: &Inner, sequence: u64, entry: &IndexEntry) -> Result<WormRecord, WormError> {
+        let segment = inner.segments.get(&entry.segment).ok_or_else(|| WormError::Corrupted {
+            segment: entry.segment,
+            sequence,
+            reason: "segmento ausente".to_string(),
+        })?;
+        let bytes = match (&segment.buffer, &self.root) {
+            (Some(buffer), _) => buffer[entry.offset as usize..entry.offset as usize + entry.len].to_vec(),
+            (None, Some(root)) => {
+                let path = segment_path(root, entry.segment);
+                let mut bytes = vec![0u8; entry.len];
+                let mut file = std::fs::File::open(&path).map_err(|e| WormError::Io(path.clone(), e))?;
+                file.seek(SeekFrom::Start(entry.offset))
+                    .and_then(|_| file.read_exact(&mut bytes))
+                    .map_err(|e| WormError::Io(path, e))?;
+                bytes
+            }
+            (None, None) => unreachable!("segmento em memória sem buffer"),
+        };
+        let record = parse_record(&bytes, entry.segment, sequence)?;
+        if record.sequence != sequence {
+            return Err(WormError::Corrupted { segment: entry.segment, sequence, reason: "sequência divergente".to_string() });
+        }
+        Ok(record)
+    }
+
+    /// Segmento que recebe o próximo registro, selando o atual se cheio ou antigo
+    fn active_segment(&self, inner: &mut Inner, now: SystemTime) -> Result<u64, WormError> {
+        let Some((&id, segment)) = inner.segments.iter_mut().next_back() else {
+            return Ok(inner.next_segment);
+        };
+        let too_old = now.duration_since(segment.first_timestamp)
+            .is_ok_and(|age| age >= self.policy.max_segment_age);
+        if !segment.sealed && segment.records < self.policy.max_records_per_segment && !too_old {
+            return Ok(id);
+        }
+        if !segment.sealed {
+            segment.sealed = true;
+            if let Some(root) = &self.root {
+                let path = segment_path(root, id);
+                let mut permissions = std::fs::metadata(&path).map_err(|e| WormError::Io(path.clone(), e))?.permissions();
+                permissions.set_readonly(true);
+                std::fs::set_permissions(&path, permissions).map_err(|e| WormError::Io(path, e))?;
+            }
+        }
+        Ok(inner.next_segment)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+async fn run_blocking<T: Send + 'static>(
+    operation: impl FnOnce() -> Result<T, WormError> + Send + 'static,
+) -> Result<T, WormError> {
+    tokio::task::spawn_blocking(operation)
+        .await
+        .map_err(|e| WormError::Interrupted(e.to_string()))?
+}
+
+/// Acrescenta a linha com fsync; em falha trunca o segmento de volta a
+/// `size`, para que um registro parcial não fique no arquivo
+fn append_line(path: &Path, line: &[u8], size: u64) -> Result<(), WormError> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)
+        .map_err(|e| WormError::Io(path.to_path_buf(), e))?;
+    file.write_all(line)
+        .and_then(|_| file.sync_data())
+        .or_else(|e| {
+            file.set_len(size).and_then(|_| file.sync_data())?;
+            Err(e)
+        })
+        .map_err(|e| WormError::Io(path.to_path_buf(), e))
+}
+
+/// Preserva a cauda interrompida em `<segmento>.torn` e trunca o segmento
+fn quarantine_torn_tail(path: &Path, tail: &[u8], size: u64) -> Result<(), WormError> {
+    let mut quarantine = path.as_os_str().to_owned();
+    quarantine.push(".torn");
+    let quarantine = PathBuf::from(quarantine);
+    let mut file = OpenOptions::new().create(true).append(true).open(&quarantine)
+        .map_err(|e| WormError::Io(quarantine.clone(), e))?;
+    file.write_all(tail)
+        .and_then(|_| file.write_all(b"\n"))
+        .and_then(|_| file.sync_data())
+        .map_err(|e| WormError::Io(quarantine.clone(), e))?;
+
+    let segment = OpenOptions::new().write(true).open(path).map_err(|e| WormError::Io(path.to_path_buf(), e))?;
+    segment.set_len(size)
+        .and_then(|_| segment.sync_data())
+        .map_err(|e| WormError::Io(path.to_path_buf(), e))?;
+    log::warn!("Gravação interrompida em {}: {} bytes movidos para {}", path.display(), tail.len(), quarantine.display());
+    Ok(())
+}
+
+fn segment_path(root: &Path, id: u64) -> PathBuf {
+    root.join(format!("{}{:010}.{}", SEGMENT_PREFIX, id, SEGMENT_EXTENSION))
+}
+
+fn list_segments(root: &Path) -> Result<Vec<u64>, WormError> {
+    let mut ids = Vec::new();
+    for entry in std::fs::read_dir(root).map_err(|e| WormError::Io(root.to_path_buf(), e))? {
+        let entry = entry.map_err(|e| WormError::Io(root.to_path_buf(), e))?;
+        let name = entry.file_name();
+        let id = name.to_str()
+            .and_then(|n| n.strip_prefix(SEGMENT_PREFIX))
+            .and_then(|n| n.strip_suffix(&format!(".{}", SEGMENT_EXTENSION)))
+            .and_then(|n| n.parse::<u64>().ok());
+        ids.extend(id);
+    }
+    ids.sort_unstable();
+    Ok(ids)
+}
+
+fn parse_record(line: &[u8], segment: u64, sequence: u64) -> Result<WormRecord, WormError> {
+    let record: WormRecord = serde_json::from_slice(line).map_err(|e| WormError::Corrupted {
+        segment,
+        sequence,
+        reason: e.to_string(),
+    })?;
+    if record.compute_hash()? != record.hash {
+        return Err(WormError::Corrupted { segment, sequence: record.sequence, reason: "hash não confere".to_string() });
+    }
+    Ok(record)
+}
+
+fn check_link(record: &WormRecord, segment: u64, expected_sequence: u64, previous_hash: [u8; 32]) -> Result<(), WormError> {
+    if record.sequence != expected_sequence {
+        return Err(WormError::Corrupted {
+            segment,
+            sequence: record.sequence,
+            reason: format!("sequência esperada {}", expected_sequence),
+        });
+    }
+    if record.previous_hash != previous_hash {
+        return Err(WormError::Corrupted { segment, sequence: record.sequence, reason: "cadeia de hashes rompida".to_string() });
+    }
+    Ok(())
+}
+
+fn read_tombstones(root: &Path) -> Result<Vec<Tombstone>, WormError> {
+    let path = root.join(RETENTION_LOG);
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(WormError::Io(path, e)),
+    };
+    bytes.split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice(line).map_err(|e| WormError::Malformed(e.to_string())))
+        .collect()
+}
+
+fn append_tombstone(root: &Path, tombstone: &Tombstone) -> Result<(), WormError> {
+    let path = root.join(RETENTION_LOG);
+    let mut line = serde_json::to_vec(tombstone).map_err(|e| WormError::Malformed(e.to_string()))?;
+    line.push(b'\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)
+        .map_err(|e| WormError::Io(path.clone(), e))?;
+    file.write_all(&line)
+        .and_then(|_| file.sync_data())
+        .map_err(|e| WormError::Io(path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("sasc_worm_{}_{}", name, std::process::id()));
+        if root.exists() {
+            for entry in std::fs::read_dir(&root).unwrap() {
+                let path = entry.unwrap().path();
+                let mut permissions = std::fs::metadata(&path).unwrap().permissions();
+                #[allow(clippy::permissions_set_readonly_false)]
+                permissions.set_readonly(false);
+                std::fs::set_permissions(&path, permissions).unwrap();
+            }
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+        root
+    }
+
+    fn outcome(status: DecisionStatus) -> WormEvent {
+        WormEvent::DecisionRecorded { status, summary: "resumo".to_string(), coherence: 0.9 }
+    }
+
+    fn small_segments() -> WormPolicy {
+        WormPolicy {
+            max_records_per_segment: 2,
+            max_segment_age: Duration::from_secs(86_400),
+            retention: Duration::from_secs(3600),
+        }
+    }
+
+    #[test]
+    fn test_indexed_queries() {
+        let store = WormStore::in_memory(small_segments());
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        store.append([1; 32], "camara", WormEvent::DecisionStarted { problem_statement: "p".into(), phi: 0.7 }, t0).unwrap();
+        store.append([1; 32], "camara", outcome(DecisionStatus::Finalized), t0 + Duration::from_secs(10)).unwrap();
+        store.append([2; 32], "senado", outcome(DecisionStatus::HumanReviewRequired), t0 + Duration::from_secs(20)).unwrap();
+        // Relógio recuou: o timestamp gravado não retrocede
+        let late = store.append([3; 32], "camara", outcome(DecisionStatus::Finalized), t0).unwrap();
+        assert_eq!(late.timestamp, t0 + Duration::from_secs(20));
+        assert_eq!(store.segments(), vec![0, 1]);
+
+        let by_requestor = store.query(&HistoryQuery { requestor_id: Some("camara".into()), outcomes_only: true, ..Default::default() }).unwrap();
+        assert_eq!(by_requestor.iter().map(|r| r.decision_id[0]).collect::<Vec<_>>(), vec![1, 3]);
+
+        let by_status = store.query(&HistoryQuery { status: Some(DecisionStatus::HumanReviewRequired), ..Default::default() }).unwrap();
+        assert_eq!(by_status.len(), 1);
+        assert_eq!(by_status[0].requestor_id, "senado");
+
+        let window = store.query(&HistoryQuery {
+            start: Some(t0 + Duration::from_secs(5)),
+            end: Some(t0 + Duration::from_secs(15)),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(window.len(), 1);
+        assert_eq!(window[0].sequence, 1);
+
+        let latest = store.latest(&[1; 32], true).unwrap().unwrap();
+        assert_eq!(latest.event.status(), Some(&DecisionStatus::Finalized));
+        assert_eq!(store.query(&HistoryQuery { limit: 2, ..Default::default() }).unwrap().len(), 2);
+        assert_eq!(store.verify().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_reopen_detects_tampering() {
+        let root = temp_root("tamper");
+        let now = SystemTime::now();
+        {
+            let store = WormStore::open(&root, small_segments()).unwrap();
+            for i in 0..3u8 {
+                store.append([i; 32], "camara", outcome(DecisionStatus::Finalized), now).unwrap();
+            }
+        }
+        let reopened = WormStore::open(&root, small_segments()).unwrap();
+        assert_eq!(reopened.len(), 3);
+        let next = reopened.append([9; 32], "camara", outcome(DecisionStatus::Finalized), now).unwrap();
+        assert_eq!(next.sequence, 3);
+        assert_eq!(reopened.verify().unwrap(), 4);
+
+        // Segmento selado é somente-leitura
+        let sealed = segment_path(&root, 0);
+        assert!(std::fs::metadata(&sealed).unwrap().permissions().readonly());
+
+        // Adulteração do segmento ativo quebra o hash na reabertura
+        let active = segment_path(&root, 1);
+        let text = std::fs::read_to_string(&active).unwrap().replace("Finalized", "HardFreezeTriggered");
+        std::fs::write(&active, text).unwrap();
+        assert!(matches!(WormStore::open(&root, small_segments()), Err(WormError::Corrupted { .. })));
+        temp_root("tamper");
+    }
+
+    #[test]
+    fn test_retention_removes_only_whole_expired_segments() {
+        let root = temp_root("retention");
+        let t0 = SystemTime::now() - Duration::from_secs(7200);
+        let store = WormStore::open(&root, small_segments()).unwrap();
+        for i in 0..5u64 {
+            store.append([i as u8; 32], "camara", outcome(DecisionStatus::Finalized), t0 + Duration::from_secs(i * 1000)).unwrap();
+        }
+        // Segmentos: 0 = {0, 1}, 1 = {2, 3}, 2 = {4} (ativo)
+        let now = t0 + Duration::from_secs(3600 + 2500);
+        assert_eq!(store.enforce_retention(now).unwrap(), vec![0]);
+        assert_eq!(store.len(), 3);
+        assert!(!store.contains(&[0; 32]));
+
+        // Segmento ativo nunca expira
+        let much_later = t0 + Duration::from_secs(100_000);
+        assert_eq!(store.enforce_retention(much_later).unwrap(), vec![1]);
+        assert_eq!(store.segments(), vec![2]);
+        drop(store);
+
+        // A cadeia continua verificável a partir do tombstone
+        let reopened = WormStore::open(&root, small_segments()).unwrap();
+        assert_eq!(reopened.verify().unwrap(), 1);
+        let next = reopened.append([7; 32], "camara", outcome(DecisionStatus::Finalized), much_later).unwrap();
+        assert_eq!(next.sequence, 5);
+        temp_root("retention");
+    }
+
+    #[test]
+    fn test_segment_left_behind_by_retention_is_removed_on_open() {
+        let root = temp_root("leftover");
+        let t0 = SystemTime::now() - Duration::from_secs(7200);
+        {
+            let store = WormStore::open(&root, small_segments()).unwrap();
+            for i in 0..3u64 {
+                store.append([i as u8; 32], "camara", outcome(DecisionStatus::Finalized), t0 + Duration::from_secs(i)).unwrap();
+            }
+        }
+        // Queda após gravar o tombstone do segmento 0, antes de removê-lo
+        let tombstone = {
+            let sealed = WormStore::open(&root, small_segments()).unwrap();
+            let inner = sealed.lock();
+            let segment = &inner.segments[&0];
+            Tombstone {
+                segment: 0,
+                first_sequence: segment.first_sequence,
+                last_sequence: segment.first_sequence + segment.records - 1,
+                last_hash: segment.last_hash,
+                last_timestamp: segment.last_timestamp,
+                deleted_at: SystemTime::now(),
+            }
+        };
+        append_tombstone(&root, &tombstone).unwrap();
+        assert!(segment_path(&root, 0).exists());
+
+        let reopened = WormStore::open(&root, small_segments()).unwrap();
+        assert!(!segment_path(&root, 0).exists());
+        assert_eq!(reopened.segments(), vec![1]);
+        assert_eq!(reopened.verify().unwrap(), 1);
+        let next = reopened.append([9; 32], "camara", outcome(DecisionStatus::Finalized), SystemTime::now()).unwrap();
+        assert_eq!(next.sequence, 3);
+        temp_root("leftover");
+    }
+
+    #[test]
+    fn test_torn_tail_is_quarantined_on_open() {
+        let root = temp_root("torn");
+        let now = SystemTime::now();
+        {
+            let store = WormStore::open(&root, small_segments()).unwrap();
+            for i in 0..3u8 {
+                store.append([i; 32], "camara", outcome(DecisionStatus::Finalized), now).unwrap();
+            }
+        }
+        // Queda no meio da gravação: linha sem a quebra final
+        let active = segment_path(&root, 1);
+        let mut file = OpenOptions::new().append(true).open(&active).unwrap();
+        file.write_all(br#"{"sequence":3,"timesta"#).unwrap();
+        drop(file);
+
+        let reopened = WormStore::open(&root, small_segments()).unwrap();
+        assert_eq!(reopened.len(), 3);
+        let torn = std::fs::read_to_string(root.join("segment-0000000001.worm.torn")).unwrap();
+        assert!(torn.starts_with(r#"{"sequence":3"#));
+        let next = reopened.append([9; 32], "camara", outcome(DecisionStatus::Finalized), now).unwrap();
+        assert_eq!(next.sequence, 3);
+        assert_eq!(reopened.verify().unwrap(), 4);
+        drop(reopened);
+        assert_eq!(WormStore::open(&root, small_segments()).unwrap().len(), 4);
+        temp_root("torn");
+    }
+
+    #[test]
+    fn test_segment_ids_are_not_reused_after_retention() {
+        let root = temp_root("reuse");
+        let t0 = SystemTime::now() - Duration::from_secs(7200);
+        {
+            let store = WormStore::open(&root, small_segments()).unwrap();
+            for i in 0..2u8 {
+                store.append([i; 32], "camara", outcome(DecisionStatus::Finalized), t0).unwrap();
+            }
+        }
+        // Segmento selado fora do processo: todos os segmentos podem expirar
+        let path = segment_path(&root, 0);
+        let mut permissions = std::fs::metadata(&path).unwrap().permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(&path, permissions).unwrap();
+
+        let store = WormStore::open(&root, small_segments()).unwrap();
+        assert_eq!(store.enforce_retention(SystemTime::now()).unwrap(), vec![0]);
+        assert!(store.segments().is_empty());
+        let next = store.append([5; 32], "camara", outcome(DecisionStatus::Finalized), SystemTime::now()).unwrap();
+        assert_eq!(next.sequence, 2);
+        assert_eq!(store.segments(), vec![1]);
+        drop(store);
+
+        let reopened = WormStore::open(&root, small_segments()).unwrap();
+        assert_eq!(reopened.verify().unwrap(), 1);
+        temp_root("reuse");
+    }
+}
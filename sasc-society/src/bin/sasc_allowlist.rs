@@ -0,0 +1,94 @@
+//! Administração offline da allowlist de clientes
+//!
+//! Monta a próxima versão do documento, assina com a chave Prince do HSM do
+//! operador (backend via `SASC_HSM_BACKEND`, ver `HsmManager::new`) e grava o
+//! arquivo, que depois é copiado para o servidor. O servidor nunca vê a
+//! chave privada: apenas verifica a assinatura ao recarregar.
+
+use clap::{Parser, Subcommand};
+use pqcrypto_dilithium::dilithium5::PublicKey;
+use pqcrypto_traits::sign::PublicKey as _;
+
+use sasc_society::grpc::allowlist::{AllowlistDocument, AllowlistEntry, SignedAllowlist, unix_now};
+use sasc_society::hsm_signer::HsmManager;
+
+#[derive(Parser)]
+#[command(name = "sasc_allowlist")]
+struct Args {
+    /// Allowlist assinada atual (inexistente = versão 0, vazia)
+    #[arg(long, default_value = "allowlist.json")]
+    file: String,
+
+    /// Destino da nova versão; por padrão sobrescreve `--file`
+    #[arg(long)]
+    out: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Inclui uma chave de cliente
+    Add {
+        /// Chave pública Dilithium5 do cliente, em hexadecimal
+        #[arg(long)]
+        public_key: String,
+        #[arg(long)]
+        label: String,
+        /// Expiração em segundos UNIX
+        #[arg(long)]
+        expires_at: Option<u64>,
+    },
+    /// Revoga uma chave a partir de agora
+    Revoke {
+        #[arg(long)]
+        public_key: String,
+    },
+    /// Lista as entradas da versão atual
+    Show,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let hsm = HsmManager::new()?;
+    let current = SignedAllowlist::read(&args.file, &hsm.prince_pubkey)?
+        .map(|signed| signed.document)
+        .unwrap_or_default();
+    let now = unix_now();
+
+    let next = match args.command {
+        Command::Add { public_key, label, expires_at } => {
+            current.with_entry(AllowlistEntry::new(&parse_key(&public_key)?, &label, now, expires_at), now)?
+        }
+        Command::Revoke { public_key } => current.with_revocation(&parse_key(&public_key)?, now)?,
+        Command::Show => {
+            show(&current);
+            return Ok(());
+        }
+    };
+
+    let signed = SignedAllowlist::sign_with(next, hsm.signer().as_ref())?;
+    signed.verify(&hsm.prince_pubkey)?;
+    let out = args.out.unwrap_or(args.file);
+    signed.write(&out)?;
+    println!("Allowlist versão {} gravada em {}", signed.document.version, out);
+    Ok(())
+}
+
+fn parse_key(hex_key: &str) -> Result<PublicKey, Box<dyn std::error::Error>> {
+    let bytes = hex::decode(hex_key.trim_start_matches("0x"))?;
+    PublicKey::from_bytes(&bytes).map_err(|_| "Chave Dilithium5 inválida".into())
+}
+
+fn show(document: &AllowlistDocument) {
+    println!("Versão {} (emitida em {})", document.version, document.issued_at);
+    for entry in &document.entries {
+        let state = match (entry.revoked_at, entry.expires_at) {
+            (Some(at), _) => format!("revogada em {}", at),
+            (None, Some(at)) => format!("expira em {}", at),
+            (None, None) => "vigente".to_string(),
+        };
+        println!("{}  {}…  {}", entry.label, &entry.public_key[..16.min(entry.public_key.len())], state);
+    }
+}
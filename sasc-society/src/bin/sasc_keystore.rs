@@ -0,0 +1,58 @@
+//! Provisionamento do keystore em arquivo (backend `software` do HSM)
+//!
+//! Gera o par Dilithium5 da chave Prince e grava o keystore cifrado que o
+//! servidor abre via `SASC_KEYSTORE_PATH`. A passphrase vem de
+//! `SASC_KEYSTORE_PASSPHRASE`, a mesma variável lida por `HsmManager::new`,
+//! para nunca passar pela linha de comando. A chave pública impressa é a que
+//! se informa ao servidor em `--prince-pubkey`.
+
+use clap::{Parser, Subcommand};
+use pqcrypto_traits::sign::PublicKey as _;
+use zeroize::Zeroizing;
+
+use sasc_society::hsm_signer::{HsmSigner, KdfParams, SoftwareKeystore};
+
+#[derive(Parser)]
+#[command(name = "sasc_keystore")]
+struct Args {
+    /// Arquivo do keystore
+    #[arg(long, default_value = "prince.keystore.json")]
+    path: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Gera um par novo; recusa sobrescrever um keystore existente
+    Create {
+        #[arg(long, default_value = "prince")]
+        label: String,
+    },
+    /// Decifra o keystore e mostra rótulo e chave pública
+    Show,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let passphrase = Zeroizing::new(
+        std::env::var("SASC_KEYSTORE_PASSPHRASE").map_err(|_| "SASC_KEYSTORE_PASSPHRASE não definida")?,
+    );
+
+    let keystore = match args.command {
+        Command::Create { label } => {
+            if std::path::Path::new(&args.path).exists() {
+                return Err(format!("{} já existe", args.path).into());
+            }
+            let keystore = SoftwareKeystore::create(&args.path, &label, passphrase.as_bytes(), KdfParams::recommended())?;
+            println!("Keystore gravado em {}", args.path);
+            keystore
+        }
+        Command::Show => SoftwareKeystore::open(&args.path, passphrase.as_bytes())?,
+    };
+
+    println!("Rótulo: {}", keystore.label());
+    println!("Chave pública: {}", hex::encode(keystore.public_key()?.as_bytes()));
+    Ok(())
+}
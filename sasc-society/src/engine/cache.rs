@@ -0,0 +1,772 @@
+//! Cache de decisões por similaridade
+//! Índice HNSW sobre TF de n-gramas com hashing; candidatos reordenados por
+//! TF-IDF com as estatísticas do momento da consulta. Persistido em disco
+//! entre reinícios.
+
+use crate::engine::dialectic::SynthesizedDecision;
+use serde::{Serialize, Deserialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use thiserror::Error;
+
+pub const EMBEDDING_DIMENSIONS: usize = 768;
+
+/// Candidatos devolvidos pelo índice antes do filtro de escopo e da reordenação
+const CANDIDATES_PER_LOOKUP: usize = 16;
+
+const STOPWORDS: &[&str] = &[
+    "as", "os", "de", "da", "do", "das", "dos", "em", "no", "na", "nos", "nas", "um", "uma",
+    "para", "por", "com", "que", "se", "ao", "aos", "às", "ou",
+    "the", "of", "and", "to", "in", "for", "on", "with", "is", "are", "be", "by", "an", "or",
+];
+
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("Falha de E/S em {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("Cache persistido malformado: {0}")]
+    Malformed(String),
+}
+
+// ===================== EMBEDDINGS =====================
+
+/// Palavras normalizadas: minúsculas, sem stopwords e sem o `s` final de plural
+fn tokens(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| token.chars().count() > 1 && !STOPWORDS.contains(token))
+        .map(|token| match token.strip_suffix('s') {
+            Some(singular) if singular.chars().count() > 3 => singular.to_string(),
+            _ => token.to_string(),
+        })
+        .collect()
+}
+
+/// Atributos com hashing: palavras, bigramas de palavras e trigramas de
+/// caracteres (robustos a flexões). Cada atributo cai num balde com sinal,
+/// de modo que colisões tendem a se cancelar.
+pub fn hashed_features(text: &str) -> HashMap<usize, f64> {
+    let words = tokens(text);
+    let mut features = HashMap::new();
+    let mut add = |feature: String, weight: f64| {
+        let hash = blake3::hash(feature.as_bytes());
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&hash.as_bytes()[..8]);
+        let value = u64::from_le_bytes(head);
+        let sign = if value >> 63 == 0 { 1.0 } else { -1.0 };
+        *features.entry((value % EMBEDDING_DIMENSIONS as u64) as usize).or_insert(0.0) += sign * weight;
+    };
+
+    for word in &words {
+        add(format!("w:{}", word), 1.0);
+        let padded: Vec<char> = format!("#{}#", word).chars().collect();
+        for gram in padded.windows(3) {
+            add(format!("c:{}", gram.iter().collect::<String>()), 0.25);
+        }
+    }
+    for pair in words.windows(2) {
+        add(format!("b:{} {}", pair[0], pair[1]), 1.0);
+    }
+    features.retain(|_, weight| *weight != 0.0);
+    features
+}
+
+/// Frequência de documentos por balde, sobre as decisões em cache
+#[derive(Debug, Clone, Default)]
+pub struct TermStatistics {
+    documents: u64,
+    document_frequency: HashMap<usize, u64>,
+}
+
+impl TermStatistics {
+    pub fn add(&mut self, features: &HashMap<usize, f64>) {
+        self.documents += 1;
+        for bucket in features.keys() {
+            *self.document_frequency.entry(*bucket).or_insert(0) += 1;
+        }
+    }
+
+    pub fn remove(&mut self, features: &HashMap<usize, f64>) {
+        self.documents = self.documents.saturating_sub(1);
+        for bucket in features.keys() {
+            if let Some(count) = self.document_frequency.get_mut(bucket) {
+                *count -= 1;
+                if *count == 0 {
+                    self.document_frequency.remove(bucket);
+                }
+            }
+        }
+    }
+
+    /// IDF suavizado: ln((1 + N) / (1 + df)) + 1
+    pub fn idf(&self, bucket: usize) -> f64 {
+        let df = self.document_frequency.get(&bucket).copied().unwrap_or(0);
+        ((1.0 + self.documents as f64) / (1.0 + df as f64)).ln() + 1.0
+    }
+
+    /// Vetor TF-IDF (TF sublinear) normalizado em L2
+    pub fn embed(&self, features: &HashMap<usize, f64>) -> Vec<f64> {
+        weighted(features, |bucket| self.idf(bucket))
+    }
+}
+
+/// Vetor só de TF sublinear, normalizado em L2: não depende do corpus e por
+/// isso não envelhece no índice conforme o cache muda
+pub fn term_frequency(features: &HashMap<usize, f64>) -> Vec<f64> {
+    weighted(features, |_| 1.0)
+}
+
+fn weighted(features: &HashMap<usize, f64>, weight_of: impl Fn(usize) -> f64) -> Vec<f64> {
+    let mut vector = vec![0.0; EMBEDDING_DIMENSIONS];
+    for (bucket, weight) in features {
+        let tf = if weight.abs() > 1.0 { weight.signum() * (1.0 + weight.abs().ln()) } else { *weight };
+        vector[*bucket] = tf * weight_of(*bucket);
+    }
+    let norm = vector.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+// ===================== ÍNDICE HNSW =====================
+
+/// Distância de cosseno entre vetores normalizados
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    1.0 - a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f64,
+    slot: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance).then(self.slot.cmp(&other.slot))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HnswNode {
+    id: [u8; 32],
+    vector: Vec<f64>,
+    /// Vizinhos por camada; o nível do nó é `neighbors.len() - 1`
+    neighbors: Vec<Vec<usize>>,
+}
+
+/// Hierarchical Navigable Small World: grafo em camadas para busca
+/// aproximada de vizinhos mais próximos. O nível de cada nó deriva do seu
+/// id, o que torna a reconstrução após o carregamento determinística.
+#[derive(Debug, Clone)]
+pub struct HnswIndex {
+    nodes: Vec<Option<HnswNode>>,
+    free: Vec<usize>,
+    slots: HashMap<[u8; 32], usize>,
+    entry: Option<usize>,
+    m: usize,
+    ef_construction: usize,
+    ef_search: usize,
+}
+
+impl Default for HnswIndex {
+    fn default() -> Self {
+        Self::new(12, 64, 48)
+    }
+}
+
+impl HnswIndex {
+    pub fn new(m: usize, ef_construction: usize, ef_search: usize) -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            slots: HashMap::new(),
+            entry: None,
+            m: m.max(2),
+            ef_construction: ef_construction.max(1),
+            ef_search: ef_search.max(1),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn contains(&self, id: &[u8; 32]) -> bool {
+        self.slots.contains_key(id)
+    }
+
+    pub fn insert(&mut self, id: [u8; 32], vector: Vec<f64>) {
+        self.remove(&id);
+        let level = self.level_for(&id);
+        let node = HnswNode { id, vector, neighbors: vec![Vec::new(); level + 1] };
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.nodes[slot] = Some(node);
+                slot
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.slots.insert(id, slot);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(slot);
+            return;
+        };
+        let query = self.node(slot).vector.clone();
+        let top = self.top_level();
+
+        let mut entry_points = vec![entry];
+        for layer in (level + 1..=top).rev() {
+            entry_points = vec![self.search_layer(&query, &entry_points, 1, layer)[0].slot];
+        }
+        for layer in (0..=level.min(top)).rev() {
+            let found = self.search_layer(&query, &entry_points, self.ef_construction, layer);
+            let selected: Vec<usize> = found.iter().map(|c| c.slot).filter(|s| *s != slot).take(self.m).collect();
+            for &neighbor in &selected {
+                self.link(neighbor, slot, layer);
+            }
+            self.node_mut(slot).neighbors[layer] = selected;
+            entry_points = found.iter().map(|c| c.slot).collect();
+        }
+        if level > top {
+            self.entry = Some(slot);
+        }
+    }
+
+    /// Remove o nó e religa quem apontava para ele aos vizinhos dele
+    pub fn remove(&mut self, id: &[u8; 32]) -> bool {
+        let Some(slot) = self.slots.remove(id) else {
+            return false;
+        };
+        let removed = self.nodes[slot].take().expect("slot indexado está ocupado");
+        self.free.push(slot);
+
+        let mut orphaned = Vec::new();
+        for (other, node) in self.nodes.iter_mut().enumerate() {
+            let Some(node) = node else { continue };
+            for (layer, neighbors) in node.neighbors.iter_mut().enumerate() {
+                if let Some(position) = neighbors.iter().position(|s| *s == slot) {
+                    neighbors.swap_remove(position);
+                    orphaned.push((other, layer));
+                }
+            }
+        }
+        for (other, layer) in orphaned {
+            for &candidate in removed.neighbors.get(layer).into_iter().flatten() {
+                if candidate != other {
+                    self.link(other, candidate, layer);
+                }
+            }
+        }
+
+        if self.entry == Some(slot) {
+            self.entry = self.nodes.iter().enumerate()
+                .filter_map(|(slot, node)| node.as_ref().map(|n| (n.neighbors.len(), Reverse(slot))))
+                .max()
+                .map(|(_, Reverse(slot))| slot);
+        }
+        true
+    }
+
+    /// Até `k` ids mais próximos de `query`, com a similaridade de cosseno
+    pub fn search(&self, query: &[f64], k: usize) -> Vec<([u8; 32], f64)> {
+        let Some(entry) = self.entry else {
+            return Vec::new();
+        };
+        let mut entry_points = vec![entry];
+        for layer in (1..=self.top_level()).rev() {
+            entry_points = vec![self.search_layer(query, &entry_points, 1, layer)[0].slot];
+        }
+        self.search_layer(query, &entry_points, self.ef_search.max(k), 0)
+            .into_iter()
+            .take(k)
+            .map(|c| (self.node(c.slot).id, 1.0 - c.distance))
+            .collect()
+    }
+
+    fn level_for(&self, id: &[u8; 32]) -> usize {
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&id[..8]);
+        let uniform = (u64::from_le_bytes(head) as f64 + 1.0) / (u64::MAX as f64 + 2.0);
+        let level = -uniform.ln() / (self.m as f64).ln();
+        (level.floor() as usize).min(16)
+    }
+
+    fn max_neighbors(&self, layer: usize) -> usize {
+        if layer == 0 { 2 * self.m } else { self.m }
+    }
+
+    fn top_level(&self) -> usize {
+        self.entry.map_or(0, |entry| self.node(entry).neighbors.len() - 1)
+    }
+
+    fn node(&self, slot: usize) -> &HnswNode {
+        self.nodes[slot].as_ref().expect("slot indexado está ocupado")
+    }
+
+    fn node_mut(&mut self, slot: usize) -> &mut HnswNode {
+        self.nodes[slot].as_mut().expect("slot indexado está ocupado")
+    }
+
+    /// Aresta `from → to` na camada, mantendo só os vizinhos mais próximos
+    fn link(&mut self, from: usize, to: usize, layer: usize) {
+        let mut neighbors = std::mem::take(&mut self.node_mut(from).neighbors[layer]);
+        if !neighbors.contains(&to) {
+            neighbors.push(to);
+        }
+        if neighbors.len() > self.max_neighbors(layer) {
+            let base = &self.node(from).vector;
+            neighbors.sort_by(|a, b| {
+                distance(base, &self.node(*a).vector).total_cmp(&distance(base, &self.node(*b).vector))
+            });
+            neighbors.truncate(self.max_neighbors(layer));
+        }
+        self.node_mut(from).neighbors[layer] = neighbors;
+    }
+
+    /// Busca gulosa com lista dinâmica de `ef` candidatos, em ordem crescente de distância
+    fn search_layer(&self, query: &[f64], entry_points: &[usize], ef: usize, layer: usize) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = entry_points.iter().copied().collect();
+        let mut frontier = BinaryHeap::new();
+        let mut best = BinaryHeap::new();
+        for &slot in entry_points {
+            let candidate = Candidate { distance: distance(query, &self.node(slot).vector), slot };
+            frontier.push(Reverse(candidate));
+            best.push(candidate);
+        }
+        while best.len() > ef {
+            best.pop();
+        }
+
+        while let Some(Reverse(current)) = frontier.pop() {
+            if best.len() >= ef && best.peek().is_some_and(|worst: &Candidate| current.distance > worst.distance) {
+                break;
+            }
+            let neighbors = self.node(current.slot).neighbors.get(layer).map(Vec::as_slice).unwrap_or(&[]);
+            for &neighbor in neighbors {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = Candidate { distance: distance(query, &self.node(neighbor).vector), slot: neighbor };
+                if best.len() < ef || best.peek().is_some_and(|worst| candidate.distance < worst.distance) {
+                    frontier.push(Reverse(candidate));
+                    best.push(candidate);
+                    if best.len() > ef {
+                        best.pop();
+                    }
+                }
+            }
+        }
+        best.into_sorted_vec()
+    }
+}
+
+// ===================== CACHE DE DECISÕES =====================
+
+#[derive(Debug, Clone)]
+pub struct CachePolicy {
+    pub max_size: usize,
+    /// Validade de cada decisão em cache; 0 = sem expiração
+    pub ttl_seconds: u64,
+    pub similarity_threshold: f64,
+    pub enable_prefetch: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedDecision {
+    pub original_request_hash: [u8; 32],
+    /// Texto embutido: enunciado e restrições
+    pub problem_text: String,
+    /// Resumo do contexto da decisão (jurisdição, contexto constitucional,
+    /// partes interessadas, risco); só há acerto com escopo idêntico.
+    /// Entradas gravadas sem escopo nunca são reaproveitadas.
+    #[serde(default)]
+    pub scope: String,
+    pub decision: SynthesizedDecision,
+    pub created_at: SystemTime,
+    pub last_accessed: SystemTime,
+    pub access_count: u64,
+    /// Relógio lógico do último acesso (ordem LRU)
+    last_used: u64,
+}
+
+/// Formato do arquivo persistido; atributos, estatísticas e índice são reconstruídos
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheSnapshot {
+    clock: u64,
+    entries: Vec<CachedDecision>,
+}
+
+/// Decisões finalizadas indexadas pelo problema que as originou
+#[derive(Debug, Clone)]
+pub struct DecisionCache {
+    entries: HashMap<[u8; 32], CachedDecision>,
+    /// Atributos brutos de cada entrada, para aplicar o IDF na consulta
+    features: HashMap<[u8; 32], HashMap<usize, f64>>,
+    /// Indexado por TF puro; o IDF entra só na reordenação dos candidatos
+    index: HnswIndex,
+    statistics: TermStatistics,
+    policy: CachePolicy,
+    path: Option<PathBuf>,
+    clock: u64,
+}
+
+impl DecisionCache {
+    /// Cache vazio; com `path`, cada inserção regrava o arquivo
+    pub fn new(policy: CachePolicy, path: Option<PathBuf>) -> Self {
+        Self {
+            entries: HashMap::new(),
+            features: HashMap::new(),
+            index: HnswIndex::default(),
+            statistics: TermStatistics::default(),
+            policy,
+            path,
+            clock: 0,
+        }
+    }
+
+    /// Carrega o cache persistido em `path`, descartando entradas expiradas.
+    /// Arquivo inexistente = cache vazio. Arquivo ilegível é movido para
+    /// `<path>.corrupt-<segundos UNIX>` antes de começar vazio, para que a
+    /// próxima gravação não destrua a evidência.
+    pub fn open(policy: CachePolicy, path: impl AsRef<Path>) -> Result<Self, CacheError> {
+        let path = path.as_ref().to_path_buf();
+        let mut cache = Self::new(policy, Some(path.clone()));
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(cache),
+            Err(e) => return Err(CacheError::Io(path, e)),
+        };
+        let snapshot: CacheSnapshot = match serde_json::from_slice(&bytes) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                let quarantine = quarantine_path(&path, SystemTime::now());
+                std::fs::rename(&path, &quarantine).map_err(|io| CacheError::Io(path.clone(), io))?;
+                log::warn!("Cache de decisões malformado ({}); movido para {}", e, quarantine.display());
+                return Ok(cache);
+            }
+        };
+
+        let now = SystemTime::now();
+        cache.clock = snapshot.clock;
+        for entry in snapshot.entries {
+            if !cache.is_expired(&entry, now) {
+                cache.add(entry);
+            }
+        }
+        Ok(cache)
+    }
+
+    pub fn policy(&self) -> &CachePolicy {
+        &self.policy
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, id: &[u8; 32]) -> Option<&CachedDecision> {
+        self.entries.get(id)
+    }
+
+    /// Embedding TF-IDF de `text` com as estatísticas atuais do cache
+    pub fn embed(&self, text: &str) -> Vec<f64> {
+        self.statistics.embed(&hashed_features(text))
+    }
+
+    /// Decisão mais similar, do mesmo escopo, acima do limiar da política.
+    /// O acesso só atualiza a memória; chame `persist` para gravá-lo.
+    pub fn find_similar(&mut self, problem_text: &str, scope: &str, now: SystemTime) -> Option<(CachedDecision, f64)> {
+        self.purge_expired(now);
+        let features = hashed_features(problem_text);
+        let query = self.statistics.embed(&features);
+        let (id, similarity) = self.index.search(&term_frequency(&features), CANDIDATES_PER_LOOKUP)
+            .into_iter()
+            .filter(|(id, _)| self.entries.get(id).is_some_and(|e| e.scope == scope))
+            .map(|(id, _)| (id, 1.0 - distance(&query, &self.statistics.embed(&self.features[&id]))))
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+        if similarity < self.policy.similarity_threshold {
+            return None;
+        }
+
+        self.clock += 1;
+        let entry = self.entries.get_mut(&id)?;
+        entry.last_accessed = now;
+        entry.access_count += 1;
+        entry.last_used = self.clock;
+        Some((entry.clone(), similarity))
+    }
+
+    /// Insere (ou substitui) uma decisão, aplica a política de tamanho e persiste
+    pub fn insert(
+        &mut self,
+        id: [u8; 32],
+        problem_text: &str,
+        scope: &str,
+        decision: SynthesizedDecision,
+        now: SystemTime,
+    ) -> Result<(), CacheError> {
+        if self.policy.max_size == 0 {
+            return Ok(());
+        }
+        self.remove(&id);
+        self.clock += 1;
+        self.add(CachedDecision {
+            original_request_hash: id,
+            problem_text: problem_text.to_string(),
+            scope: scope.to_string(),
+            decision,
+            created_at: now,
+            last_accessed: now,
+            access_count: 0,
+            last_used: self.clock,
+        });
+
+        self.purge_expired(now);
+        while self.entries.len() > self.policy.max_size {
+            self.evict_least_used();
+        }
+        self.persist()
+    }
+
+    /// Remove entradas com idade acima do TTL
+    pub fn purge_expired(&mut self, now: SystemTime) -> usize {
+        let expired: Vec<[u8; 32]> = self.entries.values()
+            .filter(|entry| self.is_expired(entry, now))
+            .map(|entry| entry.original_request_hash)
+            .collect();
+        for id in &expired {
+            self.remove(id);
+        }
+        expired.len()
+    }
+
+    /// Remove a entrada acessada há mais tempo
+    pub fn evict_least_used(&mut self) -> Option<[u8; 32]> {
+        let id = self.entries.values().min_by_key(|entry| entry.last_used)?.original_request_hash;
+        self.remove(&id);
+        Some(id)
+    }
+
+    /// Grava o cache de forma atômica (tmp + rename), se houver caminho
+    pub fn persist(&self) -> Result<(), CacheError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let snapshot = CacheSnapshot {
+            clock: self.clock,
+            entries: self.entries.values().cloned().collect(),
+        };
+        let json = serde_json::to_vec(&snapshot).map_err(|e| CacheError::Malformed(e.to_string()))?;
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        std::fs::write(&tmp, &json)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| CacheError::Io(path.clone(), e))
+    }
+
+    fn add(&mut self, entry: CachedDecision) {
+        let id = entry.original_request_hash;
+        let features = hashed_features(&entry.problem_text);
+        self.statistics.add(&features);
+        self.index.insert(id, term_frequency(&features));
+        self.features.insert(id, features);
+        self.entries.insert(id, entry);
+    }
+
+    fn remove(&mut self, id: &[u8; 32]) {
+        if self.entries.remove(id).is_some() {
+            if let Some(features) = self.features.remove(id) {
+                self.statistics.remove(&features);
+            }
+            self.index.remove(id);
+        }
+    }
+
+    fn is_expired(&self, entry: &CachedDecision, now: SystemTime) -> bool {
+        self.policy.ttl_seconds > 0
+            && now.duration_since(entry.created_at).is_ok_and(|age| age.as_secs() >= self.policy.ttl_seconds)
+    }
+}
+
+fn quarantine_path(path: &Path, now: SystemTime) -> PathBuf {
+    let seconds = now.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_secs();
+    let mut quarantine = path.as_os_str().to_owned();
+    quarantine.push(format!(".corrupt-{}", seconds));
+    PathBuf::from(quarantine)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use std::time::Duration;
+
+    fn policy(max_size: usize) -> CachePolicy {
+        CachePolicy { max_size, ttl_seconds: 3600, similarity_threshold: 0.8, enable_prefetch: false }
+    }
+
+    fn decision(text: &str) -> SynthesizedDecision {
+        SynthesizedDecision {
+            decision_text: text.to_string(),
+            coherence_score: 1.0,
+            supporting_arguments: Vec::new(),
+            counter_arguments: Vec::new(),
+            consensus_level: 1.0,
+        }
+    }
+
+    fn similarity(a: &str, b: &str) -> f64 {
+        let statistics = TermStatistics::default();
+        1.0 - distance(&statistics.embed(&hashed_features(a)), &statistics.embed(&hashed_features(b)))
+    }
+
+    #[test]
+    fn test_paraphrase_is_closer_than_unrelated_text() {
+        let original = "Reajuste da tarifa de energia elétrica residencial no estado";
+        let paraphrase = "Reajuste das tarifas de energia elétrica residenciais do estado";
+        let unrelated = "Proteção de nascentes na bacia do rio São Francisco";
+        assert!((similarity(original, original) - 1.0).abs() < 1e-9);
+        assert!(similarity(original, paraphrase) > 0.5);
+        assert!(similarity(original, paraphrase) > similarity(original, unrelated) + 0.3);
+    }
+
+    #[test]
+    fn test_hnsw_matches_exhaustive_search() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut index = HnswIndex::default();
+        let mut vectors = Vec::new();
+        for i in 0..300u32 {
+            let raw: Vec<f64> = (0..16).map(|_| rng.gen_range(-1.0..1.0)).collect();
+            let norm = raw.iter().map(|v| v * v).sum::<f64>().sqrt();
+            let vector: Vec<f64> = raw.iter().map(|v| v / norm).collect();
+            let id = *blake3::hash(&i.to_le_bytes()).as_bytes();
+            index.insert(id, vector.clone());
+            vectors.push((id, vector));
+        }
+        for (id, _) in vectors.iter().step_by(3) {
+            assert!(index.remove(id));
+        }
+        let live: Vec<_> = vectors.iter().enumerate().filter(|(i, _)| i % 3 != 0).map(|(_, v)| v).collect();
+        assert_eq!(index.len(), live.len());
+
+        let mut hits = 0;
+        for (_, query) in &live {
+            let exact = live.iter()
+                .min_by(|a, b| distance(query, &a.1).total_cmp(&distance(query, &b.1)))
+                .unwrap().0;
+            hits += usize::from(index.search(query, 1)[0].0 == exact);
+        }
+        assert!(hits as f64 / live.len() as f64 > 0.95, "recall {}/{}", hits, live.len());
+    }
+
+    #[test]
+    fn test_ttl_lru_and_scope() {
+        let mut cache = DecisionCache::new(policy(2), None);
+        let now = SystemTime::now();
+        cache.insert([1; 32], "Reajuste da tarifa de energia elétrica", "BR", decision("a"), now).unwrap();
+        cache.insert([2; 32], "Proteção de nascentes na bacia hidrográfica", "BR", decision("b"), now).unwrap();
+
+        assert!(cache.find_similar("Reajuste da tarifa de energia elétrica", "PT", now).is_none());
+        let (hit, score) = cache.find_similar("Reajuste na tarifa de energia elétrica", "BR", now).unwrap();
+        assert_eq!(hit.decision.decision_text, "a");
+        assert!(score >= 0.8);
+
+        // [2] é o menos usado e sai quando o limite é excedido
+        cache.insert([3; 32], "Licitação de ônibus elétricos municipais", "BR", decision("c"), now).unwrap();
+        assert!(cache.get(&[2; 32]).is_none());
+        assert_eq!(cache.len(), 2);
+
+        let later = now + Duration::from_secs(3600);
+        assert!(cache.find_similar("Reajuste da tarifa de energia elétrica", "BR", later).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_persists_across_restarts() {
+        let path = std::env::temp_dir().join(format!("sasc_decision_cache_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let now = SystemTime::now();
+
+        let mut cache = DecisionCache::open(policy(10), &path).unwrap();
+        cache.insert([1; 32], "Reajuste da tarifa de energia elétrica", "BR", decision("a"), now).unwrap();
+        cache.insert([2; 32], "Proteção de nascentes na bacia hidrográfica", "BR", decision("b"), now).unwrap();
+        drop(cache);
+
+        let mut reopened = DecisionCache::open(policy(10), &path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        let (hit, _) = reopened.find_similar("Proteção das nascentes da bacia hidrográfica", "BR", now).unwrap();
+        assert_eq!(hit.original_request_hash, [2; 32]);
+
+        // O acesso registrado na consulta sobrevive ao reinício
+        reopened.persist().unwrap();
+        drop(reopened);
+        let reopened = DecisionCache::open(policy(10), &path).unwrap();
+        assert_eq!(reopened.get(&[2; 32]).unwrap().access_count, 1);
+        assert_eq!(reopened.get(&[1; 32]).unwrap().access_count, 0);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_file_is_quarantined() {
+        let dir = std::env::temp_dir().join(format!("sasc_cache_corrupt_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cache.json");
+        std::fs::write(&path, b"{").unwrap();
+
+        let mut cache = DecisionCache::open(policy(10), &path).unwrap();
+        assert!(cache.is_empty());
+        cache.insert([1; 32], "Reajuste da tarifa de energia elétrica", "BR", decision("a"), SystemTime::now()).unwrap();
+
+        let quarantined: Vec<_> = std::fs::read_dir(&dir).unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|p| p.to_string_lossy().contains(".corrupt-"))
+            .collect();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(std::fs::read(&quarantined[0]).unwrap(), b"{");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_similarity_uses_current_statistics() {
+        let mut cache = DecisionCache::new(CachePolicy { similarity_threshold: 0.3, ..policy(100) }, None);
+        let now = SystemTime::now();
+        cache.insert([0; 32], "Reajuste da tarifa de energia elétrica residencial", "BR", decision("a"), now).unwrap();
+        // Inserções posteriores tornam "tarifa" e "energia" termos comuns
+        for i in 1..40u8 {
+            let text = format!("Tarifa de energia do distrito {}", i);
+            cache.insert([i; 32], &text, "BR", decision("x"), now).unwrap();
+        }
+
+        let query = "Reajuste das tarifas de energia elétrica residenciais";
+        let expected = 1.0 - distance(&cache.embed(query), &cache.embed("Reajuste da tarifa de energia elétrica residencial"));
+        let (hit, score) = cache.find_similar(query, "BR", now).unwrap();
+        assert_eq!(hit.original_request_hash, [0; 32]);
+        assert!((score - expected).abs() < 1e-9, "{} != {}", score, expected);
+    }
+}
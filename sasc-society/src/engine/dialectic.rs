@@ -1,8 +1,12 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::SystemTime;
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use tokio::sync::Mutex;
 use crate::engine::diversity::PerspectiveDiversityEngine;
+use crate::engine::reasoner::{Argument, PersonaReasoner, RoundContext, RuleBasedReasoner, Stance};
+use crate::engine::AUTONOMOUS_DECISION_THRESHOLD;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SynthesisContext {
@@ -33,6 +37,13 @@ pub struct SynthesizedDecision {
 pub struct DialecticMetrics {
     pub coherence_score: f64,
     pub consensus_reached: bool,
+    /// Rodada concluída (1 = primeira tese)
+    pub round: u32,
+    /// Fração das personas que apoiam a tese
+    pub support_share: f64,
+    /// Personas que mudaram de posição desde a rodada anterior
+    pub stance_changes: usize,
+    pub coherence_delta: f64,
 }
 
 #[derive(Debug, Error)]
@@ -43,10 +54,43 @@ pub enum SynthesisError {
     HumanEscalonationRequired(f64, f64),
 }
 
+/// Estado de uma síntese em andamento
+#[derive(Debug, Clone)]
+struct SessionState {
+    session: SynthesisSession,
+    /// Sessão de diversidade de onde vêm as personas
+    diversity_session: [u8; 32],
+    /// Condições já incorporadas à tese
+    conditions: Vec<String>,
+    /// Condições contestadas por outra persona; ficam fora da tese
+    disputed: Vec<String>,
+    previous: Vec<Argument>,
+    round: u32,
+    coherence: f64,
+}
+
+impl SessionState {
+    /// Tese vigente: a proposta original acrescida das condições acumuladas
+    fn thesis(&self) -> String {
+        if self.conditions.is_empty() {
+            format!("Adotar: {}", self.session.context.problem_statement)
+        } else {
+            format!("Adotar: {} — condicionado a: {}", self.session.context.problem_statement, self.conditions.join("; "))
+        }
+    }
+}
+
+/// Ciclo tese → antítese → síntese. A cada rodada as personas ativas
+/// argumentam sobre a tese vigente; as condições das objeções são
+/// incorporadas à tese seguinte, salvo as que outra persona contestou, que
+/// saem da tese e não voltam. A coerência combina o apoio da rodada com a
+/// estabilidade das posições em relação à rodada anterior.
 pub struct DialecticSynthesizer {
     diversity_engine: Arc<PerspectiveDiversityEngine>,
     key: [u8; 32],
     human_callback: Box<dyn Fn(SynthesisSession) + Send + Sync>,
+    reasoner: Arc<dyn PersonaReasoner>,
+    sessions: Mutex<HashMap<[u8; 32], SessionState>>,
 }
 
 impl DialecticSynthesizer {
@@ -61,24 +105,248 @@ impl DialecticSynthesizer {
             diversity_engine,
             key: *key,
             human_callback: Box::new(human_callback),
+            reasoner: Arc::new(RuleBasedReasoner),
+            sessions: Mutex::new(HashMap::new()),
         }
     }
 
-    pub async fn begin_synthesis(&self, context: SynthesisContext) -> Result<Arc<SynthesisSession>, SynthesisError> {
-        let session = Arc::new(SynthesisSession {
-            id: blake3::hash(context.problem_statement.as_bytes()).into(),
+    /// Substitui o reasoner por regras (ex.: `LocalModelReasoner`)
+    pub fn with_reasoner(mut self, reasoner: Arc<dyn PersonaReasoner>) -> Self {
+        self.reasoner = reasoner;
+        self
+    }
+
+    /// Abre a síntese sobre as personas da sessão de diversidade `diversity_session`
+    pub async fn begin_synthesis(
+        &self,
+        diversity_session: [u8; 32],
+        context: SynthesisContext,
+    ) -> Result<Arc<SynthesisSession>, SynthesisError> {
+        let mut hasher = blake3::Hasher::new_keyed(&self.key);
+        hasher.update(&diversity_session);
+        hasher.update(context.problem_statement.as_bytes());
+
+        let session = SynthesisSession {
+            id: hasher.finalize().into(),
             created_at: SystemTime::now(),
             context,
             final_synthesis: None,
+        };
+        self.sessions.lock().await.insert(session.id, SessionState {
+            session: session.clone(),
+            diversity_session,
+            conditions: Vec::new(),
+            disputed: Vec::new(),
+            previous: Vec::new(),
+            round: 0,
+            coherence: 0.0,
         });
-        Ok(session)
+        Ok(Arc::new(session))
     }
 
-    pub async fn dialectic_cycle(&self) -> Result<DialecticMetrics, SynthesisError> {
-        // Simplified cycle
+    /// Executa uma rodada dialética
+    pub async fn dialectic_cycle(&self, session_id: &[u8; 32]) -> Result<DialecticMetrics, SynthesisError> {
+        let state = self.sessions.lock().await.get(session_id).cloned()
+            .ok_or_else(|| SynthesisError::GeneralError(format!("Sessão desconhecida: {}", hex::encode(session_id))))?;
+
+        let mut personas = self.diversity_engine.active_personas(&state.diversity_session).await;
+        if personas.is_empty() {
+            return Err(SynthesisError::GeneralError("Nenhuma persona ativa para a síntese".to_string()));
+        }
+        personas.sort_by_key(|p| p.id.0);
+
+        // Tese e antítese: cada persona argumenta sobre a tese vigente
+        let thesis = state.thesis();
+        let round = state.round + 1;
+        let mut arguments = Vec::with_capacity(personas.len());
+        for persona in &personas {
+            let argument = self.reasoner.argue(persona, RoundContext {
+                context: &state.session.context,
+                round,
+                thesis: &thesis,
+                conditions: &state.conditions,
+                previous: &state.previous,
+            }).await?;
+            self.diversity_engine.record_contribution(&state.diversity_session, persona.id).await
+                .map_err(|e| SynthesisError::GeneralError(e.to_string()))?;
+            arguments.push(argument);
+        }
+
+        let supporters = arguments.iter().filter(|a| a.stance == Stance::Support).count();
+        let support_share = supporters as f64 / arguments.len() as f64;
+        let stance_changes = arguments.iter()
+            .filter(|a| state.previous.iter().any(|p| p.persona == a.persona && p.stance != a.stance))
+            .count();
+        let stability = if state.previous.is_empty() {
+            0.0
+        } else {
+            1.0 - stance_changes as f64 / arguments.len() as f64
+        };
+        let coherence = support_share * (0.5 + 0.5 * stability);
+
+        let decision = SynthesizedDecision {
+            decision_text: thesis,
+            coherence_score: coherence,
+            supporting_arguments: arguments.iter().filter(|a| a.stance == Stance::Support).map(|a| a.claim.clone()).collect(),
+            counter_arguments: arguments.iter().filter(|a| a.stance == Stance::Oppose).map(|a| a.claim.clone()).collect(),
+            consensus_level: support_share,
+        };
+
+        // Síntese: condições contestadas saem da tese; as das novas objeções
+        // entram, se ninguém as contestou antes
+        let mut disputed = state.disputed;
+        for contested in arguments.iter().filter_map(|a| a.contested.as_ref()) {
+            if !disputed.contains(contested) {
+                disputed.push(contested.clone());
+            }
+        }
+        let mut conditions = state.conditions;
+        conditions.retain(|c| !disputed.contains(c));
+        for condition in arguments.iter().filter_map(|a| a.condition.as_ref()) {
+            if !conditions.contains(condition) && !disputed.contains(condition) {
+                conditions.push(condition.clone());
+            }
+        }
+
+        let session = {
+            let mut sessions = self.sessions.lock().await;
+            let entry = sessions.get_mut(session_id)
+                .ok_or_else(|| SynthesisError::GeneralError("Sessão encerrada durante a rodada".to_string()))?;
+            entry.session.final_synthesis = Some(decision);
+            entry.conditions = conditions;
+            entry.disputed = disputed;
+            entry.previous = arguments;
+            entry.round = round;
+            entry.coherence = coherence;
+            entry.session.clone()
+        };
+
+        if supporters == 0 {
+            (self.human_callback)(session);
+            return Err(SynthesisError::HumanEscalonationRequired(coherence, AUTONOMOUS_DECISION_THRESHOLD));
+        }
+
         Ok(DialecticMetrics {
-            coherence_score: 0.75,
-            consensus_reached: true,
+            coherence_score: coherence,
+            consensus_reached: supporters == personas.len(),
+            round,
+            support_share,
+            stance_changes,
+            coherence_delta: coherence - state.coherence,
         })
     }
+
+    /// Síntese da última rodada
+    pub async fn current_synthesis(&self, session_id: &[u8; 32]) -> Option<SynthesizedDecision> {
+        self.sessions.lock().await.get(session_id).and_then(|s| s.session.final_synthesis.clone())
+    }
+
+    /// Encerra a sessão e devolve seu estado final
+    pub async fn finish_synthesis(&self, session_id: &[u8; 32]) -> Option<SynthesisSession> {
+        self.sessions.lock().await.remove(session_id).map(|s| s.session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::{ExpertiseDomain, Persona, PersonaId, SocioEmotionalRole};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn setup(problem: &str, constraints: &[&str]) -> (DialecticSynthesizer, [u8; 32], Arc<AtomicUsize>) {
+        let engine = Arc::new(PerspectiveDiversityEngine::new(&[0u8; 32]));
+        let diversity_session = [4u8; 32];
+        engine.begin_session(diversity_session).await;
+        for (i, role) in SocioEmotionalRole::ALL.iter().enumerate() {
+            let persona = Persona {
+                id: PersonaId([i as u8; 32]),
+                role: *role,
+                expertise: vec![ExpertiseDomain::ALL[i]],
+            };
+            engine.activate(&diversity_session, persona).await.unwrap();
+        }
+
+        let escalations = Arc::new(AtomicUsize::new(0));
+        let counter = escalations.clone();
+        let synthesizer = DialecticSynthesizer::new(engine, &[1u8; 32], move |_session: SynthesisSession| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let session = synthesizer.begin_synthesis(diversity_session, SynthesisContext {
+            problem_statement: problem.to_string(),
+            constraints: constraints.iter().map(|c| c.to_string()).collect(),
+            success_criteria: vec!["coherence >= 0.72".to_string()],
+            stakeholder_keys: Vec::new(),
+        }).await.unwrap();
+        (synthesizer, session.id, escalations)
+    }
+
+    #[tokio::test]
+    async fn test_objections_are_absorbed_until_convergence() {
+        let (synthesizer, id, _) = setup(
+            "Redistribuir a energia da rede regional",
+            &["Preservar a biodiversidade local", "Cumprir a lei de licitações"],
+        ).await;
+
+        let first = synthesizer.dialectic_cycle(&id).await.unwrap();
+        assert_eq!(first.round, 1);
+        assert!((first.support_share - 0.6).abs() < 1e-12);
+        assert!(first.coherence_score < AUTONOMOUS_DECISION_THRESHOLD);
+        assert_eq!(synthesizer.current_synthesis(&id).await.unwrap().counter_arguments.len(), 2);
+
+        let second = synthesizer.dialectic_cycle(&id).await.unwrap();
+        assert_eq!(second.stance_changes, 2);
+        assert!(second.consensus_reached);
+        let decision = synthesizer.current_synthesis(&id).await.unwrap();
+        assert!(decision.decision_text.contains("biodiversidade"));
+        assert!(decision.counter_arguments.is_empty());
+
+        let third = synthesizer.dialectic_cycle(&id).await.unwrap();
+        assert_eq!(third.stance_changes, 0);
+        assert!((third.coherence_score - 1.0).abs() < 1e-12);
+        assert!(third.coherence_delta > 0.0);
+        assert!(synthesizer.finish_synthesis(&id).await.is_some());
+        assert!(synthesizer.dialectic_cycle(&id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_conditions_stay_disputed() {
+        // Tecnociência quer ampliar a capacidade; Meio Ambiente, reduzi-la
+        let (synthesizer, id, _) = setup(
+            "Reorganizar a rede regional",
+            &["Ampliar a capacidade da infraestrutura da rede", "Reduzir a capacidade instalada em área de biodiversidade"],
+        ).await;
+
+        let first = synthesizer.dialectic_cycle(&id).await.unwrap();
+        assert_eq!(synthesizer.current_synthesis(&id).await.unwrap().counter_arguments.len(), 2);
+
+        // As duas condições entram e cada uma é contestada pela outra área
+        let second = synthesizer.dialectic_cycle(&id).await.unwrap();
+        assert!(!second.consensus_reached);
+        let decision = synthesizer.current_synthesis(&id).await.unwrap();
+        assert!(decision.counter_arguments.iter().all(|c| c.contains("contraria")), "{:?}", decision.counter_arguments);
+
+        // Fora da tese, voltam a faltar e não são reincorporadas: sem convergência
+        for _ in 0..3 {
+            let metrics = synthesizer.dialectic_cycle(&id).await.unwrap();
+            assert!(!metrics.consensus_reached);
+            assert!((metrics.support_share - first.support_share).abs() < 1e-12);
+        }
+        let decision = synthesizer.current_synthesis(&id).await.unwrap();
+        assert!(!decision.decision_text.contains("condicionado"), "{}", decision.decision_text);
+        assert!(decision.coherence_score < AUTONOMOUS_DECISION_THRESHOLD);
+    }
+
+    #[tokio::test]
+    async fn test_irreconcilable_objection_stays_stable() {
+        let (synthesizer, id, escalations) = setup("Aceitar perda de vidas para estabilizar a rede", &[]).await;
+
+        let first = synthesizer.dialectic_cycle(&id).await.unwrap();
+        let second = synthesizer.dialectic_cycle(&id).await.unwrap();
+        // Ética e Empática vetam; nenhuma condição é proposta
+        assert!((first.support_share - 0.6).abs() < 1e-12);
+        assert_eq!(second.stance_changes, 0);
+        assert!((second.coherence_score - 0.6).abs() < 1e-12);
+        assert_eq!(synthesizer.current_synthesis(&id).await.unwrap().counter_arguments.len(), 2);
+        assert_eq!(escalations.load(Ordering::SeqCst), 0);
+    }
 }
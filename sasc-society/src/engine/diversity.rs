@@ -1,14 +1,20 @@
-use crate::agents::PersonaId;
+use crate::agents::{ExpertiseDomain, Persona, PersonaId, SocioEmotionalRole};
 use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::hash::Hash;
 use thiserror::Error;
+use tokio::sync::RwLock;
 
 pub const GROUPTHINK_THRESHOLD: f64 = 0.30;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiversityMetrics {
+    /// Média da equitabilidade de Shannon e do Simpson normalizado nas duas dimensões (0–1)
     pub diversity_score: f64,
     pub active_perspectives: usize,
     pub dominance_indicator: DominanceIndicator,
+    pub role_diversity: CategoryDiversity,
+    pub expertise_diversity: CategoryDiversity,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,33 +24,249 @@ pub struct DominanceIndicator {
     pub activation_share: f64,
 }
 
+/// Diversidade de uma dimensão, ponderada pelas contribuições
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CategoryDiversity {
+    /// Entropia de Shannon H = −Σ pᵢ ln pᵢ (nats)
+    pub shannon: f64,
+    /// Equitabilidade de Pielou: H / ln K, com K categorias possíveis
+    pub evenness: f64,
+    /// Gini-Simpson: 1 − Σ pᵢ²
+    pub simpson: f64,
+    /// Simpson dividido pelo máximo 1 − 1/K
+    pub normalized_simpson: f64,
+    pub categories_present: usize,
+}
+
 #[derive(Debug, Error)]
 pub enum DiversityEngineError {
     #[error("Failed to evaluate diversity: {0}")]
     EvaluationError(String),
+    #[error("Sessão de diversidade desconhecida: {0}")]
+    UnknownSession(String),
+    #[error("{0} não está ativa na sessão")]
+    InactivePersona(PersonaId),
+}
+
+/// Persona ativada e quantas vezes contribuiu na sessão
+#[derive(Debug, Clone)]
+struct ActivePersona {
+    persona: Persona,
+    contributions: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct DiversitySession {
+    personas: HashMap<PersonaId, ActivePersona>,
 }
 
 pub struct PerspectiveDiversityEngine {
     prince_pubkey: Vec<u8>,
+    /// Sessões por decisão
+    sessions: RwLock<HashMap<[u8; 32], DiversitySession>>,
 }
 
 impl PerspectiveDiversityEngine {
     pub fn new(prince_pubkey: &[u8]) -> Self {
         Self {
             prince_pubkey: prince_pubkey.to_vec(),
+            sessions: RwLock::new(HashMap::new()),
         }
     }
 
-    pub async fn evaluate_diversity(&self) -> Result<DiversityMetrics, DiversityEngineError> {
-        // Simplified Shannon entropy simulation
-        Ok(DiversityMetrics {
-            diversity_score: 0.85,
-            active_perspectives: 64,
-            dominance_indicator: DominanceIndicator {
-                is_concerning: false,
-                dominant_persona: None,
-                activation_share: 0.05,
-            },
+    /// Abre (ou reinicia) a sessão de uma decisão
+    pub async fn begin_session(&self, session_id: [u8; 32]) {
+        self.sessions.write().await.insert(session_id, DiversitySession::default());
+    }
+
+    pub async fn end_session(&self, session_id: &[u8; 32]) {
+        self.sessions.write().await.remove(session_id);
+    }
+
+    /// Ativa uma persona; a ativação conta como sua primeira contribuição.
+    /// Devolve o hash de ativação.
+    pub async fn activate(&self, session_id: &[u8; 32], persona: Persona) -> Result<[u8; 32], DiversityEngineError> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions.get_mut(session_id)
+            .ok_or_else(|| DiversityEngineError::UnknownSession(hex::encode(session_id)))?;
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(session_id);
+        hasher.update(&persona.id.0);
+        hasher.update(&self.prince_pubkey);
+
+        session.personas.entry(persona.id)
+            .or_insert(ActivePersona { persona, contributions: 0 })
+            .contributions += 1;
+        Ok(hasher.finalize().into())
+    }
+
+    /// Registra uma contribuição (argumento, réplica) de uma persona ativa
+    pub async fn record_contribution(&self, session_id: &[u8; 32], persona_id: PersonaId) -> Result<(), DiversityEngineError> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions.get_mut(session_id)
+            .ok_or_else(|| DiversityEngineError::UnknownSession(hex::encode(session_id)))?;
+        session.personas.get_mut(&persona_id)
+            .ok_or(DiversityEngineError::InactivePersona(persona_id))?
+            .contributions += 1;
+        Ok(())
+    }
+
+    /// Personas ativas na sessão
+    pub async fn active_personas(&self, session_id: &[u8; 32]) -> Vec<Persona> {
+        self.sessions.read().await.get(session_id)
+            .map(|session| session.personas.values().map(|active| active.persona.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    pub async fn evaluate_diversity(&self, session_id: &[u8; 32]) -> Result<DiversityMetrics, DiversityEngineError> {
+        let sessions = self.sessions.read().await;
+        let session = sessions.get(session_id)
+            .ok_or_else(|| DiversityEngineError::UnknownSession(hex::encode(session_id)))?;
+        if session.personas.is_empty() {
+            return Err(DiversityEngineError::EvaluationError("Nenhuma perspectiva ativa".to_string()));
+        }
+        Ok(compute_metrics(session.personas.values()))
+    }
+}
+
+fn compute_metrics<'a>(personas: impl Iterator<Item = &'a ActivePersona> + Clone) -> DiversityMetrics {
+    let total: u64 = personas.clone().map(|active| active.contributions).sum();
+
+    let mut roles: HashMap<SocioEmotionalRole, f64> = HashMap::new();
+    let mut domains: HashMap<ExpertiseDomain, f64> = HashMap::new();
+    for active in personas.clone() {
+        let weight = active.contributions as f64;
+        *roles.entry(active.persona.role).or_default() += weight;
+        // Contribuições divididas entre as áreas de expertise da persona
+        let share = weight / active.persona.expertise.len().max(1) as f64;
+        for domain in &active.persona.expertise {
+            *domains.entry(*domain).or_default() += share;
+        }
+    }
+
+    let role_diversity = category_diversity(&roles, SocioEmotionalRole::ALL.len());
+    let expertise_diversity = category_diversity(&domains, ExpertiseDomain::ALL.len());
+
+    // Em empate, a menor id: o resultado não depende da ordem do HashMap
+    let dominant = personas.clone()
+        .max_by(|a, b| a.contributions.cmp(&b.contributions).then(b.persona.id.0.cmp(&a.persona.id.0)));
+    let activation_share = match (dominant, total) {
+        (Some(active), total) if total > 0 => active.contributions as f64 / total as f64,
+        _ => 0.0,
+    };
+
+    DiversityMetrics {
+        diversity_score: (role_diversity.evenness + role_diversity.normalized_simpson
+            + expertise_diversity.evenness + expertise_diversity.normalized_simpson) / 4.0,
+        active_perspectives: personas.count(),
+        dominance_indicator: DominanceIndicator {
+            is_concerning: activation_share > GROUPTHINK_THRESHOLD,
+            dominant_persona: dominant.map(|active| active.persona.id),
+            activation_share,
+        },
+        role_diversity,
+        expertise_diversity,
+    }
+}
+
+fn category_diversity<K: Eq + Hash>(counts: &HashMap<K, f64>, possible_categories: usize) -> CategoryDiversity {
+    let weights: Vec<f64> = counts.values().copied().filter(|w| *w > 0.0).collect();
+    let shannon = shannon_entropy(&weights);
+    let simpson = simpson_index(&weights);
+    let k = possible_categories as f64;
+
+    CategoryDiversity {
+        shannon,
+        evenness: if possible_categories > 1 { shannon / k.ln() } else { 0.0 },
+        simpson,
+        normalized_simpson: if possible_categories > 1 { simpson / (1.0 - 1.0 / k) } else { 0.0 },
+        categories_present: weights.len(),
+    }
+}
+
+/// Entropia de Shannon (nats) das proporções dadas por `weights`
+pub fn shannon_entropy(weights: &[f64]) -> f64 {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    -weights.iter()
+        .filter(|w| **w > 0.0)
+        .map(|w| {
+            let p = w / total;
+            p * p.ln()
         })
+        .sum::<f64>()
+}
+
+/// Índice de Gini-Simpson: probabilidade de duas contribuições sorteadas
+/// virem de categorias diferentes
+pub fn simpson_index(weights: &[f64]) -> f64 {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    1.0 - weights.iter().map(|w| (w / total).powi(2)).sum::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn persona(id: u8, role: SocioEmotionalRole, expertise: Vec<ExpertiseDomain>) -> Persona {
+        Persona { id: PersonaId([id; 32]), role, expertise }
+    }
+
+    #[test]
+    fn test_entropy_bounds() {
+        assert_eq!(shannon_entropy(&[4.0]), 0.0);
+        assert!((shannon_entropy(&[1.0; 5]) - 5f64.ln()).abs() < 1e-12);
+        assert_eq!(simpson_index(&[3.0]), 0.0);
+        assert!((simpson_index(&[1.0, 1.0]) - 0.5).abs() < 1e-12);
+        assert_eq!(shannon_entropy(&[]), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_balanced_session_is_diverse() {
+        let engine = PerspectiveDiversityEngine::new(&[0u8; 32]);
+        let session = [1u8; 32];
+        engine.begin_session(session).await;
+        for (i, (role, domain)) in SocioEmotionalRole::ALL.iter().zip(ExpertiseDomain::ALL).enumerate() {
+            engine.activate(&session, persona(i as u8, *role, vec![domain])).await.unwrap();
+        }
+
+        let metrics = engine.evaluate_diversity(&session).await.unwrap();
+        assert_eq!(metrics.active_perspectives, 5);
+        assert!((metrics.diversity_score - 1.0).abs() < 1e-9, "{}", metrics.diversity_score);
+        assert_eq!(metrics.role_diversity.categories_present, 5);
+        assert!((metrics.dominance_indicator.activation_share - 0.2).abs() < 1e-12);
+        assert!(!metrics.dominance_indicator.is_concerning);
+    }
+
+    #[tokio::test]
+    async fn test_dominant_persona_and_homogeneous_roles() {
+        let engine = PerspectiveDiversityEngine::new(&[0u8; 32]);
+        let session = [2u8; 32];
+        engine.begin_session(session).await;
+        for i in 0..4 {
+            engine.activate(&session, persona(i, SocioEmotionalRole::Analytic, vec![ExpertiseDomain::Ethics])).await.unwrap();
+        }
+        for _ in 0..4 {
+            engine.record_contribution(&session, PersonaId([3; 32])).await.unwrap();
+        }
+
+        let metrics = engine.evaluate_diversity(&session).await.unwrap();
+        assert_eq!(metrics.diversity_score, 0.0);
+        assert_eq!(metrics.dominance_indicator.dominant_persona, Some(PersonaId([3; 32])));
+        assert!((metrics.dominance_indicator.activation_share - 5.0 / 8.0).abs() < 1e-12);
+        assert!(metrics.dominance_indicator.is_concerning);
+
+        assert!(matches!(
+            engine.record_contribution(&session, PersonaId([9; 32])).await,
+            Err(DiversityEngineError::InactivePersona(_))
+        ));
+        engine.end_session(&session).await;
+        assert!(engine.evaluate_diversity(&session).await.is_err());
     }
 }
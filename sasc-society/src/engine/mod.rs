@@ -6,18 +6,23 @@
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 use std::collections::{HashMap, VecDeque};
-use tokio::sync::{RwLock, Mutex, mpsc};
+use std::path::PathBuf;
+use tokio::sync::{RwLock, Mutex, mpsc, broadcast};
 use blake3::{Hash, Hasher};
 use serde::{Serialize, Deserialize};
 
 pub mod diversity;
+pub mod cache;
 pub mod dialectic;
+pub mod reasoner;
 pub mod mod_reexport;
 
 pub use crate::engine::diversity::{PerspectiveDiversityEngine, DiversityMetrics, DiversityEngineError};
 pub use crate::engine::dialectic::{DialecticSynthesizer, SynthesisSession, SynthesisError, SynthesizedDecision, SynthesisContext, DialecticMetrics};
 pub use crate::agents::{PersonaId, Persona};
 use crate::audit::ProvenanceTracer;
+use crate::audit::worm::{HistoryQuery, WormError, WormEvent, WormPolicy, WormRecord, WormStore};
+use crate::engine::cache::{CachePolicy, CachedDecision, DecisionCache};
 use crate::integration::vajra::{report_to_vajra, VajraAlert, AlertSeverity};
 
 // ===================== CONSTANTES DE GOVERNANÇA =====================
@@ -34,6 +39,12 @@ pub const HARD_FREEZE_THRESHOLD: f64 = 0.80;
 /// Número máximo de perspectivas simultâneas (limite arquitetural)
 pub const MAX_CONCURRENT_PERSPECTIVES: usize = 128;
 
+/// Duração padrão de um Hard Freeze até revisão dos conselhos (72h)
+pub const HARD_FREEZE_DURATION: Duration = Duration::from_secs(72 * 3600);
+
+/// Capacidade do canal de alertas de Hard Freeze por assinante
+const HARD_FREEZE_ALERT_CAPACITY: usize = 64;
+
 // ===================== ESTRUTURAS DE ORQUESTRAÇÃO =====================
 
 pub use mod_reexport::SoTDecisionRequest;
@@ -122,7 +133,7 @@ pub struct SoTDecisionResponse {
     pub constitutional_compliance: ConstitutionalComplianceReport,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum DecisionStatus {
     GatheringPerspectives,
     DiversityAssessment,
@@ -239,6 +250,44 @@ pub enum ViolationSeverity {
     Existential,
 }
 
+// ===================== CONSULTAS E ALERTAS =====================
+
+/// Progresso de uma decisão, ativa ou já registrada no histórico
+#[derive(Debug, Clone)]
+pub struct DecisionProgress {
+    pub status: DecisionStatus,
+    pub progress_percentage: u32,
+    pub estimated_completion: SystemTime,
+}
+
+/// Entrada resumida do histórico de decisões
+#[derive(Debug, Clone)]
+pub struct DecisionSummary {
+    pub decision_id: [u8; 32],
+    pub timestamp: SystemTime,
+    pub summary: String,
+    pub final_status: DecisionStatus,
+}
+
+/// Ticket da fila de escalonamento humano
+#[derive(Debug, Clone)]
+pub struct EscalationTicket {
+    pub ticket_id: String,
+    pub decision_id: [u8; 32],
+    pub reason: String,
+    pub urgency: u32,
+    pub requested_at: SystemTime,
+}
+
+/// Alerta emitido quando o Hard Freeze (Art. 103) é ativado
+#[derive(Debug, Clone)]
+pub struct HardFreezeEvent {
+    pub timestamp: SystemTime,
+    pub reason: String,
+    pub current_phi: f64,
+    pub duration: Duration,
+}
+
 // ===================== ORCHESTRATOR PRINCIPAL =====================
 
 pub struct SoTOrchestrator {
@@ -254,6 +303,9 @@ pub struct SoTOrchestrator {
     /// Tracer para auditoria completa
     provenance: ProvenanceTracer,
 
+    /// Histórico durável (WORM) das decisões
+    history: Arc<WormStore>,
+
     /// Canal para comunicação com stakeholders
     stakeholder_channels: HashMap<StakeholderRole, mpsc::Sender<StakeholderNotification>>,
 
@@ -262,6 +314,12 @@ pub struct SoTOrchestrator {
 
     /// Configuração do sistema
     config: OrchestratorConfig,
+
+    /// Difusão de alertas de Hard Freeze para assinantes
+    hard_freeze_alerts: broadcast::Sender<HardFreezeEvent>,
+
+    /// Instante de criação (uptime)
+    started_at: SystemTime,
 }
 
 #[derive(Debug, Clone)]
@@ -275,7 +333,8 @@ struct OrchestratorState {
     /// Decisões ativas em processamento
     active_decisions: HashMap<[u8; 32], ActiveDecision>,
 
-    /// Histórico de decisões (últimas 1000)
+    /// Janela das últimas 1000 decisões, para as métricas em tempo real;
+    /// o histórico durável fica no armazenamento WORM
     decision_history: VecDeque<DecisionRecord>,
 
     /// Métricas em tempo real
@@ -286,6 +345,9 @@ struct OrchestratorState {
 
     /// Estado de emergência (Hard Freeze, etc.)
     emergency_state: Option<EmergencyState>,
+
+    /// Fila de escalonamentos humanos pendentes
+    escalation_queue: VecDeque<EscalationTicket>,
 }
 
 #[derive(Debug, Clone)]
@@ -315,7 +377,7 @@ struct DecisionRecord {
     response: SoTDecisionResponse,
     timestamp: SystemTime,
     hash: [u8; 32],
-    storage_location: String, // WORM drive reference
+    storage_location: String, // Referência ao registro WORM
 }
 
 #[derive(Debug, Clone)]
@@ -390,6 +452,7 @@ enum AlertType {
 #[derive(Debug, Clone)]
 struct EmergencyState {
     emergency_type: EmergencyType,
+    reason: String,
     activated_at: SystemTime,
     affected_decisions: Vec<[u8; 32]>,
     mitigation_actions: Vec<MitigationAction>,
@@ -421,71 +484,6 @@ enum MitigationStatus {
     Failed,
 }
 
-#[derive(Debug, Clone)]
-struct DecisionCache {
-    /// Cache de decisões similares para acelerar processamento
-    similarity_cache: HashMap<[u8; 32], CachedDecision>,
-
-    /// Índice para busca por similaridade
-    similarity_index: SimilarityIndex,
-
-    /// Política de cache
-    cache_policy: CachePolicy,
-}
-
-impl DecisionCache {
-    pub fn new(config: &OrchestratorConfig) -> Self {
-        Self {
-            similarity_cache: HashMap::new(),
-            similarity_index: SimilarityIndex::default(),
-            cache_policy: CachePolicy {
-                max_size: config.cache_size,
-                ttl_seconds: 3600,
-                similarity_threshold: config.cache_similarity_threshold,
-                enable_prefetch: false,
-            }
-        }
-    }
-
-    pub async fn find_similar(&self, _embedding: &[f64]) -> Option<([u8; 32], f64)> {
-        None
-    }
-
-    pub async fn evict_least_used(&mut self) {
-        // Simple eviction
-    }
-}
-
-#[derive(Debug, Clone)]
-struct CachedDecision {
-    original_request_hash: [u8; 32],
-    decision: SynthesizedDecision,
-    _similarity_score: f64,
-    _last_accessed: SystemTime,
-    _access_count: u64,
-}
-
-#[derive(Debug, Clone, Default)]
-struct SimilarityIndex {
-    /// Embeddings dos problemas para busca por similaridade
-    _problem_embeddings: HashMap<[u8; 32], Vec<f64>>,
-
-    /// Índice FAISS ou similar para busca rápida
-    _faiss_index: Option<Vec<u8>>, // Serialized index
-}
-
-impl SimilarityIndex {
-    pub fn update_embedding(&mut self, _hash: [u8; 32], _embedding: Vec<f64>) {}
-}
-
-#[derive(Debug, Clone)]
-struct CachePolicy {
-    max_size: usize,
-    ttl_seconds: u64,
-    similarity_threshold: f64,
-    enable_prefetch: bool,
-}
-
 #[derive(Debug, Clone)]
 pub struct OrchestratorConfig {
     /// Configuração de paralelismo
@@ -505,6 +503,9 @@ pub struct OrchestratorConfig {
     pub cache_enabled: bool,
     pub cache_size: usize,
     pub cache_similarity_threshold: f64,
+    pub cache_ttl_seconds: u64,
+    /// Arquivo do cache; `None` mantém o cache só em memória
+    pub cache_path: Option<PathBuf>,
 
     /// Configuração de auditoria
     pub audit_level: AuditLevel,
@@ -525,6 +526,17 @@ impl SoTOrchestrator {
         diversity_engine: Arc<PerspectiveDiversityEngine>,
         dialectic_synthesizer: Arc<DialecticSynthesizer>,
         config: OrchestratorConfig,
+    ) -> Self {
+        let history = Arc::new(WormStore::in_memory(WormPolicy::with_retention_days(config.retention_period_days)));
+        Self::with_history_store(diversity_engine, dialectic_synthesizer, config, history)
+    }
+
+    /// Cria orquestrador sobre um histórico WORM já aberto
+    pub fn with_history_store(
+        diversity_engine: Arc<PerspectiveDiversityEngine>,
+        dialectic_synthesizer: Arc<DialecticSynthesizer>,
+        config: OrchestratorConfig,
+        history: Arc<WormStore>,
     ) -> Self {
         let state = OrchestratorState {
             active_decisions: HashMap::new(),
@@ -532,16 +544,21 @@ impl SoTOrchestrator {
             realtime_metrics: RealtimeMetrics::default(),
             active_alerts: Vec::new(),
             emergency_state: None,
+            escalation_queue: VecDeque::new(),
         };
+        let (hard_freeze_alerts, _) = broadcast::channel(HARD_FREEZE_ALERT_CAPACITY);
 
         Self {
             diversity_engine,
             dialectic_synthesizer,
             state: Arc::new(RwLock::new(state)),
-            provenance: ProvenanceTracer::new("sot_orchestrator"),
+            provenance: ProvenanceTracer::new("sot_orchestrator", history.clone()),
+            history,
             stakeholder_channels: HashMap::new(),
-            decision_cache: Arc::new(Mutex::new(DecisionCache::new(&config))),
+            decision_cache: Arc::new(Mutex::new(open_decision_cache(&config))),
             config,
+            hard_freeze_alerts,
+            started_at: SystemTime::now(),
         }
     }
 
@@ -554,40 +571,57 @@ impl SoTOrchestrator {
         let start_time = SystemTime::now();
         let request_hash = self.hash_request(&request).into();
 
-        // 1. Verifica cache para decisões similares
+        // 1. Valida request constitucionalmente, mesmo para decisões em cache
+        self.validate_request_constitutionally(&request).await?;
+
+        // 2. Verifica cache para decisões similares
         if self.config.cache_enabled {
             if let Some(cached) = self.check_decision_cache(&request).await? {
-                return self.build_response_from_cache(cached, start_time).await;
+                return self.build_response_from_cache(&request, request_hash, cached, start_time).await;
             }
         }
 
-        // 2. Valida request constitucionalmente
-        self.validate_request_constitutionally(&request).await?;
-
         // 3. Inicializa decisão ativa
         let decision_id = self.initialize_decision(request.clone(), request_hash).await?;
 
-        // 4. Coleta e ativa perspectivas relevantes
-        let perspectives = self.activate_relevant_perspectives(&request).await?;
-
-        // 5. Executa validação de diversidade
-        let diversity_metrics = self.validate_diversity(&perspectives).await?;
-
-        // 6. Executa síntese dialética
-        let synthesis_result = self.execute_dialectic_synthesis(
-            &request,
-            &perspectives,
-            &diversity_metrics
-        ).await?;
-
-        // 7. Avalia resultado e toma ação apropriada
-        let response = self.evaluate_and_finalize(
-            decision_id,
-            &request,
-            diversity_metrics,
-            synthesis_result,
-            start_time,
-        ).await?;
+        // Sessão de diversidade da decisão, encerrada em qualquer desfecho
+        self.diversity_engine.begin_session(decision_id).await;
+        let outcome = async {
+            // 4. Coleta e ativa perspectivas relevantes
+            self.set_processing_stage(decision_id, ProcessingStage::PerspectiveActivation, DecisionStatus::GatheringPerspectives).await;
+            let perspectives = self.activate_relevant_perspectives(decision_id, &request).await?;
+
+            // 5. Executa validação de diversidade
+            self.set_processing_stage(decision_id, ProcessingStage::DiversityValidation, DecisionStatus::DiversityAssessment).await;
+            let diversity_metrics = self.validate_diversity(decision_id, &perspectives).await?;
+
+            // 6. Executa síntese dialética
+            self.set_processing_stage(decision_id, ProcessingStage::DialecticPhase(0), DecisionStatus::DialecticSynthesis).await;
+            let synthesis_result = self.execute_dialectic_synthesis(
+                decision_id,
+                &request,
+                &perspectives,
+                &diversity_metrics
+            ).await?;
+
+            // 7. Avalia resultado e toma ação apropriada
+            self.evaluate_and_finalize(
+                decision_id,
+                &request,
+                diversity_metrics,
+                synthesis_result,
+                start_time,
+            ).await
+        }.await;
+        self.diversity_engine.end_session(&decision_id).await;
+        let response = match outcome {
+            Ok(response) => response,
+            Err(e) => {
+                // Falha no meio do pipeline: a decisão deixa de ocupar vaga ativa
+                self.state.write().await.active_decisions.remove(&decision_id);
+                return Err(e);
+            }
+        };
 
         // 8. Atualiza cache se apropriado
         if self.config.cache_enabled {
@@ -605,26 +639,33 @@ impl SoTOrchestrator {
         &self,
         request: &SoTDecisionRequest,
     ) -> Result<Option<CachedDecision>, OrchestrationError> {
-        let cache = self.decision_cache.lock().await;
-
-        // Gera embedding do problema atual
-        let current_embedding = self.generate_problem_embedding(request);
-
-        // Busca no índice de similaridade
-        if let Some((cached_hash, similarity)) = cache.find_similar(&current_embedding).await {
-            if similarity >= cache.cache_policy.similarity_threshold {
-                if let Some(cached_decision) = cache.similarity_cache.get(&cached_hash) {
-                    return Ok(Some(cached_decision.clone()));
-                }
+        let mut cache = self.decision_cache.lock().await;
+        let found = cache.find_similar(&problem_text(request), &decision_scope(request), SystemTime::now());
+        if let Some((cached, similarity)) = &found {
+            log::info!(
+                "Decisão {} reutilizada do cache (similaridade {:.3})",
+                hex::encode(&cached.original_request_hash[..4]),
+                similarity
+            );
+            // O acesso conta para a ordem LRU após reinícios
+            if let Err(e) = cache.persist() {
+                log::warn!("Falha ao persistir cache de decisões: {}", e);
             }
         }
-
-        Ok(None)
+        Ok(found.map(|(cached, _)| cached))
     }
 
-    async fn build_response_from_cache(&self, cached: CachedDecision, start_time: SystemTime) -> Result<SoTDecisionResponse, OrchestrationError> {
-        Ok(SoTDecisionResponse {
-            request_id: cached.original_request_hash,
+    /// Resposta da nova request com a decisão reaproveitada, registrada no
+    /// histórico sob o id da própria request
+    async fn build_response_from_cache(
+        &self,
+        request: &SoTDecisionRequest,
+        request_id: [u8; 32],
+        cached: CachedDecision,
+        start_time: SystemTime,
+    ) -> Result<SoTDecisionResponse, OrchestrationError> {
+        let response = SoTDecisionResponse {
+            request_id,
             status: DecisionStatus::Finalized,
             decision: Some(cached.decision),
             metrics: OrchestrationMetrics {
@@ -632,7 +673,9 @@ impl SoTOrchestrator {
                 ..Default::default()
             },
             ..Default::default()
-        })
+        };
+        self.record_decision_history(request, &response).await?;
+        Ok(response)
     }
 
     /// Valida request contra invariantes constitucionais
@@ -646,6 +689,10 @@ impl SoTOrchestrator {
             if constraint.inviolable {
                 // Constraints invioláveis devem ser explicitamente marcadas
                 if !self.validate_inviolable_constraint(constraint).await? {
+                    self.trigger_hard_freeze(
+                        &format!("Constraint inviolável violada: {}", constraint.description),
+                        vec![self.hash_request(request).into()],
+                    ).await;
                     return Err(OrchestrationError::ConstitutionalViolation(
                         format!("Constraint inviolável violada: {}", constraint.description)
                     ));
@@ -721,13 +768,15 @@ impl SoTOrchestrator {
         };
 
         state.active_decisions.insert(request_hash, decision);
+        let phi = state.realtime_metrics.current_phi;
+        drop(state);
 
-        // Log para auditoria
+        // Log para auditoria, fora do lock: a gravação WORM faz fsync
         self.provenance.trace_decision_start(
             request_hash.into(),
             &request,
-            &state.realtime_metrics.current_phi,
-        );
+            &phi,
+        ).await;
 
         Ok(request_hash)
     }
@@ -735,12 +784,13 @@ impl SoTOrchestrator {
     /// Ativa perspectivas relevantes para o problema
     async fn activate_relevant_perspectives(
         &self,
+        decision_id: [u8; 32],
         request: &SoTDecisionRequest,
     ) -> Result<Vec<PersonaId>, OrchestrationError> {
 
         // 1. Análise semântica do problema
         let problem_keywords = self.extract_keywords(&request.problem_statement);
-        let problem_embedding = self.generate_problem_embedding(request);
+        let problem_embedding = self.generate_problem_embedding(request).await;
 
         // 2. Consulta repositório de personas
         let relevant_personas = self.query_persona_repository(
@@ -753,7 +803,7 @@ impl SoTOrchestrator {
         let mut activated_personas = Vec::new();
 
         for persona in relevant_personas {
-            match self.activate_persona(persona.id, request).await {
+            match self.activate_persona(decision_id, &persona).await {
                 Ok(activation_hash) => {
                     activated_personas.push(persona.id);
 
@@ -785,29 +835,36 @@ impl SoTOrchestrator {
     }
 
     async fn query_persona_repository(&self, _keywords: &[String], _embedding: &[f64], limit: usize) -> Result<Vec<Persona>, OrchestrationError> {
+        use crate::agents::{ExpertiseDomain, SocioEmotionalRole};
+
+        // Repositório de referência: papéis e áreas distribuídos em rodízio
         let mut personas = Vec::new();
         for i in 0..limit.min(10) {
             personas.push(Persona {
                 id: PersonaId([i as u8; 32]),
-                role: crate::agents::SocioEmotionalRole::Analytic,
-                expertise: vec![crate::agents::ExpertiseDomain::Ethics],
+                role: SocioEmotionalRole::ALL[i % SocioEmotionalRole::ALL.len()],
+                expertise: vec![
+                    ExpertiseDomain::ALL[i % ExpertiseDomain::ALL.len()],
+                    ExpertiseDomain::ALL[(i + 2) % ExpertiseDomain::ALL.len()],
+                ],
             });
         }
         Ok(personas)
     }
 
-    async fn activate_persona(&self, _id: PersonaId, _request: &SoTDecisionRequest) -> Result<[u8; 32], OrchestrationError> {
-        Ok([0u8; 32])
+    async fn activate_persona(&self, decision_id: [u8; 32], persona: &Persona) -> Result<[u8; 32], OrchestrationError> {
+        Ok(self.diversity_engine.activate(&decision_id, persona.clone()).await?)
     }
 
     /// Executa validação de diversidade das perspectivas ativadas
     async fn validate_diversity(
         &self,
+        decision_id: [u8; 32],
         _perspectives: &[PersonaId],
     ) -> Result<DiversityMetrics, OrchestrationError> {
 
-        // 1. Avalia diversidade atual
-        let diversity_metrics = self.diversity_engine.evaluate_diversity().await
+        // 1. Avalia diversidade das perspectivas ativadas nesta decisão
+        let diversity_metrics = self.diversity_engine.evaluate_diversity(&decision_id).await
             .map_err(OrchestrationError::DiversityError)?;
 
         // 2. Verifica thresholds
@@ -853,6 +910,7 @@ impl SoTOrchestrator {
     /// Executa síntese dialética completa
     async fn execute_dialectic_synthesis(
         &self,
+        decision_id: [u8; 32],
         request: &SoTDecisionRequest,
         _perspectives: &[PersonaId],
         _diversity_metrics: &DiversityMetrics,
@@ -873,11 +931,49 @@ impl SoTOrchestrator {
                 .collect(),
         };
 
-        // 2. Inicia sessão de síntese
-        let session = self.dialectic_synthesizer.begin_synthesis(synthesis_context).await
+        // 2. Inicia sessão de síntese sobre as personas ativadas para a decisão
+        let session = self.dialectic_synthesizer.begin_synthesis(decision_id, synthesis_context).await
             .map_err(OrchestrationError::SynthesisError)?;
+        let outcome = self.run_dialectic_rounds(decision_id, &session).await;
+        let final_session = self.dialectic_synthesizer.finish_synthesis(&session.id).await
+            .unwrap_or_else(|| (*session).clone());
+
+        Ok(match outcome? {
+            RoundsOutcome::Converged { decision, iterations, coherence } => SynthesisResult::Success {
+                _session: final_session,
+                final_decision: decision,
+                iterations,
+                final_coherence: coherence,
+            },
+            RoundsOutcome::Stagnated { best, iterations, coherence, reason } => SynthesisResult::Stagnation {
+                _session: final_session,
+                best_decision: best,
+                iterations,
+                best_coherence: coherence,
+                _stagnation_reason: reason,
+            },
+            RoundsOutcome::Escalated { coherence, threshold, iterations } => SynthesisResult::HumanEscalationRequired {
+                _session: final_session,
+                current_coherence: coherence,
+                required_threshold: threshold,
+                iterations,
+                _reason: "Nenhuma persona apoia a tese; requer escalonamento humano".to_string(),
+            },
+            RoundsOutcome::Exhausted { best, iterations, coherence } => SynthesisResult::MaxIterationsReached {
+                _session: final_session,
+                best_decision: best,
+                iterations,
+                best_coherence: coherence,
+            },
+        })
+    }
 
-        // 3. Executa ciclos dialéticos com monitoramento
+    /// Executa ciclos dialéticos com monitoramento
+    async fn run_dialectic_rounds(
+        &self,
+        decision_id: [u8; 32],
+        session: &SynthesisSession,
+    ) -> Result<RoundsOutcome, OrchestrationError> {
         let mut iterations = 0;
         let mut best_coherence = 0.0;
         let mut best_synthesis: Option<SynthesizedDecision> = None;
@@ -889,77 +985,60 @@ impl SoTOrchestrator {
             }
 
             // Executa um ciclo
-            match self.dialectic_synthesizer.dialectic_cycle().await {
+            match self.dialectic_synthesizer.dialectic_cycle(&session.id).await {
                 Ok(metrics) => {
+                    iterations += 1;
+                    self.set_processing_stage(decision_id, ProcessingStage::DialecticPhase(iterations), DecisionStatus::DialecticSynthesis).await;
+
                     // Atualiza melhor resultado
-                    if metrics.coherence_score > best_coherence {
+                    if metrics.coherence_score > best_coherence || best_synthesis.is_none() {
                         best_coherence = metrics.coherence_score;
-
-                        // Obtém síntese atual se disponível
-                        if let Some(ref synth_session) = session.final_synthesis {
-                            best_synthesis = Some(synth_session.clone());
-                        }
+                        best_synthesis = self.dialectic_synthesizer.current_synthesis(&session.id).await;
                     }
 
                     // Verifica se atingiu threshold
                     if metrics.coherence_score >= self.config.coherence_threshold {
-                        return Ok(SynthesisResult::Success {
-                            _session: (*session).clone(),
-                            final_decision: best_synthesis.unwrap_or(SynthesizedDecision {
-                                decision_text: "Final decision".to_string(),
-                                coherence_score: metrics.coherence_score,
-                                supporting_arguments: vec![],
-                                counter_arguments: vec![],
-                                consensus_level: 1.0,
-                            }),
-                            iterations,
-                            final_coherence: metrics.coherence_score,
-                        });
+                        if let Some(decision) = best_synthesis.clone() {
+                            return Ok(RoundsOutcome::Converged { decision, iterations, coherence: metrics.coherence_score });
+                        }
                     }
 
                     // Verifica se está estagnado
                     if self.check_stagnation(iterations, &metrics).await? {
-                        return Ok(SynthesisResult::Stagnation {
-                            _session: (*session).clone(),
-                            best_decision: best_synthesis,
+                        return Ok(RoundsOutcome::Stagnated {
+                            best: best_synthesis,
                             iterations,
-                            best_coherence,
-                            _stagnation_reason: "Progresso insuficiente após múltiplas iterações".to_string(),
+                            coherence: best_coherence,
+                            reason: format!(
+                                "Posições inalteradas na rodada {} com apoio de {:.0}%",
+                                metrics.round, metrics.support_share * 100.0
+                            ),
                         });
                     }
                 }
                 Err(SynthesisError::HumanEscalonationRequired(score, threshold)) => {
-                    return Ok(SynthesisResult::HumanEscalationRequired {
-                        _session: (*session).clone(),
-                        current_coherence: score,
-                        required_threshold: threshold,
-                        iterations,
-                        _reason: "Síntese falhou, requer escalonamento humano".to_string(),
-                    });
+                    return Ok(RoundsOutcome::Escalated { coherence: score, threshold, iterations: iterations + 1 });
                 }
                 Err(e) => {
                     return Err(OrchestrationError::SynthesisError(e));
                 }
             }
-
-            iterations += 1;
         }
 
         // Máximo de iterações atingido
-        Ok(SynthesisResult::MaxIterationsReached {
-            _session: (*session).clone(),
-            best_decision: best_synthesis,
-            iterations,
-            best_coherence,
-        })
+        Ok(RoundsOutcome::Exhausted { best: best_synthesis, iterations, coherence: best_coherence })
     }
 
     async fn check_decision_timeout(&self, created_at: SystemTime) -> Result<bool, OrchestrationError> {
         Ok(created_at.elapsed().unwrap_or_default() > SOT_DECISION_TIMEOUT)
     }
 
-    async fn check_stagnation(&self, iterations: u32, _metrics: &DialecticMetrics) -> Result<bool, OrchestrationError> {
-        Ok(iterations > 5)
+    /// Estagnação: nenhuma persona mudou de posição e a coerência segue abaixo
+    /// do limiar — novas rodadas repetiriam os mesmos argumentos
+    async fn check_stagnation(&self, iterations: u32, metrics: &DialecticMetrics) -> Result<bool, OrchestrationError> {
+        Ok(iterations >= 2
+            && metrics.stance_changes == 0
+            && metrics.coherence_score < self.config.coherence_threshold)
     }
 
     /// Avalia resultado e finaliza decisão
@@ -974,9 +1053,10 @@ impl SoTOrchestrator {
 
         let processing_time = start_time.elapsed()
             .map_err(|_| OrchestrationError::TimeError)?;
+        self.set_processing_stage(decision_id, ProcessingStage::Finalization, DecisionStatus::DialecticSynthesis).await;
 
         // Constrói resposta baseada no resultado
-        let response = match synthesis_result {
+        let mut response = match synthesis_result {
             SynthesisResult::Success { final_decision, iterations, final_coherence, .. } => {
                 // Decisão autônoma bem-sucedida
                 self.build_success_response(
@@ -1030,6 +1110,11 @@ impl SoTOrchestrator {
             }
         };
 
+        // Reavalia a diversidade com as contribuições da dialética: na
+        // ativação todas as personas pesam o mesmo
+        let final_diversity = self.diversity_engine.evaluate_diversity(&decision_id).await?;
+        response.warnings.extend(self.diversity_warnings(&final_diversity));
+
         // Atualiza estado da decisão
         self.update_decision_state(decision_id, &response).await?;
 
@@ -1042,6 +1127,35 @@ impl SoTOrchestrator {
         Ok(response)
     }
 
+    /// Avisos de diversidade baixa ou de dominância de uma persona
+    fn diversity_warnings(&self, metrics: &DiversityMetrics) -> Vec<DecisionWarning> {
+        let mut warnings = Vec::new();
+        if metrics.diversity_score < self.config.diversity_threshold {
+            warnings.push(DecisionWarning {
+                warning_type: WarningType::LowDiversity,
+                description: format!("Diversidade das contribuições abaixo do threshold: {:.2} < {:.2}",
+                                     metrics.diversity_score,
+                                     self.config.diversity_threshold),
+                severity: if metrics.diversity_score < 0.4 { AlertSeverity::Critical } else { AlertSeverity::Warning },
+                recommended_action: "Revisar a decisão com perspectivas pouco ouvidas".to_string(),
+            });
+        }
+        if metrics.dominance_indicator.is_concerning {
+            warnings.push(DecisionWarning {
+                warning_type: WarningType::HighDominance,
+                description: format!("Dominância preocupante na dialética: {} com {:.1}% das contribuições",
+                                     metrics.dominance_indicator.dominant_persona
+                                         .as_ref()
+                                         .map(|id| id.to_string())
+                                         .unwrap_or_else(|| "Unknown".to_string()),
+                                     metrics.dominance_indicator.activation_share * 100.0),
+                severity: AlertSeverity::Warning,
+                recommended_action: "Verificar se a síntese reflete as demais perspectivas".to_string(),
+            });
+        }
+        warnings
+    }
+
     async fn build_success_response(&self, decision_id: [u8; 32], _request: &SoTDecisionRequest, diversity_metrics: DiversityMetrics, final_decision: SynthesizedDecision, iterations: u32, final_coherence: f64, processing_time: Duration) -> Result<SoTDecisionResponse, OrchestrationError> {
         Ok(SoTDecisionResponse {
             request_id: decision_id,
@@ -1058,16 +1172,16 @@ impl SoTOrchestrator {
         })
     }
 
-    async fn build_stagnation_response(&self, decision_id: [u8; 32], _request: &SoTDecisionRequest, _diversity: DiversityMetrics, _best_decision: Option<SynthesizedDecision>, _iterations: u32, _best_coherence: f64, _processing_time: Duration) -> Result<SoTDecisionResponse, OrchestrationError> {
-        Ok(SoTDecisionResponse { request_id: decision_id, status: DecisionStatus::HumanReviewRequired, ..Default::default() })
+    async fn build_stagnation_response(&self, decision_id: [u8; 32], _request: &SoTDecisionRequest, _diversity: DiversityMetrics, best_decision: Option<SynthesizedDecision>, _iterations: u32, _best_coherence: f64, _processing_time: Duration) -> Result<SoTDecisionResponse, OrchestrationError> {
+        Ok(SoTDecisionResponse { request_id: decision_id, status: DecisionStatus::HumanReviewRequired, decision: best_decision, ..Default::default() })
     }
 
     async fn build_escalation_response(&self, decision_id: [u8; 32], _request: &SoTDecisionRequest, _diversity: DiversityMetrics, _current_coherence: f64, _required_threshold: f64, _iterations: u32, _processing_time: Duration) -> Result<SoTDecisionResponse, OrchestrationError> {
         Ok(SoTDecisionResponse { request_id: decision_id, status: DecisionStatus::HumanReviewRequired, ..Default::default() })
     }
 
-    async fn build_max_iterations_response(&self, decision_id: [u8; 32], _request: &SoTDecisionRequest, _diversity: DiversityMetrics, _best_decision: Option<SynthesizedDecision>, _iterations: u32, _best_coherence: f64, _processing_time: Duration) -> Result<SoTDecisionResponse, OrchestrationError> {
-        Ok(SoTDecisionResponse { request_id: decision_id, status: DecisionStatus::HumanReviewRequired, ..Default::default() })
+    async fn build_max_iterations_response(&self, decision_id: [u8; 32], _request: &SoTDecisionRequest, _diversity: DiversityMetrics, best_decision: Option<SynthesizedDecision>, _iterations: u32, _best_coherence: f64, _processing_time: Duration) -> Result<SoTDecisionResponse, OrchestrationError> {
+        Ok(SoTDecisionResponse { request_id: decision_id, status: DecisionStatus::HumanReviewRequired, decision: best_decision, ..Default::default() })
     }
 
     async fn update_decision_state(&self, decision_id: [u8; 32], response: &SoTDecisionResponse) -> Result<(), OrchestrationError> {
@@ -1083,21 +1197,272 @@ impl SoTOrchestrator {
         Ok(())
     }
 
+    /// Grava o desfecho no histórico WORM, move a decisão de ativa para a
+    /// janela recente e atualiza as métricas
     async fn record_decision_history(&self, request: &SoTDecisionRequest, response: &SoTDecisionResponse) -> Result<(), OrchestrationError> {
-        let mut state = self.state.write().await;
-        state.decision_history.push_back(DecisionRecord {
+        let now = SystemTime::now();
+        let mut record = DecisionRecord {
             request: request.clone(),
             response: response.clone(),
-            timestamp: SystemTime::now(),
+            timestamp: now,
             hash: response.request_id,
-            storage_location: "WORM".to_string(),
-        });
+            storage_location: String::new(),
+        };
+        // E/S bloqueante (fsync) antes de tomar o lock do estado
+        let stored = self.history.append_async(
+            record.hash,
+            &request.metadata.requestor_id,
+            WormEvent::DecisionRecorded {
+                status: response.status.clone(),
+                summary: summarize_record(&record),
+                coherence: response.metrics.coherence_achieved,
+            },
+            now,
+        ).await?;
+        record.timestamp = stored.timestamp;
+        record.storage_location = format!("worm:{}", stored.sequence);
+
+        match self.history.enforce_retention_async(now).await {
+            Ok(removed) if !removed.is_empty() => log::info!("Retenção: segmentos WORM {:?} expirados e removidos", removed),
+            Ok(_) => {}
+            Err(e) => log::error!("Falha ao aplicar retenção do histórico: {}", e),
+        }
+
+        let mut state = self.state.write().await;
+        state.active_decisions.remove(&response.request_id);
+        state.decision_history.push_back(record);
         if state.decision_history.len() > 1000 {
             state.decision_history.pop_front();
         }
+
+        let last_minute = state.decision_history.iter()
+            .filter(|r| now.duration_since(r.timestamp).map_or(true, |age| age <= Duration::from_secs(60)))
+            .count();
+        let average_coherence = state.decision_history.iter()
+            .map(|r| r.response.metrics.coherence_achieved)
+            .sum::<f64>() / state.decision_history.len() as f64;
+        let metrics = &mut state.realtime_metrics;
+        metrics.decisions_per_minute = last_minute as f64;
+        metrics.average_coherence = average_coherence;
+        metrics.current_phi = response.metrics.phi_trajectory.last()
+            .map(|snapshot| snapshot.phi_value)
+            .unwrap_or(response.metrics.coherence_achieved);
         Ok(())
     }
 
+    async fn set_processing_stage(&self, decision_id: [u8; 32], stage: ProcessingStage, status: DecisionStatus) {
+        let mut state = self.state.write().await;
+        if let Some(decision) = state.active_decisions.get_mut(&decision_id) {
+            decision.processing_stage = stage;
+            decision.response.status = status;
+            decision.last_update = SystemTime::now();
+        }
+    }
+
+    // ===================== CONSULTAS E ALERTAS =====================
+
+    /// Progresso de uma decisão. Decisões ativas extrapolam o tempo gasto até
+    /// o estágio atual, limitado por `SOT_DECISION_TIMEOUT`; decisões já
+    /// registradas estão concluídas, salvo se aguardam revisão humana.
+    pub async fn decision_status(&self, decision_id: &[u8; 32]) -> Option<DecisionProgress> {
+        let state = self.state.read().await;
+        let escalated = state.escalation_queue.iter().any(|ticket| ticket.decision_id == *decision_id);
+
+        if let Some(decision) = state.active_decisions.get(decision_id) {
+            let progress = stage_progress(&decision.processing_stage);
+            let deadline = decision.started_at + SOT_DECISION_TIMEOUT;
+            let elapsed = decision.started_at.elapsed().unwrap_or_default();
+            let estimated = decision.started_at + elapsed * 100 / progress.max(1);
+            return Some(DecisionProgress {
+                status: decision.response.status.clone(),
+                progress_percentage: progress,
+                estimated_completion: estimated.clamp(SystemTime::now().min(deadline), deadline),
+            });
+        }
+
+        drop(state);
+        match self.history.latest(decision_id, true) {
+            Ok(record) => record.and_then(|record| {
+                let status = record.event.status()?.clone();
+                let awaiting_review = escalated || status == DecisionStatus::HumanReviewRequired;
+                Some(DecisionProgress {
+                    status,
+                    progress_percentage: if awaiting_review { stage_progress(&ProcessingStage::Escalation) } else { 100 },
+                    estimated_completion: record.timestamp,
+                })
+            }),
+            Err(e) => {
+                log::error!("Falha ao consultar histórico WORM: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Decisões registradas em `[start, end]`, da mais antiga para a mais
+    /// recente. `limit` 0 não limita.
+    pub async fn decision_history(
+        &self,
+        start: Option<SystemTime>,
+        end: Option<SystemTime>,
+        limit: usize,
+    ) -> Result<Vec<DecisionSummary>, OrchestrationError> {
+        self.query_history(HistoryQuery { start, end, limit, ..Default::default() }).await
+    }
+
+    /// Desfechos do histórico WORM que atendem a `query`
+    pub async fn query_history(&self, query: HistoryQuery) -> Result<Vec<DecisionSummary>, OrchestrationError> {
+        let query = HistoryQuery { outcomes_only: true, ..query };
+        Ok(self.history.query(&query)?
+            .into_iter()
+            .filter_map(|record| match record.event {
+                WormEvent::DecisionRecorded { status, summary, .. } => Some(DecisionSummary {
+                    decision_id: record.decision_id,
+                    timestamp: record.timestamp,
+                    summary,
+                    final_status: status,
+                }),
+                WormEvent::DecisionStarted { .. } => None,
+            })
+            .collect())
+    }
+
+    /// Trilha de proveniência da decisão no histórico WORM
+    pub fn decision_provenance(&self, decision_id: &[u8; 32]) -> Result<Vec<WormRecord>, OrchestrationError> {
+        Ok(self.provenance.provenance(decision_id)?)
+    }
+
+    /// Enfileira um escalonamento humano. Um pedido repetido para a mesma
+    /// decisão devolve o ticket já pendente.
+    pub async fn request_escalation(
+        &self,
+        decision_id: [u8; 32],
+        reason: &str,
+        urgency: u32,
+    ) -> Result<EscalationTicket, OrchestrationError> {
+        let mut state = self.state.write().await;
+
+        if let Some(ticket) = state.escalation_queue.iter().find(|t| t.decision_id == decision_id) {
+            return Ok(ticket.clone());
+        }
+
+        let known = state.active_decisions.contains_key(&decision_id)
+            || self.history.contains(&decision_id);
+        if !known {
+            return Err(OrchestrationError::DecisionNotFound(hex::encode(decision_id)));
+        }
+
+        if let Some(decision) = state.active_decisions.get_mut(&decision_id) {
+            decision.processing_stage = ProcessingStage::Escalation;
+            decision.response.status = DecisionStatus::HumanReviewRequired;
+            decision.last_update = SystemTime::now();
+        }
+
+        let ticket = EscalationTicket {
+            ticket_id: uuid::Uuid::new_v4().to_string(),
+            decision_id,
+            reason: reason.to_string(),
+            urgency,
+            requested_at: SystemTime::now(),
+        };
+        state.escalation_queue.push_back(ticket.clone());
+        log::warn!("Escalonamento humano {} para decisão {} (urgência {})",
+                   ticket.ticket_id, hex::encode(decision_id), urgency);
+
+        Ok(ticket)
+    }
+
+    /// Tickets pendentes, mais urgentes primeiro
+    pub async fn pending_escalations(&self) -> Vec<EscalationTicket> {
+        let state = self.state.read().await;
+        let mut tickets: Vec<EscalationTicket> = state.escalation_queue.iter().cloned().collect();
+        tickets.sort_by(|a, b| b.urgency.cmp(&a.urgency).then(a.requested_at.cmp(&b.requested_at)));
+        tickets
+    }
+
+    /// Retira da fila o ticket mais urgente para atendimento humano
+    pub async fn take_escalation(&self) -> Option<EscalationTicket> {
+        let mut state = self.state.write().await;
+        let index = state.escalation_queue.iter().enumerate()
+            .max_by(|(_, a), (_, b)| a.urgency.cmp(&b.urgency).then(b.requested_at.cmp(&a.requested_at)))
+            .map(|(index, _)| index)?;
+        state.escalation_queue.remove(index)
+    }
+
+    /// Métricas em tempo real, por nome
+    pub async fn metrics_snapshot(&self) -> HashMap<String, f64> {
+        let state = self.state.read().await;
+        let metrics = &state.realtime_metrics;
+        let uptime = self.started_at.elapsed().unwrap_or_default();
+
+        [
+            ("decisions_per_minute", metrics.decisions_per_minute),
+            ("average_coherence", metrics.average_coherence),
+            ("current_phi", metrics.current_phi),
+            ("cpu_utilization", metrics.resource_usage.cpu_utilization),
+            ("memory_usage", metrics.resource_usage.memory_usage),
+            ("network_throughput", metrics.resource_usage.network_throughput),
+            ("storage_iops", metrics.resource_usage.storage_iops),
+            ("error_rate", metrics.system_health.error_rate),
+            ("uptime_seconds", uptime.as_secs_f64()),
+            ("active_decisions", state.active_decisions.len() as f64),
+            ("pending_escalations", state.escalation_queue.len() as f64),
+            ("hard_freeze_active", if is_hard_freeze(&state.emergency_state) { 1.0 } else { 0.0 }),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+    }
+
+    /// Assina os alertas de Hard Freeze emitidos a partir de agora
+    pub fn subscribe_hard_freeze_alerts(&self) -> broadcast::Receiver<HardFreezeEvent> {
+        self.hard_freeze_alerts.subscribe()
+    }
+
+    /// Hard Freeze em vigor, se houver
+    pub async fn active_hard_freeze(&self) -> Option<HardFreezeEvent> {
+        let state = self.state.read().await;
+        let emergency = match &state.emergency_state {
+            Some(emergency @ EmergencyState { emergency_type: EmergencyType::HardFreezeActive, .. }) => emergency,
+            _ => return None,
+        };
+        let until = emergency.estimated_resolution_time.unwrap_or(emergency.activated_at + HARD_FREEZE_DURATION);
+        if until <= SystemTime::now() {
+            return None;
+        }
+        Some(HardFreezeEvent {
+            timestamp: emergency.activated_at,
+            reason: emergency.reason.clone(),
+            current_phi: state.realtime_metrics.current_phi,
+            duration: until.duration_since(emergency.activated_at).unwrap_or_default(),
+        })
+    }
+
+    /// Ativa o Hard Freeze (Art. 103) e notifica os assinantes
+    pub async fn trigger_hard_freeze(&self, reason: &str, affected_decisions: Vec<[u8; 32]>) -> HardFreezeEvent {
+        let now = SystemTime::now();
+        let mut state = self.state.write().await;
+        state.emergency_state = Some(EmergencyState {
+            emergency_type: EmergencyType::HardFreezeActive,
+            reason: reason.to_string(),
+            activated_at: now,
+            affected_decisions,
+            mitigation_actions: Vec::new(),
+            estimated_resolution_time: Some(now + HARD_FREEZE_DURATION),
+        });
+
+        let event = HardFreezeEvent {
+            timestamp: now,
+            reason: reason.to_string(),
+            current_phi: state.realtime_metrics.current_phi,
+            duration: HARD_FREEZE_DURATION,
+        };
+        log::error!("🧊 Hard Freeze ativado: {}", reason);
+
+        // Sem assinantes o envio falha; o estado de emergência já registra o freeze
+        let _ = self.hard_freeze_alerts.send(event.clone());
+        event
+    }
+
     /// Atualiza cache de decisões
     async fn update_decision_cache(
         &self,
@@ -1105,29 +1470,20 @@ impl SoTOrchestrator {
         response: &SoTDecisionResponse,
     ) -> Result<(), OrchestrationError> {
 
-        let mut cache = self.decision_cache.lock().await;
-
-        // Gera embedding do problema
-        let embedding = self.generate_problem_embedding(request);
-
         // Adiciona ao cache se apropriado
         if response.status == DecisionStatus::Finalized {
             if let Some(ref decision) = response.decision {
                 if decision.coherence_score >= self.config.cache_similarity_threshold {
-                    let cached_decision = CachedDecision {
-                        original_request_hash: response.request_id,
-                        decision: decision.clone(),
-                        _similarity_score: 1.0,
-                        _last_accessed: SystemTime::now(),
-                        _access_count: 1,
-                    };
-
-                    cache.similarity_cache.insert(response.request_id, cached_decision);
-                    cache.similarity_index.update_embedding(response.request_id, embedding);
-
-                    // Aplica política de limpeza se necessário
-                    if cache.similarity_cache.len() > cache.cache_policy.max_size {
-                        cache.evict_least_used().await;
+                    let mut cache = self.decision_cache.lock().await;
+                    // A decisão já foi tomada: falha de persistência não a invalida
+                    if let Err(e) = cache.insert(
+                        response.request_id,
+                        &problem_text(request),
+                        &decision_scope(request),
+                        decision.clone(),
+                        SystemTime::now(),
+                    ) {
+                        log::warn!("Falha ao persistir cache de decisões: {}", e);
                     }
                 }
             }
@@ -1180,11 +1536,82 @@ impl SoTOrchestrator {
             .collect()
     }
 
-    /// Gera embedding vetorial de um problema
-    fn generate_problem_embedding(&self, _request: &SoTDecisionRequest) -> Vec<f64> {
-        // Em produção, usar modelo de embeddings (ex: BERT, GPT)
-        // Aqui, implementação dummy para exemplo
-        vec![0.5; 768] // Dummy embedding de 768 dimensões
+    /// Gera embedding vetorial de um problema (TF-IDF de n-gramas com
+    /// hashing, com as estatísticas atuais do cache de decisões)
+    async fn generate_problem_embedding(&self, request: &SoTDecisionRequest) -> Vec<f64> {
+        self.decision_cache.lock().await.embed(&problem_text(request))
+    }
+}
+
+/// Texto que identifica o problema para o cache: enunciado e restrições
+fn problem_text(request: &SoTDecisionRequest) -> String {
+    std::iter::once(request.problem_statement.as_str())
+        .chain(request.constraints.iter().map(|c| c.description.as_str()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Contexto em que uma decisão em cache pode ser reaproveitada: jurisdição,
+/// contexto constitucional, partes interessadas e avaliação de risco
+fn decision_scope(request: &SoTDecisionRequest) -> String {
+    let mut hasher = Hasher::new();
+    hasher.update(request.metadata.jurisdiction.as_bytes());
+    hasher.update(&serde_json::to_vec(&request.metadata.risk_assessment).unwrap());
+    let mut context: Vec<&String> = request.constitutional_context.iter().collect();
+    context.sort();
+    hasher.update(&serde_json::to_vec(&context).unwrap());
+    let mut stakeholders: Vec<Vec<u8>> = request.stakeholders.iter()
+        .map(|s| serde_json::to_vec(s).unwrap())
+        .collect();
+    stakeholders.sort();
+    hasher.update(&serde_json::to_vec(&stakeholders).unwrap());
+    hasher.finalize().to_hex().to_string()
+}
+
+fn open_decision_cache(config: &OrchestratorConfig) -> DecisionCache {
+    let policy = CachePolicy {
+        max_size: config.cache_size,
+        ttl_seconds: config.cache_ttl_seconds,
+        similarity_threshold: config.cache_similarity_threshold,
+        enable_prefetch: false,
+    };
+    match &config.cache_path {
+        // Arquivo que não pôde ser lido não é sobrescrito: o cache fica só em memória
+        Some(path) => DecisionCache::open(policy.clone(), path).unwrap_or_else(|e| {
+            log::error!("Cache de decisões ignorado: {}", e);
+            DecisionCache::new(policy, None)
+        }),
+        None => DecisionCache::new(policy, None),
+    }
+}
+
+/// Percentual associado a cada estágio do pipeline
+fn stage_progress(stage: &ProcessingStage) -> u32 {
+    match stage {
+        ProcessingStage::Initialization => 5,
+        ProcessingStage::PerspectiveActivation => 15,
+        ProcessingStage::DiversityValidation => 30,
+        // Até 7 iterações dialéticas entre 40% e 90%
+        ProcessingStage::DialecticPhase(iteration) => 40 + (*iteration).min(7) * 50 / 7,
+        ProcessingStage::Finalization => 95,
+        // Aguarda revisão humana: nunca reportada como concluída
+        ProcessingStage::Escalation => 90,
+    }
+}
+
+fn is_hard_freeze(emergency: &Option<EmergencyState>) -> bool {
+    matches!(emergency, Some(EmergencyState { emergency_type: EmergencyType::HardFreezeActive, .. }))
+}
+
+/// Texto da decisão (ou o problema, se não houve decisão) em até 160 caracteres
+fn summarize_record(record: &DecisionRecord) -> String {
+    let text = record.response.decision.as_ref()
+        .map(|decision| decision.decision_text.as_str())
+        .unwrap_or(&record.request.problem_statement);
+    if text.chars().count() <= 160 {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(159).collect::<String>())
     }
 }
 
@@ -1220,6 +1647,14 @@ enum SynthesisResult {
     },
 }
 
+/// Desfecho das rodadas, antes de anexar o estado final da sessão
+enum RoundsOutcome {
+    Converged { decision: SynthesizedDecision, iterations: u32, coherence: f64 },
+    Stagnated { best: Option<SynthesizedDecision>, iterations: u32, coherence: f64, reason: String },
+    Escalated { coherence: f64, threshold: f64, iterations: u32 },
+    Exhausted { best: Option<SynthesizedDecision>, iterations: u32, coherence: f64 },
+}
+
 // ===================== ERROS DE ORQUESTRAÇÃO =====================
 
 #[derive(Debug, thiserror::Error)]
@@ -1271,6 +1706,12 @@ pub enum OrchestrationError {
 
     #[error("Invalid metadata")]
     InvalidMetadata,
+
+    #[error("Decisão não encontrada: {0}")]
+    DecisionNotFound(String),
+
+    #[error("Erro no histórico WORM: {0}")]
+    HistoryStore(#[from] WormError),
 }
 
 // ===================== IMPLEMENTAÇÕES DEFAULT =====================
@@ -1288,6 +1729,8 @@ impl Default for OrchestratorConfig {
             cache_enabled: true,
             cache_size: 1000,
             cache_similarity_threshold: 0.80,
+            cache_ttl_seconds: 3600,
+            cache_path: None,
             audit_level: AuditLevel::Comprehensive,
             retention_period_days: 3650, // 10 anos
         }
@@ -1418,4 +1861,194 @@ mod tests {
         let result = orchestrator.process_decision(request).await;
         assert!(result.is_ok());
     }
+
+    fn build_orchestrator() -> SoTOrchestrator {
+        let diversity_engine = Arc::new(PerspectiveDiversityEngine::new(&[0u8; 32]));
+        let human_callback = |_session: SynthesisSession| {};
+        let dialectic_synthesizer = Arc::new(DialecticSynthesizer::new(
+            diversity_engine.clone(),
+            &[0u8; 32],
+            human_callback,
+        ));
+        SoTOrchestrator::new(diversity_engine, dialectic_synthesizer, OrchestratorConfig::default())
+    }
+
+    fn sample_request(problem: &str) -> SoTDecisionRequest {
+        SoTDecisionRequest {
+            problem_statement: problem.to_string(),
+            constitutional_context: vec!["Art. 5º-A".to_string()],
+            constraints: Vec::new(),
+            stakeholders: Vec::new(),
+            deadline: None,
+            priority: 10,
+            metadata: DecisionMetadata {
+                requestor_id: "test_system".to_string(),
+                request_timestamp: SystemTime::now(),
+                jurisdiction: "BR".to_string(),
+                legal_basis: "Art. 5º-LXXX".to_string(),
+                risk_assessment: RiskLevel::Routine,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_history_and_escalation() {
+        let orchestrator = build_orchestrator();
+        let before = SystemTime::now();
+        let response = orchestrator.process_decision(sample_request("Alocação de energia regional")).await.unwrap();
+        let id = response.request_id;
+
+        let progress = orchestrator.decision_status(&id).await.unwrap();
+        assert_eq!(progress.progress_percentage, 100);
+        assert_eq!(progress.status, response.status);
+        assert!(orchestrator.decision_status(&[9u8; 32]).await.is_none());
+
+        let history = orchestrator.decision_history(Some(before), None, 0).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].decision_id, id);
+        assert!(orchestrator.decision_history(None, Some(before), 0).await.unwrap().is_empty());
+
+        let trail = orchestrator.decision_provenance(&id).unwrap();
+        assert_eq!(trail.len(), 2);
+        assert_eq!(trail[1].previous_hash, trail[0].hash);
+        assert_eq!(trail[1].event.status(), Some(&response.status));
+
+        let ticket = orchestrator.request_escalation(id, "Revisão cidadã", 3).await.unwrap();
+        assert!(orchestrator.decision_status(&id).await.unwrap().progress_percentage < 100);
+        let again = orchestrator.request_escalation(id, "Revisão cidadã", 5).await.unwrap();
+        assert_eq!(ticket.ticket_id, again.ticket_id);
+        assert!(orchestrator.request_escalation([9u8; 32], "?", 1).await.is_err());
+
+        let metrics = orchestrator.metrics_snapshot().await;
+        assert_eq!(metrics["pending_escalations"], 1.0);
+        assert_eq!(metrics["active_decisions"], 0.0);
+        assert_eq!(orchestrator.take_escalation().await.unwrap().ticket_id, ticket.ticket_id);
+        assert!(orchestrator.pending_escalations().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recurrent_question_is_served_from_cache() {
+        let orchestrator = build_orchestrator();
+        let first = orchestrator.process_decision(sample_request("Revisão anual da tarifa de transporte metropolitano")).await.unwrap();
+        assert_eq!(first.status, DecisionStatus::Finalized);
+
+        let again = orchestrator.process_decision(sample_request("Revisão anual das tarifas de transporte metropolitano")).await.unwrap();
+        assert_ne!(again.request_id, first.request_id);
+        assert_eq!(again.decision.as_ref().unwrap().decision_text, first.decision.as_ref().unwrap().decision_text);
+        assert_eq!(orchestrator.decision_history(None, None, 0).await.unwrap().len(), 2);
+        assert_eq!(orchestrator.decision_status(&again.request_id).await.unwrap().progress_percentage, 100);
+
+        // Mesmo enunciado em outro contexto exige nova deliberação
+        let mut strategic = sample_request("Revisão anual da tarifa de transporte metropolitano");
+        strategic.metadata.risk_assessment = RiskLevel::Strategic;
+        let fresh = orchestrator.process_decision(strategic).await.unwrap();
+        assert_eq!(orchestrator.decision_history(None, None, 0).await.unwrap().len(), 3);
+        assert_ne!(fresh.request_id, first.request_id);
+
+        let other = orchestrator.process_decision(sample_request("Demarcação de áreas de preservação costeira")).await.unwrap();
+        assert_ne!(other.request_id, first.request_id);
+    }
+
+    #[test]
+    fn test_stage_progress_is_monotonic() {
+        let stages = [
+            ProcessingStage::Initialization,
+            ProcessingStage::PerspectiveActivation,
+            ProcessingStage::DiversityValidation,
+            ProcessingStage::DialecticPhase(0),
+            ProcessingStage::DialecticPhase(3),
+            ProcessingStage::DialecticPhase(7),
+            ProcessingStage::Finalization,
+        ];
+        let progress: Vec<u32> = stages.iter().map(stage_progress).collect();
+        assert!(progress.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", progress);
+        assert!(progress.iter().all(|&p| p < 100));
+        assert!(stage_progress(&ProcessingStage::Escalation) < 100);
+    }
+
+    #[tokio::test]
+    async fn test_failed_decision_leaves_active_set() {
+        let diversity_engine = Arc::new(PerspectiveDiversityEngine::new(&[0u8; 32]));
+        let human_callback = |_session: SynthesisSession| {};
+        let dialectic_synthesizer = Arc::new(DialecticSynthesizer::new(
+            diversity_engine.clone(),
+            &[0u8; 32],
+            human_callback,
+        ));
+        // Duas perspectivas não bastam: o pipeline falha após a inicialização
+        let config = OrchestratorConfig { max_perspectives_per_decision: 2, max_concurrent_decisions: 1, ..Default::default() };
+        let orchestrator = SoTOrchestrator::new(diversity_engine, dialectic_synthesizer, config);
+
+        for problem in ["Primeira tentativa", "Segunda tentativa"] {
+            let result = orchestrator.process_decision(sample_request(problem)).await;
+            assert!(matches!(result, Err(OrchestrationError::InsufficientPerspectives(_))), "{:?}", result.err());
+        }
+        assert_eq!(orchestrator.metrics_snapshot().await["active_decisions"], 0.0);
+        let id: [u8; 32] = orchestrator.hash_request(&sample_request("Primeira tentativa")).into();
+        assert!(orchestrator.decision_status(&id).await.is_none());
+    }
+
+    /// Reasoner que credita contribuições extras à primeira persona a argumentar
+    struct DominatingReasoner {
+        diversity_engine: Arc<PerspectiveDiversityEngine>,
+        session: [u8; 32],
+        dominant: std::sync::Mutex<Option<PersonaId>>,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::engine::reasoner::PersonaReasoner for DominatingReasoner {
+        async fn argue(&self, persona: &Persona, round: crate::engine::reasoner::RoundContext<'_>) -> Result<crate::engine::reasoner::Argument, SynthesisError> {
+            let dominant = *self.dominant.lock().unwrap().get_or_insert(persona.id);
+            if dominant == persona.id {
+                for _ in 0..20 {
+                    self.diversity_engine.record_contribution(&self.session, persona.id).await.unwrap();
+                }
+            }
+            crate::engine::reasoner::RuleBasedReasoner.argue(persona, round).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dominance_during_dialectic_is_reported() {
+        let request = sample_request("Alocação de energia regional");
+        let plain = build_orchestrator().process_decision(request.clone()).await.unwrap();
+        assert!(plain.warnings.is_empty(), "{:?}", plain.warnings);
+
+        let diversity_engine = Arc::new(PerspectiveDiversityEngine::new(&[0u8; 32]));
+        let human_callback = |_session: SynthesisSession| {};
+        let reasoner = Arc::new(DominatingReasoner {
+            diversity_engine: diversity_engine.clone(),
+            session: build_orchestrator().hash_request(&request).into(),
+            dominant: std::sync::Mutex::new(None),
+        });
+        let dialectic_synthesizer = Arc::new(DialecticSynthesizer::new(
+            diversity_engine.clone(),
+            &[0u8; 32],
+            human_callback,
+        ).with_reasoner(reasoner.clone()));
+        let orchestrator = SoTOrchestrator::new(diversity_engine, dialectic_synthesizer, OrchestratorConfig::default());
+
+        // A ativação foi equilibrada; a dominância só aparece nas rodadas
+        let response = orchestrator.process_decision(request).await.unwrap();
+        let dominant = reasoner.dominant.lock().unwrap().unwrap();
+        let dominance = response.warnings.iter()
+            .find(|w| matches!(w.warning_type, WarningType::HighDominance))
+            .expect("aviso de dominância");
+        assert!(dominance.description.contains(&dominant.to_string()), "{}", dominance.description);
+    }
+
+    #[tokio::test]
+    async fn test_hard_freeze_alert_broadcast() {
+        let orchestrator = build_orchestrator();
+        let mut alerts = orchestrator.subscribe_hard_freeze_alerts();
+        assert!(orchestrator.active_hard_freeze().await.is_none());
+
+        orchestrator.trigger_hard_freeze("Constraint inviolável violada", Vec::new()).await;
+
+        let alert = alerts.recv().await.unwrap();
+        assert_eq!(alert.reason, "Constraint inviolável violada");
+        assert_eq!(alert.duration, HARD_FREEZE_DURATION);
+        assert_eq!(orchestrator.active_hard_freeze().await.unwrap().reason, alert.reason);
+        assert_eq!(orchestrator.metrics_snapshot().await["hard_freeze_active"], 1.0);
+    }
 }
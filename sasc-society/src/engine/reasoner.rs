@@ -0,0 +1,477 @@
+//! Raciocínio das personas na síntese dialética
+//! Reasoner determinístico por regras + adaptador opcional para modelo local
+
+use crate::agents::{ExpertiseDomain, Persona, PersonaId, SocioEmotionalRole};
+use crate::engine::dialectic::{SynthesisContext, SynthesisError};
+use async_trait::async_trait;
+use serde::{Serialize, Deserialize};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stance {
+    Support,
+    Oppose,
+}
+
+/// Contribuição de uma persona numa rodada
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Argument {
+    pub persona: PersonaId,
+    pub stance: Stance,
+    pub claim: String,
+    /// Condição que, incorporada à síntese, resolveria a objeção
+    pub condition: Option<String>,
+    /// Condição já incorporada à tese que a persona rejeita
+    #[serde(default)]
+    pub contested: Option<String>,
+    pub confidence: f64,
+}
+
+/// O que cada persona vê ao argumentar
+#[derive(Debug, Clone, Copy)]
+pub struct RoundContext<'a> {
+    pub context: &'a SynthesisContext,
+    pub round: u32,
+    /// Tese vigente (síntese da rodada anterior)
+    pub thesis: &'a str,
+    /// Condições já incorporadas à tese
+    pub conditions: &'a [String],
+    /// Argumentos da rodada anterior
+    pub previous: &'a [Argument],
+}
+
+#[async_trait]
+pub trait PersonaReasoner: Send + Sync {
+    async fn argue(&self, persona: &Persona, round: RoundContext<'_>) -> Result<Argument, SynthesisError>;
+}
+
+// ===================== REASONER POR REGRAS =====================
+
+/// Termos de cada área. Terminados em `*` casam como prefixo de palavra
+/// (radical); os demais, só a palavra inteira.
+fn domain_keywords(domain: ExpertiseDomain) -> &'static [&'static str] {
+    match domain {
+        ExpertiseDomain::Ethics => &["étic*", "ethic*", "dignidade", "dignity", "direito*", "rights", "justiça", "justice", "dano*", "harm*"],
+        ExpertiseDomain::Technoscience => &["tecnolog*", "technolog*", "energia", "energy", "dados", "data", "algoritm*", "infraestrutura*", "infrastructure*"],
+        ExpertiseDomain::Legislation => &["lei", "leis", "law", "laws", "legal", "constitu*", "art", "jurisdi*", "regula*", "norma", "normas"],
+        ExpertiseDomain::Sociology => &["social", "sociais", "comunidade*", "community", "communities", "cidad*", "citizen*", "popula*", "desigualdade*", "inequality"],
+        ExpertiseDomain::Environment => &["ambient*", "environment*", "clima*", "climate", "carbono", "carbon", "água", "águas", "water", "biodivers*"],
+    }
+}
+
+/// Termos de dano irreversível a pessoas: nenhuma condição o compensa
+const IRREVERSIBLE_HARM: &[&str] = &[
+    "perda de vidas", "loss of life", "sacrifício humano", "sacrifício de", "irreversível", "irreversible",
+    "exclusão permanente", "permanent exclusion",
+];
+
+/// Verbos que restringem ou promovem o objeto de uma restrição; condições
+/// de polaridade oposta sobre o mesmo objeto são incompatíveis
+const RESTRICTING: &[&str] = &[
+    "reduzir", "limitar", "restringir", "proibir", "vedar", "suspender", "impedir", "evitar",
+    "reduce", "limit", "restrict", "prohibit", "ban", "suspend", "prevent", "avoid",
+];
+const PROMOTING: &[&str] = &[
+    "ampliar", "expandir", "aumentar", "acelerar", "intensificar", "liberar",
+    "expand", "increase", "accelerate", "intensify", "extend", "release",
+];
+
+/// Palavras sem conteúdo, ignoradas ao comparar textos
+const STOPWORDS: &[&str] = &[
+    "para", "pelo", "pela", "pelos", "pelas", "como", "com", "sem", "sobre", "entre", "desde", "todo", "toda",
+    "todos", "todas", "cada", "este", "esta", "esse", "essa", "that", "this", "with", "from", "into", "each",
+];
+
+/// Palavras em minúsculas, sem pontuação
+pub fn tokens(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn keyword_matches(keyword: &str, token: &str) -> bool {
+    match keyword.strip_suffix('*') {
+        Some(stem) => token.starts_with(stem),
+        None => token == keyword,
+    }
+}
+
+/// `phrase` ocorre em `words` como sequência de palavras inteiras
+fn contains_phrase(words: &[String], phrase: &str) -> bool {
+    let phrase = tokens(phrase);
+    !phrase.is_empty() && words.windows(phrase.len()).any(|window| window == phrase.as_slice())
+}
+
+/// Palavras que identificam o objeto de um texto (sem verbos de polaridade)
+fn salient_tokens(text: &str) -> Vec<String> {
+    tokens(text).into_iter()
+        .filter(|t| t.chars().count() >= 4)
+        .filter(|t| !STOPWORDS.contains(&t.as_str()))
+        .filter(|t| !RESTRICTING.contains(&t.as_str()) && !PROMOTING.contains(&t.as_str()))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Polarity {
+    Restricting,
+    Promoting,
+}
+
+fn polarity(text: &str) -> Option<Polarity> {
+    let words = tokens(text);
+    if words.iter().any(|w| RESTRICTING.contains(&w.as_str())) {
+        Some(Polarity::Restricting)
+    } else if words.iter().any(|w| PROMOTING.contains(&w.as_str())) {
+        Some(Polarity::Promoting)
+    } else {
+        None
+    }
+}
+
+/// Duas condições sobre o mesmo objeto, uma restringindo e outra promovendo
+pub fn conflicts(a: &str, b: &str) -> bool {
+    let (Some(pa), Some(pb)) = (polarity(a), polarity(b)) else {
+        return false;
+    };
+    let subject = salient_tokens(b);
+    pa != pb && salient_tokens(a).iter().any(|t| subject.contains(t))
+}
+
+/// A tese cobre `constraint` quando suas condições contêm todas as palavras
+/// de conteúdo da restrição, qualquer que seja a redação
+fn addressed(constraint: &str, conditions: &[String]) -> bool {
+    let covered: Vec<String> = conditions.iter().flat_map(|c| tokens(c)).collect();
+    salient_tokens(constraint).iter().all(|t| covered.contains(t))
+}
+
+pub fn role_label(role: SocioEmotionalRole) -> &'static str {
+    match role {
+        SocioEmotionalRole::Analytic => "Analítica",
+        SocioEmotionalRole::Empathic => "Empática",
+        SocioEmotionalRole::Creative => "Criativa",
+        SocioEmotionalRole::Stoic => "Estoica",
+        SocioEmotionalRole::Diplomatic => "Diplomática",
+    }
+}
+
+fn role_base_confidence(role: SocioEmotionalRole) -> f64 {
+    match role {
+        SocioEmotionalRole::Analytic => 0.60,
+        SocioEmotionalRole::Empathic => 0.55,
+        SocioEmotionalRole::Creative => 0.65,
+        SocioEmotionalRole::Stoic => 0.70,
+        SocioEmotionalRole::Diplomatic => 0.60,
+    }
+}
+
+/// Reasoner determinístico: cada persona objeta às restrições da sua área
+/// que a tese ainda não cobre e contesta condições incorporadas que
+/// contrariam essas restrições; apoia quando nada disso resta. Personas
+/// éticas ou empáticas vetam dano irreversível em qualquer rodada.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuleBasedReasoner;
+
+impl RuleBasedReasoner {
+    pub fn reason(&self, persona: &Persona, round: RoundContext<'_>) -> Argument {
+        let role = role_label(persona.role);
+        let matches = |text: &str| {
+            tokens(text).iter().any(|token| {
+                persona.expertise.iter().any(|d| domain_keywords(*d).iter().any(|k| keyword_matches(k, token)))
+            })
+        };
+
+        let guards_people = persona.role == SocioEmotionalRole::Empathic
+            || persona.expertise.contains(&ExpertiseDomain::Ethics);
+        let proposal = tokens(&format!("{} {}", round.context.problem_statement, round.thesis));
+        if guards_people {
+            if let Some(term) = IRREVERSIBLE_HARM.iter().find(|t| contains_phrase(&proposal, t)) {
+                return Argument {
+                    persona: persona.id,
+                    stance: Stance::Oppose,
+                    claim: format!("{}: a proposta implica \"{}\", dano que nenhuma condição compensa", role, term),
+                    condition: None,
+                    contested: None,
+                    confidence: 0.9,
+                };
+            }
+        }
+
+        let own: Vec<&String> = round.context.constraints.iter().filter(|c| matches(c)).collect();
+        let opposing = round.conditions.iter()
+            .find_map(|condition| own.iter().find(|c| conflicts(condition, c)).map(|c| (condition, *c)));
+        if let Some((condition, constraint)) = opposing {
+            return Argument {
+                persona: persona.id,
+                stance: Stance::Oppose,
+                claim: format!("{}: \"{}\" contraria \"{}\"", role, condition, constraint),
+                condition: None,
+                contested: Some(condition.clone()),
+                confidence: (role_base_confidence(persona.role) + 0.1).min(1.0),
+            };
+        }
+
+        if let Some(constraint) = own.iter().find(|c| !addressed(c, round.conditions)) {
+            return Argument {
+                persona: persona.id,
+                stance: Stance::Oppose,
+                claim: format!("{}: a proposta não garante \"{}\"", role, constraint),
+                condition: Some((*constraint).clone()),
+                contested: None,
+                confidence: (role_base_confidence(persona.role) + 0.1).min(1.0),
+            };
+        }
+
+        let relevance = own.len() + usize::from(matches(&round.context.problem_statement));
+        let claim = match persona.role {
+            SocioEmotionalRole::Analytic => format!("{}: critérios verificáveis ({})", role, round.context.success_criteria.join(", ")),
+            SocioEmotionalRole::Empathic => format!("{}: os afetados estão protegidos pelas condições da tese", role),
+            SocioEmotionalRole::Creative => format!("{}: a tese abre alternativas sem violar restrições", role),
+            SocioEmotionalRole::Stoic => format!("{}: os riscos aceitos são proporcionais ao objetivo", role),
+            SocioEmotionalRole::Diplomatic => format!("{}: a tese concilia {} objeções anteriores", role,
+                round.previous.iter().filter(|a| a.stance == Stance::Oppose).count()),
+        };
+        Argument {
+            persona: persona.id,
+            stance: Stance::Support,
+            claim,
+            condition: None,
+            contested: None,
+            confidence: (role_base_confidence(persona.role) + 0.05 * relevance as f64).min(1.0),
+        }
+    }
+}
+
+#[async_trait]
+impl PersonaReasoner for RuleBasedReasoner {
+    async fn argue(&self, persona: &Persona, round: RoundContext<'_>) -> Result<Argument, SynthesisError> {
+        Ok(self.reason(persona, round))
+    }
+}
+
+// ===================== ADAPTADOR PARA MODELO LOCAL =====================
+
+/// Consulta um servidor de modelo local (API `/api/generate` estilo Ollama,
+/// HTTP sem TLS). Em falha de rede ou resposta fora do formato, recorre ao
+/// reasoner por regras.
+#[derive(Debug, Clone)]
+pub struct LocalModelReasoner {
+    pub address: SocketAddr,
+    pub model: String,
+    pub timeout: Duration,
+    client: reqwest::Client,
+    fallback: RuleBasedReasoner,
+}
+
+impl LocalModelReasoner {
+    pub fn new(address: SocketAddr, model: &str) -> Self {
+        Self {
+            address,
+            model: model.to_string(),
+            timeout: Duration::from_secs(20),
+            client: reqwest::Client::new(),
+            fallback: RuleBasedReasoner,
+        }
+    }
+
+    pub fn prompt(persona: &Persona, round: RoundContext<'_>) -> String {
+        let expertise: Vec<String> = persona.expertise.iter().map(|d| format!("{:?}", d)).collect();
+        format!(
+            "Você é uma persona {} com expertise em {}.\n\
+             Problema: {}\nRestrições: {}\nTese atual (rodada {}): {}\n\n\
+             Responda exatamente neste formato:\n\
+             STANCE: SUPPORT ou OPPOSE\nCONFIDENCE: número entre 0 e 1\nCLAIM: uma frase\n\
+             CONDITION: condição que resolveria a objeção, ou NONE\n\
+             CONTEST: condição da tese que você rejeita, ou NONE\n",
+            role_label(persona.role),
+            expertise.join(", "),
+            round.context.problem_statement,
+            round.context.constraints.join("; "),
+            round.round,
+            round.thesis,
+        )
+    }
+
+    async fn generate(&self, prompt: &str) -> Result<String, String> {
+        let body = serde_json::json!({
+            "model": self.model,
+            "prompt": prompt,
+            "stream": false,
+            "options": { "temperature": 0 },
+        });
+        // `SocketAddr` formata IPv6 entre colchetes, como a URL exige
+        let response = self.client
+            .post(format!("http://{}/api/generate", self.address))
+            .timeout(self.timeout)
+            .json(&body)
+            .send().await
+            .map_err(|e| if e.is_timeout() { "timeout do modelo local".to_string() } else { e.to_string() })?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+
+        let reply: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        reply.get("response").and_then(|r| r.as_str()).map(str::to_string)
+            .ok_or_else(|| "resposta sem campo `response`".to_string())
+    }
+}
+
+#[async_trait]
+impl PersonaReasoner for LocalModelReasoner {
+    async fn argue(&self, persona: &Persona, round: RoundContext<'_>) -> Result<Argument, SynthesisError> {
+        match self.generate(&Self::prompt(persona, round)).await
+            .and_then(|reply| parse_model_reply(persona.id, &reply))
+        {
+            Ok(argument) => Ok(argument),
+            Err(e) => {
+                log::warn!("Modelo local indisponível para {} ({}); usando regras", persona.id, e);
+                Ok(self.fallback.reason(persona, round))
+            }
+        }
+    }
+}
+
+/// Interpreta a resposta `STANCE/CONFIDENCE/CLAIM/CONDITION` do modelo
+pub fn parse_model_reply(persona: PersonaId, reply: &str) -> Result<Argument, String> {
+    let field = |name: &str| {
+        reply.lines()
+            .filter_map(|line| line.trim().split_once(':'))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim().to_string())
+    };
+
+    let stance = match field("STANCE").map(|s| s.to_uppercase()).as_deref() {
+        Some("SUPPORT") => Stance::Support,
+        Some("OPPOSE") => Stance::Oppose,
+        other => return Err(format!("STANCE inválido: {:?}", other)),
+    };
+    let confidence = field("CONFIDENCE")
+        .and_then(|c| c.parse::<f64>().ok())
+        .filter(|c| (0.0..=1.0).contains(c))
+        .ok_or("CONFIDENCE ausente ou fora de [0, 1]")?;
+    let claim = field("CLAIM").filter(|c| !c.is_empty()).ok_or("CLAIM ausente")?;
+    let optional = |name: &str| field(name)
+        .filter(|c| !c.is_empty() && !c.eq_ignore_ascii_case("none") && stance == Stance::Oppose);
+    let condition = optional("CONDITION");
+    let contested = optional("CONTEST");
+
+    Ok(Argument { persona, stance, claim, condition, contested, confidence })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn persona() -> Persona {
+        Persona { id: PersonaId([3; 32]), role: SocioEmotionalRole::Stoic, expertise: vec![ExpertiseDomain::Environment] }
+    }
+
+    fn context() -> SynthesisContext {
+        SynthesisContext {
+            problem_statement: "Ampliar a usina hidrelétrica".to_string(),
+            constraints: vec!["Garantir a vazão de água a jusante".to_string()],
+            success_criteria: Vec::new(),
+            stakeholder_keys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_model_reply() {
+        let reply = "STANCE: oppose\nCONFIDENCE: 0.8\nCLAIM: Falta garantir a vazão\nCONDITION: vazão mínima de 30%";
+        let argument = parse_model_reply(PersonaId([1; 32]), reply).unwrap();
+        assert_eq!(argument.stance, Stance::Oppose);
+        assert_eq!(argument.condition.as_deref(), Some("vazão mínima de 30%"));
+
+        assert!(parse_model_reply(PersonaId([1; 32]), "STANCE: SUPPORT\nCONFIDENCE: 2\nCLAIM: x").is_err());
+        assert!(parse_model_reply(PersonaId([1; 32]), "Concordo plenamente").is_err());
+    }
+
+    /// Atende uma única requisição `/api/generate` com `reply`
+    async fn serve_once(listener: TcpListener, reply: &'static str) {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 4096];
+        loop {
+            let read = socket.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request).to_lowercase();
+            let Some(split) = text.find("\r\n\r\n") else { continue };
+            let length = text.lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            if read == 0 || request.len() >= split + 4 + length {
+                break;
+            }
+        }
+        assert!(String::from_utf8_lossy(&request).starts_with("POST /api/generate"));
+        let body = serde_json::json!({ "response": reply }).to_string();
+        let response = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+        socket.write_all(response.as_bytes()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_local_model_reply_and_fallback() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve_once(listener, "STANCE: SUPPORT\nCONFIDENCE: 0.9\nCLAIM: Vazão preservada\nCONDITION: NONE"));
+
+        let context = context();
+        let round = RoundContext { context: &context, round: 1, thesis: "Adotar", conditions: &[], previous: &[] };
+        let argument = LocalModelReasoner::new(address, "llama3").argue(&persona(), round).await.unwrap();
+        assert_eq!(argument.stance, Stance::Support);
+        assert_eq!(argument.claim, "Vazão preservada");
+
+        // Servidor já encerrado: recorre às regras, que objetam pela restrição ambiental
+        let argument = LocalModelReasoner::new(address, "llama3").argue(&persona(), round).await.unwrap();
+        assert_eq!(argument.stance, Stance::Oppose);
+        assert_eq!(argument.condition.as_deref(), Some("Garantir a vazão de água a jusante"));
+    }
+
+    #[tokio::test]
+    async fn test_local_model_over_ipv6() {
+        // Sem IPv6 no ambiente não há o que testar
+        let Ok(listener) = TcpListener::bind("[::1]:0").await else { return };
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve_once(listener, "STANCE: OPPOSE\nCONFIDENCE: 0.7\nCLAIM: Vazão incerta\nCONDITION: vazão mínima"));
+
+        let context = context();
+        let round = RoundContext { context: &context, round: 1, thesis: "Adotar", conditions: &[], previous: &[] };
+        let argument = LocalModelReasoner::new(address, "llama3").argue(&persona(), round).await.unwrap();
+        assert_eq!(argument.claim, "Vazão incerta");
+        assert_eq!(argument.condition.as_deref(), Some("vazão mínima"));
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words() {
+        let legal = Persona { id: PersonaId([4; 32]), role: SocioEmotionalRole::Analytic, expertise: vec![ExpertiseDomain::Legislation] };
+        let context = SynthesisContext {
+            problem_statement: "Organizar o leilão de frequências".to_string(),
+            constraints: vec!["Publicar o leilão com antecedência".to_string(), "Cumprir a lei de licitações".to_string()],
+            success_criteria: Vec::new(),
+            stakeholder_keys: Vec::new(),
+        };
+        let round = RoundContext { context: &context, round: 1, thesis: "Adotar", conditions: &[], previous: &[] };
+        // "leilão" não é "lei": a objeção é pela restrição legal
+        let argument = RuleBasedReasoner.reason(&legal, round);
+        assert_eq!(argument.condition.as_deref(), Some("Cumprir a lei de licitações"));
+
+        // Condição redigida de outro modo, com as mesmas palavras de conteúdo, cobre a restrição
+        let conditions = vec!["Cumprir as licitações conforme a lei".to_string()];
+        let round = RoundContext { conditions: &conditions, ..round };
+        assert_eq!(RuleBasedReasoner.reason(&legal, round).stance, Stance::Support);
+        assert!(contains_phrase(&tokens("Houve perda de vidas."), "perda de vidas"));
+        assert!(!contains_phrase(&tokens("A perda de vidraças"), "perda de vidas"));
+    }
+
+    #[test]
+    fn test_opposite_conditions_conflict() {
+        assert!(conflicts("Ampliar a capacidade da rede", "Reduzir a capacidade instalada"));
+        assert!(!conflicts("Ampliar a capacidade da rede", "Ampliar a capacidade instalada"));
+        assert!(!conflicts("Ampliar a capacidade da rede", "Reduzir as emissões"));
+        assert!(!conflicts("Cumprir a lei", "Reduzir a capacidade"));
+    }
+}
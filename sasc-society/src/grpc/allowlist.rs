@@ -0,0 +1,367 @@
+//! Allowlist de chaves Dilithium5 - INV-3 Compliance
+//! Arquivo JSON assinado pela chave Prince, recarregado a quente
+//!
+//! Novas versões são montadas e assinadas fora do servidor (binário
+//! `sasc_allowlist`); o servidor apenas verifica e aplica.
+
+use crate::hsm_signer::{HsmError, HsmSigner};
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey, DetachedSignature};
+use pqcrypto_traits::sign::{PublicKey as _, DetachedSignature as _};
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Separador de domínio da assinatura do documento
+const ALLOWLIST_DOMAIN: &[u8] = b"sasc-society-allowlist-v1";
+
+#[derive(Error, Debug)]
+pub enum AllowlistError {
+    #[error("Falha de E/S em {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("Allowlist malformada: {0}")]
+    Malformed(String),
+    #[error("Assinatura Prince da allowlist inválida")]
+    InvalidSignature,
+    #[error("Versão {found} da allowlist anterior à carregada ({current})")]
+    Rollback { current: u64, found: u64 },
+    #[error("Chave não consta na allowlist")]
+    Unknown,
+    #[error("Chave revogada em {0}")]
+    Revoked(u64),
+    #[error("Chave expirada em {0}")]
+    Expired(u64),
+    #[error("Chave já presente na allowlist")]
+    Duplicate,
+    #[error("Falha ao assinar a allowlist: {0}")]
+    Signer(#[from] HsmError),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllowlistEntry {
+    /// Chave pública Dilithium5 em hexadecimal
+    pub public_key: String,
+    pub label: String,
+    pub added_at: u64,
+    pub expires_at: Option<u64>,
+    pub revoked_at: Option<u64>,
+}
+
+impl AllowlistEntry {
+    pub fn new(public_key: &PublicKey, label: &str, added_at: u64, expires_at: Option<u64>) -> Self {
+        Self {
+            public_key: hex::encode(public_key.as_bytes()),
+            label: label.to_string(),
+            added_at,
+            expires_at,
+            revoked_at: None,
+        }
+    }
+
+    /// Vigência da entrada no instante `now` (segundos UNIX)
+    pub fn check(&self, now: u64) -> Result<(), AllowlistError> {
+        if let Some(revoked_at) = self.revoked_at {
+            if revoked_at <= now {
+                return Err(AllowlistError::Revoked(revoked_at));
+            }
+        }
+        if let Some(expires_at) = self.expires_at {
+            if expires_at <= now {
+                return Err(AllowlistError::Expired(expires_at));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Conteúdo coberto pela assinatura. `version` só cresce: um arquivo com
+/// versão menor que a carregada é tratado como rollback.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AllowlistDocument {
+    pub version: u64,
+    pub issued_at: u64,
+    pub entries: Vec<AllowlistEntry>,
+}
+
+impl AllowlistDocument {
+    /// Próxima versão com `entry` incluída
+    pub fn with_entry(&self, entry: AllowlistEntry, now: u64) -> Result<Self, AllowlistError> {
+        if self.entries.iter().any(|e| e.public_key.eq_ignore_ascii_case(&entry.public_key)) {
+            return Err(AllowlistError::Duplicate);
+        }
+        let mut next = self.next(now);
+        next.entries.push(entry);
+        Ok(next)
+    }
+
+    /// Próxima versão com `pubkey` revogada a partir de `now`. A entrada
+    /// permanece para auditoria.
+    pub fn with_revocation(&self, pubkey: &PublicKey, now: u64) -> Result<Self, AllowlistError> {
+        let key = hex::encode(pubkey.as_bytes());
+        let mut next = self.next(now);
+        let entry = next.entries.iter_mut()
+            .find(|e| e.public_key.eq_ignore_ascii_case(&key))
+            .ok_or(AllowlistError::Unknown)?;
+        entry.revoked_at.get_or_insert(now);
+        Ok(next)
+    }
+
+    fn next(&self, now: u64) -> Self {
+        Self { version: self.version + 1, issued_at: now, entries: self.entries.clone() }
+    }
+
+    fn signing_message(&self) -> Result<Vec<u8>, AllowlistError> {
+        let mut message = ALLOWLIST_DOMAIN.to_vec();
+        message.extend(serde_json::to_vec(self).map_err(|e| AllowlistError::Malformed(e.to_string()))?);
+        Ok(message)
+    }
+}
+
+/// Formato do arquivo: documento e assinatura Prince em hexadecimal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedAllowlist {
+    pub document: AllowlistDocument,
+    pub signature: String,
+}
+
+impl SignedAllowlist {
+    pub fn sign(document: AllowlistDocument, prince_secret: &SecretKey) -> Result<Self, AllowlistError> {
+        let signature = dilithium5::detached_sign(&document.signing_message()?, prince_secret);
+        Ok(Self {
+            document,
+            signature: hex::encode(signature.as_bytes()),
+        })
+    }
+
+    /// Assina com a chave Prince guardada no HSM do operador
+    pub fn sign_with(document: AllowlistDocument, signer: &dyn HsmSigner) -> Result<Self, AllowlistError> {
+        let signature = signer.sign(&document.signing_message()?)?;
+        Ok(Self {
+            document,
+            signature: hex::encode(signature),
+        })
+    }
+
+    pub fn verify(&self, prince_pubkey: &PublicKey) -> Result<(), AllowlistError> {
+        let bytes = hex::decode(&self.signature).map_err(|_| AllowlistError::InvalidSignature)?;
+        let signature = DetachedSignature::from_bytes(&bytes).map_err(|_| AllowlistError::InvalidSignature)?;
+        dilithium5::verify_detached_signature(&signature, &self.document.signing_message()?, prince_pubkey)
+            .map_err(|_| AllowlistError::InvalidSignature)
+    }
+
+    /// Lê e verifica um arquivo assinado. Arquivo inexistente = `None`.
+    pub fn read(path: impl AsRef<Path>, prince_pubkey: &PublicKey) -> Result<Option<Self>, AllowlistError> {
+        let Some(bytes) = read_file(path.as_ref())? else {
+            return Ok(None);
+        };
+        let signed = parse_signed(&bytes)?;
+        signed.verify(prince_pubkey)?;
+        Ok(Some(signed))
+    }
+
+    /// Grava o arquivo de forma atômica (tmp + rename)
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), AllowlistError> {
+        write_signed(path.as_ref(), self).map(|_| ())
+    }
+}
+
+#[derive(Debug)]
+struct LoadedAllowlist {
+    document: AllowlistDocument,
+    /// Índice chave (bytes) -> posição em `document.entries`
+    index: HashMap<Vec<u8>, usize>,
+    /// BLAKE3 do arquivo aplicado, para detectar alterações
+    file_digest: Option<[u8; 32]>,
+}
+
+impl LoadedAllowlist {
+    fn new(document: AllowlistDocument, file_digest: Option<[u8; 32]>) -> Result<Self, AllowlistError> {
+        let mut index = HashMap::new();
+        for (position, entry) in document.entries.iter().enumerate() {
+            let key = hex::decode(&entry.public_key)
+                .map_err(|_| AllowlistError::Malformed(format!("Chave não-hexadecimal: {}", entry.label)))?;
+            PublicKey::from_bytes(&key)
+                .map_err(|_| AllowlistError::Malformed(format!("Chave Dilithium5 inválida: {}", entry.label)))?;
+            if index.insert(key, position).is_some() {
+                return Err(AllowlistError::Malformed(format!("Chave duplicada: {}", entry.label)));
+            }
+        }
+        Ok(Self { document, index, file_digest })
+    }
+}
+
+/// Allowlist persistente. Leituras usam a cópia em memória; novas versões
+/// chegam já assinadas. A maior versão aceita fica em `<path>.version`, de
+/// modo que o rollback é detectado também entre reinícios.
+pub struct PubkeyAllowlist {
+    path: PathBuf,
+    prince_pubkey: PublicKey,
+    loaded: RwLock<LoadedAllowlist>,
+}
+
+impl PubkeyAllowlist {
+    /// Carrega e verifica o arquivo. Arquivo inexistente = allowlist vazia,
+    /// desde que nenhuma versão tenha sido aceita antes.
+    pub fn load(path: impl AsRef<Path>, prince_pubkey: PublicKey) -> Result<Self, AllowlistError> {
+        let path = path.as_ref().to_path_buf();
+        let loaded = match read_file(&path)? {
+            Some(bytes) => LoadedAllowlist::new(verified(&bytes, &prince_pubkey)?, Some(file_digest(&bytes)))?,
+            None => LoadedAllowlist::new(AllowlistDocument::default(), None)?,
+        };
+        let highest = read_version_mark(&version_path(&path))?;
+        if loaded.document.version < highest {
+            return Err(AllowlistError::Rollback { current: highest, found: loaded.document.version });
+        }
+        if loaded.document.version > highest {
+            write_version_mark(&version_path(&path), loaded.document.version)?;
+        }
+        Ok(Self { path, prince_pubkey, loaded: RwLock::new(loaded) })
+    }
+
+    pub fn version(&self) -> u64 {
+        self.read().document.version
+    }
+
+    pub fn document(&self) -> AllowlistDocument {
+        self.read().document.clone()
+    }
+
+    pub fn entries(&self) -> Vec<AllowlistEntry> {
+        self.read().document.entries.clone()
+    }
+
+    /// Autoriza `pubkey` no instante `now` (segundos UNIX)
+    pub fn check(&self, pubkey: &PublicKey, now: u64) -> Result<(), AllowlistError> {
+        let loaded = self.read();
+        let position = loaded.index.get(pubkey.as_bytes()).ok_or(AllowlistError::Unknown)?;
+        loaded.document.entries[*position].check(now)
+    }
+
+    pub fn is_allowed(&self, pubkey: &PublicKey, now: u64) -> bool {
+        self.check(pubkey, now).is_ok()
+    }
+
+    /// Recarrega se o arquivo mudou desde a última leitura. Devolve `true`
+    /// quando uma nova versão foi aplicada; em erro, mantém a versão atual.
+    pub fn reload(&self) -> Result<bool, AllowlistError> {
+        let Some(bytes) = read_file(&self.path)? else {
+            return Ok(false);
+        };
+        let digest = file_digest(&bytes);
+        if self.read().file_digest == Some(digest) {
+            return Ok(false);
+        }
+        let document = verified(&bytes, &self.prince_pubkey)?;
+
+        let mut loaded = self.loaded.write().unwrap_or_else(|e| e.into_inner());
+        if document.version < loaded.document.version {
+            return Err(AllowlistError::Rollback { current: loaded.document.version, found: document.version });
+        }
+        let changed = document != loaded.document;
+        let next = LoadedAllowlist::new(document, Some(digest))?;
+        if next.document.version > loaded.document.version {
+            write_version_mark(&version_path(&self.path), next.document.version)?;
+        }
+        *loaded = next;
+        Ok(changed)
+    }
+
+    /// Verifica o arquivo periodicamente e aplica novas versões
+    pub fn spawn_reload_task(self: &Arc<Self>, period: Duration) -> tokio::task::JoinHandle<()> {
+        let allowlist = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                ticker.tick().await;
+                match allowlist.reload() {
+                    Ok(true) => log::info!("Allowlist recarregada (versão {})", allowlist.version()),
+                    Ok(false) => {}
+                    Err(e) => log::error!("Allowlist {} rejeitada: {}", allowlist.path.display(), e),
+                }
+            }
+        })
+    }
+
+    /// Aplica uma versão assinada fora do servidor. A versão tem de ser
+    /// maior que a carregada.
+    pub fn publish(&self, signed: &SignedAllowlist) -> Result<(), AllowlistError> {
+        signed.verify(&self.prince_pubkey)?;
+        let mut loaded = self.loaded.write().unwrap_or_else(|e| e.into_inner());
+        if signed.document.version <= loaded.document.version {
+            return Err(AllowlistError::Rollback { current: loaded.document.version, found: signed.document.version });
+        }
+        let next = LoadedAllowlist::new(signed.document.clone(), None)?;
+        // Arquivo antes da marca: uma falha entre os dois nunca deixa a
+        // marca à frente do arquivo
+        let digest = write_signed(&self.path, signed)?;
+        write_version_mark(&version_path(&self.path), signed.document.version)?;
+        *loaded = LoadedAllowlist { file_digest: Some(digest), ..next };
+        Ok(())
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, LoadedAllowlist> {
+        self.loaded.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+fn read_file(path: &Path) -> Result<Option<Vec<u8>>, AllowlistError> {
+    match std::fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(AllowlistError::Io(path.to_path_buf(), e)),
+    }
+}
+
+fn parse_signed(bytes: &[u8]) -> Result<SignedAllowlist, AllowlistError> {
+    serde_json::from_slice(bytes).map_err(|e| AllowlistError::Malformed(e.to_string()))
+}
+
+fn verified(bytes: &[u8], prince_pubkey: &PublicKey) -> Result<AllowlistDocument, AllowlistError> {
+    let signed = parse_signed(bytes)?;
+    signed.verify(prince_pubkey)?;
+    Ok(signed.document)
+}
+
+fn version_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".version");
+    PathBuf::from(name)
+}
+
+/// Maior versão já aceita; 0 se nenhuma
+fn read_version_mark(path: &Path) -> Result<u64, AllowlistError> {
+    match read_file(path)? {
+        Some(bytes) => String::from_utf8_lossy(&bytes).trim().parse()
+            .map_err(|_| AllowlistError::Malformed(format!("Marca de versão ilegível em {}", path.display()))),
+        None => Ok(0),
+    }
+}
+
+fn write_version_mark(path: &Path, version: u64) -> Result<(), AllowlistError> {
+    write_atomic(path, version.to_string().as_bytes())
+}
+
+fn file_digest(bytes: &[u8]) -> [u8; 32] {
+    *blake3::hash(bytes).as_bytes()
+}
+
+fn write_signed(path: &Path, signed: &SignedAllowlist) -> Result<[u8; 32], AllowlistError> {
+    let json = serde_json::to_vec_pretty(signed).map_err(|e| AllowlistError::Malformed(e.to_string()))?;
+    write_atomic(path, &json)?;
+    Ok(file_digest(&json))
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), AllowlistError> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    std::fs::write(&tmp, bytes)
+        .and_then(|_| std::fs::rename(&tmp, path))
+        .map_err(|e| AllowlistError::Io(path.to_path_buf(), e))
+}
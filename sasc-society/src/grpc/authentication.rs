@@ -1,17 +1,135 @@
 //! Autenticação PQC - INV-1 Non-Repudiation
 //! Verifica assinaturas Dilithium5 e integridade BLAKE3
 
+use tonic::metadata::{BinaryMetadataValue, MetadataValue};
 use tonic::{Request, Status};
-use pqcrypto_dilithium::dilithium5::{PublicKey, DetachedSignature};
+use pqcrypto_dilithium::dilithium5::{PublicKey, SecretKey, DetachedSignature};
 use pqcrypto_traits::sign::{PublicKey as _, DetachedSignature as _};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-pub async fn authenticate_request<T>(
+use crate::grpc::allowlist::{AllowlistError, PubkeyAllowlist, unix_now};
+use crate::grpc::sasc_society_proto::ProcessDecisionResponse;
+
+/// Janela aceita entre o relógio do cliente e o do servidor
+pub const MAX_CLOCK_DRIFT: Duration = Duration::from_secs(300);
+
+/// Separador de domínio da mensagem assinada pelo cliente
+const REQUEST_SIGNING_DOMAIN: &[u8] = b"sasc-society-request-v1";
+
+/// Separador de domínio da resposta assinada pela chave Prince
+const RESPONSE_SIGNING_DOMAIN: &[u8] = b"sasc-society-response-v1";
+
+/// BLAKE3 do corpo protobuf codificado
+pub fn request_digest<T: prost::Message>(message: &T) -> [u8; 32] {
+    *blake3::hash(&message.encode_to_vec()).as_bytes()
+}
+
+/// Mensagem coberta pela assinatura: domínio || timestamp (LE) || digest
+pub fn signing_message(timestamp: u64, digest: &[u8; 32]) -> Vec<u8> {
+    let mut message = REQUEST_SIGNING_DOMAIN.to_vec();
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message.extend_from_slice(digest);
+    message
+}
+
+/// Mensagem da assinatura Prince: domínio || BLAKE3 da resposta com o
+/// campo `signature` vazio
+pub fn response_signing_message(response: &ProcessDecisionResponse) -> Vec<u8> {
+    let unsigned = ProcessDecisionResponse { signature: Vec::new(), ..response.clone() };
+    let mut message = RESPONSE_SIGNING_DOMAIN.to_vec();
+    message.extend_from_slice(&request_digest(&unsigned));
+    message
+}
+
+/// Lado cliente: confere a assinatura Prince de uma resposta
+pub fn verify_response(response: &ProcessDecisionResponse, prince_pubkey: &PublicKey) -> Result<(), Status> {
+    let signature = DetachedSignature::from_bytes(&response.signature)
+        .map_err(|_| Status::data_loss("Assinatura Prince malformada"))?;
+    pqcrypto_dilithium::dilithium5::verify_detached_signature(&signature, &response_signing_message(response), prince_pubkey)
+        .map_err(|_| Status::data_loss("Assinatura Prince não confere"))
+}
+
+/// Lado cliente: preenche os metadados de autenticação de `request`
+pub fn sign_request<T: prost::Message>(
+    request: &mut Request<T>,
+    public_key: &PublicKey,
+    secret_key: &SecretKey,
+    timestamp: u64,
+) {
+    let digest = request_digest(request.get_ref());
+    let signature = pqcrypto_dilithium::dilithium5::detached_sign(&signing_message(timestamp, &digest), secret_key);
+
+    let metadata = request.metadata_mut();
+    metadata.insert("x-request-timestamp", MetadataValue::from(timestamp));
+    metadata.insert(
+        "x-blake3-integrity",
+        hex::encode(digest).parse().expect("hex é ASCII válido"),
+    );
+    metadata.insert_bin("x-pqc-signature-bin", BinaryMetadataValue::from_bytes(signature.as_bytes()));
+    metadata.insert_bin("x-requestor-pubkey-bin", BinaryMetadataValue::from_bytes(public_key.as_bytes()));
+}
+
+/// Requests já aceitas, por (chave, timestamp, digest). Entradas fora da
+/// janela de drift são descartadas: o timestamp já as rejeitaria.
+#[derive(Debug)]
+pub struct ReplayCache {
+    window: Duration,
+    seen: Mutex<HashSet<([u8; 32], u64, [u8; 32])>>,
+}
+
+impl ReplayCache {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            seen: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Registra a request; `false` se ela já foi vista
+    pub fn check_and_insert(&self, pubkey: &PublicKey, timestamp: u64, digest: [u8; 32], now: u64) -> bool {
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        let horizon = now.saturating_sub(self.window.as_secs());
+        seen.retain(|(_, seen_at, _)| *seen_at >= horizon);
+        seen.insert((*blake3::hash(pubkey.as_bytes()).as_bytes(), timestamp, digest))
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Estado compartilhado da autenticação: allowlist e cache anti-replay
+pub struct Authenticator {
+    allowlist: Arc<PubkeyAllowlist>,
+    replay_cache: ReplayCache,
+}
+
+impl Authenticator {
+    pub fn new(allowlist: Arc<PubkeyAllowlist>) -> Self {
+        Self {
+            allowlist,
+            replay_cache: ReplayCache::new(MAX_CLOCK_DRIFT),
+        }
+    }
+
+    pub fn allowlist(&self) -> &Arc<PubkeyAllowlist> {
+        &self.allowlist
+    }
+}
+
+pub async fn authenticate_request<T: prost::Message>(
     request: Request<T>,
-    _prince_pubkey: &PublicKey,
+    authenticator: &Authenticator,
 ) -> Result<Request<T>, Status> {
 
     let metadata = request.metadata();
+    let now = unix_now();
 
     // 1. Timestamp (prevenção replay)
     let timestamp_str = metadata.get("x-request-timestamp")
@@ -22,59 +140,57 @@ pub async fn authenticate_request<T>(
     let timestamp = timestamp_str.parse::<u64>()
         .map_err(|_| Status::unauthenticated("Timestamp não numérico"))?;
 
-    let request_time = UNIX_EPOCH + Duration::from_secs(timestamp);
-    let now = SystemTime::now();
-
-    let drift = if now > request_time {
-        now.duration_since(request_time).unwrap()
-    } else {
-        request_time.duration_since(now).unwrap()
-    };
-
-    if drift > Duration::from_secs(300) {
+    if now.abs_diff(timestamp) > MAX_CLOCK_DRIFT.as_secs() {
         return Err(Status::unauthenticated("Request fora da janela de tempo"));
     }
 
-    // 2. Assinatura PQC
-    let signature_bytes = metadata.get("x-pqc-signature")
-        .ok_or_else(|| Status::unauthenticated("Assinatura ausente"))?
-        .as_bytes();
-
-    let signature = DetachedSignature::from_bytes(signature_bytes)
-        .map_err(|_| Status::unauthenticated("Formato de assinatura inválido"))?;
-
-    // 3. Chave pública do solicitante
-    let pubkey_bytes = metadata.get("x-requestor-pubkey")
+    // 2. Chave pública do solicitante
+    let pubkey_bytes = metadata.get_bin("x-requestor-pubkey-bin")
         .ok_or_else(|| Status::unauthenticated("Chave pública ausente"))?
-        .as_bytes();
+        .to_bytes()
+        .map_err(|_| Status::unauthenticated("Chave pública mal codificada"))?;
 
-    let requestor_pubkey = PublicKey::from_bytes(pubkey_bytes)
+    let requestor_pubkey = PublicKey::from_bytes(&pubkey_bytes)
         .map_err(|_| Status::unauthenticated("Formato de chave pública inválido"))?;
 
-    // 4. Verificar whitelist (INV-3 compliance)
-    if !is_pubkey_whitelisted(&requestor_pubkey) {
-        return Err(Status::permission_denied("Chave pública não autorizada"));
-    }
+    // 3. Verificar allowlist (INV-3 compliance)
+    authenticator.allowlist.check(&requestor_pubkey, now).map_err(|e| match e {
+        AllowlistError::Unknown => Status::permission_denied("Chave pública não autorizada"),
+        other => Status::permission_denied(other.to_string()),
+    })?;
+
+    // 4. Assinatura PQC
+    let signature_bytes = metadata.get_bin("x-pqc-signature-bin")
+        .ok_or_else(|| Status::unauthenticated("Assinatura ausente"))?
+        .to_bytes()
+        .map_err(|_| Status::unauthenticated("Assinatura mal codificada"))?;
+
+    let signature = DetachedSignature::from_bytes(&signature_bytes)
+        .map_err(|_| Status::unauthenticated("Formato de assinatura inválido"))?;
 
-    // 5. Reconstruir mensagem assinada
+    // 5. Recalcula o hash de integridade sobre o corpo recebido
     let integrity_hash_hex = metadata.get("x-blake3-integrity")
         .ok_or_else(|| Status::unauthenticated("Hash de integridade ausente"))?
         .to_str()
         .map_err(|_| Status::unauthenticated("Hash de integridade inválido"))?;
 
-    let integrity_hash = hex::decode(integrity_hash_hex)
+    let declared_hash = hex::decode(integrity_hash_hex)
         .map_err(|_| Status::unauthenticated("Hash de integridade não-hexadecimal"))?;
 
-    // 6. Verificar assinatura do hash
-    if pqcrypto_dilithium::dilithium5::verify_detached_signature(&signature, &integrity_hash, &requestor_pubkey).is_err() {
+    let digest = request_digest(request.get_ref());
+    if blake3::Hash::from(digest) != declared_hash[..] {
+        return Err(Status::unauthenticated("Hash de integridade não confere com o corpo"));
+    }
+
+    // 6. Verificar assinatura sobre timestamp e hash
+    if pqcrypto_dilithium::dilithium5::verify_detached_signature(&signature, &signing_message(timestamp, &digest), &requestor_pubkey).is_err() {
         return Err(Status::unauthenticated("Assinatura PQC inválida"));
     }
 
-    Ok(request)
-}
+    // 7. Replay: registrado só após a assinatura, para não ser envenenado
+    if !authenticator.replay_cache.check_and_insert(&requestor_pubkey, timestamp, digest, now) {
+        return Err(Status::unauthenticated("Request repetida (replay)"));
+    }
 
-fn is_pubkey_whitelisted(pubkey: &PublicKey) -> bool {
-    // Em produção, consulta whitelist no WORM ledger
-    // Para este bloco, verificamos apenas se a chave não está vazia como placeholder de segurança
-    !pubkey.as_bytes().iter().all(|&b| b == 0)
+    Ok(request)
 }
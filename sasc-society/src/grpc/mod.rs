@@ -6,6 +6,7 @@ pub mod google {
     }
 }
 
+pub mod allowlist;
 pub mod authentication;
 pub mod server;
 
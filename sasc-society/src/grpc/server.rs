@@ -4,14 +4,18 @@
 use tonic::{transport::Server, Request, Response, Status};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
+use tokio::sync::{broadcast, mpsc};
 use tokio::time::timeout;
+use tokio_stream::wrappers::ReceiverStream;
 use tower::ServiceBuilder;
 use tower::limit::RateLimitLayer;
 use tower::buffer::BufferLayer;
-use log::info;
+use log::{info, warn};
 
-use crate::engine::{SoTOrchestrator, SoTDecisionRequest, SoTDecisionResponse};
-use crate::grpc::authentication::authenticate_request;
+use crate::engine::{SoTOrchestrator, SoTDecisionRequest, SoTDecisionResponse, HardFreezeEvent};
+use crate::grpc::allowlist::PubkeyAllowlist;
+use crate::grpc::authentication::{authenticate_request, response_signing_message, Authenticator};
+use crate::hsm_signer::HsmSigner;
 use crate::grpc::sasc_society_proto::sot_orchestrator_server::{SotOrchestrator as SotOrchestratorTrait, SotOrchestratorServer};
 use crate::grpc::sasc_society_proto::{
     ProcessDecisionRequest, ProcessDecisionResponse,
@@ -24,19 +28,31 @@ use crate::grpc::sasc_society_proto::{
 
 const GRPC_TIMEOUT: Duration = Duration::from_secs(120);
 
+/// Intervalo de verificação do arquivo da allowlist
+const ALLOWLIST_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Intervalo entre atualizações do StreamMetrics
+const METRICS_STREAM_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Buffer dos canais que alimentam os streams de resposta
+const STREAM_BUFFER: usize = 32;
+
 pub struct GrpcServer {
     orchestrator: Arc<SoTOrchestrator>,
-    prince_pubkey: pqcrypto_dilithium::dilithium5::PublicKey,
+    authenticator: Arc<Authenticator>,
+    signer: Arc<dyn HsmSigner>,
 }
 
 impl GrpcServer {
     pub fn new(
         orchestrator: Arc<SoTOrchestrator>,
-        prince_pubkey: pqcrypto_dilithium::dilithium5::PublicKey,
+        authenticator: Arc<Authenticator>,
+        signer: Arc<dyn HsmSigner>,
     ) -> Self {
         Self {
             orchestrator,
-            prince_pubkey,
+            authenticator,
+            signer,
         }
     }
 }
@@ -49,7 +65,7 @@ impl SotOrchestratorTrait for GrpcServer {
     ) -> Result<Response<ProcessDecisionResponse>, Status> {
 
         // 1. Autenticação PQC (INV-1 non-repudiation)
-        let request = authenticate_request(request, &self.prince_pubkey).await?;
+        let request = authenticate_request(request, &self.authenticator).await?;
         let inner_request = request.into_inner();
 
         // 2. Conversão para Rust structs
@@ -70,50 +86,206 @@ impl SotOrchestratorTrait for GrpcServer {
 
         // 5. Assinatura Prince (INV-2 proveniência)
         let proto_response = convert_decision_response_to_proto(response)?;
-        let signed_response = sign_response_with_prince(proto_response)?;
+        let signed_response = sign_response_with_prince(proto_response, self.signer.as_ref())?;
 
         Ok(Response::new(signed_response))
     }
 
     async fn get_decision_status(
         &self,
-        _request: Request<GetDecisionStatusRequest>,
+        request: Request<GetDecisionStatusRequest>,
     ) -> Result<Response<GetDecisionStatusResponse>, Status> {
-        Err(Status::unimplemented("Not implemented in Block #48"))
+        let request = authenticate_request(request, &self.authenticator).await?;
+        let decision_id = parse_decision_id(&request.into_inner().decision_id)?;
+
+        let progress = self.orchestrator.decision_status(&decision_id).await
+            .ok_or_else(|| Status::not_found("Decisão não encontrada"))?;
+
+        Ok(Response::new(GetDecisionStatusResponse {
+            status: convert_status_to_proto(&progress.status),
+            progress_percentage: progress.progress_percentage,
+            estimated_completion: Some(to_timestamp(progress.estimated_completion)),
+        }))
     }
 
-    type GetDecisionHistoryStream = tokio_stream::wrappers::ReceiverStream<Result<DecisionRecord, Status>>;
+    type GetDecisionHistoryStream = ReceiverStream<Result<DecisionRecord, Status>>;
 
     async fn get_decision_history(
         &self,
-        _request: Request<GetDecisionHistoryRequest>,
+        request: Request<GetDecisionHistoryRequest>,
     ) -> Result<Response<Self::GetDecisionHistoryStream>, Status> {
-        Err(Status::unimplemented("Not implemented in Block #48"))
+        let request = authenticate_request(request, &self.authenticator).await?;
+        let inner = request.into_inner();
+
+        let start = inner.start_time.map(from_timestamp);
+        let end = inner.end_time.map(from_timestamp);
+        if let (Some(start), Some(end)) = (start, end) {
+            if start > end {
+                return Err(Status::invalid_argument("start_time posterior a end_time"));
+            }
+        }
+
+        let records = self.orchestrator.decision_history(start, end, inner.limit as usize).await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        tokio::spawn(async move {
+            for record in records {
+                let record = DecisionRecord {
+                    decision_id: record.decision_id.to_vec(),
+                    timestamp: Some(to_timestamp(record.timestamp)),
+                    summary: record.summary,
+                    final_status: convert_status_to_proto(&record.final_status),
+                };
+                if tx.send(Ok(record)).await.is_err() {
+                    break; // Cliente desconectou
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
     }
 
     async fn request_human_escalation(
         &self,
-        _request: Request<RequestHumanEscalationRequest>,
+        request: Request<RequestHumanEscalationRequest>,
     ) -> Result<Response<RequestHumanEscalationResponse>, Status> {
-        Err(Status::unimplemented("Not implemented in Block #48"))
+        let request = authenticate_request(request, &self.authenticator).await?;
+        let inner = request.into_inner();
+        let decision_id = parse_decision_id(&inner.decision_id)?;
+
+        if inner.reason.trim().is_empty() {
+            return Err(Status::invalid_argument("Motivo do escalonamento ausente"));
+        }
+
+        let ticket = self.orchestrator
+            .request_escalation(decision_id, &inner.reason, inner.urgency)
+            .await
+            .map_err(|e| match e {
+                crate::engine::OrchestrationError::DecisionNotFound(_) => Status::not_found(e.to_string()),
+                _ => Status::internal(format!("Erro orquestrador: {}", e)),
+            })?;
+
+        Ok(Response::new(RequestHumanEscalationResponse {
+            accepted: true,
+            ticket_id: ticket.ticket_id,
+        }))
     }
 
-    type StreamMetricsStream = tokio_stream::wrappers::ReceiverStream<Result<MetricsUpdate, Status>>;
+    type StreamMetricsStream = ReceiverStream<Result<MetricsUpdate, Status>>;
 
     async fn stream_metrics(
         &self,
-        _request: Request<MetricsRequest>,
+        request: Request<MetricsRequest>,
     ) -> Result<Response<Self::StreamMetricsStream>, Status> {
-        Err(Status::unimplemented("Not implemented in Block #48"))
+        let request = authenticate_request(request, &self.authenticator).await?;
+        let metric_names = request.into_inner().metric_names;
+
+        // Nomes desconhecidos são rejeitados já na abertura do stream
+        let available = self.orchestrator.metrics_snapshot().await;
+        if let Some(unknown) = metric_names.iter().find(|name| !available.contains_key(*name)) {
+            return Err(Status::invalid_argument(format!("Métrica desconhecida: {}", unknown)));
+        }
+
+        let orchestrator = self.orchestrator.clone();
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(METRICS_STREAM_INTERVAL);
+            loop {
+                ticker.tick().await;
+                let mut metrics = orchestrator.metrics_snapshot().await;
+                if !metric_names.is_empty() {
+                    metrics.retain(|name, _| metric_names.contains(name));
+                }
+                let update = MetricsUpdate {
+                    timestamp: Some(to_timestamp(SystemTime::now())),
+                    metrics,
+                };
+                if tx.send(Ok(update)).await.is_err() {
+                    break; // Cliente desconectou
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
     }
 
-    type SubscribeHardFreezeAlertsStream = tokio_stream::wrappers::ReceiverStream<Result<HardFreezeAlert, Status>>;
+    type SubscribeHardFreezeAlertsStream = ReceiverStream<Result<HardFreezeAlert, Status>>;
 
     async fn subscribe_hard_freeze_alerts(
         &self,
-        _request: Request<HardFreezeAlertRequest>,
+        request: Request<HardFreezeAlertRequest>,
     ) -> Result<Response<Self::SubscribeHardFreezeAlertsStream>, Status> {
-        Err(Status::unimplemented("Not implemented in Block #48"))
+        authenticate_request(request, &self.authenticator).await?;
+
+        // Assina antes de consultar o freeze vigente para não perder alertas
+        let mut alerts = self.orchestrator.subscribe_hard_freeze_alerts();
+        let active = self.orchestrator.active_hard_freeze().await;
+
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        tokio::spawn(async move {
+            if let Some(event) = active {
+                if tx.send(Ok(convert_hard_freeze_to_proto(event))).await.is_err() {
+                    return;
+                }
+            }
+            loop {
+                match alerts.recv().await {
+                    Ok(event) => {
+                        if tx.send(Ok(convert_hard_freeze_to_proto(event))).await.is_err() {
+                            break; // Cliente desconectou
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Assinante de Hard Freeze perdeu {} alertas", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+fn parse_decision_id(bytes: &[u8]) -> Result<[u8; 32], Status> {
+    bytes.try_into()
+        .map_err(|_| Status::invalid_argument("decision_id deve ter 32 bytes"))
+}
+
+fn to_timestamp(time: SystemTime) -> prost_types::Timestamp {
+    let since_epoch = time.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+    prost_types::Timestamp {
+        seconds: since_epoch.as_secs() as i64,
+        nanos: since_epoch.subsec_nanos() as i32,
+    }
+}
+
+fn from_timestamp(ts: prost_types::Timestamp) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::new(ts.seconds.max(0) as u64, ts.nanos.max(0) as u32)
+}
+
+fn convert_hard_freeze_to_proto(event: HardFreezeEvent) -> HardFreezeAlert {
+    HardFreezeAlert {
+        timestamp: Some(to_timestamp(event.timestamp)),
+        reason: event.reason,
+        current_phi: event.current_phi,
+        duration: Some(prost_types::Duration {
+            seconds: event.duration.as_secs() as i64,
+            nanos: event.duration.subsec_nanos() as i32,
+        }),
+    }
+}
+
+fn convert_status_to_proto(status: &crate::engine::DecisionStatus) -> i32 {
+    match status {
+        crate::engine::DecisionStatus::GatheringPerspectives => crate::grpc::sasc_society_proto::DecisionStatus::GatheringPerspectives as i32,
+        crate::engine::DecisionStatus::DiversityAssessment => crate::grpc::sasc_society_proto::DecisionStatus::DiversityAssessment as i32,
+        crate::engine::DecisionStatus::DialecticSynthesis => crate::grpc::sasc_society_proto::DecisionStatus::DialecticSynthesis as i32,
+        crate::engine::DecisionStatus::HumanReviewRequired => crate::grpc::sasc_society_proto::DecisionStatus::HumanReviewRequired as i32,
+        crate::engine::DecisionStatus::Finalized => crate::grpc::sasc_society_proto::DecisionStatus::Finalized as i32,
+        crate::engine::DecisionStatus::HardFreezeTriggered => crate::grpc::sasc_society_proto::DecisionStatus::HardFreezeTriggered as i32,
+        crate::engine::DecisionStatus::TimeoutExceeded => crate::grpc::sasc_society_proto::DecisionStatus::TimeoutExceeded as i32,
+        crate::engine::DecisionStatus::ConstitutionalViolationDetected => crate::grpc::sasc_society_proto::DecisionStatus::ConstitutionalViolationDetected as i32,
     }
 }
 
@@ -198,16 +370,7 @@ fn convert_decision_response_to_proto(resp: SoTDecisionResponse) -> Result<Proce
 
     Ok(ProcessDecisionResponse {
         decision_id: resp.request_id.to_vec(),
-        status: match resp.status {
-            crate::engine::DecisionStatus::GatheringPerspectives => crate::grpc::sasc_society_proto::DecisionStatus::GatheringPerspectives as i32,
-            crate::engine::DecisionStatus::DiversityAssessment => crate::grpc::sasc_society_proto::DecisionStatus::DiversityAssessment as i32,
-            crate::engine::DecisionStatus::DialecticSynthesis => crate::grpc::sasc_society_proto::DecisionStatus::DialecticSynthesis as i32,
-            crate::engine::DecisionStatus::HumanReviewRequired => crate::grpc::sasc_society_proto::DecisionStatus::HumanReviewRequired as i32,
-            crate::engine::DecisionStatus::Finalized => crate::grpc::sasc_society_proto::DecisionStatus::Finalized as i32,
-            crate::engine::DecisionStatus::HardFreezeTriggered => crate::grpc::sasc_society_proto::DecisionStatus::HardFreezeTriggered as i32,
-            crate::engine::DecisionStatus::TimeoutExceeded => crate::grpc::sasc_society_proto::DecisionStatus::TimeoutExceeded as i32,
-            crate::engine::DecisionStatus::ConstitutionalViolationDetected => crate::grpc::sasc_society_proto::DecisionStatus::ConstitutionalViolationDetected as i32,
-        },
+        status: convert_status_to_proto(&resp.status),
         decision: resp.decision.map(|d| crate::grpc::sasc_society_proto::SynthesizedDecision {
             decision_text: d.decision_text,
             coherence_score: d.coherence_score,
@@ -296,19 +459,32 @@ fn convert_decision_response_to_proto(resp: SoTDecisionResponse) -> Result<Proce
     })
 }
 
-fn sign_response_with_prince(mut proto: ProcessDecisionResponse) -> Result<ProcessDecisionResponse, Status> {
-    // Em produção, usa a chave Prince para assinar o hash da resposta
-    proto.signature = vec![0u8; 4608]; // Dilithium5 signature placeholder
+/// Assina a resposta com a chave Prince do HSM; verificável com
+/// `authentication::verify_response`
+pub fn sign_response_with_prince(
+    mut proto: ProcessDecisionResponse,
+    signer: &dyn HsmSigner,
+) -> Result<ProcessDecisionResponse, Status> {
+    proto.signature = signer.sign(&response_signing_message(&proto))
+        .map_err(|e| Status::internal(format!("Erro HSM: {}", e)))?;
     Ok(proto)
 }
 
 pub async fn start_server(
     orchestrator: Arc<SoTOrchestrator>,
     prince_pubkey: pqcrypto_dilithium::dilithium5::PublicKey,
+    signer: Arc<dyn HsmSigner>,
+    allowlist_path: String,
     addr: String,
 ) -> Result<(), Box<dyn std::error::Error>> {
 
-    let server = GrpcServer::new(orchestrator, prince_pubkey);
+    // Allowlist assinada pela chave Prince, recarregada a quente
+    let allowlist = Arc::new(PubkeyAllowlist::load(&allowlist_path, prince_pubkey)?);
+    allowlist.spawn_reload_task(ALLOWLIST_RELOAD_INTERVAL);
+    info!("🔑 Allowlist {} carregada (versão {}, {} chaves)",
+          allowlist_path, allowlist.version(), allowlist.entries().len());
+
+    let server = GrpcServer::new(orchestrator, Arc::new(Authenticator::new(allowlist)), signer);
 
     // Rate limiting: 10 req/s por IP (INV-3: não-concentração)
     // BufferLayer used to make it Clone
@@ -1,11 +1,27 @@
 //! Assinatura PQC via HSM (Hardware Security Module)
 //! Gate 1: Prince Key Verification
 //! Memory ID 16, 20
+//!
+//! Dois backends de `HsmSigner`: token PKCS#11 (feature `pkcs11`) e keystore
+//! em arquivo, com a chave Dilithium5 cifrada em repouso (Argon2id +
+//! XChaCha20-Poly1305), para desenvolvimento e testes.
+//! O keystore é provisionado com o bin `sasc_keystore`.
 
-use pqcrypto_dilithium::dilithium5::PublicKey;
-use pqcrypto_traits::sign::PublicKey as _;
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use pqcrypto_dilithium::dilithium5::{self, DetachedSignature, PublicKey, SecretKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _, SecretKey as _};
+use rand::RngCore;
+use serde::{Serialize, Deserialize};
 use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// Versão do formato do keystore em arquivo
+const KEYSTORE_VERSION: u32 = 1;
 
 #[derive(Error, Debug)]
 pub enum HsmError {
@@ -17,26 +33,378 @@ pub enum HsmError {
     KeyNotFound,
     #[error("Assinatura falhou: {0}")]
     SignatureFailed(String),
+    #[error("Falha de E/S em {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("Keystore malformado: {0}")]
+    MalformedKeystore(String),
+}
+
+/// Signatário Dilithium5 cuja chave privada não sai do backend
+pub trait HsmSigner: Send + Sync {
+    /// Identificação da chave (rótulo no token ou no keystore)
+    fn label(&self) -> &str;
+
+    fn public_key(&self) -> Result<PublicKey, HsmError>;
+
+    /// Assinatura Dilithium5 destacada de `payload`
+    fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, HsmError>;
 }
 
+pub fn verify_signature(public_key: &PublicKey, payload: &[u8], signature: &[u8]) -> Result<(), HsmError> {
+    let signature = DetachedSignature::from_bytes(signature)
+        .map_err(|_| HsmError::SignatureFailed("Formato de assinatura inválido".to_string()))?;
+    dilithium5::verify_detached_signature(&signature, payload, public_key)
+        .map_err(|_| HsmError::SignatureFailed("Assinatura não confere com a chave".to_string()))
+}
+
+// ===================== KEYSTORE EM ARQUIVO =====================
+
+/// Parâmetros Argon2id da derivação da chave de cifragem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memória em KiB
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    pub salt: String,
+}
+
+impl KdfParams {
+    /// Custos recomendados para uso interativo (64 MiB, 3 passagens)
+    pub fn recommended() -> Self {
+        Self { m_cost: 64 * 1024, t_cost: 3, p_cost: 1, salt: String::new() }
+    }
+
+    fn derive_key(&self, passphrase: &[u8]) -> Result<Zeroizing<[u8; 32]>, HsmError> {
+        let salt = hex::decode(&self.salt).map_err(|e| HsmError::MalformedKeystore(e.to_string()))?;
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(32))
+            .map_err(|e| HsmError::MalformedKeystore(e.to_string()))?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase, &salt, &mut key[..])
+            .map_err(|e| HsmError::MalformedKeystore(e.to_string()))?;
+        Ok(key)
+    }
+}
+
+/// Formato do arquivo: chave pública em claro, privada cifrada. Rótulo e
+/// chave pública entram como dados associados, então trocá-los invalida o
+/// arquivo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeystoreFile {
+    version: u32,
+    label: String,
+    public_key: String,
+    kdf: KdfParams,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl KeystoreFile {
+    fn associated_data(label: &str, public_key: &[u8]) -> Vec<u8> {
+        let mut aad = b"sasc-society-keystore-v1".to_vec();
+        aad.extend_from_slice(&(label.len() as u64).to_le_bytes());
+        aad.extend_from_slice(label.as_bytes());
+        aad.extend_from_slice(public_key);
+        aad
+    }
+}
+
+/// Token de software: chave Dilithium5 cifrada em repouso, decifrada só em
+/// memória (zerada ao descartar)
+pub struct SoftwareKeystore {
+    label: String,
+    public_key: PublicKey,
+    secret_key: Zeroizing<Vec<u8>>,
+}
+
+impl SoftwareKeystore {
+    /// Gera um par novo e grava o keystore cifrado em `path`
+    pub fn create(path: impl AsRef<Path>, label: &str, passphrase: &[u8], kdf: KdfParams) -> Result<Self, HsmError> {
+        let (public_key, secret_key) = dilithium5::keypair();
+        Self::import(path, label, &public_key, &secret_key, passphrase, kdf)
+    }
+
+    /// Grava um par existente no keystore cifrado
+    pub fn import(
+        path: impl AsRef<Path>,
+        label: &str,
+        public_key: &PublicKey,
+        secret_key: &SecretKey,
+        passphrase: &[u8],
+        kdf: KdfParams,
+    ) -> Result<Self, HsmError> {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 24];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+        let kdf = KdfParams { salt: hex::encode(salt), ..kdf };
+
+        let key = kdf.derive_key(passphrase)?;
+        let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&key[..]))
+            .encrypt(XNonce::from_slice(&nonce), Payload {
+                msg: secret_key.as_bytes(),
+                aad: &KeystoreFile::associated_data(label, public_key.as_bytes()),
+            })
+            .map_err(|_| HsmError::InitializationFailed("Falha ao cifrar a chave".to_string()))?;
+
+        let file = KeystoreFile {
+            version: KEYSTORE_VERSION,
+            label: label.to_string(),
+            public_key: hex::encode(public_key.as_bytes()),
+            kdf,
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        };
+        write_private(path.as_ref(), &file)?;
+
+        Ok(Self {
+            label: label.to_string(),
+            public_key: *public_key,
+            secret_key: Zeroizing::new(secret_key.as_bytes().to_vec()),
+        })
+    }
+
+    /// Decifra o keystore. Passphrase errada ou arquivo adulterado falham na
+    /// autenticação do AEAD.
+    pub fn open(path: impl AsRef<Path>, passphrase: &[u8]) -> Result<Self, HsmError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| HsmError::Io(path.to_path_buf(), e))?;
+        let file: KeystoreFile = serde_json::from_slice(&bytes)
+            .map_err(|e| HsmError::MalformedKeystore(e.to_string()))?;
+        if file.version != KEYSTORE_VERSION {
+            return Err(HsmError::MalformedKeystore(format!("Versão {} não suportada", file.version)));
+        }
+
+        let decode = |field: &str| hex::decode(field).map_err(|e| HsmError::MalformedKeystore(e.to_string()));
+        let public_bytes = decode(&file.public_key)?;
+        let public_key = PublicKey::from_bytes(&public_bytes)
+            .map_err(|_| HsmError::MalformedKeystore("Chave pública Dilithium5 inválida".to_string()))?;
+        let nonce = decode(&file.nonce)?;
+        if nonce.len() != 24 {
+            return Err(HsmError::MalformedKeystore("Nonce deve ter 24 bytes".to_string()));
+        }
+
+        let key = file.kdf.derive_key(passphrase)?;
+        let secret_key = XChaCha20Poly1305::new(Key::from_slice(&key[..]))
+            .decrypt(XNonce::from_slice(&nonce), Payload {
+                msg: &decode(&file.ciphertext)?,
+                aad: &KeystoreFile::associated_data(&file.label, &public_bytes),
+            })
+            .map(Zeroizing::new)
+            .map_err(|_| HsmError::AuthenticationFailed)?;
+
+        let keystore = Self { label: file.label, public_key, secret_key };
+        // A chave privada tem de corresponder à pública declarada
+        let probe = keystore.sign(b"sasc-society-keystore-probe")?;
+        verify_signature(&keystore.public_key, b"sasc-society-keystore-probe", &probe)
+            .map_err(|_| HsmError::MalformedKeystore("Chave privada não corresponde à pública".to_string()))?;
+        Ok(keystore)
+    }
+}
+
+impl HsmSigner for SoftwareKeystore {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn public_key(&self) -> Result<PublicKey, HsmError> {
+        Ok(self.public_key)
+    }
+
+    fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, HsmError> {
+        let secret_key = SecretKey::from_bytes(&self.secret_key)
+            .map_err(|_| HsmError::SignatureFailed("Chave privada inválida".to_string()))?;
+        Ok(dilithium5::detached_sign(payload, &secret_key).as_bytes().to_vec())
+    }
+}
+
+/// Gravação atômica (tmp + rename) com permissão 0600 em Unix
+fn write_private(path: &Path, file: &KeystoreFile) -> Result<(), HsmError> {
+    let json = serde_json::to_vec_pretty(file).map_err(|e| HsmError::MalformedKeystore(e.to_string()))?;
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let result = options.open(&tmp)
+        .and_then(|mut f| std::io::Write::write_all(&mut f, &json).and_then(|_| f.sync_all()))
+        .and_then(|_| std::fs::rename(&tmp, path));
+    result.map_err(|e| HsmError::Io(path.to_path_buf(), e))
+}
+
+// ===================== PKCS#11 =====================
+
+/// Configuração do token PKCS#11
+#[derive(Clone)]
+pub struct Pkcs11Config {
+    /// Biblioteca do fabricante (ex.: `/usr/lib/softhsm/libsofthsm2.so`)
+    pub module_path: PathBuf,
+    pub slot_id: u64,
+    /// Zerado ao descartar; nunca aparece em `Debug`
+    pub pin: Zeroizing<String>,
+    /// `CKA_LABEL` dos objetos de chave pública e privada
+    pub key_label: String,
+    /// Mecanismo Dilithium5 do fabricante, como deslocamento sobre
+    /// `CKM_VENDOR_DEFINED`
+    pub mechanism_offset: u64,
+}
+
+impl std::fmt::Debug for Pkcs11Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pkcs11Config")
+            .field("module_path", &self.module_path)
+            .field("slot_id", &self.slot_id)
+            .field("pin", &"<redacted>")
+            .field("key_label", &self.key_label)
+            .field("mechanism_offset", &self.mechanism_offset)
+            .finish()
+    }
+}
+
+#[cfg(feature = "pkcs11")]
+pub use pkcs11::Pkcs11Signer;
+
+#[cfg(feature = "pkcs11")]
+mod pkcs11 {
+    use super::{HsmError, HsmSigner, Pkcs11Config};
+    use cryptoki::context::{CInitializeArgs, Pkcs11};
+    use cryptoki::mechanism::vendor_defined::VendorDefinedMechanism;
+    use cryptoki::mechanism::{Mechanism, MechanismType};
+    use cryptoki::object::{Attribute, AttributeType, ObjectClass, ObjectHandle};
+    use cryptoki::session::{Session, UserType};
+    use cryptoki::types::AuthPin;
+    use pqcrypto_dilithium::dilithium5::PublicKey;
+    use pqcrypto_traits::sign::PublicKey as _;
+    use std::sync::Mutex;
+
+    /// Assina com a chave privada do token; ela nunca é exportada
+    pub struct Pkcs11Signer {
+        // Mantém a biblioteca carregada enquanto a sessão existir
+        _context: Pkcs11,
+        session: Mutex<Session>,
+        private_key: ObjectHandle,
+        public_key: PublicKey,
+        mechanism: MechanismType,
+        label: String,
+    }
+
+    impl Pkcs11Signer {
+        pub fn connect(config: &Pkcs11Config) -> Result<Self, HsmError> {
+            let init = |e: cryptoki::error::Error| HsmError::InitializationFailed(e.to_string());
+            let context = Pkcs11::new(&config.module_path).map_err(init)?;
+            context.initialize(CInitializeArgs::OsThreads).map_err(init)?;
+
+            let slot = context.get_slots_with_token().map_err(init)?
+                .into_iter()
+                .find(|slot| slot.id() == config.slot_id)
+                .ok_or_else(|| HsmError::InitializationFailed(format!("Slot {} sem token", config.slot_id)))?;
+            let session = context.open_ro_session(slot).map_err(init)?;
+            session.login(UserType::User, Some(&AuthPin::new(config.pin.as_str().to_owned())))
+                .map_err(|_| HsmError::AuthenticationFailed)?;
+
+            let find = |class: ObjectClass| -> Result<ObjectHandle, HsmError> {
+                session.find_objects(&[Attribute::Class(class), Attribute::Label(config.key_label.as_bytes().to_vec())])
+                    .map_err(|e| HsmError::InitializationFailed(e.to_string()))?
+                    .into_iter()
+                    .next()
+                    .ok_or(HsmError::KeyNotFound)
+            };
+            let private_key = find(ObjectClass::PRIVATE_KEY)?;
+            let public_handle = find(ObjectClass::PUBLIC_KEY)?;
+
+            let value = session.get_attributes(public_handle, &[AttributeType::Value]).map_err(init)?
+                .into_iter()
+                .find_map(|attribute| match attribute {
+                    Attribute::Value(bytes) => Some(bytes),
+                    _ => None,
+                })
+                .ok_or(HsmError::KeyNotFound)?;
+            let public_key = PublicKey::from_bytes(&value).map_err(|_| HsmError::KeyNotFound)?;
+            let mechanism = MechanismType::new_vendor_defined(config.mechanism_offset).map_err(init)?;
+
+            Ok(Self {
+                _context: context,
+                session: Mutex::new(session),
+                private_key,
+                public_key,
+                mechanism,
+                label: config.key_label.clone(),
+            })
+        }
+    }
+
+    impl HsmSigner for Pkcs11Signer {
+        fn label(&self) -> &str {
+            &self.label
+        }
+
+        fn public_key(&self) -> Result<PublicKey, HsmError> {
+            Ok(self.public_key)
+        }
+
+        fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, HsmError> {
+            let mechanism = Mechanism::VendorDefined(VendorDefinedMechanism::new::<()>(self.mechanism, None));
+            let session = self.session.lock().unwrap_or_else(|e| e.into_inner());
+            session.sign(&mechanism, self.private_key, payload)
+                .map_err(|e| HsmError::SignatureFailed(e.to_string()))
+        }
+    }
+}
+
+// ===================== GERENCIADOR =====================
+
 pub struct HsmManager {
     pub prince_pubkey: PublicKey,
+    signer: Arc<dyn HsmSigner>,
 }
 
 impl HsmManager {
+    /// Backend escolhido por variáveis de ambiente:
+    /// - `SASC_HSM_BACKEND`: `software` (padrão) ou `pkcs11`
+    /// - software: `SASC_KEYSTORE_PATH`, `SASC_KEYSTORE_PASSPHRASE`
+    /// - pkcs11: `SASC_PKCS11_MODULE`, `SASC_PKCS11_SLOT`, `SASC_PKCS11_PIN`,
+    ///   `SASC_PKCS11_KEY_LABEL`, `SASC_PKCS11_MECHANISM`
+    ///
+    /// Se `SASC_PRINCE_PUBKEY` estiver definida, a chave do backend tem de coincidir.
     pub fn new() -> Result<Self, HsmError> {
-        // Carregar chave pública do Prince (whitelist)
-        let prince_hex = env::var("SASC_PRINCE_PUBKEY")
-            .unwrap_or_else(|_| "0x0000000000000000000000000000000000000000000000000000000000000000".to_string());
+        let backend = env::var("SASC_HSM_BACKEND").unwrap_or_else(|_| "software".to_string());
+        let signer: Arc<dyn HsmSigner> = match backend.as_str() {
+            "software" => {
+                let passphrase = Zeroizing::new(required_env("SASC_KEYSTORE_PASSPHRASE")?);
+                Arc::new(SoftwareKeystore::open(required_env("SASC_KEYSTORE_PATH")?, passphrase.as_bytes())?)
+            }
+            "pkcs11" => connect_pkcs11(&Pkcs11Config {
+                module_path: required_env("SASC_PKCS11_MODULE")?.into(),
+                slot_id: parse_env("SASC_PKCS11_SLOT")?,
+                pin: Zeroizing::new(required_env("SASC_PKCS11_PIN")?),
+                key_label: required_env("SASC_PKCS11_KEY_LABEL")?,
+                mechanism_offset: parse_env("SASC_PKCS11_MECHANISM")?,
+            })?,
+            other => return Err(HsmError::InitializationFailed(format!("Backend HSM desconhecido: {}", other))),
+        };
 
-        let prince_bytes = hex::decode(&prince_hex[2..]) // Remove "0x"
-            .map_err(|e| HsmError::InitializationFailed(e.to_string()))?;
-
-        let prince_pubkey = PublicKey::from_bytes(&prince_bytes)
-            .map_err(|_| HsmError::KeyNotFound)?;
+        let manager = Self::from_signer(signer)?;
+        if let Ok(expected) = env::var("SASC_PRINCE_PUBKEY") {
+            let expected = hex::decode(expected.trim_start_matches("0x"))
+                .map_err(|e| HsmError::InitializationFailed(e.to_string()))?;
+            if expected != manager.prince_pubkey.as_bytes() {
+                return Err(HsmError::KeyNotFound);
+            }
+        }
+        Ok(manager)
+    }
 
+    pub fn from_signer(signer: Arc<dyn HsmSigner>) -> Result<Self, HsmError> {
         Ok(HsmManager {
-            prince_pubkey,
+            prince_pubkey: signer.public_key()?,
+            signer,
         })
     }
 
@@ -44,9 +412,37 @@ impl HsmManager {
         Ok(self.prince_pubkey)
     }
 
+    pub fn signer(&self) -> Arc<dyn HsmSigner> {
+        Arc::clone(&self.signer)
+    }
+
     /// Gate 1 & Gate 3: Assinar e verificar via HSM
-    pub fn sign_request(&self, _payload: &[u8]) -> Result<Vec<u8>, HsmError> {
-        // Mock HSM signature
-        Ok(vec![0u8; 4608]) // Dilithium5 signature size
+    pub fn sign_request(&self, payload: &[u8]) -> Result<Vec<u8>, HsmError> {
+        let signature = self.signer.sign(payload)?;
+        verify_signature(&self.prince_pubkey, payload, &signature)?;
+        Ok(signature)
     }
 }
+
+#[cfg(feature = "pkcs11")]
+fn connect_pkcs11(config: &Pkcs11Config) -> Result<Arc<dyn HsmSigner>, HsmError> {
+    Ok(Arc::new(Pkcs11Signer::connect(config)?))
+}
+
+#[cfg(not(feature = "pkcs11"))]
+fn connect_pkcs11(_config: &Pkcs11Config) -> Result<Arc<dyn HsmSigner>, HsmError> {
+    Err(HsmError::InitializationFailed("Compilado sem a feature `pkcs11`".to_string()))
+}
+
+fn required_env(name: &str) -> Result<String, HsmError> {
+    env::var(name).map_err(|_| HsmError::InitializationFailed(format!("{} não definida", name)))
+}
+
+fn parse_env(name: &str) -> Result<u64, HsmError> {
+    let value = required_env(name)?;
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|_| HsmError::InitializationFailed(format!("{} inválida: {}", name, value)))
+}
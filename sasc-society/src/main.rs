@@ -9,6 +9,8 @@ use pqcrypto_traits::sign::PublicKey as _;
 use sasc_society::grpc::server::start_server;
 use sasc_society::engine::{SoTOrchestrator, OrchestratorConfig, DialecticSynthesizer};
 use sasc_society::engine::diversity::PerspectiveDiversityEngine;
+use sasc_society::audit::worm::{WormPolicy, WormStore};
+use sasc_society::hsm_signer::HsmManager;
 
 #[derive(Parser)]
 #[command(name = "sasc-society")]
@@ -21,6 +23,18 @@ struct Args {
 
     #[arg(long, required = true)]
     prince_pubkey: String,
+
+    /// Allowlist de chaves de clientes, assinada pela chave Prince
+    #[arg(long, default_value = "allowlist.json")]
+    allowlist: String,
+
+    /// Arquivo do cache de decisões por similaridade
+    #[arg(long, default_value = "decision_cache.json")]
+    decision_cache: String,
+
+    /// Diretório do histórico WORM de decisões
+    #[arg(long, default_value = "decision_history")]
+    history_dir: String,
 }
 
 #[tokio::main]
@@ -29,17 +43,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
     // 1. Configuração
-    let config = match args.mode.as_str() {
+    let mut config = match args.mode.as_str() {
         "production" => OrchestratorConfig::default(),
         "staging" => OrchestratorConfig::default(),
         _ => OrchestratorConfig::default(),
     };
+    config.cache_path = Some(args.decision_cache.into());
 
     // 2. Carrega chave Prince
     let prince_key_bytes = hex::decode(args.prince_pubkey)?;
     let prince_key = pqcrypto_dilithium::dilithium5::PublicKey::from_bytes(&prince_key_bytes)
         .map_err(|_| "Chave Prince inválida")?;
 
+    // Signatário das respostas (backend via SASC_HSM_BACKEND); a chave do
+    // HSM tem de ser a mesma anunciada aos clientes
+    let hsm = HsmManager::new()?;
+    if hsm.prince_pubkey.as_bytes() != prince_key.as_bytes() {
+        return Err("Chave do HSM difere de --prince-pubkey".into());
+    }
+    info!("🔐 HSM: chave '{}'", hsm.signer().label());
+
     // 3. Cria componentes
     let prince_key_raw: [u8; 32] = prince_key_bytes[0..32].try_into().unwrap_or([0u8; 32]);
     let diversity_engine = Arc::new(PerspectiveDiversityEngine::new(&prince_key_raw));
@@ -53,14 +76,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         },
     ));
 
-    // 4. Cria orchestrator
-    let orchestrator = Arc::new(SoTOrchestrator::new(
+    // 4. Abre o histórico WORM (cadeia verificada na abertura) e cria o orchestrator
+    let history = Arc::new(WormStore::open(
+        &args.history_dir,
+        WormPolicy::with_retention_days(config.retention_period_days),
+    )?);
+    info!("Histórico WORM: {} registros em {}", history.len(), args.history_dir);
+
+    let orchestrator = Arc::new(SoTOrchestrator::with_history_store(
         diversity_engine,
         dialectic_synthesizer,
         config,
+        history,
     ));
 
     // 5. Inicia servidor gRPC com rate limiting
     info!("🚀 SASC-SOCIETY gRPC Server ativo em {}", args.address);
-    start_server(orchestrator, prince_key, args.address).await
+    start_server(orchestrator, prince_key, hsm.signer(), args.allowlist, args.address).await
 }
@@ -8,8 +8,12 @@ use blake3::Hasher;
 use pqcrypto_dilithium::dilithium5::PublicKey;
 use pqcrypto_traits::sign::PublicKey as _;
 use serde::{Serialize, Deserialize};
+use std::sync::OnceLock;
 use thiserror::Error;
 
+/// Monitor compartilhado: janelas e fases por sessão persistem entre requests
+static VAJRA: OnceLock<Result<VajraEntropyMonitor, String>> = OnceLock::new();
+
 #[derive(Error, Debug)]
 pub enum BridgeError {
     #[error("Coherence collapse iminente: {risk:.3}")]
@@ -43,15 +47,16 @@ pub async fn assess_with_vajra(
     let delta2_hash = hasher.finalize();
 
     // Inicializar Vajra com contexto oncológico
-    let vajra = VajraEntropyMonitor::new_with_config(
+    let vajra = VAJRA.get_or_init(|| VajraEntropyMonitor::new_with_config(
         "oncológico_pediatrico_cluster",
         vajra_entropy_monitor::Config {
             hilbert_dim: 2048,
             fidelity_threshold: 0.9997, // Memory ID 11
             overload_threshold: 0.85,
             panic_threshold: EntropyPhase::Disordered,
+            ..Default::default()
         },
-    ).map_err(|_| BridgeError::VajraOffline)?;
+    )).as_ref().map_err(|_| BridgeError::VajraOffline)?;
 
     // Calcular carga cognitiva (Memory ID 3, Eq. 1-4)
     let report = vajra.assess_cognitive_load(
@@ -63,16 +68,13 @@ pub async fn assess_with_vajra(
     // Gate 5: Verificar fase de entropia
     match report.phase {
         EntropyPhase::Disordered => {
-            // Enviar alerta para KARNAK Sealer (Memory ID 4)
-            let alert = OverloadAlert {
-                coherence_collapse_risk: report.coherence_collapse_probability,
-                recommended_action: "Hard freeze e isolamento de processos".to_string(),
-                affected_session: session_id.to_string(),
-            };
-
-            // Escalar para Prince via gRPC
-            if let Err(e) = send_karnak_alert(alert).await {
-                eprintln!("KARNAK alert failed: {}", e);
+            // Enviar alerta para KARNAK Sealer (Memory ID 4), uma vez por
+            // entrada da sessão na fase desordenada
+            if let Some(alert) = report.alert.clone() {
+                // Escalar para Prince via gRPC
+                if let Err(e) = send_karnak_alert(alert).await {
+                    eprintln!("KARNAK alert failed: {}", e);
+                }
             }
 
             return Err(BridgeError::CollapseImminent {
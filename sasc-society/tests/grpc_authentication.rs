@@ -0,0 +1,180 @@
+//! Autenticação gRPC: allowlist assinada, integridade BLAKE3 e anti-replay
+
+use sasc_society::grpc::allowlist::{AllowlistDocument, AllowlistEntry, AllowlistError, PubkeyAllowlist, SignedAllowlist, unix_now};
+use sasc_society::grpc::authentication::{authenticate_request, sign_request, Authenticator};
+use sasc_society::grpc::sasc_society_proto::GetDecisionStatusRequest;
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tonic::{Code, Request};
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("sasc_allowlist_{}_{}.json", name, std::process::id()));
+    remove(&path);
+    path
+}
+
+/// Remove a allowlist e a marca de versão
+fn remove(path: &Path) {
+    let _ = std::fs::remove_file(path);
+    let _ = std::fs::remove_file(path.with_extension("json.version"));
+}
+
+/// Assina "offline" a próxima versão e a aplica no servidor
+fn publish(allowlist: &PubkeyAllowlist, prince_sk: &SecretKey, next: Result<AllowlistDocument, AllowlistError>) {
+    allowlist.publish(&SignedAllowlist::sign(next.unwrap(), prince_sk).unwrap()).unwrap();
+}
+
+fn signed_request(public_key: &PublicKey, secret_key: &SecretKey, timestamp: u64) -> Request<GetDecisionStatusRequest> {
+    let mut request = Request::new(GetDecisionStatusRequest { decision_id: vec![7u8; 32] });
+    sign_request(&mut request, public_key, secret_key, timestamp);
+    request
+}
+
+#[tokio::test]
+async fn test_allowlisted_request_is_accepted_once() {
+    let (prince_pk, prince_sk) = keypair();
+    let (client_pk, client_sk) = keypair();
+    let path = temp_path("accept");
+
+    let allowlist = Arc::new(PubkeyAllowlist::load(&path, prince_pk).unwrap());
+    let now = unix_now();
+    publish(&allowlist, &prince_sk, allowlist.document().with_entry(AllowlistEntry::new(&client_pk, "council-node", now, None), now));
+    let authenticator = Authenticator::new(allowlist);
+
+    let request = signed_request(&client_pk, &client_sk, now);
+    let replay = Request::from_parts(request.metadata().clone(), Default::default(), request.get_ref().clone());
+    assert!(authenticate_request(request, &authenticator).await.is_ok());
+
+    let err = authenticate_request(replay, &authenticator).await.unwrap_err();
+    assert_eq!(err.code(), Code::Unauthenticated);
+    assert!(err.message().contains("replay"));
+    remove(&path);
+}
+
+#[tokio::test]
+async fn test_rejects_unknown_key_tampered_body_and_stale_timestamp() {
+    let (prince_pk, prince_sk) = keypair();
+    let (client_pk, client_sk) = keypair();
+    let (stranger_pk, stranger_sk) = keypair();
+    let path = temp_path("reject");
+
+    let allowlist = Arc::new(PubkeyAllowlist::load(&path, prince_pk).unwrap());
+    let now = unix_now();
+    publish(&allowlist, &prince_sk, allowlist.document().with_entry(AllowlistEntry::new(&client_pk, "council-node", now, None), now));
+    let authenticator = Authenticator::new(allowlist);
+
+    let err = authenticate_request(signed_request(&stranger_pk, &stranger_sk, now), &authenticator).await.unwrap_err();
+    assert_eq!(err.code(), Code::PermissionDenied);
+
+    // Corpo alterado depois da assinatura: o hash recalculado não confere
+    let mut tampered = signed_request(&client_pk, &client_sk, now);
+    tampered.get_mut().decision_id = vec![8u8; 32];
+    let err = authenticate_request(tampered, &authenticator).await.unwrap_err();
+    assert!(err.message().contains("integridade"), "{}", err.message());
+
+    let err = authenticate_request(signed_request(&client_pk, &client_sk, now - 3600), &authenticator).await.unwrap_err();
+    assert!(err.message().contains("janela"), "{}", err.message());
+
+    // Assinatura de outra chave sob a identidade de um cliente autorizado
+    let mut forged = signed_request(&stranger_pk, &stranger_sk, now);
+    let genuine = signed_request(&client_pk, &client_sk, now);
+    forged.metadata_mut().insert_bin(
+        "x-requestor-pubkey-bin",
+        genuine.metadata().get_bin("x-requestor-pubkey-bin").unwrap().clone(),
+    );
+    let err = authenticate_request(forged, &authenticator).await.unwrap_err();
+    assert!(err.message().contains("Assinatura PQC"), "{}", err.message());
+    remove(&path);
+}
+
+#[tokio::test]
+async fn test_revocation_and_expiry() {
+    let (prince_pk, prince_sk) = keypair();
+    let (revoked_pk, revoked_sk) = keypair();
+    let (expiring_pk, _) = keypair();
+    let path = temp_path("revoke");
+
+    let allowlist = Arc::new(PubkeyAllowlist::load(&path, prince_pk).unwrap());
+    let now = unix_now();
+    publish(&allowlist, &prince_sk, allowlist.document().with_entry(AllowlistEntry::new(&revoked_pk, "retired-node", now, None), now));
+    publish(&allowlist, &prince_sk, allowlist.document().with_entry(AllowlistEntry::new(&expiring_pk, "temporary", now, Some(now + 60)), now));
+    assert!(matches!(
+        allowlist.document().with_entry(AllowlistEntry::new(&expiring_pk, "again", now, None), now),
+        Err(AllowlistError::Duplicate)
+    ));
+
+    assert!(allowlist.is_allowed(&expiring_pk, now + 59));
+    assert!(matches!(allowlist.check(&expiring_pk, now + 60), Err(AllowlistError::Expired(_))));
+
+    publish(&allowlist, &prince_sk, allowlist.document().with_revocation(&revoked_pk, now));
+    assert!(matches!(allowlist.check(&revoked_pk, now), Err(AllowlistError::Revoked(_))));
+    assert_eq!(allowlist.version(), 3);
+
+    let authenticator = Authenticator::new(allowlist);
+    let err = authenticate_request(signed_request(&revoked_pk, &revoked_sk, now), &authenticator).await.unwrap_err();
+    assert_eq!(err.code(), Code::PermissionDenied);
+    remove(&path);
+}
+
+#[test]
+fn test_hot_reload_applies_signed_updates_only() {
+    let (prince_pk, prince_sk) = keypair();
+    let (client_pk, _) = keypair();
+    let (_, impostor_sk) = keypair();
+    let path = temp_path("reload");
+    let now = unix_now();
+
+    let server = PubkeyAllowlist::load(&path, prince_pk).unwrap();
+    assert!(!server.is_allowed(&client_pk, now));
+
+    // Ferramenta de administração grava nova versão assinada offline
+    let next = server.document().with_entry(AllowlistEntry::new(&client_pk, "council-node", now, None), now).unwrap();
+    SignedAllowlist::sign(next, &prince_sk).unwrap().write(&path).unwrap();
+    assert!(server.reload().unwrap());
+    assert!(server.is_allowed(&client_pk, now));
+    assert!(!server.reload().unwrap());
+
+    // Arquivo assinado por outra chave: rejeitado, versão atual preservada
+    let forged = SignedAllowlist::sign(
+        AllowlistDocument { version: 9, issued_at: now, entries: Vec::new() },
+        &impostor_sk,
+    ).unwrap();
+    std::fs::write(&path, serde_json::to_vec(&forged).unwrap()).unwrap();
+    assert!(matches!(server.reload(), Err(AllowlistError::InvalidSignature)));
+    assert!(server.is_allowed(&client_pk, now));
+
+    // Versão antiga, ainda que assinada pela Prince: rollback
+    let old = SignedAllowlist::sign(AllowlistDocument::default(), &prince_sk).unwrap();
+    std::fs::write(&path, serde_json::to_vec(&old).unwrap()).unwrap();
+    assert!(matches!(server.reload(), Err(AllowlistError::Rollback { current: 1, found: 0 })));
+    assert!(server.is_allowed(&client_pk, now));
+    remove(&path);
+}
+
+#[test]
+fn test_rollback_detected_across_restarts() {
+    let (prince_pk, prince_sk) = keypair();
+    let (client_pk, _) = keypair();
+    let path = temp_path("restart");
+    let now = unix_now();
+
+    let server = PubkeyAllowlist::load(&path, prince_pk).unwrap();
+    let v1 = SignedAllowlist::sign(
+        server.document().with_entry(AllowlistEntry::new(&client_pk, "council-node", now, None), now).unwrap(),
+        &prince_sk,
+    ).unwrap();
+    server.publish(&v1).unwrap();
+    assert!(matches!(server.publish(&v1), Err(AllowlistError::Rollback { current: 1, found: 1 })));
+    publish(&server, &prince_sk, server.document().with_revocation(&client_pk, now));
+    drop(server);
+
+    // Versão antiga recolocada enquanto o servidor estava parado
+    v1.write(&path).unwrap();
+    assert!(matches!(PubkeyAllowlist::load(&path, prince_pk), Err(AllowlistError::Rollback { current: 2, found: 1 })));
+
+    // Arquivo apagado não reabre a allowlist vazia
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(PubkeyAllowlist::load(&path, prince_pk), Err(AllowlistError::Rollback { current: 2, found: 0 })));
+    remove(&path);
+}
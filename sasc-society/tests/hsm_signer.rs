@@ -0,0 +1,111 @@
+//! HSM: keystore cifrado em repouso e respostas assinadas verificáveis
+
+use sasc_society::grpc::authentication::verify_response;
+use sasc_society::grpc::sasc_society_proto::ProcessDecisionResponse;
+use sasc_society::grpc::server::sign_response_with_prince;
+use sasc_society::hsm_signer::{verify_signature, HsmError, HsmManager, HsmSigner, KdfParams, SoftwareKeystore};
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("sasc_keystore_{}_{}.json", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// Custos baixos: os testes exercitam o formato, não a resistência do KDF
+fn test_kdf() -> KdfParams {
+    KdfParams { m_cost: 256, t_cost: 1, p_cost: 1, salt: String::new() }
+}
+
+#[test]
+fn test_keystore_roundtrip_signs_with_imported_key() {
+    let (public_key, secret_key) = keypair();
+    let path = temp_path("roundtrip");
+    SoftwareKeystore::import(&path, "prince", &public_key, &secret_key, b"correct horse", test_kdf()).unwrap();
+
+    let raw = std::fs::read_to_string(&path).unwrap();
+    assert!(!raw.contains(&hex::encode(&secret_key_bytes(&secret_key)[..64])));
+
+    let keystore = SoftwareKeystore::open(&path, b"correct horse").unwrap();
+    assert_eq!(keystore.label(), "prince");
+    assert_eq!(keystore.public_key().unwrap().as_bytes(), public_key.as_bytes());
+
+    let signature = keystore.sign(b"payload").unwrap();
+    assert!(verify_signature(&public_key, b"payload", &signature).is_ok());
+    assert!(verify_signature(&public_key, b"other payload", &signature).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_wrong_passphrase_and_tampering_are_rejected() {
+    let path = temp_path("reject");
+    SoftwareKeystore::create(&path, "prince", b"correct horse", test_kdf()).unwrap();
+
+    assert!(matches!(SoftwareKeystore::open(&path, b"wrong horse"), Err(HsmError::AuthenticationFailed)));
+
+    // Rótulo trocado: os dados associados não conferem
+    let tampered = std::fs::read_to_string(&path).unwrap().replace("\"prince\"", "\"impostor\"");
+    std::fs::write(&path, tampered).unwrap();
+    assert!(matches!(SoftwareKeystore::open(&path, b"correct horse"), Err(HsmError::AuthenticationFailed)));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_signed_response_verifies_against_prince_key() {
+    let path = temp_path("response");
+    let keystore = SoftwareKeystore::create(&path, "prince", b"correct horse", test_kdf()).unwrap();
+    let hsm = HsmManager::from_signer(Arc::new(keystore)).unwrap();
+    let signer = hsm.signer();
+
+    let response = ProcessDecisionResponse { decision_id: vec![7u8; 32], ..Default::default() };
+    let signed = sign_response_with_prince(response, signer.as_ref()).unwrap();
+    assert!(verify_response(&signed, &hsm.prince_pubkey).is_ok());
+
+    let mut tampered = signed.clone();
+    tampered.decision_id = vec![8u8; 32];
+    assert!(verify_response(&tampered, &hsm.prince_pubkey).is_err());
+
+    let (stranger, _) = keypair();
+    assert!(verify_response(&signed, &stranger).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+fn secret_key_bytes(secret_key: &pqcrypto_dilithium::dilithium5::SecretKey) -> Vec<u8> {
+    use pqcrypto_traits::sign::SecretKey as _;
+    secret_key.as_bytes().to_vec()
+}
+
+#[test]
+fn test_inconsistent_keystore_is_malformed() {
+    let (public_key, secret_key) = keypair();
+    let (other_public, _) = keypair();
+
+    // Par que não se corresponde: cifragem válida, chaves divergentes
+    let path = temp_path("mismatch");
+    SoftwareKeystore::import(&path, "prince", &other_public, &secret_key, b"correct horse", test_kdf()).unwrap();
+    assert!(matches!(SoftwareKeystore::open(&path, b"correct horse"), Err(HsmError::MalformedKeystore(_))));
+
+    // Chave pública que não é Dilithium5
+    SoftwareKeystore::import(&path, "prince", &public_key, &secret_key, b"correct horse", test_kdf()).unwrap();
+    let raw = std::fs::read_to_string(&path).unwrap().replace(&hex::encode(public_key.as_bytes()), "00ff");
+    std::fs::write(&path, raw).unwrap();
+    assert!(matches!(SoftwareKeystore::open(&path, b"correct horse"), Err(HsmError::MalformedKeystore(_))));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_pkcs11_pin_is_redacted() {
+    let config = sasc_society::hsm_signer::Pkcs11Config {
+        module_path: "/usr/lib/softhsm/libsofthsm2.so".into(),
+        slot_id: 0,
+        pin: zeroize::Zeroizing::new("123456".to_string()),
+        key_label: "prince".to_string(),
+        mechanism_offset: 0x10,
+    };
+    let debug = format!("{:?}", config);
+    assert!(!debug.contains("123456"));
+    assert!(debug.contains("<redacted>"));
+}
@@ -1,6 +1,27 @@
+//! Vajra Entropy Monitor
+//! Carga cognitiva por sessão a partir da entropia dos payloads: entropia de
+//! bytes e de tokens, compressibilidade e fidelidade à distribuição recente
+//! da sessão, agregadas numa janela deslizante com histerese de fase.
+
+pub mod metrics;
+
 use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Sessão usada quando o chamador não informa `session_id`
+pub const DEFAULT_SESSION: &str = "default";
+
+/// Acima deste total o histograma de referência da sessão é reduzido à
+/// metade, esquecendo gradualmente payloads antigos
+const REFERENCE_HISTOGRAM_LIMIT: u64 = 1 << 20;
+
+/// Largura da transição logística da probabilidade de colapso em torno do
+/// limiar de sobrecarga
+const COLLAPSE_STEEPNESS: f64 = 0.05;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum EntropyPhase {
     Ordered,
     Disordered,
@@ -12,6 +33,12 @@ pub struct VajraReport {
     pub cognitive_load: f64,
     pub coherence_collapse_probability: f64,
     pub phase: EntropyPhase,
+    /// Medidas do payload avaliado
+    pub byte_entropy: f64,
+    pub token_entropy: f64,
+    pub compression_ratio: f64,
+    /// Presente quando a sessão entra na fase `Config::panic_threshold`
+    pub alert: Option<OverloadAlert>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -21,31 +48,349 @@ pub struct OverloadAlert {
     pub affected_session: String,
 }
 
+/// Ponto da série temporal de carga de uma sessão
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CognitiveLoadSnapshot {
+    /// Milissegundos desde a época Unix
     pub timestamp: u64,
+    /// Carga da janela deslizante após este payload
     pub load: f64,
+    /// Carga instantânea do payload
+    pub sample_load: f64,
+    pub coherence_score: f64,
+    pub phase: EntropyPhase,
 }
 
+#[derive(Debug, Clone)]
 pub struct Config {
+    /// Dimensão do espaço de estados do cluster; identifica a configuração,
+    /// não entra no cálculo de carga
     pub hilbert_dim: usize,
+    /// Escala de volume: payloads deste tamanho (bytes) contam metade da
+    /// pressão máxima de volume
+    pub volume_scale: usize,
+    /// Fidelidade mínima à distribuição de bytes recente da sessão; abaixo
+    /// dela o desvio eleva a probabilidade de colapso
     pub fidelity_threshold: f64,
+    /// Carga da janela a partir da qual a sessão passa a `Disordered`
     pub overload_threshold: f64,
+    /// Fase que dispara `OverloadAlert`
     pub panic_threshold: EntropyPhase,
+    /// Payloads considerados na janela deslizante
+    pub window_size: usize,
+    /// Payloads na janela antes que a fase possa mudar: uma amostra isolada
+    /// não decide a fase de uma sessão nova
+    pub min_samples: usize,
+    /// A sessão só volta a `Ordered` abaixo de `overload_threshold - hysteresis`
+    pub hysteresis: f64,
+    /// Pontos mantidos na série temporal de cada sessão
+    pub history_capacity: usize,
+    /// Sessões mantidas; ao exceder, descarta a inativa há mais tempo
+    pub max_sessions: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            hilbert_dim: 2048,
+            volume_scale: 2048,
+            fidelity_threshold: 0.9,
+            overload_threshold: 0.85,
+            panic_threshold: EntropyPhase::Disordered,
+            window_size: 16,
+            min_samples: 4,
+            hysteresis: 0.1,
+            history_capacity: 1024,
+            max_sessions: 4096,
+        }
+    }
 }
 
-pub struct VajraEntropyMonitor {}
+#[derive(Debug)]
+struct SessionState {
+    window: VecDeque<f64>,
+    reference: [u64; 256],
+    phase: EntropyPhase,
+    history: VecDeque<CognitiveLoadSnapshot>,
+    last_seen: Instant,
+}
+
+impl SessionState {
+    fn new() -> Self {
+        Self {
+            window: VecDeque::new(),
+            reference: [0; 256],
+            phase: EntropyPhase::Ordered,
+            history: VecDeque::new(),
+            last_seen: Instant::now(),
+        }
+    }
+
+    fn absorb(&mut self, histogram: &[u64; 256]) {
+        for (total, count) in self.reference.iter_mut().zip(histogram.iter()) {
+            *total += count;
+        }
+        if self.reference.iter().sum::<u64>() > REFERENCE_HISTOGRAM_LIMIT {
+            self.reference.iter_mut().for_each(|count| *count /= 2);
+        }
+    }
+}
+
+pub struct VajraEntropyMonitor {
+    cluster_id: String,
+    config: Config,
+    sessions: Mutex<HashMap<String, SessionState>>,
+}
 
 impl VajraEntropyMonitor {
-    pub fn new_with_config(_id: &str, _config: Config) -> Result<Self, String> {
-        Ok(Self {})
+    pub fn new_with_config(cluster_id: &str, config: Config) -> Result<Self, String> {
+        if config.volume_scale == 0 || config.window_size == 0 || config.max_sessions == 0 {
+            return Err("volume_scale, window_size e max_sessions devem ser positivos".to_string());
+        }
+        if !(1..=config.window_size).contains(&config.min_samples) {
+            return Err(format!("min_samples fora de [1, window_size]: {}", config.min_samples));
+        }
+        for (name, value) in [
+            ("fidelity_threshold", config.fidelity_threshold),
+            ("overload_threshold", config.overload_threshold),
+            ("hysteresis", config.hysteresis),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("{} fora de [0, 1]: {}", name, value));
+            }
+        }
+        Ok(Self {
+            cluster_id: cluster_id.to_string(),
+            config,
+            sessions: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn cluster_id(&self) -> &str {
+        &self.cluster_id
     }
-    pub fn assess_cognitive_load(&self, _payload: &[u8], _hash: Option<Vec<u8>>, _session_id: Option<String>) -> Result<VajraReport, String> {
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Avalia `payload` e atualiza a janela, a fase e a série temporal da
+    /// sessão. `_hash` é a integridade calculada pelo chamador; não entra no
+    /// modelo.
+    pub fn assess_cognitive_load(&self, payload: &[u8], _hash: Option<Vec<u8>>, session_id: Option<String>) -> Result<VajraReport, String> {
+        let session_id = session_id.unwrap_or_else(|| DEFAULT_SESSION.to_string());
+        let byte_entropy = metrics::byte_entropy(payload);
+        let token_entropy = metrics::token_entropy(payload);
+        let compression_ratio = metrics::compression_ratio(payload);
+        let histogram = metrics::byte_histogram(payload);
+
+        // Complexidade do conteúdo, ponderada pela pressão de volume
+        let complexity = 0.4 * byte_entropy + 0.3 * token_entropy + 0.3 * compression_ratio;
+        let volume = payload.len() as f64 / (payload.len() + self.config.volume_scale) as f64;
+        let sample_load = complexity * (0.5 + 0.5 * volume);
+
+        let mut sessions = self.sessions.lock().map_err(|_| "Estado de sessões envenenado".to_string())?;
+        if !sessions.contains_key(&session_id) && sessions.len() >= self.config.max_sessions {
+            let idle = sessions.iter().min_by_key(|(_, s)| s.last_seen).map(|(id, _)| id.clone());
+            if let Some(idle) = idle {
+                sessions.remove(&idle);
+            }
+        }
+        let session = sessions.entry(session_id.clone()).or_insert_with(SessionState::new);
+        session.last_seen = Instant::now();
+
+        let coherence_score = metrics::fidelity(&histogram, &session.reference);
+        session.absorb(&histogram);
+
+        session.window.push_back(sample_load);
+        while session.window.len() > self.config.window_size {
+            session.window.pop_front();
+        }
+        let cognitive_load = session.window.iter().sum::<f64>() / session.window.len() as f64;
+
+        let previous_phase = session.phase;
+        session.phase = match session.phase {
+            phase if session.window.len() < self.config.min_samples => phase,
+            EntropyPhase::Ordered if cognitive_load >= self.config.overload_threshold => EntropyPhase::Disordered,
+            EntropyPhase::Disordered if cognitive_load < self.config.overload_threshold - self.config.hysteresis => EntropyPhase::Ordered,
+            phase => phase,
+        };
+
+        let overload_risk = 1.0 / (1.0 + (-(cognitive_load - self.config.overload_threshold) / COLLAPSE_STEEPNESS).exp());
+        let drift = ((self.config.fidelity_threshold - coherence_score) / self.config.fidelity_threshold.max(f64::EPSILON)).max(0.0);
+        let coherence_collapse_probability = 1.0 - (1.0 - overload_risk) * (1.0 - drift.min(1.0));
+
+        let alert = (session.phase == self.config.panic_threshold && previous_phase != session.phase).then(|| OverloadAlert {
+            coherence_collapse_risk: coherence_collapse_probability,
+            recommended_action: "Hard freeze e isolamento de processos".to_string(),
+            affected_session: session_id.clone(),
+        });
+
+        session.history.push_back(CognitiveLoadSnapshot {
+            timestamp: unix_millis(),
+            load: cognitive_load,
+            sample_load,
+            coherence_score,
+            phase: session.phase,
+        });
+        while session.history.len() > self.config.history_capacity {
+            session.history.pop_front();
+        }
+
         Ok(VajraReport {
-            coherence_score: 0.78,
-            cognitive_load: 0.45,
-            coherence_collapse_probability: 0.0001,
-            phase: EntropyPhase::Ordered,
+            coherence_score,
+            cognitive_load,
+            coherence_collapse_probability,
+            phase: session.phase,
+            byte_entropy,
+            token_entropy,
+            compression_ratio,
+            alert,
         })
     }
+
+    /// Série temporal da sessão, do ponto mais antigo ao mais recente
+    pub fn snapshots(&self, session_id: &str) -> Vec<CognitiveLoadSnapshot> {
+        self.sessions.lock()
+            .map(|sessions| sessions.get(session_id).map(|s| s.history.iter().cloned().collect()).unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    /// Fase atual da sessão; `None` se ela ainda não foi avaliada
+    pub fn phase(&self, session_id: &str) -> Option<EntropyPhase> {
+        self.sessions.lock().ok()?.get(session_id).map(|s| s.phase)
+    }
+
+    /// Descarta janela, referência e série temporal da sessão
+    pub fn reset_session(&self, session_id: &str) {
+        if let Ok(mut sessions) = self.sessions.lock() {
+            sessions.remove(session_id);
+        }
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor() -> VajraEntropyMonitor {
+        let config = Config { window_size: 4, min_samples: 2, overload_threshold: 0.8, hysteresis: 0.2, ..Config::default() };
+        VajraEntropyMonitor::new_with_config("test", config).unwrap()
+    }
+
+    /// Gerador xorshift: bytes de alta entropia sem depender de `rand`
+    fn noise(len: usize, mut state: u64) -> Vec<u8> {
+        (0..len).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        }).collect()
+    }
+
+    #[test]
+    fn test_metrics_separate_redundant_text_from_noise() {
+        let text = "o conselho aprova o plano o conselho aprova o plano ".repeat(20);
+        let random = noise(4096, 0x9E37_79B9_7F4A_7C15);
+
+        assert!(metrics::compression_ratio(text.as_bytes()) < 0.3);
+        assert!(metrics::compression_ratio(&random) > 0.95);
+        assert!(metrics::byte_entropy(&random) > 0.95);
+        assert!(metrics::byte_entropy(text.as_bytes()) < 0.6);
+        assert!(metrics::token_entropy(text.as_bytes()) < 0.5);
+        assert_eq!(metrics::compression_ratio(b""), 0.0);
+        assert_eq!(metrics::compression_ratio(b"a"), 1.0);
+    }
+
+    #[test]
+    fn test_phase_switches_with_hysteresis() {
+        let monitor = monitor();
+        let session = Some("s1".to_string());
+        let calm = "paciente estável, sinais vitais normais. ".repeat(10);
+
+        let report = monitor.assess_cognitive_load(calm.as_bytes(), None, session.clone()).unwrap();
+        assert_eq!(report.phase, EntropyPhase::Ordered);
+        assert!(report.alert.is_none());
+
+        // Ruído volumoso satura a janela e cruza o limiar
+        let mut entered = None;
+        for seed in 1..=4 {
+            let report = monitor.assess_cognitive_load(&noise(65536, seed), None, session.clone()).unwrap();
+            if report.phase == EntropyPhase::Disordered && entered.is_none() {
+                assert!(report.alert.is_some());
+                assert!(report.coherence_collapse_probability > 0.5);
+                entered = Some(seed);
+            }
+        }
+        assert!(entered.is_some());
+
+        // Uma amostra calma não basta: a carga ainda está na faixa de histerese
+        let report = monitor.assess_cognitive_load(calm.as_bytes(), None, session.clone()).unwrap();
+        assert!(report.cognitive_load < monitor.config().overload_threshold);
+        assert_eq!(report.phase, EntropyPhase::Disordered);
+        assert!(report.alert.is_none());
+
+        for _ in 0..4 {
+            monitor.assess_cognitive_load(calm.as_bytes(), None, session.clone()).unwrap();
+        }
+        assert_eq!(monitor.phase("s1"), Some(EntropyPhase::Ordered));
+    }
+
+    #[test]
+    fn test_single_sample_does_not_switch_phase() {
+        let config = Config { window_size: 4, min_samples: 3, overload_threshold: 0.8, ..Config::default() };
+        let monitor = VajraEntropyMonitor::new_with_config("test", config).unwrap();
+        let session = Some("nova".to_string());
+
+        for seed in 1..=2 {
+            let report = monitor.assess_cognitive_load(&noise(65536, seed), None, session.clone()).unwrap();
+            assert!(report.cognitive_load >= 0.8);
+            assert_eq!(report.phase, EntropyPhase::Ordered);
+            assert!(report.alert.is_none());
+        }
+        let report = monitor.assess_cognitive_load(&noise(65536, 3), None, session).unwrap();
+        assert_eq!(report.phase, EntropyPhase::Disordered);
+        assert!(report.alert.is_some());
+
+        let invalid = Config { window_size: 4, min_samples: 5, ..Config::default() };
+        assert!(VajraEntropyMonitor::new_with_config("test", invalid).is_err());
+    }
+
+    #[test]
+    fn test_idle_sessions_are_evicted_at_capacity() {
+        let config = Config { max_sessions: 2, ..Config::default() };
+        let monitor = VajraEntropyMonitor::new_with_config("test", config).unwrap();
+        monitor.assess_cognitive_load(b"consulta", None, Some("a".to_string())).unwrap();
+        monitor.assess_cognitive_load(b"consulta", None, Some("b".to_string())).unwrap();
+        // "a" volta a ser usada: "b" passa a ser a inativa há mais tempo
+        monitor.assess_cognitive_load(b"consulta", None, Some("a".to_string())).unwrap();
+        monitor.assess_cognitive_load(b"consulta", None, Some("c".to_string())).unwrap();
+
+        assert_eq!(monitor.snapshots("a").len(), 2);
+        assert_eq!(monitor.phase("b"), None);
+        assert!(monitor.phase("c").is_some());
+    }
+
+    #[test]
+    fn test_snapshots_are_kept_per_session() {
+        let monitor = monitor();
+        for _ in 0..3 {
+            monitor.assess_cognitive_load(b"consulta de rotina", None, Some("a".to_string())).unwrap();
+        }
+        monitor.assess_cognitive_load(b"outra consulta", None, None).unwrap();
+
+        let series = monitor.snapshots("a");
+        assert_eq!(series.len(), 3);
+        assert!(series.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+        assert_eq!(series[2].coherence_score, 1.0);
+        assert_eq!(monitor.snapshots(DEFAULT_SESSION).len(), 1);
+
+        monitor.reset_session("a");
+        assert!(monitor.snapshots("a").is_empty());
+        assert_eq!(monitor.phase("a"), None);
+    }
 }
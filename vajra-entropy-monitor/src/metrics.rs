@@ -0,0 +1,124 @@
+//! Medidas de entropia de um payload: bytes, tokens e compressibilidade
+
+use std::collections::HashMap;
+
+/// Janela de busca do estimador LZ77
+const LZ_WINDOW: usize = 4096;
+/// Menor repetição que compensa codificar como referência
+const LZ_MIN_MATCH: usize = 3;
+const LZ_MAX_MATCH: usize = 258;
+/// Profundidade máxima da cadeia de candidatos por prefixo
+const LZ_MAX_CHAIN: usize = 32;
+/// Custo em bits: literal = flag + byte; referência = flag + offset(12) + comprimento(8)
+const LZ_LITERAL_BITS: usize = 9;
+const LZ_MATCH_BITS: usize = 21;
+
+/// Entropia de Shannon da distribuição de bytes, normalizada para [0, 1]
+/// (8 bits por byte = 1.0)
+pub fn byte_entropy(payload: &[u8]) -> f64 {
+    if payload.is_empty() {
+        return 0.0;
+    }
+    shannon(byte_histogram(payload).iter().copied(), payload.len() as u64) / 8.0
+}
+
+pub fn byte_histogram(payload: &[u8]) -> [u64; 256] {
+    let mut histogram = [0u64; 256];
+    for &byte in payload {
+        histogram[byte as usize] += 1;
+    }
+    histogram
+}
+
+/// Entropia de Shannon dos tokens (palavras em minúsculas), normalizada pelo
+/// máximo possível para a quantidade de tokens
+pub fn token_entropy(payload: &[u8]) -> f64 {
+    let text = String::from_utf8_lossy(payload).to_lowercase();
+    let mut counts: HashMap<&str, u64> = HashMap::new();
+    let mut total = 0u64;
+    for token in text.split(|c: char| !c.is_alphanumeric()).filter(|t| !t.is_empty()) {
+        *counts.entry(token).or_insert(0) += 1;
+        total += 1;
+    }
+    if total < 2 {
+        return 0.0;
+    }
+    shannon(counts.values().copied(), total) / (total as f64).log2()
+}
+
+/// Razão comprimido/original estimada por um LZ77 guloso, em [0, 1].
+/// Conteúdo redundante comprime (razão baixa); ruído não comprime (1.0).
+pub fn compression_ratio(payload: &[u8]) -> f64 {
+    if payload.is_empty() {
+        return 0.0;
+    }
+    let mut head: HashMap<[u8; 3], usize> = HashMap::new();
+    let mut previous = vec![usize::MAX; payload.len()];
+    let mut bits = 0usize;
+    let mut position = 0usize;
+    while position < payload.len() {
+        let best = longest_match(payload, position, &head, &previous);
+        let advance = if best >= LZ_MIN_MATCH {
+            bits += LZ_MATCH_BITS;
+            best
+        } else {
+            bits += LZ_LITERAL_BITS;
+            1
+        };
+        for offset in position..(position + advance).min((payload.len() + 1).saturating_sub(LZ_MIN_MATCH)) {
+            let key = [payload[offset], payload[offset + 1], payload[offset + 2]];
+            previous[offset] = head.insert(key, offset).unwrap_or(usize::MAX);
+        }
+        position += advance;
+    }
+    (bits as f64 / (payload.len() * 8) as f64).min(1.0)
+}
+
+fn longest_match(payload: &[u8], position: usize, head: &HashMap<[u8; 3], usize>, previous: &[usize]) -> usize {
+    if position + LZ_MIN_MATCH > payload.len() {
+        return 0;
+    }
+    let key = [payload[position], payload[position + 1], payload[position + 2]];
+    let limit = (payload.len() - position).min(LZ_MAX_MATCH);
+
+    let mut best = 0;
+    let mut candidate = head.get(&key).copied().unwrap_or(usize::MAX);
+    for _ in 0..LZ_MAX_CHAIN {
+        if candidate == usize::MAX || position - candidate >= LZ_WINDOW {
+            break;
+        }
+        let length = (0..limit)
+            .take_while(|&i| payload[candidate + i] == payload[position + i])
+            .count();
+        best = best.max(length);
+        if best == limit {
+            break;
+        }
+        candidate = previous[candidate];
+    }
+    best
+}
+
+/// Fidelidade clássica (Bhattacharyya ao quadrado) entre dois histogramas
+/// de bytes; 1.0 quando um deles está vazio
+pub fn fidelity(current: &[u64; 256], reference: &[u64; 256]) -> f64 {
+    let current_total: u64 = current.iter().sum();
+    let reference_total: u64 = reference.iter().sum();
+    if current_total == 0 || reference_total == 0 {
+        return 1.0;
+    }
+    let overlap: f64 = current.iter().zip(reference.iter())
+        .map(|(&p, &q)| ((p as f64 / current_total as f64) * (q as f64 / reference_total as f64)).sqrt())
+        .sum();
+    (overlap * overlap).min(1.0)
+}
+
+fn shannon(counts: impl Iterator<Item = u64>, total: u64) -> f64 {
+    counts
+        .filter(|&count| count > 0)
+        .map(|count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}